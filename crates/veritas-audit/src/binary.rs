@@ -0,0 +1,217 @@
+//! Compact binary encoding of audit logs, behind the `bincode` feature.
+//!
+//! Hashes are stored as raw bytes rather than hex, and field names are not
+//! written at all.  Each `StepRecord` is kept as the exact canonical JSON the
+//! chain hashes (`chain::hash_event`): bincode cannot carry the free-form
+//! payload values or the fields omitted when empty, and re-encoding the
+//! record through JSON guarantees a decoded log rehashes to the same chain.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use veritas_contracts::{error::VeritasResult, execution::StepRecord};
+
+use crate::event::{load_error, AuditEvent, AuditLog};
+
+/// The binary layout of an `AuditLog`.
+#[derive(Serialize, Deserialize)]
+struct BinaryLog {
+    execution_id: String,
+    events: Vec<BinaryEvent>,
+    finalized_at: DateTime<Utc>,
+    terminal_hash: Vec<u8>,
+    chain_version: u32,
+    execution_group_id: Option<String>,
+}
+
+/// The binary layout of an `AuditEvent`.
+#[derive(Serialize, Deserialize)]
+struct BinaryEvent {
+    sequence: u64,
+    execution_id: String,
+    record_json: Vec<u8>,
+    prev_hash: Vec<u8>,
+    this_hash: Vec<u8>,
+}
+
+impl AuditLog {
+    /// Encode the log in a compact binary form, read back by `from_bincode`.
+    ///
+    /// Returns `VeritasError::AuditWriteFailed` if a hash is not hex — which
+    /// no log produced by this crate contains.
+    pub fn to_bincode(&self) -> VeritasResult<Vec<u8>> {
+        let events = self
+            .events
+            .iter()
+            .map(|event| {
+                Ok(BinaryEvent {
+                    sequence: event.sequence,
+                    execution_id: event.execution_id.clone(),
+                    record_json: serde_json::to_vec(&event.record)
+                        .expect("StepRecord must always be serializable to JSON"),
+                    prev_hash: hash_bytes(&event.prev_hash)?,
+                    this_hash: hash_bytes(&event.this_hash)?,
+                })
+            })
+            .collect::<VeritasResult<Vec<_>>>()?;
+
+        let binary = BinaryLog {
+            execution_id: self.execution_id.clone(),
+            events,
+            finalized_at: self.finalized_at,
+            terminal_hash: hash_bytes(&self.terminal_hash)?,
+            chain_version: self.chain_version,
+            execution_group_id: self.execution_group_id.clone(),
+        };
+        bincode::serialize(&binary)
+            .map_err(|e| load_error(format!("failed to encode audit log: {}", e)))
+    }
+
+    /// Decode a log written by `to_bincode` and verify it exactly as
+    /// `from_reader` does.
+    ///
+    /// Returns `VeritasError::AuditWriteFailed` if the bytes cannot be
+    /// decoded, or if the decoded log fails any of `from_reader`'s checks.
+    pub fn from_bincode(bytes: &[u8]) -> VeritasResult<AuditLog> {
+        let binary: BinaryLog = bincode::deserialize(bytes)
+            .map_err(|e| load_error(format!("failed to decode audit log: {}", e)))?;
+
+        let events = binary
+            .events
+            .into_iter()
+            .map(|event| {
+                let record: StepRecord =
+                    serde_json::from_slice(&event.record_json).map_err(|e| {
+                        load_error(format!(
+                            "failed to parse record of audit event {}: {}",
+                            event.sequence, e
+                        ))
+                    })?;
+                Ok(AuditEvent {
+                    sequence: event.sequence,
+                    execution_id: event.execution_id,
+                    record,
+                    prev_hash: hex::encode(event.prev_hash),
+                    this_hash: hex::encode(event.this_hash),
+                })
+            })
+            .collect::<VeritasResult<Vec<_>>>()?;
+
+        AuditLog {
+            execution_id: binary.execution_id,
+            events,
+            finalized_at: binary.finalized_at,
+            terminal_hash: hex::encode(binary.terminal_hash),
+            chain_version: binary.chain_version,
+            execution_group_id: binary.execution_group_id,
+        }
+        .validated()
+    }
+}
+
+/// Decode a hex hash; the empty `terminal_hash` of an empty log decodes to
+/// no bytes.
+fn hash_bytes(hash: &str) -> VeritasResult<Vec<u8>> {
+    hex::decode(hash).map_err(|e| load_error(format!("audit hash '{}' is not hex: {}", hash, e)))
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::Utc;
+    use serde_json::json;
+
+    use veritas_contracts::{
+        agent::{AgentInput, AgentOutput},
+        error::VeritasError,
+        execution::StepRecord,
+        policy::PolicyVerdict,
+    };
+    use veritas_core::traits::AuditWriter;
+
+    use crate::{AuditLog, InMemoryAuditWriter};
+
+    fn sample_log() -> AuditLog {
+        let writer = InMemoryAuditWriter::new("exec-bincode");
+        for step in 0..5 {
+            writer
+                .write(&StepRecord {
+                    step,
+                    input: AgentInput {
+                        kind: "user_message".to_string(),
+                        payload: json!({ "text": format!("step {step}"), "tags": ["a", "b"] }),
+                    },
+                    verdict: PolicyVerdict::Allow,
+                    output: Some(AgentOutput {
+                        kind: "response".to_string(),
+                        payload: json!({ "ok": true, "score": 0.5 }),
+                        provenance: None,
+                        advisories: vec![],
+                    }),
+                    timestamp: Utc::now(),
+                    capability_justifications: BTreeMap::new(),
+                    advisories: Vec::new(),
+                    policy_eval_micros: Some(12),
+                    propose_micros: None,
+                    verify_micros: None,
+                    request_fingerprint: "ab".repeat(32),
+                    derived_from: None,
+                    decision_trace: None,
+                    action: String::new(),
+                    resource: String::new(),
+                    break_glass: None,
+                    correlation_id: None,
+                    shadow_verdict: None,
+                    policy_tags: Vec::new(),
+                    defaulted_fields: Vec::new(),
+                })
+                .unwrap();
+        }
+        writer.finalize("exec-bincode").unwrap();
+        writer.export_log()
+    }
+
+    /// A log survives the round trip with its chain intact and encodes
+    /// smaller than JSON.
+    #[test]
+    fn test_bincode_round_trip_preserves_chain() {
+        let log = sample_log();
+
+        let bytes = log.to_bincode().unwrap();
+        let decoded = AuditLog::from_bincode(&bytes).unwrap();
+
+        assert_eq!(decoded.events.len(), 5);
+        assert_eq!(decoded.terminal_hash, log.terminal_hash);
+        assert_eq!(decoded.chain_version, log.chain_version);
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            serde_json::to_value(&log).unwrap()
+        );
+        assert!(decoded.summary().intact);
+
+        let json = serde_json::to_vec(&log).unwrap();
+        assert!(
+            bytes.len() < json.len(),
+            "bincode {} bytes, JSON {} bytes",
+            bytes.len(),
+            json.len()
+        );
+    }
+
+    /// Tampering with an encoded record is caught on decode.
+    #[test]
+    fn test_bincode_decode_rejects_tampering() {
+        let mut tampered = sample_log();
+        tampered.events[2].record.input.payload = json!({ "text": "TAMPERED" });
+        let bytes = tampered.to_bincode().unwrap();
+
+        assert!(matches!(
+            AuditLog::from_bincode(&bytes),
+            Err(VeritasError::AuditWriteFailed { .. })
+        ));
+        assert!(AuditLog::from_bincode(&bytes[..bytes.len() / 2]).is_err());
+    }
+}
@@ -9,6 +9,17 @@
 //!   2. sequence as 8-byte little-endian
 //!   3. prev_hash as UTF-8 bytes (64 ASCII hex chars)
 //!   4. canonical JSON of record (serde_json with no pretty-printing)
+//!
+//! The layout is versioned by `CHAIN_VERSION`.  Version 2 lets a record
+//! carry the optional fields version 1 lacks (see
+//! `StepRecord::has_optional_fields`): capability justifications,
+//! advisories, phase timings, a `request_fingerprint`, a `derived_from` link
+//! to an upstream chain, a policy `decision_trace`, the described `action`
+//! and `resource`, a `break_glass` override annotation, a `correlation_id`,
+//! a denial's `remediation` hint and a `shadow_verdict`.  Each is omitted
+//! from the canonical JSON when absent, so every version 1 chain hashes and
+//! verifies unchanged.  New optional fields of the same kind join version 2;
+//! only a change to how existing records hash needs a new version.
 
 use sha2::{Digest, Sha256};
 
@@ -16,6 +27,23 @@ use veritas_contracts::execution::StepRecord;
 
 use crate::event::AuditEvent;
 
+/// The newest hash-chain layout this crate writes and reads.
+///
+/// - `1` — the original record layout.
+/// - `2` — records may carry the optional fields of
+///   `StepRecord::has_optional_fields`.
+pub const CHAIN_VERSION: u32 = 2;
+
+/// The lowest chain version able to hold `events`: `2` if any record
+/// carries an optional field, otherwise `1`.
+pub fn required_chain_version(events: &[AuditEvent]) -> u32 {
+    if events.iter().any(|e| e.record.has_optional_fields()) {
+        2
+    } else {
+        1
+    }
+}
+
 /// Compute the SHA-256 hash for a single audit event.
 ///
 /// The hash commits to every field that uniquely identifies an event:
@@ -51,17 +79,22 @@ pub fn hash_event(
 
 /// Verify the integrity of a hash chain.
 ///
-/// Returns `true` when the chain is valid according to both rules:
+/// Returns `true` when the chain is valid according to all three rules:
 ///
 /// 1. **Prev-hash linkage** — each event's `prev_hash` equals the
 ///    `this_hash` of the preceding event (or `GENESIS_HASH` for event 0).
 /// 2. **Hash correctness** — each event's `this_hash` matches the value
 ///    recomputed from its own fields.
+/// 3. **Chronological order** — each event's `record.timestamp` is no
+///    earlier than the preceding event's.  Equal timestamps are allowed (a
+///    fixed clock produces them); a regression means the events were
+///    written out of order or the clock went backwards.
 ///
 /// Returns `false` the moment any mismatch is detected.  An empty chain
 /// is defined as valid.
 pub fn verify_chain(events: &[AuditEvent]) -> bool {
     let mut expected_prev = AuditEvent::GENESIS_HASH.to_string();
+    let mut previous_timestamp = None;
 
     for event in events {
         // Rule 1: the stored prev_hash must match what we expect.
@@ -80,6 +113,12 @@ pub fn verify_chain(events: &[AuditEvent]) -> bool {
             return false;
         }
 
+        // Rule 3: time must not run backwards along the chain.
+        if previous_timestamp.is_some_and(|previous| event.record.timestamp < previous) {
+            return false;
+        }
+        previous_timestamp = Some(event.record.timestamp);
+
         // Advance the expected prev_hash to this event's hash.
         expected_prev = event.this_hash.clone();
     }
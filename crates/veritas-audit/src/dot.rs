@@ -0,0 +1,201 @@
+//! Graphviz DOT rendering of audit chains.
+//!
+//! `AuditLog::to_dot` draws one chain: a node per event, labeled with its
+//! sequence, verdict and a short hash, and an edge from each event to the
+//! next along the `prev_hash` linkage.  `chains_to_dot` draws several chains
+//! — typically the logs of a `Pipeline` run — as clusters in one graph and
+//! adds a dashed edge wherever a record's `derived_from` names an event of
+//! another chain.  Render the output with e.g. `dot -Tsvg`.
+
+use std::fmt::Write;
+
+use veritas_contracts::policy::PolicyVerdict;
+
+use crate::event::{AuditEvent, AuditLog};
+
+/// Number of hex digits of a hash shown in a node label.
+const SHORT_HASH_LEN: usize = 12;
+
+impl AuditLog {
+    /// Render the chain as a Graphviz `digraph`.
+    ///
+    /// Each event is a node labeled `#<sequence>`, its verdict and the first
+    /// digits of its `this_hash`.  An edge joins two events when the later
+    /// one's `prev_hash` is the earlier one's `this_hash`, so a broken link
+    /// shows up as a missing edge.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph audit {\n    rankdir=LR;\n    node [shape=box];\n");
+        write_chain(&mut dot, self, "    ");
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Render several chains as one Graphviz `digraph`, one cluster per log.
+///
+/// Within a cluster, edges follow `prev_hash` as in `AuditLog::to_dot`.
+/// Across clusters, a dashed edge runs from an event to every record in
+/// another log whose `derived_from` is that event's `this_hash`.
+pub fn chains_to_dot(logs: &[AuditLog]) -> String {
+    let mut dot = String::from("digraph audit {\n    rankdir=LR;\n    node [shape=box];\n");
+    for (index, log) in logs.iter().enumerate() {
+        let _ = writeln!(dot, "    subgraph cluster_{index} {{");
+        let _ = writeln!(dot, "        label={};", quote(&log.execution_id));
+        write_chain(&mut dot, log, "        ");
+        dot.push_str("    }\n");
+    }
+
+    for upstream in logs.iter().flat_map(|log| &log.events) {
+        for downstream in logs.iter().flat_map(|log| &log.events) {
+            if downstream.execution_id != upstream.execution_id
+                && downstream.record.derived_from.as_deref() == Some(upstream.this_hash.as_str())
+            {
+                let _ = writeln!(
+                    dot,
+                    "    {} -> {} [style=dashed, label=\"derived_from\"];",
+                    node_id(upstream),
+                    node_id(downstream)
+                );
+            }
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Append the nodes and `prev_hash` edges of `log`, indented by `indent`.
+fn write_chain(dot: &mut String, log: &AuditLog, indent: &str) {
+    for event in &log.events {
+        let label = format!(
+            "#{}\\n{}\\n{}",
+            event.sequence,
+            verdict_label(&event.record.verdict),
+            &event.this_hash[..event.this_hash.len().min(SHORT_HASH_LEN)]
+        );
+        let _ = writeln!(dot, "{indent}{} [label=\"{label}\"];", node_id(event));
+    }
+    for pair in log.events.windows(2) {
+        if pair[1].prev_hash == pair[0].this_hash {
+            let _ = writeln!(
+                dot,
+                "{indent}{} -> {};",
+                node_id(&pair[0]),
+                node_id(&pair[1])
+            );
+        }
+    }
+}
+
+/// The DOT identifier of `event`, unique across the logs of one graph.
+fn node_id(event: &AuditEvent) -> String {
+    quote(&format!("{}#{}", event.execution_id, event.sequence))
+}
+
+/// `s` as a quoted DOT identifier.
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// The verdict shown in a node label, matching its serialized name.
+fn verdict_label(verdict: &PolicyVerdict) -> &'static str {
+    match verdict {
+        PolicyVerdict::Allow => "allow",
+        PolicyVerdict::Deny { .. } => "deny",
+        PolicyVerdict::RequireApproval { .. } => "require-approval",
+        PolicyVerdict::RequireVerification { .. } => "require-verification",
+        PolicyVerdict::RequireBoth { .. } => "require-both",
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::Utc;
+    use serde_json::json;
+
+    use veritas_contracts::{agent::AgentInput, execution::StepRecord, policy::PolicyVerdict};
+    use veritas_core::traits::AuditWriter;
+
+    use super::chains_to_dot;
+    use crate::{AuditEvent, AuditLog, InMemoryAuditWriter};
+
+    fn log(execution_id: &str, steps: u64, derived_from: Option<&str>) -> AuditLog {
+        let writer = InMemoryAuditWriter::new(execution_id);
+        for step in 0..steps {
+            let verdict = if step == 1 {
+                PolicyVerdict::Deny {
+                    reason: "blocked".to_string(),
+                    remediation: None,
+                }
+            } else {
+                PolicyVerdict::Allow
+            };
+            writer
+                .write(&StepRecord {
+                    step,
+                    input: AgentInput {
+                        kind: "user_message".to_string(),
+                        payload: json!({ "step": step }),
+                    },
+                    verdict,
+                    output: None,
+                    timestamp: Utc::now(),
+                    capability_justifications: BTreeMap::new(),
+                    advisories: Vec::new(),
+                    policy_eval_micros: None,
+                    propose_micros: None,
+                    verify_micros: None,
+                    request_fingerprint: String::new(),
+                    derived_from: derived_from.map(str::to_string),
+                    decision_trace: None,
+                    action: String::new(),
+                    resource: String::new(),
+                    break_glass: None,
+                    correlation_id: None,
+                    shadow_verdict: None,
+                    policy_tags: Vec::new(),
+                    defaulted_fields: Vec::new(),
+                })
+                .unwrap();
+        }
+        writer.finalize(execution_id).unwrap();
+        writer.export_log()
+    }
+
+    #[test]
+    fn test_to_dot_has_one_node_per_event_and_prev_hash_edges() {
+        let log = log("exec-dot", 3, None);
+        let dot = log.to_dot();
+
+        assert!(dot.starts_with("digraph audit {"));
+        assert_eq!(dot.matches("[label=").count(), 3);
+        assert_eq!(dot.matches(" -> ").count(), 2);
+        assert!(dot.contains("\"exec-dot#0\" -> \"exec-dot#1\";"));
+        assert!(dot.contains("\"exec-dot#1\" -> \"exec-dot#2\";"));
+        assert!(dot.contains("#1\\ndeny\\n"));
+        assert!(dot.contains(&log.events[2].this_hash[..12]));
+
+        // A broken link drops its edge.
+        let mut tampered = log.clone();
+        tampered.events[2].prev_hash = AuditEvent::GENESIS_HASH.to_string();
+        assert_eq!(tampered.to_dot().matches(" -> ").count(), 1);
+    }
+
+    #[test]
+    fn test_chains_to_dot_links_derived_records() {
+        let upstream = log("exec-up", 2, None);
+        let downstream = log("exec-down", 2, Some(upstream.terminal_hash.as_str()));
+        let dot = chains_to_dot(&[upstream, downstream]);
+
+        assert_eq!(dot.matches("subgraph cluster_").count(), 2);
+        assert_eq!(dot.matches("[label=\"#").count(), 4);
+        // One prev_hash edge per chain, plus one derived_from edge per
+        // downstream record.
+        assert_eq!(dot.matches(" -> ").count(), 4);
+        assert_eq!(dot.matches("style=dashed").count(), 2);
+        assert!(dot.contains("\"exec-up#1\" -> \"exec-down#0\" [style=dashed"));
+    }
+}
@@ -3,12 +3,25 @@
 //! `AuditEvent` is a single entry in the hash chain — it wraps a `StepRecord`
 //! with sequence numbering and the SHA-256 hashes that make tampering
 //! detectable.  `AuditLog` is the sealed record produced when an execution
-//! finalizes.
+//! finalizes.  `AuditLog::scan_pii` sweeps a sealed log for PII that slipped
+//! past the per-step verifier.  `AuditLog::from_reader` loads a log from any
+//! byte source and rejects it unless its chain verifies.  `AuditLog::summary`
+//! condenses a log into verdict counts, its time span and its integrity.
+//! `AuditLog::pending_approvals` lists the steps still awaiting sign-off.
+
+use std::io::BufRead;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use veritas_contracts::execution::StepRecord;
+use veritas_contracts::{
+    error::{VeritasError, VeritasResult},
+    execution::StepRecord,
+    policy::PolicyVerdict,
+};
+use veritas_verify::pii::{self, PiiKind};
+
+use crate::chain::{required_chain_version, verify_chain, CHAIN_VERSION};
 
 /// A single entry in the SHA-256 hash chain for one execution.
 ///
@@ -65,4 +78,281 @@ pub struct AuditLog {
 
     /// The `this_hash` of the last event.  Empty string if the log is empty.
     pub terminal_hash: String,
+
+    /// The hash-chain layout the log was written with (see
+    /// `chain::CHAIN_VERSION`).  Logs serialized before the field existed
+    /// read as version 1.
+    #[serde(default = "legacy_chain_version")]
+    pub chain_version: u32,
+
+    /// The `ExecutionGroup` this execution belongs to, if any.  Chains of
+    /// one multi-agent run share a group id, so an auditor can collect them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_group_id: Option<String>,
+}
+
+/// The chain version of a log serialized without one.
+fn legacy_chain_version() -> u32 {
+    1
+}
+
+/// Serialized layouts accepted by `AuditLog::from_reader`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditFormat {
+    /// One JSON-encoded `AuditEvent` per line, in chain order.  Blank lines
+    /// are ignored.
+    Jsonl,
+
+    /// A single JSON-encoded `AuditLog`, as produced by serializing
+    /// `InMemoryAuditWriter::export_log()`.
+    Json,
+}
+
+impl AuditLog {
+    /// Load an audit log from `reader` and verify its hash chain.
+    ///
+    /// The source can be anything readable — a file, an object-store body,
+    /// an HTTP response.  For `AuditFormat::Jsonl` the log's `execution_id`
+    /// is taken from the events, `terminal_hash` from the last event and
+    /// `finalized_at` from the last record's timestamp.
+    ///
+    /// A JSONL log's `chain_version` is the lowest version able to hold its
+    /// events; its events do not record an `execution_group_id`.
+    ///
+    /// Returns `VeritasError::AuditWriteFailed` if the input cannot be read
+    /// or parsed, if a JSONL stream holds no events or mixes executions, if
+    /// the chain (or a JSON log's `terminal_hash`) does not verify, or if
+    /// the log's `chain_version` is newer than this crate supports or too
+    /// old for the fields its records carry.
+    pub fn from_reader<R: BufRead>(mut reader: R, format: AuditFormat) -> VeritasResult<AuditLog> {
+        let log = match format {
+            AuditFormat::Json => {
+                let mut contents = String::new();
+                reader
+                    .read_to_string(&mut contents)
+                    .map_err(|e| load_error(format!("failed to read audit log: {}", e)))?;
+                serde_json::from_str::<AuditLog>(&contents)
+                    .map_err(|e| load_error(format!("failed to parse audit log JSON: {}", e)))?
+            }
+            AuditFormat::Jsonl => Self::from_jsonl(reader)?,
+        };
+
+        log.validated()
+    }
+
+    /// Return the log if it may be trusted: its `chain_version` is supported
+    /// and sufficient for its records, its chain verifies and its
+    /// `terminal_hash` seals the last event.
+    pub(crate) fn validated(self) -> VeritasResult<AuditLog> {
+        if self.chain_version > CHAIN_VERSION {
+            return Err(load_error(format!(
+                "audit log for execution '{}' uses chain version {}, newer than the supported {}",
+                self.execution_id, self.chain_version, CHAIN_VERSION
+            )));
+        }
+        let required = required_chain_version(&self.events);
+        if self.chain_version < required {
+            return Err(load_error(format!(
+                "audit log for execution '{}' declares chain version {} but its records need {}",
+                self.execution_id, self.chain_version, required
+            )));
+        }
+
+        if !verify_chain(&self.events) {
+            return Err(load_error(format!(
+                "audit log for execution '{}' failed chain verification",
+                self.execution_id
+            )));
+        }
+        let last_hash = self.events.last().map(|e| e.this_hash.as_str()).unwrap_or("");
+        if self.terminal_hash != last_hash {
+            return Err(load_error(format!(
+                "audit log for execution '{}' has a terminal hash that does not match its last event",
+                self.execution_id
+            )));
+        }
+
+        Ok(self)
+    }
+
+    /// Parse one `AuditEvent` per non-blank line and seal them into a log.
+    fn from_jsonl<R: BufRead>(reader: R) -> VeritasResult<AuditLog> {
+        let mut events: Vec<AuditEvent> = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line =
+                line.map_err(|e| load_error(format!("failed to read audit log: {}", e)))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event = serde_json::from_str(&line).map_err(|e| {
+                load_error(format!("failed to parse audit event on line {}: {}", index + 1, e))
+            })?;
+            events.push(event);
+        }
+
+        let last = events
+            .last()
+            .ok_or_else(|| load_error("JSONL audit log contains no events".to_string()))?;
+        let execution_id = events[0].execution_id.clone();
+        if let Some(stray) = events.iter().find(|e| e.execution_id != execution_id) {
+            return Err(load_error(format!(
+                "JSONL audit log mixes executions '{}' and '{}'",
+                execution_id, stray.execution_id
+            )));
+        }
+
+        Ok(AuditLog {
+            execution_id,
+            finalized_at: last.record.timestamp,
+            terminal_hash: last.this_hash.clone(),
+            chain_version: required_chain_version(&events),
+            events,
+            execution_group_id: None,
+        })
+    }
+
+    /// Summarize the log: how many events carry each verdict kind, the
+    /// time span of their records and whether the chain verifies.
+    ///
+    /// `intact` also requires `terminal_hash` to match the last event, the
+    /// same check `from_reader` applies.
+    pub fn summary(&self) -> AuditSummary {
+        let mut summary = AuditSummary {
+            total_events: self.events.len(),
+            allowed: 0,
+            denied: 0,
+            approval_required: 0,
+            verification_required: 0,
+            first_timestamp: self.events.first().map(|e| e.record.timestamp),
+            last_timestamp: self.events.last().map(|e| e.record.timestamp),
+            intact: verify_chain(&self.events)
+                && self.terminal_hash
+                    == self.events.last().map(|e| e.this_hash.as_str()).unwrap_or(""),
+        };
+        for event in &self.events {
+            match event.record.verdict {
+                PolicyVerdict::Allow => summary.allowed += 1,
+                PolicyVerdict::Deny { .. } => summary.denied += 1,
+                PolicyVerdict::RequireApproval { .. } | PolicyVerdict::RequireBoth { .. } => {
+                    summary.approval_required += 1
+                }
+                PolicyVerdict::RequireVerification { .. } => summary.verification_required += 1,
+            }
+        }
+        summary
+    }
+
+    /// Scan every event's input and output payloads for PII.
+    ///
+    /// A post-hoc compliance sweep: it catches leakage that per-rule output
+    /// checks missed, including PII echoed back in inputs.  Findings are
+    /// returned in chain order; see `PiiFinding` for how each is located.
+    pub fn scan_pii(&self, detectors: &[PiiKind]) -> Vec<PiiFinding> {
+        let mut findings = Vec::new();
+        for event in &self.events {
+            let mut payloads = vec![("/record/input/payload", &event.record.input.payload)];
+            if let Some(output) = &event.record.output {
+                payloads.push(("/record/output/payload", &output.payload));
+            }
+
+            for (prefix, payload) in payloads {
+                for (pointer, kind) in pii::scan_value(payload, detectors) {
+                    findings.push(PiiFinding {
+                        sequence: event.sequence,
+                        json_pointer: format!("{}{}", prefix, pointer),
+                        kind,
+                    });
+                }
+            }
+        }
+        findings
+    }
+
+    /// List the approvals this log is still waiting on, in chain order.
+    ///
+    /// A pending approval is a suspension — a `RequireApproval` or
+    /// `RequireBoth` record without output — that no later such record for
+    /// the same step supersedes: neither a completed resumption nor a fresh
+    /// suspension after a partial approval.  A rejected approval is audited
+    /// as a denial and leaves the step pending.
+    pub fn pending_approvals(&self) -> Vec<PendingApproval> {
+        let gated = |event: &AuditEvent| event.record.verdict.approval_requirement().is_some();
+
+        self.events
+            .iter()
+            .enumerate()
+            .filter_map(|(i, event)| {
+                let (reason, approver_role, _) = event.record.verdict.approval_requirement()?;
+                let superseded = self.events[i + 1..]
+                    .iter()
+                    .any(|later| later.record.step == event.record.step && gated(later));
+                (event.record.output.is_none() && !superseded).then(|| PendingApproval {
+                    sequence: event.sequence,
+                    reason: reason.to_string(),
+                    approver_role: approver_role.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Quick statistics over an `AuditLog`, computed by `AuditLog::summary`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditSummary {
+    /// Number of events in the log.
+    pub total_events: usize,
+
+    /// Events whose verdict was `Allow`.
+    pub allowed: usize,
+
+    /// Events whose verdict was `Deny` — including capability failures and
+    /// rejected outputs, which the executor audits as denials.
+    pub denied: usize,
+
+    /// Events whose verdict was `RequireApproval` or `RequireBoth`.
+    pub approval_required: usize,
+
+    /// Events whose verdict was `RequireVerification`.
+    pub verification_required: usize,
+
+    /// Timestamp of the first event's record; `None` for an empty log.
+    pub first_timestamp: Option<DateTime<Utc>>,
+
+    /// Timestamp of the last event's record; `None` for an empty log.
+    pub last_timestamp: Option<DateTime<Utc>>,
+
+    /// True if the hash chain verifies and `terminal_hash` seals it.
+    pub intact: bool,
+}
+
+/// One PII occurrence found by `AuditLog::scan_pii`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PiiFinding {
+    /// The `sequence` of the event containing the PII.
+    pub sequence: u64,
+
+    /// JSON Pointer (RFC 6901) to the offending string, relative to the
+    /// serialized `AuditEvent` — e.g. `/record/output/payload/notes`.
+    pub json_pointer: String,
+
+    /// Which detector matched.
+    pub kind: PiiKind,
+}
+
+/// A step awaiting human sign-off, found by `AuditLog::pending_approvals`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingApproval {
+    /// The `sequence` of the suspension event.
+    pub sequence: u64,
+
+    /// Why the policy required approval.
+    pub reason: String,
+
+    /// The role that must approve.
+    pub approver_role: String,
+}
+
+/// Build the error returned for an audit log that cannot be loaded.
+pub(crate) fn load_error(reason: String) -> VeritasError {
+    VeritasError::AuditWriteFailed { reason }
 }
@@ -8,6 +8,8 @@
 //! Every step the executor records is wrapped in an `AuditEvent` that links
 //! to the previous event via its SHA-256 hash.  Tampering with any event —
 //! even a single byte — breaks the chain and is detected by `verify_chain`.
+//! The hash input layout is versioned by `CHAIN_VERSION`; exported logs
+//! record the version they were written with.
 //!
 //! ## Usage
 //!
@@ -22,30 +24,61 @@
 //! assert!(writer.verify_integrity());
 //! let log = writer.export_log();
 //! ```
+//!
+//! A [`Pipeline`] runs several agents as linked chains: each later stage's
+//! records carry, as `derived_from`, the hash of the upstream event whose
+//! output became their input, and [`verify_lineage`] checks the link.
+//!
+//! `AuditLog::to_dot` renders a chain as a Graphviz graph for visualization;
+//! [`chains_to_dot`] renders the chains of a pipeline run together, with
+//! their `derived_from` links.
+//!
+//! `AuditLog::query` filters a log's events by verdict kind, described
+//! action and resource, and time range (an [`AuditQuery`]).
+//!
+//! Behind the `testing` feature, `testing::FlakyAuditWriter` fails a chosen
+//! write or the finalize, for testing how callers handle audit failures.
+//!
+//! Behind the `bincode` feature, `AuditLog::to_bincode` and
+//! `AuditLog::from_bincode` store a log in a compact binary form that is
+//! verified on load exactly like `AuditLog::from_reader`.
 
+#[cfg(any(test, feature = "bincode"))]
+mod binary;
 pub mod chain;
+pub mod dot;
 pub mod event;
 pub mod memory;
+pub mod pipeline;
+pub mod query;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
-pub use chain::{hash_event, verify_chain};
-pub use event::{AuditEvent, AuditLog};
+pub use chain::{hash_event, required_chain_version, verify_chain, CHAIN_VERSION};
+pub use dot::chains_to_dot;
+pub use event::{AuditEvent, AuditFormat, AuditLog, AuditSummary, PendingApproval, PiiFinding};
 pub use memory::InMemoryAuditWriter;
+pub use pipeline::{verify_lineage, Pipeline, PipelineStage, StageRun};
+pub use query::{AuditQuery, VerdictKind};
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
-    use chrono::Utc;
+    use std::collections::BTreeMap;
+
+    use chrono::{Duration, Utc};
     use serde_json::json;
 
     use veritas_contracts::{
-        agent::{AgentInput, AgentOutput},
+        agent::{AgentInput, AgentOutput, ExecutionGroup},
+        error::VeritasError,
         execution::StepRecord,
         policy::PolicyVerdict,
     };
     use veritas_core::traits::AuditWriter;
 
-    use super::{AuditEvent, InMemoryAuditWriter};
+    use super::{AuditEvent, AuditFormat, AuditLog, InMemoryAuditWriter};
 
     // ── Helpers ───────────────────────────────────────────────────────────────
 
@@ -61,8 +94,25 @@ mod tests {
             output: Some(AgentOutput {
                 kind: "response".to_string(),
                 payload: json!({ "text": "ok" }),
+                provenance: None,
+                advisories: vec![],
             }),
             timestamp: Utc::now(),
+            capability_justifications: BTreeMap::new(),
+            advisories: Vec::new(),
+            policy_eval_micros: None,
+            propose_micros: None,
+            verify_micros: None,
+            request_fingerprint: String::new(),
+            derived_from: None,
+            decision_trace: None,
+            action: String::new(),
+            resource: String::new(),
+            break_glass: None,
+            correlation_id: None,
+            shadow_verdict: None,
+            policy_tags: Vec::new(),
+            defaulted_fields: Vec::new(),
         }
     }
 
@@ -163,6 +213,98 @@ mod tests {
         );
     }
 
+    /// A snapshot taken between writes holds exactly the events written so
+    /// far, and later writes leave it unchanged.
+    #[test]
+    fn test_snapshot_between_writes() {
+        let writer = InMemoryAuditWriter::new("exec-snapshot");
+        assert!(writer.is_empty());
+        assert_eq!(writer.last_hash(), AuditEvent::GENESIS_HASH);
+        assert!(writer.snapshot().events.is_empty());
+
+        writer.write(&make_record(0, "first")).unwrap();
+        let first = writer.snapshot();
+        assert_eq!(writer.len(), 1);
+        assert_eq!(first.events.len(), 1);
+        assert_eq!(first.terminal_hash, writer.last_hash());
+
+        writer.write(&make_record(1, "second")).unwrap();
+        let second = writer.snapshot();
+        assert_eq!(writer.len(), 2);
+        assert_eq!(second.events.len(), 2);
+        assert_eq!(second.terminal_hash, writer.last_hash());
+        assert_eq!(second.events[1].prev_hash, first.terminal_hash);
+        assert!(super::verify_chain(&second.events));
+
+        // The earlier snapshot is a copy, not a view.
+        assert_eq!(first.events.len(), 1);
+        assert_eq!(first.events[0].this_hash, second.events[0].this_hash);
+    }
+
+    /// Snapshots taken while another thread writes are always a valid
+    /// prefix of the finished chain.
+    #[test]
+    fn test_snapshot_concurrent_with_writes() {
+        let writer = std::sync::Arc::new(InMemoryAuditWriter::new("exec-live"));
+        let background = {
+            let writer = writer.clone();
+            std::thread::spawn(move || {
+                for step in 0..50 {
+                    writer.write(&make_record(step, "live")).unwrap();
+                }
+            })
+        };
+
+        let mut snapshots = Vec::new();
+        while !background.is_finished() {
+            snapshots.push(writer.snapshot());
+        }
+        background.join().unwrap();
+
+        let finished = writer.export_log();
+        assert_eq!(finished.events.len(), 50);
+        for snapshot in snapshots {
+            assert!(super::verify_chain(&snapshot.events));
+            let hashes = |events: &[AuditEvent]| {
+                events.iter().map(|e| e.this_hash.clone()).collect::<Vec<_>>()
+            };
+            assert_eq!(
+                hashes(&snapshot.events),
+                hashes(&finished.events[..snapshot.events.len()])
+            );
+            let sealed = snapshot.events.last().map(|e| e.this_hash.clone()).unwrap_or_default();
+            assert_eq!(snapshot.terminal_hash, sealed);
+        }
+    }
+
+    /// Every chain of an execution group records the group id, and the id
+    /// survives a JSON round trip; an ungrouped log carries none.
+    #[test]
+    fn test_export_log_records_execution_group() {
+        let group = ExecutionGroup::new("pipeline-run-1");
+        let logs: Vec<AuditLog> = (0..2)
+            .map(|stage| {
+                let writer = InMemoryAuditWriter::new(group.child(stage).0.to_string())
+                    .with_execution_group(&group);
+                writer.write(&make_record(0, "stage")).unwrap();
+                writer.export_log()
+            })
+            .collect();
+
+        assert_ne!(logs[0].execution_id, logs[1].execution_id);
+        for log in &logs {
+            assert_eq!(log.execution_group_id.as_deref(), Some("pipeline-run-1"));
+        }
+
+        let json = serde_json::to_string(&logs[0]).unwrap();
+        let loaded = AuditLog::from_reader(json.as_bytes(), AuditFormat::Json).unwrap();
+        assert_eq!(loaded.execution_group_id.as_deref(), Some("pipeline-run-1"));
+
+        let ungrouped = InMemoryAuditWriter::new("exec-alone").export_log();
+        assert_eq!(ungrouped.execution_group_id, None);
+        assert!(!serde_json::to_string(&ungrouped).unwrap().contains("execution_group_id"));
+    }
+
     /// An empty chain is trivially valid — there is nothing to verify.
     #[test]
     fn test_verify_empty() {
@@ -178,4 +320,628 @@ mod tests {
             "verify_chain on empty slice must return true"
         );
     }
+
+    /// Build a correctly hashed chain over `records`, in the given order.
+    fn hashed_chain(execution_id: &str, records: Vec<StepRecord>) -> Vec<AuditEvent> {
+        let mut prev_hash = AuditEvent::GENESIS_HASH.to_string();
+        let mut events = Vec::new();
+        for (sequence, record) in records.into_iter().enumerate() {
+            let this_hash =
+                super::hash_event(execution_id, sequence as u64, &record, &prev_hash);
+            events.push(AuditEvent {
+                sequence: sequence as u64,
+                execution_id: execution_id.to_string(),
+                record,
+                prev_hash,
+                this_hash: this_hash.clone(),
+            });
+            prev_hash = this_hash;
+        }
+        events
+    }
+
+    /// A record carrying a shadow verdict needs chain version 2, hashes
+    /// it, and survives an export round trip.
+    #[test]
+    fn test_shadow_verdict_needs_chain_version_2() {
+        let writer = InMemoryAuditWriter::new("exec-shadow");
+        writer.write(&make_record(0, "plain")).unwrap();
+        let mut shadowed = make_record(1, "shadowed");
+        shadowed.shadow_verdict = Some(PolicyVerdict::Deny {
+            reason: "stricter policy would deny".to_string(),
+            remediation: None,
+        });
+        writer.write(&shadowed).unwrap();
+
+        let log = writer.export_log();
+        assert_eq!(super::required_chain_version(&log.events[..1]), 1);
+        assert_eq!(super::required_chain_version(&log.events), 2);
+        assert!(super::verify_chain(&log.events));
+
+        let mut tampered = log.events.clone();
+        tampered[1].record.shadow_verdict = Some(PolicyVerdict::Allow);
+        assert!(!super::verify_chain(&tampered));
+
+        let json = serde_json::to_string(&log).unwrap();
+        let loaded = AuditLog::from_reader(json.as_bytes(), AuditFormat::Json).unwrap();
+        assert_eq!(loaded.chain_version, 2);
+        assert_eq!(loaded.events[1].record.shadow_verdict, shadowed.shadow_verdict);
+    }
+
+    /// Timestamps that rise or repeat along the chain verify.
+    #[test]
+    fn test_verify_accepts_non_decreasing_timestamps() {
+        let start = Utc::now();
+        let offsets = [0, 1, 1, 5];
+        let records = offsets
+            .iter()
+            .enumerate()
+            .map(|(step, minutes)| {
+                let mut record = make_record(step as u64, "tick");
+                record.timestamp = start + Duration::minutes(*minutes);
+                record
+            })
+            .collect();
+
+        assert!(super::verify_chain(&hashed_chain("exec-ordered", records)));
+    }
+
+    /// A timestamp earlier than its predecessor's fails verification even
+    /// when every hash was recomputed to match.
+    #[test]
+    fn test_verify_rejects_backwards_timestamp() {
+        let start = Utc::now();
+        let mut records: Vec<StepRecord> =
+            (0..3).map(|step| make_record(step, "tick")).collect();
+        records[0].timestamp = start;
+        records[1].timestamp = start + Duration::minutes(2);
+        records[2].timestamp = start + Duration::minutes(1);
+
+        let events = hashed_chain("exec-backwards", records);
+        // The first two events are in order, so the break is the regression.
+        assert!(super::verify_chain(&events[..2]));
+        assert!(!super::verify_chain(&events));
+    }
+
+    /// Two writers sharing a FixedClock export identical logs — timestamps,
+    /// hashes and all.
+    #[test]
+    fn test_fixed_clock_export_is_reproducible() {
+        use veritas_core::clock::{Clock, FixedClock};
+
+        let instant = chrono::DateTime::parse_from_rfc3339("2026-02-18T10:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let clock = FixedClock::new(instant);
+
+        let export = || {
+            let writer = InMemoryAuditWriter::new_with_clock("exec-fixed", Box::new(clock.clone()));
+            for step in 0..3 {
+                let mut record = make_record(step, "deterministic");
+                record.timestamp = clock.now();
+                writer.write(&record).unwrap();
+            }
+            writer.export_log()
+        };
+
+        let first = export();
+        let second = export();
+
+        assert_eq!(first.finalized_at, instant);
+        assert_eq!(first.terminal_hash, second.terminal_hash);
+        assert_eq!(
+            serde_json::to_string(&first).unwrap(),
+            serde_json::to_string(&second).unwrap()
+        );
+    }
+
+    /// A PII sweep over the sealed log pinpoints the event and the JSON
+    /// Pointer of a leaked SSN, and leaves clean events alone.
+    #[test]
+    fn test_scan_pii_pinpoints_leak() {
+        use veritas_verify::pii::PiiKind;
+
+        let writer = InMemoryAuditWriter::new("exec-pii");
+        writer.write(&make_record(0, "clean")).unwrap();
+
+        let mut leaky = make_record(1, "summarize the chart");
+        leaky.output = Some(AgentOutput {
+            kind: "summary".to_string(),
+            payload: json!({ "sections": [{ "text": "fine" }, { "text": "SSN 123-45-6789" }] }),
+            provenance: None,
+            advisories: vec![],
+        });
+        writer.write(&leaky).unwrap();
+        writer.write(&make_record(2, "also clean")).unwrap();
+
+        let findings = writer.export_log().scan_pii(&PiiKind::ALL);
+
+        assert_eq!(findings.len(), 1, "got {:?}", findings);
+        assert_eq!(findings[0].sequence, 1);
+        assert_eq!(findings[0].json_pointer, "/record/output/payload/sections/1/text");
+        assert_eq!(findings[0].kind, PiiKind::Ssn);
+
+        // Restricting the detectors excludes the SSN.
+        let emails_only = writer.export_log().scan_pii(&[PiiKind::Email]);
+        assert!(emails_only.is_empty());
+    }
+
+    /// Serialize a writer's events as JSONL, one event per line.
+    fn to_jsonl(writer: &InMemoryAuditWriter) -> String {
+        writer
+            .export_log()
+            .events
+            .iter()
+            .map(|e| serde_json::to_string(e).unwrap() + "\n")
+            .collect()
+    }
+
+    /// A JSONL log read from an in-memory buffer round-trips and verifies.
+    #[test]
+    fn test_from_reader_loads_jsonl_and_json() {
+        let writer = InMemoryAuditWriter::new("exec-reader");
+        for (step, text) in ["a", "b", "c"].iter().enumerate() {
+            writer.write(&make_record(step as u64, text)).unwrap();
+        }
+        let exported = writer.export_log();
+
+        let jsonl = to_jsonl(&writer);
+        let loaded = AuditLog::from_reader(jsonl.as_bytes(), AuditFormat::Jsonl).unwrap();
+        assert_eq!(loaded.execution_id, "exec-reader");
+        assert_eq!(loaded.events.len(), 3);
+        assert_eq!(loaded.terminal_hash, exported.terminal_hash);
+        assert_eq!(loaded.finalized_at, exported.events[2].record.timestamp);
+        assert!(super::verify_chain(&loaded.events));
+
+        let json = serde_json::to_string(&exported).unwrap();
+        let loaded = AuditLog::from_reader(json.as_bytes(), AuditFormat::Json).unwrap();
+        assert_eq!(loaded.terminal_hash, exported.terminal_hash);
+    }
+
+    /// A buffer cut off mid-event, or missing an event, is rejected.
+    #[test]
+    fn test_from_reader_rejects_truncated_buffer() {
+        let writer = InMemoryAuditWriter::new("exec-truncated");
+        for step in 0..3 {
+            writer.write(&make_record(step, "payload")).unwrap();
+        }
+        let jsonl = to_jsonl(&writer);
+
+        // Cut inside the last line.
+        let cut = &jsonl[..jsonl.len() - 20];
+        assert!(matches!(
+            AuditLog::from_reader(cut.as_bytes(), AuditFormat::Jsonl),
+            Err(VeritasError::AuditWriteFailed { .. })
+        ));
+
+        // Drop the middle event: every line parses, but the chain breaks.
+        let lines: Vec<&str> = jsonl.lines().collect();
+        let gapped = format!("{}\n{}\n", lines[0], lines[2]);
+        match AuditLog::from_reader(gapped.as_bytes(), AuditFormat::Jsonl) {
+            Err(VeritasError::AuditWriteFailed { reason }) => {
+                assert!(reason.contains("chain verification"), "got: {}", reason);
+            }
+            other => panic!("expected AuditWriteFailed, got {:?}", other),
+        }
+
+        // An empty source has no chain to load.
+        assert!(AuditLog::from_reader(&b""[..], AuditFormat::Jsonl).is_err());
+    }
+
+    /// A capped writer accepts exactly `limit` events, rejects the next one,
+    /// and leaves the existing chain intact.
+    #[test]
+    fn test_max_events_rejects_overflow() {
+        let writer = InMemoryAuditWriter::new("exec-capped").with_max_events(2);
+        writer.write(&make_record(0, "a")).unwrap();
+        writer.write(&make_record(1, "b")).unwrap();
+
+        match writer.write(&make_record(2, "c")) {
+            Err(VeritasError::AuditWriteFailed { reason }) => {
+                assert_eq!(reason, "audit event limit exceeded");
+            }
+            other => panic!("expected AuditWriteFailed, got {:?}", other),
+        }
+
+        let log = writer.export_log();
+        assert_eq!(log.events.len(), 2);
+        assert!(writer.verify_integrity());
+        assert_eq!(log.terminal_hash, log.events[1].this_hash);
+    }
+
+    /// The summary counts each verdict kind, spans the records' timestamps
+    /// and reports whether the chain is intact.
+    #[test]
+    fn test_summary_counts_mixed_log() {
+        let writer = InMemoryAuditWriter::new("exec-summary");
+        let verdicts = [
+            PolicyVerdict::Allow,
+            PolicyVerdict::Deny { reason: "no".to_string(), remediation: None },
+            PolicyVerdict::Allow,
+            PolicyVerdict::RequireApproval {
+                reason: "high risk".to_string(),
+                approver_role: "attending_physician".to_string(),
+                required_attributes: BTreeMap::new(),
+            },
+        ];
+        for (step, verdict) in verdicts.into_iter().enumerate() {
+            let mut record = make_record(step as u64, "mixed");
+            record.verdict = verdict;
+            writer.write(&record).unwrap();
+        }
+
+        let log = writer.export_log();
+        let summary = log.summary();
+
+        assert_eq!(summary.total_events, 4);
+        assert_eq!(summary.allowed, 2);
+        assert_eq!(summary.denied, 1);
+        assert_eq!(summary.approval_required, 1);
+        assert_eq!(summary.verification_required, 0);
+        assert_eq!(summary.first_timestamp, Some(log.events[0].record.timestamp));
+        assert_eq!(summary.last_timestamp, Some(log.events[3].record.timestamp));
+        assert!(summary.intact);
+
+        // Tampering with a record breaks the integrity flag.
+        let mut tampered = log.clone();
+        tampered.events[1].record.verdict = PolicyVerdict::Allow;
+        let summary = tampered.summary();
+        assert_eq!(summary.allowed, 3);
+        assert!(!summary.intact);
+    }
+
+    /// Both suspensions awaiting sign-off are listed with their roles; a
+    /// suspension later resumed to completion is not.
+    #[test]
+    fn test_pending_approvals_lists_outstanding_suspensions() {
+        let approval = |reason: &str, role: &str| PolicyVerdict::RequireApproval {
+            reason: reason.to_string(),
+            approver_role: role.to_string(),
+            required_attributes: BTreeMap::new(),
+        };
+
+        let writer = InMemoryAuditWriter::new("exec-pending");
+        let events = [
+            (0, approval("controlled substance", "pharmacist"), false),
+            (0, approval("controlled substance", "pharmacist"), true),
+            (1, PolicyVerdict::Allow, true),
+            (2, approval("high-risk order", "attending_physician"), false),
+            (3, approval("off-label use", "compliance_officer"), false),
+        ];
+        for (step, verdict, completed) in events {
+            let mut record = make_record(step, "pending");
+            record.verdict = verdict;
+            if !completed {
+                record.output = None;
+            }
+            writer.write(&record).unwrap();
+        }
+
+        let pending = writer.export_log().pending_approvals();
+
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].sequence, 3);
+        assert_eq!(pending[0].reason, "high-risk order");
+        assert_eq!(pending[0].approver_role, "attending_physician");
+        assert_eq!(pending[1].sequence, 4);
+        assert_eq!(pending[1].approver_role, "compliance_officer");
+    }
+
+    /// A writer resumed from an exported 3-event log appends the 4th event
+    /// to the same chain, which verifies end to end.
+    #[test]
+    fn test_resume_continues_exported_chain() {
+        let writer = InMemoryAuditWriter::new("exec-resume");
+        for (step, text) in ["first", "second", "third"].into_iter().enumerate() {
+            writer.write(&make_record(step as u64, text)).unwrap();
+        }
+        let exported = writer.export_log();
+
+        // Round-trip through JSON, as when reloading the log from disk.
+        let reloaded: AuditLog =
+            serde_json::from_str(&serde_json::to_string(&exported).unwrap()).unwrap();
+        let resumed = InMemoryAuditWriter::resume(reloaded).unwrap();
+        resumed.write(&make_record(3, "fourth")).unwrap();
+
+        let log = resumed.export_log();
+        assert_eq!(log.execution_id, "exec-resume");
+        assert_eq!(log.events.len(), 4);
+        assert_eq!(log.events[3].sequence, 3);
+        assert_eq!(log.events[3].prev_hash, exported.terminal_hash);
+        assert!(super::verify_chain(&log.events));
+        assert!(resumed.verify_integrity());
+    }
+
+    /// Resuming from a log whose chain is broken is refused.
+    #[test]
+    fn test_resume_rejects_broken_chain() {
+        let writer = InMemoryAuditWriter::new("exec-resume-broken");
+        for step in 0..3 {
+            writer.write(&make_record(step, "original")).unwrap();
+        }
+        let mut log = writer.export_log();
+        log.events[1].record.input.payload = json!({ "text": "tampered" });
+
+        match InMemoryAuditWriter::resume(log) {
+            Err(VeritasError::AuditWriteFailed { reason }) => {
+                assert!(reason.contains("failed chain verification"), "{reason}")
+            }
+            Ok(_) => panic!("resumed from a broken chain"),
+            Err(other) => panic!("expected AuditWriteFailed, got {:?}", other),
+        }
+    }
+
+    // ── Executor integration ──────────────────────────────────────────────────
+
+    mod executor_support {
+        use std::sync::Arc;
+
+        use serde_json::json;
+
+        use veritas_contracts::{
+            agent::{AgentId, AgentInput, AgentOutput, AgentState, ExecutionId},
+            error::VeritasResult,
+            execution::StepRecord,
+            policy::{PolicyContext, PolicyVerdict},
+            verify::{OutputSchema, VerificationReport},
+        };
+        use veritas_core::traits::{Agent, AuditWriter, PolicyEngine, Verifier};
+
+        use crate::InMemoryAuditWriter;
+
+        pub struct AllowAll;
+
+        impl PolicyEngine for AllowAll {
+            fn evaluate(&self, _ctx: &PolicyContext) -> VeritasResult<PolicyVerdict> {
+                Ok(PolicyVerdict::Allow)
+            }
+        }
+
+        pub struct PassAll;
+
+        impl Verifier for PassAll {
+            fn verify(
+                &self,
+                _output: &AgentOutput,
+                _schema: &OutputSchema,
+            ) -> VeritasResult<VerificationReport> {
+                Ok(VerificationReport::from_failures(vec![]))
+            }
+        }
+
+        /// Hands an executor a writer the test keeps a handle to.
+        pub struct SharedWriter(pub Arc<InMemoryAuditWriter>);
+
+        impl AuditWriter for SharedWriter {
+            fn write(&self, record: &StepRecord) -> VeritasResult<()> {
+                self.0.write(record)
+            }
+            fn finalize(&self, execution_id: &str) -> VeritasResult<()> {
+                self.0.finalize(execution_id)
+            }
+        }
+
+        /// Echoes its input back; never reaches a terminal state.
+        pub struct EchoAgent;
+
+        impl Agent for EchoAgent {
+            fn propose(&self, _state: &AgentState, input: &AgentInput) -> VeritasResult<AgentOutput> {
+                Ok(AgentOutput {
+                    kind: "echo".to_string(),
+                    payload: input.payload.clone(),
+                    provenance: None,
+                    advisories: vec![],
+                })
+            }
+
+            fn transition(&self, state: &AgentState, _output: &AgentOutput) -> VeritasResult<AgentState> {
+                Ok(AgentState { step: state.step + 1, ..state.clone() })
+            }
+
+            fn required_capabilities(&self, _state: &AgentState, _input: &AgentInput) -> Vec<String> {
+                vec![]
+            }
+
+            fn describe_action(&self, _state: &AgentState, _input: &AgentInput) -> (String, String) {
+                ("echo".to_string(), "test".to_string())
+            }
+
+            fn is_terminal(&self, _state: &AgentState) -> bool {
+                false
+            }
+        }
+
+        pub fn schema() -> OutputSchema {
+            OutputSchema {
+                schema_id: "echo-v1".to_string(),
+                json_schema: serde_json::Value::Null,
+                rules: vec![],
+                defaults: vec![],
+            }
+        }
+
+        pub fn state(execution_id: &ExecutionId, step: u64) -> AgentState {
+            AgentState {
+                agent_id: AgentId("echo-agent".to_string()),
+                execution_id: execution_id.clone(),
+                phase: "active".to_string(),
+                context: serde_json::Value::Null,
+                step,
+                correlation_id: None,
+            }
+        }
+
+        pub fn input(text: &str) -> AgentInput {
+            AgentInput {
+                kind: "user_message".to_string(),
+                payload: json!({ "text": text }),
+            }
+        }
+    }
+
+    /// One executor backed by an AuditRegistry keeps a separate, verifiable
+    /// chain for each execution it serves.
+    #[test]
+    fn test_audit_registry_routes_per_execution() {
+        use std::{
+            collections::HashMap,
+            sync::{Arc, Mutex},
+        };
+
+        use veritas_contracts::{agent::ExecutionId, capability::CapabilitySet};
+        use veritas_core::{registry::AuditRegistry, Executor};
+
+        use executor_support::{input, schema, state, AllowAll, EchoAgent, PassAll};
+
+        // Keep concrete handles to every writer the registry creates.
+        let created: Arc<Mutex<HashMap<ExecutionId, Arc<InMemoryAuditWriter>>>> =
+            Arc::new(Mutex::new(HashMap::new()));
+        let created_by_factory = Arc::clone(&created);
+        let factory = move |execution_id: &ExecutionId| -> Arc<dyn AuditWriter> {
+            let writer = Arc::new(InMemoryAuditWriter::new(execution_id.0.to_string()));
+            created_by_factory
+                .lock()
+                .unwrap()
+                .insert(execution_id.clone(), Arc::clone(&writer));
+            writer
+        };
+        let registry = Arc::new(AuditRegistry::new(Box::new(factory)));
+
+        let executor = Executor::new_with_registry(
+            Box::new(AllowAll),
+            Arc::clone(&registry),
+            Box::new(PassAll),
+            schema(),
+        );
+
+        let exec_a = ExecutionId::new();
+        let exec_b = ExecutionId::new();
+        let caps = CapabilitySet::default();
+
+        executor.step(&EchoAgent, state(&exec_a, 0), input("a0"), &caps).unwrap();
+        executor.step(&EchoAgent, state(&exec_b, 0), input("b0"), &caps).unwrap();
+        executor.step(&EchoAgent, state(&exec_a, 1), input("a1"), &caps).unwrap();
+
+        assert_eq!(registry.len().unwrap(), 2, "one writer per execution");
+
+        let created = created.lock().unwrap();
+        let log_a = created[&exec_a].export_log();
+        let log_b = created[&exec_b].export_log();
+
+        assert_eq!(log_a.execution_id, exec_a.0.to_string());
+        assert_eq!(log_a.events.len(), 2);
+        assert_eq!(log_b.execution_id, exec_b.0.to_string());
+        assert_eq!(log_b.events.len(), 1);
+
+        assert!(super::verify_chain(&log_a.events));
+        assert!(super::verify_chain(&log_b.events));
+        assert_eq!(log_a.events[1].record.input.payload["text"], "a1");
+        assert_eq!(log_b.events[0].prev_hash, AuditEvent::GENESIS_HASH);
+
+        // A finalized execution is evicted; the other stays registered.
+        registry.finalize(&exec_b).unwrap();
+        assert!(registry.get(&exec_b).unwrap().is_none());
+        assert!(registry.get(&exec_a).unwrap().is_some());
+        assert_eq!(registry.len().unwrap(), 1);
+        assert!(created[&exec_b].verify_integrity());
+    }
+
+    /// When the writer hits its event limit the executor fails the step:
+    /// the caller gets `AuditWriteFailed` and no successor state.
+    #[test]
+    fn test_executor_surfaces_event_limit() {
+        use std::sync::Arc;
+
+        use veritas_contracts::{agent::ExecutionId, capability::CapabilitySet};
+        use veritas_core::Executor;
+
+        use executor_support::{input, schema, state, AllowAll, EchoAgent, PassAll, SharedWriter};
+
+        let writer = Arc::new(InMemoryAuditWriter::new("exec-limit").with_max_events(1));
+        let executor = Executor::new(
+            Box::new(AllowAll),
+            Box::new(SharedWriter(Arc::clone(&writer))),
+            Box::new(PassAll),
+            schema(),
+        );
+
+        let exec = ExecutionId::new();
+        let caps = CapabilitySet::default();
+
+        executor.step(&EchoAgent, state(&exec, 0), input("first"), &caps).unwrap();
+        let second = executor.step(&EchoAgent, state(&exec, 1), input("second"), &caps);
+
+        assert!(matches!(second, Err(VeritasError::AuditWriteFailed { .. })));
+        assert_eq!(writer.export_log().events.len(), 1);
+        assert!(writer.verify_integrity());
+    }
+
+    /// With timing enabled every completed record carries the three phase
+    /// timings; with timing off the fields are absent from the serialized
+    /// record.  Executor records also carry a request fingerprint, so both
+    /// logs need chain version 2, while a log of records with neither still
+    /// loads as version 1.
+    #[test]
+    fn test_timing_in_audit_is_opt_in() {
+        use std::sync::Arc;
+
+        use veritas_contracts::{agent::ExecutionId, capability::CapabilitySet};
+        use veritas_core::Executor;
+
+        use executor_support::{input, schema, state, AllowAll, EchoAgent, PassAll, SharedWriter};
+
+        let run = |timing: bool| {
+            let writer = Arc::new(InMemoryAuditWriter::new("exec-timing"));
+            let executor = Executor::new(
+                Box::new(AllowAll),
+                Box::new(SharedWriter(Arc::clone(&writer))),
+                Box::new(PassAll),
+                schema(),
+            )
+            .with_timing_in_audit(timing);
+            let exec = ExecutionId::new();
+            executor
+                .step(&EchoAgent, state(&exec, 0), input("timed"), &CapabilitySet::default())
+                .unwrap();
+            writer.export_log()
+        };
+
+        let timed = run(true);
+        let record = &timed.events[0].record;
+        assert!(record.policy_eval_micros.is_some());
+        assert!(record.propose_micros.is_some());
+        assert!(record.verify_micros.is_some());
+        assert!(record.has_fingerprint());
+        assert!(record.has_action());
+        assert_eq!(super::required_chain_version(&timed.events), 2);
+        assert_eq!(timed.chain_version, super::CHAIN_VERSION);
+
+        // A log claiming version 1 cannot hold timed, fingerprinted records.
+        let mut downgraded = timed.clone();
+        downgraded.chain_version = 1;
+        let json = serde_json::to_string(&downgraded).unwrap();
+        assert!(matches!(
+            AuditLog::from_reader(json.as_bytes(), AuditFormat::Json),
+            Err(VeritasError::AuditWriteFailed { .. })
+        ));
+
+        let untimed = run(false);
+        let record = &untimed.events[0].record;
+        assert!(!record.has_timings());
+        let serialized = serde_json::to_value(record).unwrap();
+        for field in ["policy_eval_micros", "propose_micros", "verify_micros"] {
+            assert!(serialized.get(field).is_none(), "{field} serialized");
+        }
+        assert_eq!(super::required_chain_version(&untimed.events), 2);
+
+        // Logs of records without timings or fingerprints remain readable as
+        // version 1, e.g. those written before `chain_version` existed.
+        let writer = InMemoryAuditWriter::new("exec-legacy");
+        writer.write(&make_record(0, "legacy")).unwrap();
+        let mut legacy = serde_json::to_value(writer.export_log()).unwrap();
+        legacy.as_object_mut().unwrap().remove("chain_version");
+        let loaded =
+            AuditLog::from_reader(legacy.to_string().as_bytes(), AuditFormat::Json).unwrap();
+        assert_eq!(loaded.chain_version, 1);
+    }
 }
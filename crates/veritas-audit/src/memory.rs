@@ -7,21 +7,27 @@
 //!
 //! Use `export_log()` after execution completes to obtain a sealed
 //! `AuditLog`, and `verify_integrity()` at any time to confirm the chain
-//! has not been tampered with in memory.
+//! has not been tampered with in memory.  For live progress mid-run,
+//! `snapshot()`, `len()` and `last_hash()` read the chain while another
+//! thread keeps writing.  `resume()` picks up the chain of
+//! an exported log so later writes extend it.
 
 use std::sync::{Arc, Mutex};
 
-use chrono::Utc;
 use tracing::info;
 
 use veritas_contracts::{
+    agent::ExecutionGroup,
     error::{VeritasError, VeritasResult},
     execution::StepRecord,
 };
-use veritas_core::traits::AuditWriter;
+use veritas_core::{
+    clock::{Clock, SystemClock},
+    traits::AuditWriter,
+};
 
 use crate::{
-    chain::{hash_event, verify_chain},
+    chain::{hash_event, verify_chain, CHAIN_VERSION},
     event::{AuditEvent, AuditLog},
 };
 
@@ -55,6 +61,11 @@ pub(crate) struct InMemoryState {
 pub struct InMemoryAuditWriter {
     execution_id: String,
     pub(crate) state: Arc<Mutex<InMemoryState>>,
+    clock: Box<dyn Clock>,
+    /// Maximum number of events the writer accepts; `None` is unlimited.
+    max_events: Option<usize>,
+    /// Group id stamped on exported logs; see `with_execution_group`.
+    execution_group_id: Option<String>,
 }
 
 impl InMemoryAuditWriter {
@@ -63,6 +74,13 @@ impl InMemoryAuditWriter {
     /// The internal `last_hash` is initialized to `AuditEvent::GENESIS_HASH`
     /// so the first event's `prev_hash` is automatically correct.
     pub fn new(execution_id: impl Into<String>) -> Self {
+        Self::new_with_clock(execution_id, Box::new(SystemClock))
+    }
+
+    /// Create a new writer that reads timestamps from `clock`.
+    ///
+    /// Use a `FixedClock` to make `export_log()` output reproducible.
+    pub fn new_with_clock(execution_id: impl Into<String>, clock: Box<dyn Clock>) -> Self {
         let execution_id = execution_id.into();
         let state = InMemoryState {
             events: Vec::new(),
@@ -72,9 +90,99 @@ impl InMemoryAuditWriter {
         Self {
             execution_id,
             state: Arc::new(Mutex::new(state)),
+            clock,
+            max_events: None,
+            execution_group_id: None,
         }
     }
 
+    /// Continue the chain of a previously exported `log`, e.g. one reloaded
+    /// from disk.
+    ///
+    /// The writer takes over the log's execution id, events and group id;
+    /// the next `write()` links to its terminal hash with sequence
+    /// `log.events.len()`, so the continued chain verifies as a whole.
+    /// Timestamps come from `SystemClock`.
+    ///
+    /// Returns `VeritasError::AuditWriteFailed` if the log fails the checks
+    /// `AuditLog::from_reader` applies — a broken chain, a terminal hash not
+    /// sealing the last event, an unsupported chain version — or holds events
+    /// of another execution.
+    pub fn resume(log: AuditLog) -> VeritasResult<Self> {
+        let log = log.validated()?;
+        if let Some(stray) = log.events.iter().find(|e| e.execution_id != log.execution_id) {
+            return Err(VeritasError::AuditWriteFailed {
+                reason: format!(
+                    "cannot resume audit log for execution '{}': event {} belongs to execution '{}'",
+                    log.execution_id, stray.sequence, stray.execution_id
+                ),
+            });
+        }
+
+        let state = InMemoryState {
+            sequence: log.events.len() as u64,
+            last_hash: log
+                .events
+                .last()
+                .map(|e| e.this_hash.clone())
+                .unwrap_or_else(|| AuditEvent::GENESIS_HASH.to_string()),
+            events: log.events,
+        };
+        Ok(Self {
+            execution_id: log.execution_id,
+            state: Arc::new(Mutex::new(state)),
+            clock: Box::new(SystemClock),
+            max_events: None,
+            execution_group_id: log.execution_group_id,
+        })
+    }
+
+    /// Cap the chain at `limit` events.
+    ///
+    /// Once `limit` events are held, every further `write()` fails with
+    /// `VeritasError::AuditWriteFailed` and leaves the chain untouched, so a
+    /// runaway agent cannot grow the log without bound.  The executor treats
+    /// that failure as fatal for the step.
+    pub fn with_max_events(mut self, limit: usize) -> Self {
+        self.max_events = Some(limit);
+        self
+    }
+
+    /// Record that this execution is a chain of `group`; exported logs carry
+    /// its group id.
+    pub fn with_execution_group(mut self, group: &ExecutionGroup) -> Self {
+        self.execution_group_id = Some(group.group_id().to_string());
+        self
+    }
+
+    /// A copy of the chain as it stands, taken while the execution may
+    /// still be writing.
+    ///
+    /// Safe to call from another thread concurrently with `write()`: the
+    /// snapshot holds exactly the events written before it was taken, its
+    /// `terminal_hash` seals the last of them, and later writes do not
+    /// change it.  It is the same `AuditLog` that `export_log()` returns.
+    pub fn snapshot(&self) -> AuditLog {
+        self.export_log()
+    }
+
+    /// Number of events written so far.
+    pub fn len(&self) -> usize {
+        self.state.lock().expect("audit state lock poisoned").events.len()
+    }
+
+    /// True if no event has been written yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The `this_hash` of the last event written, or
+    /// `AuditEvent::GENESIS_HASH` before any event has been written — the
+    /// `prev_hash` the next event will link to.
+    pub fn last_hash(&self) -> String {
+        self.state.lock().expect("audit state lock poisoned").last_hash.clone()
+    }
+
     /// Export a sealed `AuditLog` containing all events written so far.
     ///
     /// The `terminal_hash` is the `this_hash` of the last event, or an empty
@@ -90,15 +198,17 @@ impl InMemoryAuditWriter {
         AuditLog {
             execution_id: self.execution_id.clone(),
             events: state.events.clone(),
-            finalized_at: Utc::now(),
+            finalized_at: self.clock.now(),
             terminal_hash,
+            chain_version: CHAIN_VERSION,
+            execution_group_id: self.execution_group_id.clone(),
         }
     }
 
     /// Verify that the in-memory chain has not been tampered with.
     ///
-    /// Delegates to `verify_chain`, which checks both prev-hash linkage and
-    /// hash correctness for every event.
+    /// Delegates to `verify_chain`, which checks prev-hash linkage, hash
+    /// correctness and chronological order for every event.
     pub fn verify_integrity(&self) -> bool {
         let state = self.state.lock().expect("audit state lock poisoned");
         verify_chain(&state.events)
@@ -114,13 +224,20 @@ impl AuditWriter for InMemoryAuditWriter {
     /// wraps the record in an `AuditEvent`, appends it, then advances the
     /// sequence counter and `last_hash`.
     ///
-    /// Returns `Err(AuditWriteFailed)` only if the internal mutex is poisoned,
+    /// Returns `Err(AuditWriteFailed)` if the event limit set by
+    /// `with_max_events` is reached, or if the internal mutex is poisoned,
     /// which cannot happen under normal operation.
     fn write(&self, record: &StepRecord) -> VeritasResult<()> {
         let mut state = self.state.lock().map_err(|e| VeritasError::AuditWriteFailed {
             reason: format!("audit state lock poisoned: {}", e),
         })?;
 
+        if self.max_events.is_some_and(|max| state.events.len() >= max) {
+            return Err(VeritasError::AuditWriteFailed {
+                reason: "audit event limit exceeded".to_string(),
+            });
+        }
+
         let prev_hash = state.last_hash.clone();
         let sequence = state.sequence;
 
@@ -0,0 +1,492 @@
+//! Multi-agent pipelines with cryptographic data lineage.
+//!
+//! A `Pipeline` runs a sequence of agents, each as one step of its own
+//! execution with its own audit chain, and feeds each stage's verified
+//! output payload to the next stage as input.  The chains are linked: every
+//! record a later stage writes carries, in `StepRecord::derived_from`, the
+//! `this_hash` of the upstream event whose output became its input.
+//! `verify_lineage` checks that link, so altering an upstream record — even
+//! while recomputing its whole chain — is detected downstream.
+//!
+//! All stages run as children of one `ExecutionGroup`.  A pipeline may be
+//! given a capability ceiling: a stage granted any capability outside it is
+//! rejected before the first stage runs.  A correlation id set with
+//! `with_correlation_id` is handed unchanged to every stage, so each
+//! stage's records carry it as `StepRecord::correlation_id`.
+
+use std::sync::Arc;
+
+use veritas_contracts::{
+    agent::{AgentId, AgentInput, AgentState, ExecutionGroup},
+    capability::CapabilitySet,
+    error::{VeritasError, VeritasResult},
+    execution::{StepRecord, StepResult},
+    verify::OutputSchema,
+};
+use veritas_core::{
+    traits::{Agent, AuditWriter, PolicyEngine, Verifier},
+    Executor,
+};
+
+use crate::{chain::verify_chain, event::AuditLog, memory::InMemoryAuditWriter};
+
+/// One agent in a `Pipeline`, with the trusted components its executor is
+/// built from.
+pub struct PipelineStage {
+    /// Name of the stage in the results, e.g. `"Diagnosis Suggester"`.
+    pub label: String,
+    /// The `AgentId` the stage's agent runs under.
+    pub agent_id: String,
+    /// The agent.
+    pub agent: Box<dyn Agent>,
+    /// `AgentInput::kind` of the input the stage receives.
+    pub input_kind: String,
+    /// Capabilities granted to the stage's agent.
+    pub capabilities: CapabilitySet,
+    /// The stage's policy engine.
+    pub policy: Box<dyn PolicyEngine>,
+    /// The stage's output verifier.
+    pub verifier: Box<dyn Verifier>,
+    /// The schema the stage's output is verified against.
+    pub schema: OutputSchema,
+}
+
+/// The outcome of one stage of a pipeline run.
+#[derive(Debug)]
+pub struct StageRun {
+    /// The stage's `label`.
+    pub label: String,
+    /// What the stage's step returned.
+    pub result: VeritasResult<StepResult>,
+    /// The stage's audit log.
+    pub log: AuditLog,
+}
+
+impl StageRun {
+    /// The stage's verified output payload, if its step produced one.
+    pub fn output_payload(&self) -> Option<&serde_json::Value> {
+        match &self.result {
+            Ok(StepResult::Transitioned { output, .. } | StepResult::Complete { output, .. }) => {
+                Some(&output.payload)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// An ordered sequence of agents run as one `ExecutionGroup`.
+///
+/// ```rust,ignore
+/// let runs = Pipeline::new(ExecutionGroup::new("clinical-run-1"))
+///     .with_stage(symptom_stage)
+///     .with_stage(diagnosis_stage)
+///     .run(json!({ "patient_id": "P-1" }))?;
+/// assert!(verify_lineage(&runs[0].log, &runs[1].log));
+/// ```
+pub struct Pipeline {
+    group: ExecutionGroup,
+    stages: Vec<PipelineStage>,
+    /// The most any stage may be granted; `None` is unlimited.
+    authorized_capabilities: Option<CapabilitySet>,
+    /// Correlation id given to every stage's state; see
+    /// `with_correlation_id`.
+    correlation_id: Option<String>,
+}
+
+impl Pipeline {
+    /// Create an empty pipeline whose stages run as children of `group`.
+    pub fn new(group: ExecutionGroup) -> Self {
+        Self {
+            group,
+            stages: Vec::new(),
+            authorized_capabilities: None,
+            correlation_id: None,
+        }
+    }
+
+    /// Start every stage with `correlation_id` as its
+    /// `AgentState::correlation_id`, so all records of the run can be
+    /// grouped by it outside the audit chains.
+    pub fn with_correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    /// Limit every stage to capabilities in `ceiling`, the budget the
+    /// pipeline as a whole was authorized for.
+    pub fn with_authorized_capabilities(mut self, ceiling: CapabilitySet) -> Self {
+        self.authorized_capabilities = Some(ceiling);
+        self
+    }
+
+    /// Check every stage's granted capabilities against the ceiling set with
+    /// `with_authorized_capabilities`.
+    ///
+    /// Returns `VeritasError::CapabilityMissing` naming the first stage, in
+    /// stage order, granted a capability outside the ceiling, and the
+    /// capability.  Always passes without a ceiling.
+    pub fn check_capabilities(&self) -> VeritasResult<()> {
+        let Some(ceiling) = &self.authorized_capabilities else {
+            return Ok(());
+        };
+        for stage in &self.stages {
+            if let Some(capability) = stage.capabilities.difference(ceiling).all().next() {
+                return Err(VeritasError::CapabilityMissing {
+                    capability: capability.0.clone(),
+                    action: format!(
+                        "pipeline stage '{}' (outside the pipeline's authorized capabilities)",
+                        stage.label
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Append `stage`; it runs after every stage already added.
+    pub fn with_stage(mut self, stage: PipelineStage) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    /// Run the stages in order, starting from `payload`.
+    ///
+    /// First runs `check_capabilities`; if it fails, no stage runs.
+    ///
+    /// Stage `i` runs one step as execution `group.child(i)`, audited by its
+    /// own `InMemoryAuditWriter`.  Its verified output payload becomes the
+    /// next stage's input payload, and the next stage's records are
+    /// stamped with the `terminal_hash` of its log as `derived_from`.  The
+    /// run stops after the first stage that produces no output — a denial,
+    /// a suspension or an error — so the result holds fewer runs than
+    /// stages only then.
+    pub fn run(self, payload: serde_json::Value) -> VeritasResult<Vec<StageRun>> {
+        self.check_capabilities()?;
+
+        let mut runs = Vec::with_capacity(self.stages.len());
+        let mut payload = payload;
+        let mut derived_from = None;
+
+        for (index, stage) in self.stages.into_iter().enumerate() {
+            let execution_id = self.group.child(index);
+            let writer = Arc::new(
+                InMemoryAuditWriter::new(execution_id.0.to_string())
+                    .with_execution_group(&self.group),
+            );
+            let executor = Executor::new(
+                stage.policy,
+                Box::new(LineageWriter {
+                    inner: Arc::clone(&writer),
+                    derived_from: derived_from.take(),
+                }),
+                stage.verifier,
+                stage.schema,
+            );
+
+            let state = AgentState {
+                agent_id: AgentId(stage.agent_id),
+                execution_id,
+                phase: "active".to_string(),
+                context: serde_json::Value::Null,
+                step: 0,
+                correlation_id: self.correlation_id.clone(),
+            };
+            let input = AgentInput {
+                kind: stage.input_kind,
+                payload,
+            };
+            let result = executor.step(stage.agent.as_ref(), state, input, &stage.capabilities);
+
+            let run = StageRun {
+                label: stage.label,
+                result,
+                log: writer.export_log(),
+            };
+            let next = run.output_payload().cloned();
+            let upstream_hash = run.log.terminal_hash.clone();
+            runs.push(run);
+            match next {
+                Some(next) => {
+                    payload = next;
+                    derived_from = Some(upstream_hash);
+                }
+                None => break,
+            }
+        }
+
+        Ok(runs)
+    }
+}
+
+/// Return true if `downstream` is verifiably derived from `upstream`.
+///
+/// Both chains must verify, and every record of `downstream` must carry the
+/// `this_hash` of `upstream`'s last event as `derived_from`.  An upstream
+/// event altered after the fact either breaks the upstream chain or, if the
+/// chain was recomputed, changes that hash.
+pub fn verify_lineage(upstream: &AuditLog, downstream: &AuditLog) -> bool {
+    let Some(last) = upstream.events.last() else {
+        return false;
+    };
+    verify_chain(&upstream.events)
+        && verify_chain(&downstream.events)
+        && !downstream.events.is_empty()
+        && downstream
+            .events
+            .iter()
+            .all(|e| e.record.derived_from.as_deref() == Some(last.this_hash.as_str()))
+}
+
+/// Stamps `derived_from` on every record before it is chained.
+struct LineageWriter {
+    inner: Arc<InMemoryAuditWriter>,
+    derived_from: Option<String>,
+}
+
+impl AuditWriter for LineageWriter {
+    fn write(&self, record: &StepRecord) -> VeritasResult<()> {
+        let record = StepRecord {
+            derived_from: self.derived_from.clone(),
+            ..record.clone()
+        };
+        self.inner.write(&record)
+    }
+
+    fn finalize(&self, execution_id: &str) -> VeritasResult<()> {
+        self.inner.finalize(execution_id)
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use veritas_contracts::{
+        agent::{AgentInput, AgentOutput, AgentState, ExecutionGroup},
+        capability::CapabilitySet,
+        error::VeritasResult,
+        policy::{PolicyContext, PolicyVerdict},
+        verify::{OutputSchema, VerificationReport},
+    };
+    use veritas_core::traits::{Agent, PolicyEngine, Verifier};
+
+    use super::{verify_lineage, Pipeline, PipelineStage, StageRun};
+    use crate::chain::hash_event;
+
+    struct AllowAll;
+
+    impl PolicyEngine for AllowAll {
+        fn evaluate(&self, _ctx: &PolicyContext) -> VeritasResult<PolicyVerdict> {
+            Ok(PolicyVerdict::Allow)
+        }
+    }
+
+    struct PassAll;
+
+    impl Verifier for PassAll {
+        fn verify(
+            &self,
+            _output: &AgentOutput,
+            _schema: &OutputSchema,
+        ) -> VeritasResult<VerificationReport> {
+            Ok(VerificationReport::from_failures(vec![]))
+        }
+    }
+
+    /// Appends its name to the input's `trail` array.
+    struct TrailAgent(&'static str);
+
+    impl Agent for TrailAgent {
+        fn propose(&self, _state: &AgentState, input: &AgentInput) -> VeritasResult<AgentOutput> {
+            let mut payload = input.payload.clone();
+            payload["trail"]
+                .as_array_mut()
+                .expect("trail array")
+                .push(json!(self.0));
+            Ok(AgentOutput {
+                kind: "trail".to_string(),
+                payload,
+                provenance: None,
+                advisories: vec![],
+            })
+        }
+
+        fn transition(&self, state: &AgentState, _output: &AgentOutput) -> VeritasResult<AgentState> {
+            Ok(AgentState {
+                step: state.step + 1,
+                phase: "complete".to_string(),
+                ..state.clone()
+            })
+        }
+
+        fn required_capabilities(&self, _state: &AgentState, _input: &AgentInput) -> Vec<String> {
+            vec![]
+        }
+
+        fn describe_action(&self, _state: &AgentState, _input: &AgentInput) -> (String, String) {
+            ("append".to_string(), "trail".to_string())
+        }
+
+        fn is_terminal(&self, state: &AgentState) -> bool {
+            state.phase == "complete"
+        }
+    }
+
+    fn stage(name: &'static str) -> PipelineStage {
+        PipelineStage {
+            label: name.to_string(),
+            agent_id: format!("{name}-agent"),
+            agent: Box::new(TrailAgent(name)),
+            input_kind: "trail".to_string(),
+            capabilities: CapabilitySet::default(),
+            policy: Box::new(AllowAll),
+            verifier: Box::new(PassAll),
+            schema: OutputSchema {
+                schema_id: "trail-v1".to_string(),
+                json_schema: serde_json::Value::Null,
+                rules: vec![],
+                defaults: vec![],
+            },
+        }
+    }
+
+    fn two_stage_run() -> Vec<StageRun> {
+        Pipeline::new(ExecutionGroup::new("lineage-run"))
+            .with_stage(stage("first"))
+            .with_stage(stage("second"))
+            .run(json!({ "trail": [] }))
+            .unwrap()
+    }
+
+    /// Stage 2's record references stage 1's terminal hash, and its input
+    /// is stage 1's output.
+    #[test]
+    fn test_stage_two_references_stage_one_terminal_hash() {
+        let runs = two_stage_run();
+
+        assert_eq!(runs.len(), 2);
+        let (first, second) = (&runs[0].log, &runs[1].log);
+        assert_eq!(first.events[0].record.derived_from, None);
+        assert_eq!(
+            second.events[0].record.derived_from.as_deref(),
+            Some(first.terminal_hash.as_str())
+        );
+        assert_eq!(second.events[0].record.input.payload, json!({ "trail": ["first"] }));
+        assert_eq!(runs[1].output_payload(), Some(&json!({ "trail": ["first", "second"] })));
+        assert_eq!(second.chain_version, crate::chain::CHAIN_VERSION);
+        assert!(verify_lineage(first, second));
+    }
+
+    /// The pipeline's correlation id reaches every record of all three
+    /// stages unchanged; without one, records carry none.
+    #[test]
+    fn test_correlation_id_reaches_every_stage() {
+        let runs = Pipeline::new(ExecutionGroup::new("correlated-run"))
+            .with_correlation_id("req-7f3a")
+            .with_stage(stage("first"))
+            .with_stage(stage("second"))
+            .with_stage(stage("third"))
+            .run(json!({ "trail": [] }))
+            .unwrap();
+
+        assert_eq!(runs.len(), 3);
+        for run in &runs {
+            assert!(!run.log.events.is_empty());
+            for event in &run.log.events {
+                assert_eq!(event.record.correlation_id.as_deref(), Some("req-7f3a"));
+            }
+            assert_eq!(crate::chain::required_chain_version(&run.log.events), 2);
+            assert!(crate::chain::verify_chain(&run.log.events));
+        }
+        assert!(verify_lineage(&runs[0].log, &runs[1].log));
+        assert!(verify_lineage(&runs[1].log, &runs[2].log));
+
+        for run in &two_stage_run() {
+            assert!(run.log.events.iter().all(|e| e.record.correlation_id.is_none()));
+        }
+    }
+
+    /// Altering stage 1 breaks the link, whether or not its chain is
+    /// recomputed to hide the edit.
+    #[test]
+    fn test_altering_stage_one_breaks_lineage() {
+        let runs = two_stage_run();
+        let second = &runs[1].log;
+
+        let mut tampered = runs[0].log.clone();
+        tampered.events[0].record.output.as_mut().unwrap().payload = json!({ "trail": ["forged"] });
+        assert!(!verify_lineage(&tampered, second));
+
+        // Recompute the tampered chain so it verifies on its own.
+        let event = &mut tampered.events[0];
+        event.this_hash =
+            hash_event(&event.execution_id, event.sequence, &event.record, &event.prev_hash);
+        assert!(crate::chain::verify_chain(&tampered.events));
+        assert!(!verify_lineage(&tampered, second));
+    }
+
+    /// A stage granted `pa.write` under a read-only ceiling is rejected
+    /// before any stage runs.
+    #[test]
+    fn test_stage_beyond_capability_ceiling_is_rejected() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        use veritas_contracts::{capability::Capability, error::VeritasError};
+
+        struct CountingPolicy(Arc<AtomicUsize>);
+
+        impl PolicyEngine for CountingPolicy {
+            fn evaluate(&self, _ctx: &PolicyContext) -> VeritasResult<PolicyVerdict> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                Ok(PolicyVerdict::Allow)
+            }
+        }
+
+        let mut read_only = CapabilitySet::default();
+        read_only.grant(Capability::new("pa.read"));
+        let mut writer_caps = read_only.clone();
+        writer_caps.grant(Capability::new("pa.write"));
+
+        let evaluations = Arc::new(AtomicUsize::new(0));
+        let reader = PipelineStage {
+            capabilities: read_only.clone(),
+            policy: Box::new(CountingPolicy(Arc::clone(&evaluations))),
+            ..stage("eligibility")
+        };
+        let submitter = PipelineStage {
+            capabilities: writer_caps,
+            ..stage("submission")
+        };
+
+        let result = Pipeline::new(ExecutionGroup::new("ceiling-run"))
+            .with_authorized_capabilities(read_only.clone())
+            .with_stage(reader)
+            .with_stage(submitter)
+            .run(json!({ "trail": [] }));
+
+        match result {
+            Err(VeritasError::CapabilityMissing { capability, action }) => {
+                assert_eq!(capability, "pa.write");
+                assert!(action.contains("'submission'"), "{action}");
+            }
+            other => panic!("expected CapabilityMissing, got {other:?}"),
+        }
+        assert_eq!(evaluations.load(Ordering::SeqCst), 0, "no stage may run");
+
+        // Within the ceiling the same reader stage runs.
+        let runs = Pipeline::new(ExecutionGroup::new("ceiling-run"))
+            .with_authorized_capabilities(read_only.clone())
+            .with_stage(PipelineStage {
+                capabilities: read_only,
+                ..stage("eligibility")
+            })
+            .run(json!({ "trail": [] }))
+            .unwrap();
+        assert_eq!(runs.len(), 1);
+    }
+}
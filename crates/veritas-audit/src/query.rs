@@ -0,0 +1,190 @@
+//! Filtering the events of an audit log.
+//!
+//! `AuditLog::query` returns the events matching an `AuditQuery`: by verdict
+//! kind, by the action and resource the agent described (recorded since
+//! chain version 2), and by a time range over the record timestamps.  Unset
+//! criteria match every event, so `AuditQuery::default()` returns the whole
+//! log.
+
+use chrono::{DateTime, Utc};
+
+use veritas_contracts::policy::PolicyVerdict;
+
+use crate::event::{AuditEvent, AuditLog};
+
+/// The kind of a `PolicyVerdict`, without its payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VerdictKind {
+    Allow,
+    Deny,
+    RequireApproval,
+    RequireVerification,
+    RequireBoth,
+}
+
+impl VerdictKind {
+    /// The kind of `verdict`.
+    pub fn of(verdict: &PolicyVerdict) -> Self {
+        match verdict {
+            PolicyVerdict::Allow => VerdictKind::Allow,
+            PolicyVerdict::Deny { .. } => VerdictKind::Deny,
+            PolicyVerdict::RequireApproval { .. } => VerdictKind::RequireApproval,
+            PolicyVerdict::RequireVerification { .. } => VerdictKind::RequireVerification,
+            PolicyVerdict::RequireBoth { .. } => VerdictKind::RequireBoth,
+        }
+    }
+}
+
+/// Criteria for `AuditLog::query`.  An event matches when it meets every
+/// criterion that is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditQuery {
+    /// Only events whose verdict is of this kind.  Capability failures and
+    /// rejected outputs are audited as `Deny`.
+    pub verdict: Option<VerdictKind>,
+
+    /// Only events whose record's `action` equals this.
+    pub action: Option<String>,
+
+    /// Only events whose record's `resource` equals this.
+    pub resource: Option<String>,
+
+    /// Only events recorded at or after this time.
+    pub since: Option<DateTime<Utc>>,
+
+    /// Only events recorded before this time.
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl AuditQuery {
+    /// True if `event` meets every criterion that is set.
+    pub fn matches(&self, event: &AuditEvent) -> bool {
+        let record = &event.record;
+        self.verdict.is_none_or(|kind| VerdictKind::of(&record.verdict) == kind)
+            && self.action.as_ref().is_none_or(|action| record.action == *action)
+            && self.resource.as_ref().is_none_or(|resource| record.resource == *resource)
+            && self.since.is_none_or(|since| record.timestamp >= since)
+            && self.until.is_none_or(|until| record.timestamp < until)
+    }
+}
+
+impl AuditLog {
+    /// The events matching `filter`, in chain order.
+    ///
+    /// Records written before chain version 2, and denials written before
+    /// the action was described (an abort condition or the step limit), have
+    /// an empty action and resource, so an `action` or `resource` criterion
+    /// never matches them.
+    pub fn query(&self, filter: AuditQuery) -> Vec<&AuditEvent> {
+        self.events.iter().filter(|event| filter.matches(event)).collect()
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::{Duration, TimeZone, Utc};
+    use serde_json::json;
+
+    use veritas_contracts::{agent::AgentInput, execution::StepRecord, policy::PolicyVerdict};
+    use veritas_core::traits::AuditWriter;
+
+    use super::{AuditQuery, VerdictKind};
+    use crate::{AuditLog, InMemoryAuditWriter};
+
+    /// A log of four steps: (action, resource, denied).
+    fn log() -> AuditLog {
+        let steps = [
+            ("read", "patient/1", false),
+            ("write", "patient/1", true),
+            ("read", "patient/2", false),
+            ("delete", "patient/2", true),
+        ];
+        let start = Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap();
+        let writer = InMemoryAuditWriter::new("exec-query");
+        for (step, (action, resource, denied)) in steps.into_iter().enumerate() {
+            let verdict = if denied {
+                PolicyVerdict::Deny {
+                    reason: format!("{action} not allowed"),
+                    remediation: None,
+                }
+            } else {
+                PolicyVerdict::Allow
+            };
+            writer
+                .write(&StepRecord {
+                    step: step as u64,
+                    input: AgentInput {
+                        kind: "user_message".to_string(),
+                        payload: json!({ "step": step }),
+                    },
+                    verdict,
+                    output: None,
+                    timestamp: start + Duration::minutes(step as i64),
+                    capability_justifications: BTreeMap::new(),
+                    advisories: Vec::new(),
+                    policy_eval_micros: None,
+                    propose_micros: None,
+                    verify_micros: None,
+                    request_fingerprint: String::new(),
+                    derived_from: None,
+                    decision_trace: None,
+                    action: action.to_string(),
+                    resource: resource.to_string(),
+                    break_glass: None,
+                    correlation_id: None,
+                    shadow_verdict: None,
+                    policy_tags: Vec::new(),
+                    defaulted_fields: Vec::new(),
+                })
+                .unwrap();
+        }
+        writer.export_log()
+    }
+
+    fn steps(events: &[&crate::AuditEvent]) -> Vec<u64> {
+        events.iter().map(|e| e.record.step).collect()
+    }
+
+    #[test]
+    fn test_query_all_denials() {
+        let log = log();
+        let denied = log.query(AuditQuery {
+            verdict: Some(VerdictKind::Deny),
+            ..AuditQuery::default()
+        });
+        assert_eq!(steps(&denied), [1, 3]);
+
+        // No criteria: every event.
+        assert_eq!(log.query(AuditQuery::default()).len(), 4);
+    }
+
+    #[test]
+    fn test_query_by_resource_action_and_time() {
+        let log = log();
+        let on_patient_2 = log.query(AuditQuery {
+            resource: Some("patient/2".to_string()),
+            ..AuditQuery::default()
+        });
+        assert_eq!(steps(&on_patient_2), [2, 3]);
+
+        let reads_of_patient_2 = log.query(AuditQuery {
+            action: Some("read".to_string()),
+            resource: Some("patient/2".to_string()),
+            ..AuditQuery::default()
+        });
+        assert_eq!(steps(&reads_of_patient_2), [2]);
+
+        // `since` is inclusive, `until` exclusive.
+        let start = log.events[0].record.timestamp;
+        let window = log.query(AuditQuery {
+            since: Some(start + Duration::minutes(1)),
+            until: Some(start + Duration::minutes(3)),
+            ..AuditQuery::default()
+        });
+        assert_eq!(steps(&window), [1, 2]);
+    }
+}
@@ -0,0 +1,243 @@
+//! Test doubles for exercising audit failure paths.
+//!
+//! `FlakyAuditWriter` is a real hash-chained writer that can be told to fail
+//! its Nth `write()` or its `finalize()`.  The executor treats every audit
+//! failure as fatal for the step, so a test can place the failure at any
+//! pipeline point — the denial record, the approval suspension, the
+//! completed step, the finalize of a terminal step — and assert how the
+//! executor or a pipeline built on it reacts.
+//!
+//! Available to this crate's own tests and, to downstream crates, behind the
+//! `testing` feature.
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use veritas_contracts::{
+    error::{VeritasError, VeritasResult},
+    execution::StepRecord,
+};
+use veritas_core::traits::AuditWriter;
+
+use crate::{event::AuditLog, memory::InMemoryAuditWriter};
+
+/// An `AuditWriter` that fails on demand and otherwise behaves like
+/// `InMemoryAuditWriter`.
+///
+/// Cheap to clone; clones share the same chain and write counter, so a test
+/// can hand one clone to the executor and inspect the other.
+#[derive(Clone)]
+pub struct FlakyAuditWriter {
+    inner: Arc<InMemoryAuditWriter>,
+    writes: Arc<AtomicUsize>,
+    fail_on_write: Option<usize>,
+    fail_on_finalize: bool,
+}
+
+impl FlakyAuditWriter {
+    /// Create a writer for `execution_id` that never fails until configured
+    /// to.
+    pub fn new(execution_id: impl Into<String>) -> Self {
+        Self {
+            inner: Arc::new(InMemoryAuditWriter::new(execution_id)),
+            writes: Arc::new(AtomicUsize::new(0)),
+            fail_on_write: None,
+            fail_on_finalize: false,
+        }
+    }
+
+    /// Fail the `n`th call to `write()` (counting from 1).  The failed
+    /// record is not appended; earlier and later writes succeed.
+    pub fn fail_on_write(mut self, n: usize) -> Self {
+        self.fail_on_write = Some(n);
+        self
+    }
+
+    /// Fail every call to `finalize()`.
+    pub fn fail_on_finalize(mut self) -> Self {
+        self.fail_on_finalize = true;
+        self
+    }
+
+    /// Number of `write()` calls made so far, failed ones included.
+    pub fn write_attempts(&self) -> usize {
+        self.writes.load(Ordering::SeqCst)
+    }
+
+    /// Export the events successfully written so far.
+    pub fn export_log(&self) -> AuditLog {
+        self.inner.export_log()
+    }
+}
+
+impl AuditWriter for FlakyAuditWriter {
+    fn write(&self, record: &StepRecord) -> VeritasResult<()> {
+        let attempt = self.writes.fetch_add(1, Ordering::SeqCst) + 1;
+        if self.fail_on_write == Some(attempt) {
+            return Err(VeritasError::AuditWriteFailed {
+                reason: format!("injected failure on audit write {}", attempt),
+            });
+        }
+        self.inner.write(record)
+    }
+
+    fn finalize(&self, execution_id: &str) -> VeritasResult<()> {
+        if self.fail_on_finalize {
+            return Err(VeritasError::AuditWriteFailed {
+                reason: format!("injected failure finalizing execution '{}'", execution_id),
+            });
+        }
+        self.inner.finalize(execution_id)
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use veritas_contracts::{
+        agent::{AgentId, AgentInput, AgentOutput, AgentState, ExecutionId},
+        capability::CapabilitySet,
+        execution::StepResult,
+        policy::{PolicyContext, PolicyVerdict},
+        verify::{OutputSchema, VerificationReport},
+    };
+    use veritas_core::{
+        traits::{Agent, PolicyEngine, Verifier},
+        Executor,
+    };
+
+    use super::*;
+
+    struct FixedPolicy(PolicyVerdict);
+
+    impl PolicyEngine for FixedPolicy {
+        fn evaluate(&self, _ctx: &PolicyContext) -> VeritasResult<PolicyVerdict> {
+            Ok(self.0.clone())
+        }
+    }
+
+    struct PassVerifier;
+
+    impl Verifier for PassVerifier {
+        fn verify(&self, _output: &AgentOutput, _schema: &OutputSchema) -> VeritasResult<VerificationReport> {
+            Ok(VerificationReport::from_failures(vec![]))
+        }
+    }
+
+    /// Counts its proposals; terminal after one step when `terminal` is set.
+    #[derive(Default)]
+    struct CountingAgent {
+        terminal: bool,
+        proposals: Mutex<usize>,
+    }
+
+    impl Agent for CountingAgent {
+        fn propose(&self, _state: &AgentState, _input: &AgentInput) -> VeritasResult<AgentOutput> {
+            *self.proposals.lock().unwrap() += 1;
+            Ok(AgentOutput {
+                kind: "result".to_string(),
+                payload: serde_json::json!({ "ok": true }),
+                provenance: None,
+                advisories: vec![],
+            })
+        }
+
+        fn transition(&self, state: &AgentState, _output: &AgentOutput) -> VeritasResult<AgentState> {
+            Ok(AgentState { step: state.step + 1, ..state.clone() })
+        }
+
+        fn required_capabilities(&self, _state: &AgentState, _input: &AgentInput) -> Vec<String> {
+            vec![]
+        }
+
+        fn describe_action(&self, _state: &AgentState, _input: &AgentInput) -> (String, String) {
+            ("read".to_string(), "records".to_string())
+        }
+
+        fn is_terminal(&self, state: &AgentState) -> bool {
+            self.terminal && state.step > 0
+        }
+    }
+
+    fn step(
+        verdict: PolicyVerdict,
+        agent: &CountingAgent,
+        writer: &FlakyAuditWriter,
+    ) -> VeritasResult<StepResult> {
+        let executor = Executor::new(
+            Box::new(FixedPolicy(verdict)),
+            Box::new(writer.clone()),
+            Box::new(PassVerifier),
+            OutputSchema {
+                schema_id: "flaky-v1".to_string(),
+                json_schema: serde_json::Value::Null,
+                rules: vec![],
+                defaults: vec![],
+            },
+        );
+        let state = AgentState {
+            agent_id: AgentId("flaky-agent".to_string()),
+            execution_id: ExecutionId::new(),
+            phase: "active".to_string(),
+            context: serde_json::Value::Null,
+            step: 0,
+            correlation_id: None,
+        };
+        let input = AgentInput {
+            kind: "read".to_string(),
+            payload: serde_json::Value::Null,
+        };
+        executor.step(agent, state, input, &CapabilitySet::default())
+    }
+
+    /// A failed write of the completed step errors the step: the agent
+    /// proposed, but the caller receives no successor state and the chain
+    /// holds nothing.
+    #[test]
+    fn test_failed_completed_step_write_errors_without_transition() {
+        let agent = CountingAgent::default();
+        let writer = FlakyAuditWriter::new("exec-flaky").fail_on_write(1);
+
+        let result = step(PolicyVerdict::Allow, &agent, &writer);
+
+        assert!(matches!(result, Err(VeritasError::AuditWriteFailed { .. })), "got {:?}", result);
+        assert_eq!(*agent.proposals.lock().unwrap(), 1);
+        assert_eq!(writer.write_attempts(), 1);
+        assert!(writer.export_log().events.is_empty());
+    }
+
+    /// A failed denial write surfaces as an error rather than a `Denied`
+    /// result, and the agent never runs.
+    #[test]
+    fn test_failed_denial_write_errors_before_propose() {
+        let agent = CountingAgent::default();
+        let writer = FlakyAuditWriter::new("exec-flaky").fail_on_write(1);
+
+        let verdict = PolicyVerdict::Deny {
+            reason: "not permitted".to_string(),
+            remediation: None,
+        };
+        let result = step(verdict, &agent, &writer);
+
+        assert!(matches!(result, Err(VeritasError::AuditWriteFailed { .. })), "got {:?}", result);
+        assert_eq!(*agent.proposals.lock().unwrap(), 0);
+    }
+
+    /// A failed finalize errors the terminal step even though its record was
+    /// written.
+    #[test]
+    fn test_failed_finalize_errors_terminal_step() {
+        let agent = CountingAgent { terminal: true, ..Default::default() };
+        let writer = FlakyAuditWriter::new("exec-flaky").fail_on_finalize();
+
+        let result = step(PolicyVerdict::Allow, &agent, &writer);
+
+        assert!(matches!(result, Err(VeritasError::AuditWriteFailed { .. })), "got {:?}", result);
+        assert_eq!(writer.export_log().events.len(), 1);
+    }
+}
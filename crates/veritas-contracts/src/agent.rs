@@ -5,6 +5,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::provenance::Provenance;
+
 /// Stable, human-readable identifier for an agent type.
 ///
 /// Used across policy rules, audit logs, and capability grants.
@@ -24,14 +26,57 @@ impl ExecutionId {
     pub fn new() -> Self {
         Self(uuid::Uuid::new_v4())
     }
+
+    /// Derive an execution ID from `seed`.
+    ///
+    /// The same seed always yields the same ID (a name-based UUID), so
+    /// replays and tests can name their executions reproducibly.  Distinct
+    /// seeds yield distinct IDs.
+    pub fn from_seed(seed: &str) -> Self {
+        Self(uuid::Uuid::new_v5(&EXECUTION_ID_NAMESPACE, seed.as_bytes()))
+    }
 }
 
+/// The UUID namespace seeded execution IDs are derived in.
+const EXECUTION_ID_NAMESPACE: uuid::Uuid = uuid::Uuid::from_u128(0x5f0c_2a1e_7b3d_4c89_a6e2_91d4_3b7f_0e58);
+
 impl Default for ExecutionId {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// One logical execution made of several chains, such as the stages of a
+/// multi-agent pipeline.
+///
+/// Each stage runs under its own `ExecutionId`; the group vends those IDs
+/// from its `group_id` and the stage index, so an auditor holding the group
+/// id can recompute every child ID and correlate the chains of one run.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ExecutionGroup {
+    group_id: String,
+}
+
+impl ExecutionGroup {
+    /// Create a group identified by `group_id`.
+    pub fn new(group_id: impl Into<String>) -> Self {
+        Self { group_id: group_id.into() }
+    }
+
+    /// The identifier shared by every chain in the group.
+    pub fn group_id(&self) -> &str {
+        &self.group_id
+    }
+
+    /// The execution ID of the child at `index`.
+    ///
+    /// Stable: the same group and index always yield the same ID, and
+    /// different indices yield different IDs.
+    pub fn child(&self, index: usize) -> ExecutionId {
+        ExecutionId::from_seed(&format!("{}/{}", self.group_id, index))
+    }
+}
+
 /// A snapshot of all state the agent carries between steps.
 ///
 /// The runtime treats this as an opaque blob it passes to `Agent::propose()`
@@ -49,6 +94,12 @@ pub struct AgentState {
     pub context: serde_json::Value,
     /// Monotonically increasing step counter within this execution.
     pub step: u64,
+    /// Caller-chosen id grouping every step of one logical request, across
+    /// executions and pipeline stages, for distributed tracing.  The
+    /// executor copies it into each `StepRecord` and carries it onto the
+    /// next state; the runtime never interprets it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
 }
 
 /// An input event delivered to the agent at the start of a step.
@@ -73,4 +124,14 @@ pub struct AgentOutput {
     pub kind: String,
     /// Arbitrary JSON body. The verifier inspects this against the OutputSchema.
     pub payload: serde_json::Value,
+    /// Optional signature binding `payload` to the agent that produced it.
+    /// Attach with `provenance::sign_output`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
+    /// Free-text caveats the agent attaches to its own output (e.g. "dose
+    /// adjusted for renal function").  Informational only: the executor
+    /// never blocks on them, and they are distinct from verifier warnings.
+    /// Recorded in the audit trail with the rest of the output.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub advisories: Vec<String>,
 }
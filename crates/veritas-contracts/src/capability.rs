@@ -3,16 +3,26 @@
 //! VERITAS uses a capability model: an agent may only take an action if it
 //! holds the corresponding capability. Capabilities are granted at startup
 //! and are never elevated at runtime — this is a hard security invariant.
+//!
+//! Sets compose with `intersection`, `union` and `difference`, so layered
+//! authorization (what a user delegated ∩ what the organization permits) is
+//! one call rather than a manual loop.
+//!
+//! A `CapabilityCatalog` lists the capability names a deployment knows, so
+//! policies can be checked for misspelled requirements.
 
-use std::collections::HashSet;
+use std::collections::BTreeMap;
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// An opaque capability token.
 ///
 /// Capability names should be namespaced and descriptive:
 /// e.g. "phi:read", "phi:write", "order:submit", "audit:write".
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// `namespace`, `action_part` and `is_under` read the `<namespace>.<action>`
+/// or `<namespace>:<action>` structure of such a name.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Capability(pub String);
 
 impl Capability {
@@ -20,30 +30,282 @@ impl Capability {
     pub fn new(name: impl Into<String>) -> Self {
         Self(name.into())
     }
+
+    /// Everything before the last `.` or `:` — `"drug-database"` for
+    /// `"drug-database.read"`, `"phi.notes"` for `"phi.notes:read"`.
+    /// `None` for a name without either separator.
+    pub fn namespace(&self) -> Option<&str> {
+        self.0.rsplit_once(['.', ':']).map(|(namespace, _)| namespace)
+    }
+
+    /// Everything after the last `.` or `:` — `"read"` for
+    /// `"drug-database.read"`.  The whole name when it has no separator.
+    pub fn action_part(&self) -> &str {
+        self.0.rsplit_once(['.', ':']).map_or(self.0.as_str(), |(_, action)| action)
+    }
+
+    /// True if the name lies anywhere beneath `namespace`: it continues
+    /// past `namespace` with a `.` or `:` and at least one more character.
+    /// `"drug-database.interactions.read"` is under both `"drug-database"`
+    /// and `"drug-database.interactions"`; `"drug-database"` itself and
+    /// `"drug-databases.read"` are not under `"drug-database"`.
+    pub fn is_under(&self, namespace: &str) -> bool {
+        self.0
+            .strip_prefix(namespace)
+            .and_then(|rest| rest.strip_prefix(['.', ':']))
+            .is_some_and(|rest| !rest.is_empty())
+    }
 }
 
 /// The full set of capabilities granted to an agent execution.
 ///
 /// This is constructed at startup by the hosting application and passed
 /// to the executor. The executor checks it before calling `agent.propose()`.
+///
+/// Each grant may carry a justification (`grant_with_reason`) explaining why
+/// the capability was granted; the executor copies the justifications of the
+/// capabilities a step used into that step's audit record.
+///
+/// A grant may also expire (`grant_until`); the executor treats an expired
+/// capability as not held, and leaves it out of the policy context.
 #[derive(Debug, Clone, Default)]
 pub struct CapabilitySet {
-    inner: HashSet<Capability>,
+    inner: BTreeMap<Capability, Grant>,
+}
+
+/// The bookkeeping kept for one granted capability.
+#[derive(Debug, Clone, Default)]
+struct Grant {
+    justification: Option<String>,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+impl Grant {
+    /// True if the grant has not expired at `now`.
+    fn is_live_at(&self, now: DateTime<Utc>) -> bool {
+        !matches!(self.expires_at, Some(at) if now >= at)
+    }
+
+    /// A grant expiring at `expires_at` that keeps this grant's
+    /// justification, falling back to `other`'s.
+    fn merged(&self, other: &Grant, expires_at: Option<DateTime<Utc>>) -> Grant {
+        Grant {
+            justification: self.justification.clone().or_else(|| other.justification.clone()),
+            expires_at,
+        }
+    }
+
+    /// The earlier of two expiries, where `None` means "never".
+    fn earliest(a: Option<DateTime<Utc>>, b: Option<DateTime<Utc>>) -> Option<DateTime<Utc>> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// The later of two expiries, where `None` means "never".
+    fn latest(a: Option<DateTime<Utc>>, b: Option<DateTime<Utc>>) -> Option<DateTime<Utc>> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            _ => None,
+        }
+    }
 }
 
 impl CapabilitySet {
-    /// Grant a capability to this set.
+    /// Grant a capability to this set, without a justification.
+    ///
+    /// Re-granting an already-held capability clears its justification.
     pub fn grant(&mut self, capability: Capability) {
-        self.inner.insert(capability);
+        self.inner.insert(capability, Grant::default());
+    }
+
+    /// Grant a capability to this set and record why it was granted.
+    pub fn grant_with_reason(&mut self, capability: Capability, reason: impl Into<String>) {
+        self.inner.insert(
+            capability,
+            Grant {
+                justification: Some(reason.into()),
+                expires_at: None,
+            },
+        );
     }
 
-    /// Return true if the set contains the given capability.
+    /// Grant a capability that stops counting as held at `expires_at`.
+    pub fn grant_until(&mut self, capability: Capability, expires_at: DateTime<Utc>) {
+        self.inner.insert(
+            capability,
+            Grant {
+                justification: None,
+                expires_at: Some(expires_at),
+            },
+        );
+    }
+
+    /// Return true if the set contains the given capability, expired or not.
     pub fn has(&self, capability: &Capability) -> bool {
-        self.inner.contains(capability)
+        self.inner.contains_key(capability)
+    }
+
+    /// Return true if the set contains `capability` and its grant has not
+    /// expired at `now`.  A grant expires at exactly its `expires_at`.
+    pub fn has_at(&self, capability: &Capability, now: DateTime<Utc>) -> bool {
+        self.inner
+            .get(capability)
+            .is_some_and(|grant| grant.is_live_at(now))
+    }
+
+    /// The grants that have not expired at `now`, unchanged — what the set
+    /// holds as far as a check at `now` is concerned.
+    pub fn live_at(&self, now: DateTime<Utc>) -> CapabilitySet {
+        let inner = self
+            .inner
+            .iter()
+            .filter(|(_, grant)| grant.is_live_at(now))
+            .map(|(capability, grant)| (capability.clone(), grant.clone()))
+            .collect();
+        CapabilitySet { inner }
+    }
+
+    /// When the grant of `capability` expires; `None` if it never does or
+    /// the capability is not held.
+    pub fn expires_at(&self, capability: &Capability) -> Option<DateTime<Utc>> {
+        self.inner.get(capability).and_then(|grant| grant.expires_at)
+    }
+
+    /// The justification recorded when `capability` was granted.
+    ///
+    /// Returns `None` if the capability is not held or was granted with
+    /// plain `grant`.
+    pub fn justification(&self, capability: &Capability) -> Option<&str> {
+        self.inner
+            .get(capability)
+            .and_then(|grant| grant.justification.as_deref())
     }
 
-    /// Return an iterator over all granted capabilities.
+    /// Return an iterator over all granted capabilities, sorted by name.
+    ///
+    /// The order is stable regardless of grant order, so anything built from
+    /// it — the policy context, audit records, hashes — is deterministic.
     pub fn all(&self) -> impl Iterator<Item = &Capability> {
-        self.inner.iter()
+        self.inner.keys()
+    }
+
+    /// The capabilities held by both sets.
+    ///
+    /// Each kept grant expires at the earlier of its two expiries and keeps
+    /// `self`'s justification, falling back to `other`'s.
+    pub fn intersection(&self, other: &CapabilitySet) -> CapabilitySet {
+        let inner = self
+            .inner
+            .iter()
+            .filter_map(|(capability, mine)| {
+                let theirs = other.inner.get(capability)?;
+                let expires_at = Grant::earliest(mine.expires_at, theirs.expires_at);
+                Some((capability.clone(), mine.merged(theirs, expires_at)))
+            })
+            .collect();
+        CapabilitySet { inner }
+    }
+
+    /// The capabilities held by either set.
+    ///
+    /// A capability held by both expires at the later of its two expiries
+    /// and keeps `self`'s justification, falling back to `other`'s.
+    pub fn union(&self, other: &CapabilitySet) -> CapabilitySet {
+        let mut inner = other.inner.clone();
+        for (capability, mine) in &self.inner {
+            let grant = match other.inner.get(capability) {
+                Some(theirs) => {
+                    mine.merged(theirs, Grant::latest(mine.expires_at, theirs.expires_at))
+                }
+                None => mine.clone(),
+            };
+            inner.insert(capability.clone(), grant);
+        }
+        CapabilitySet { inner }
+    }
+
+    /// The capabilities held by `self` but not by `other`, with `self`'s
+    /// grants unchanged.
+    pub fn difference(&self, other: &CapabilitySet) -> CapabilitySet {
+        let inner = self
+            .inner
+            .iter()
+            .filter(|(capability, _)| !other.inner.contains_key(*capability))
+            .map(|(capability, grant)| (capability.clone(), grant.clone()))
+            .collect();
+        CapabilitySet { inner }
+    }
+}
+
+/// The capabilities a deployment knows about, each with a description.
+///
+/// A single source of truth for capability names: policies can be checked
+/// against it at load time (`TomlPolicyEngine::from_toml_str_validated`) so
+/// a typo such as `clinical_data.read` for `clinical-data.read` is caught
+/// before it silently denies every request.
+///
+/// A lenient catalog (the default) only warns about unknown names; a strict
+/// one (`strict(true)`) makes them a configuration error.
+#[derive(Debug, Clone, Default)]
+pub struct CapabilityCatalog {
+    entries: BTreeMap<Capability, String>,
+    strict: bool,
+}
+
+impl CapabilityCatalog {
+    /// Create an empty, lenient catalog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `capability` with a human-readable `description`.
+    ///
+    /// Registering a name twice replaces its description.
+    pub fn register(&mut self, capability: Capability, description: impl Into<String>) {
+        self.entries.insert(capability, description.into());
+    }
+
+    /// Make unknown capabilities an error (`true`) or a warning (`false`)
+    /// when a policy is validated against this catalog.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// True if unknown capabilities are an error rather than a warning.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Return true if `capability` is registered.
+    pub fn contains(&self, capability: &Capability) -> bool {
+        self.entries.contains_key(capability)
+    }
+
+    /// The description `capability` was registered with.
+    pub fn description(&self, capability: &Capability) -> Option<&str> {
+        self.entries.get(capability).map(String::as_str)
+    }
+
+    /// Return true if a policy requirement names a known capability.
+    ///
+    /// A namespace wildcard such as `drug-database.*` is known when at least
+    /// one registered capability lies under the namespace.
+    pub fn recognizes(&self, requirement: &str) -> bool {
+        match requirement.strip_suffix(".*") {
+            Some(namespace) => self.entries.keys().any(|cap| {
+                cap.0
+                    .strip_prefix(namespace)
+                    .is_some_and(|rest| rest.starts_with('.') && rest.len() > 1)
+            }),
+            None => self.entries.contains_key(&Capability::new(requirement)),
+        }
+    }
+
+    /// Every registered capability and its description, sorted by name.
+    pub fn all(&self) -> impl Iterator<Item = (&Capability, &str)> {
+        self.entries.iter().map(|(cap, description)| (cap, description.as_str()))
     }
 }
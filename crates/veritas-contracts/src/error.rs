@@ -30,6 +30,11 @@ pub enum VeritasError {
     #[error("state machine error: {reason}")]
     StateMachineError { reason: String },
 
+    /// A human approval presented to resume a suspended step was refused —
+    /// held by the wrong role, or expired.
+    #[error("approval rejected: {reason}")]
+    ApprovalRejected { reason: String },
+
     /// A required configuration value is missing or invalid.
     #[error("configuration error: {reason}")]
     ConfigError { reason: String },
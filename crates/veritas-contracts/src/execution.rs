@@ -1,14 +1,23 @@
 //! Step-level execution results and audit records.
 //!
 //! `StepResult` is what the executor returns to the caller after each step.
+//! `StepOutcome` condenses a step's result into a flat tag for metrics.
 //! `StepRecord` is what gets written to the audit log — one per step.
+//! `request_fingerprint` identifies the request a record answers.
+//! `BreakGlass` annotates a record whose failed verification was overridden.
+
+use std::collections::BTreeMap;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use crate::{
     agent::{AgentInput, AgentOutput, AgentState},
-    policy::PolicyVerdict,
+    capability::CapabilitySet,
+    error::{VeritasError, VeritasResult},
+    policy::{DenyCode, PolicyVerdict},
+    verify::VerificationFailure,
 };
 
 /// The outcome of a single executor step.
@@ -26,12 +35,23 @@ pub enum StepResult {
         next_state: AgentState,
         /// The verified output from this step.
         output: AgentOutput,
+        /// Tags the policy's `continue` rules applied (`PolicyDecision::tags`).
+        tags: Vec<String>,
     },
 
     /// A policy rule denied the action. The agent's proposal was never evaluated.
     Denied {
         /// The policy's denial reason.
         reason: String,
+        /// The category of the denial.
+        code: DenyCode,
+        /// The policy rule that denied, when the engine reports one.
+        rule_id: Option<String>,
+        /// How to proceed, from the `PolicyVerdict::Deny` that denied.
+        remediation: Option<String>,
+        /// Tags the policy applied on the way to the denial; empty when the
+        /// step was stopped before the policy decided.
+        tags: Vec<String>,
         /// The state at the time of denial, preserved for audit purposes.
         final_state: AgentState,
     },
@@ -47,6 +67,8 @@ pub enum StepResult {
         approver_role: String,
         /// The full state at suspension time, to be restored when resuming.
         suspended_state: AgentState,
+        /// Tags the policy applied on the way to the approval requirement.
+        tags: Vec<String>,
     },
 
     /// The agent reached a terminal state. Execution is finished.
@@ -55,6 +77,119 @@ pub enum StepResult {
         final_state: AgentState,
         /// The final output produced before termination.
         output: AgentOutput,
+        /// Tags the policy's `continue` rules applied (`PolicyDecision::tags`).
+        tags: Vec<String>,
+    },
+}
+
+/// What `Executor::preflight()` predicts `step()` would do with the same
+/// arguments.
+///
+/// Preflight runs only the policy and capability gates; it never calls the
+/// agent's `propose()`, the verifier, or the audit writer.  `Allowed` therefore
+/// means the step would reach `propose()`, not that its output would pass
+/// verification.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PreflightResult {
+    /// Policy allows the action and every required capability is held.
+    Allowed,
+
+    /// Policy denied the action (or could not be evaluated).
+    Denied {
+        /// The policy's denial reason.
+        reason: String,
+        /// How to proceed, from the `PolicyVerdict::Deny` that denied; `None`
+        /// for a denial the policy did not decide.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        remediation: Option<String>,
+    },
+
+    /// Policy allows the action but the agent lacks a required capability.
+    CapabilityMissing {
+        /// The first required capability that is not granted.
+        capability: String,
+        /// The action that required it.
+        action: String,
+    },
+
+    /// The action requires human approval before proceeding.
+    RequiresApproval {
+        /// Why approval is required.
+        reason: String,
+        /// The role that must provide approval.
+        approver_role: String,
+    },
+}
+
+/// A flat, payload-free summary of how a step ended.
+///
+/// Derived from what `Executor::step()` returned via `StepOutcome::from_result`,
+/// so logging and metrics code can tag steps without destructuring
+/// `StepResult` or matching on error variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StepOutcome {
+    /// The step passed every gate and produced a verified output.
+    Allowed,
+    /// The policy denied the action.
+    Denied,
+    /// The agent lacked a required capability.
+    CapabilityMissing,
+    /// The policy suspended the step pending human approval.
+    AwaitingApproval,
+    /// The verifier rejected the agent's output.
+    VerificationRejected,
+}
+
+impl StepOutcome {
+    /// Classify the return value of `Executor::step()`.
+    ///
+    /// Returns `None` for errors that are not a step decision — audit write
+    /// failures, state machine, configuration and schema errors — which
+    /// callers should report as failures rather than outcomes.
+    pub fn from_result(result: &VeritasResult<StepResult>) -> Option<Self> {
+        match result {
+            Ok(StepResult::Transitioned { .. }) | Ok(StepResult::Complete { .. }) => {
+                Some(StepOutcome::Allowed)
+            }
+            Ok(StepResult::Denied { .. }) => Some(StepOutcome::Denied),
+            Ok(StepResult::AwaitingApproval { .. }) => Some(StepOutcome::AwaitingApproval),
+            Err(VeritasError::PolicyDenied { .. }) | Err(VeritasError::ApprovalRejected { .. }) => {
+                Some(StepOutcome::Denied)
+            }
+            Err(VeritasError::CapabilityMissing { .. }) => Some(StepOutcome::CapabilityMissing),
+            Err(VeritasError::VerificationFailed { .. }) => Some(StepOutcome::VerificationRejected),
+            Err(VeritasError::AuditWriteFailed { .. })
+            | Err(VeritasError::StateMachineError { .. })
+            | Err(VeritasError::ConfigError { .. })
+            | Err(VeritasError::SchemaValidation { .. }) => None,
+        }
+    }
+
+    /// The kebab-case tag for this outcome, e.g. `"capability-missing"`.
+    ///
+    /// Matches the serde representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StepOutcome::Allowed => "allowed",
+            StepOutcome::Denied => "denied",
+            StepOutcome::CapabilityMissing => "capability-missing",
+            StepOutcome::AwaitingApproval => "awaiting-approval",
+            StepOutcome::VerificationRejected => "verification-rejected",
+        }
+    }
+}
+
+/// Why the executor rejected a step, reported back to the agent.
+///
+/// Passed to `Agent::on_rejected()` so the agent can adjust its next input
+/// (e.g. regenerate a summary without the PII the verifier flagged).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RejectionReason {
+    /// The proposed output failed verification.
+    VerificationFailed {
+        /// Every rule failure reported by the verifier.
+        failures: Vec<VerificationFailure>,
     },
 }
 
@@ -74,4 +209,229 @@ pub struct StepRecord {
     pub output: Option<AgentOutput>,
     /// Wall-clock time the record was created (UTC).
     pub timestamp: DateTime<Utc>,
+    /// Why each capability this step used was granted, keyed by capability
+    /// name.  Only capabilities granted with a justification appear; empty
+    /// when the step never got past the capability check.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub capability_justifications: BTreeMap<String, String>,
+    /// The agent's own advisories on the delivered output, copied from
+    /// `AgentOutput::advisories`.  Empty when the step produced no output.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub advisories: Vec<String>,
+    /// Microseconds the policy engine took to decide.  Only recorded by an
+    /// executor built `with_timing_in_audit(true)`; omitted from the
+    /// serialized (and hashed) record when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_eval_micros: Option<u64>,
+    /// Microseconds `Agent::propose` took; recorded like `policy_eval_micros`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub propose_micros: Option<u64>,
+    /// Microseconds output verification took; recorded like
+    /// `policy_eval_micros`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verify_micros: Option<u64>,
+    /// `request_fingerprint` of the action, resource, held capabilities and
+    /// input payload this step answered, so identical re-submissions can be
+    /// matched across executions.  Empty — and omitted from the serialized
+    /// record — when written before the action was described (an abort
+    /// condition or the step limit stopped the step) and on records predating
+    /// audit chain version 2.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_fingerprint: String,
+    /// The `this_hash` of the upstream audit event whose output became this
+    /// step's input, when the step ran as a later stage of a pipeline.
+    /// Links the stages' chains so data lineage can be checked across them.
+    /// Omitted from the serialized record when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derived_from: Option<String>,
+    /// How the policy engine reached `verdict` — the engine's
+    /// `PolicyDecision::trace`, e.g. the deciding rule and why earlier rules
+    /// were skipped.  Only recorded by an executor built
+    /// `with_decision_trace_in_audit(true)`; omitted from the serialized
+    /// record when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decision_trace: Option<serde_json::Value>,
+    /// The action the agent described for this step (`Agent::describe_action`).
+    /// Empty — and omitted from the serialized record — when written before
+    /// the action was described, like `request_fingerprint`, and on records
+    /// predating audit chain version 2.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub action: String,
+    /// The resource the agent described for this step; recorded like
+    /// `action`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub resource: String,
+    /// Set when the step was run through `Executor::step_break_glass` and
+    /// its output was delivered despite failing verification.  Omitted from
+    /// the serialized record when `None`, so it only ever appears on
+    /// overridden steps.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub break_glass: Option<BreakGlass>,
+    /// The caller's correlation id for the logical request this step served,
+    /// copied from `AgentState::correlation_id`.  Omitted from the serialized
+    /// record when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+    /// What the executor's shadow policy (`Executor::with_shadow_policy`)
+    /// would have decided for this step.  Recorded for comparison only — it
+    /// never affects the step.  Omitted from the serialized record when
+    /// `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadow_verdict: Option<PolicyVerdict>,
+    /// The tags the policy's `continue` rules applied to this step
+    /// (`PolicyDecision::tags`).  Omitted from the serialized record when
+    /// empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub policy_tags: Vec<String>,
+    /// JSON Pointers to the output fields the runtime filled from schema
+    /// defaults (`Executor::with_output_normalization`) because the agent
+    /// left them out.  Omitted from the serialized record when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub defaulted_fields: Vec<String>,
+}
+
+/// A break-glass override of a failed output verification: who authorized
+/// delivering the output anyway, why, and what verification rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BreakGlass {
+    /// Why the output had to be delivered despite failing verification.
+    pub justification: String,
+    /// Who authorized the override.
+    pub authorizer: String,
+    /// The verification failures that were overridden, as
+    /// `"[rule_id] message"`.  Empty until the executor fills it in.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overridden_failures: Vec<String>,
+}
+
+impl BreakGlass {
+    /// An override authorized by `authorizer` for `justification`.
+    pub fn new(justification: impl Into<String>, authorizer: impl Into<String>) -> Self {
+        Self {
+            justification: justification.into(),
+            authorizer: authorizer.into(),
+            overridden_failures: Vec::new(),
+        }
+    }
+}
+
+impl StepRecord {
+    /// True if the record carries any of the optional fields that version 1
+    /// records lack — capability justifications, advisories, phase timings,
+    /// a request fingerprint, an upstream link, a decision trace, the
+    /// described action or resource, a break-glass override, a correlation
+    /// id, a remediation hint, a shadow verdict, policy tags or defaulted
+    /// output fields.  Such records need audit chain version 2.
+    pub fn has_optional_fields(&self) -> bool {
+        !self.capability_justifications.is_empty()
+            || !self.advisories.is_empty()
+            || self.has_timings()
+            || self.has_fingerprint()
+            || self.is_derived()
+            || self.has_decision_trace()
+            || self.has_action()
+            || self.has_break_glass()
+            || self.has_correlation_id()
+            || self.has_remediation()
+            || self.has_shadow_verdict()
+            || self.has_policy_tags()
+            || self.has_defaulted_fields()
+    }
+
+    /// True if the record carries any phase timing.
+    pub fn has_timings(&self) -> bool {
+        self.policy_eval_micros.is_some() || self.propose_micros.is_some() || self.verify_micros.is_some()
+    }
+
+    /// True if the record carries a request fingerprint.
+    pub fn has_fingerprint(&self) -> bool {
+        !self.request_fingerprint.is_empty()
+    }
+
+    /// True if the record references an upstream event.
+    pub fn is_derived(&self) -> bool {
+        self.derived_from.is_some()
+    }
+
+    /// True if the record carries a policy decision trace.
+    pub fn has_decision_trace(&self) -> bool {
+        self.decision_trace.is_some()
+    }
+
+    /// True if the record carries its described action or resource.
+    pub fn has_action(&self) -> bool {
+        !self.action.is_empty() || !self.resource.is_empty()
+    }
+
+    /// True if the record's output was delivered through a break-glass
+    /// override.
+    pub fn has_break_glass(&self) -> bool {
+        self.break_glass.is_some()
+    }
+
+    /// True if the record carries a correlation id.
+    pub fn has_correlation_id(&self) -> bool {
+        self.correlation_id.is_some()
+    }
+
+    /// True if the record's verdict is a denial carrying a remediation hint.
+    pub fn has_remediation(&self) -> bool {
+        matches!(self.verdict, PolicyVerdict::Deny { remediation: Some(_), .. })
+    }
+
+    /// True if the record carries a shadow policy verdict.
+    pub fn has_shadow_verdict(&self) -> bool {
+        self.shadow_verdict.is_some()
+    }
+
+    /// True if the record carries tags applied by the policy.
+    pub fn has_policy_tags(&self) -> bool {
+        !self.policy_tags.is_empty()
+    }
+
+    /// True if the record names output fields filled from schema defaults.
+    pub fn has_defaulted_fields(&self) -> bool {
+        !self.defaulted_fields.is_empty()
+    }
+}
+
+/// SHA-256 fingerprint of a request, as a lowercase 64-character hex string.
+///
+/// Hashes the canonical JSON array `[action, resource, capabilities,
+/// payload]`, where `capabilities` are the held capability names sorted and
+/// `payload`'s object keys are sorted at every depth.  Expiry is not
+/// consulted: the executor passes only the grants live at the step's clock
+/// (`CapabilitySet::live_at`), the same ones its policy context carries.  Two requests agree on
+/// the fingerprint exactly when they agree on all four, whatever the grant or
+/// key insertion order.
+pub fn request_fingerprint(
+    action: &str,
+    resource: &str,
+    capabilities: &CapabilitySet,
+    payload: &serde_json::Value,
+) -> String {
+    // `CapabilitySet::all` already yields names in sorted order.
+    let capabilities: Vec<&str> = capabilities.all().map(|c| c.0.as_str()).collect();
+    let canonical = serde_json::json!([action, resource, capabilities, canonicalize(payload)]);
+    let bytes = serde_json::to_vec(&canonical).expect("a JSON value always serializes");
+
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// `value` with object keys inserted in sorted order at every depth, so its
+/// serialization does not depend on how the map preserves key order.
+fn canonicalize(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(k, _)| *k);
+            serde_json::Value::Object(
+                entries.into_iter().map(|(k, v)| (k.clone(), canonicalize(v))).collect(),
+            )
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.iter().map(canonicalize).collect())
+        }
+        other => other.clone(),
+    }
 }
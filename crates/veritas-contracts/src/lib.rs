@@ -3,23 +3,56 @@
 //! Shared types, schemas, and contracts for the VERITAS runtime.
 //!
 //! All crates in the workspace import from here. No business logic lives in
-//! this crate — only data definitions and error types.
+//! this crate — only data definitions, error types, and the signing format
+//! of output provenance (which must be fixed in one place to be verifiable).
 
 pub mod agent;
 pub mod capability;
 pub mod error;
 pub mod execution;
 pub mod policy;
+pub mod provenance;
 pub mod verify;
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use agent::ExecutionId;
+    use agent::{ExecutionGroup, ExecutionId};
     use capability::{Capability, CapabilitySet};
     use error::VeritasError;
     use policy::PolicyVerdict;
 
+    // ── Capability ───────────────────────────────────────────────────────────
+
+    #[test]
+    fn capability_splits_namespace_and_action() {
+        let cap = Capability::new("drug-database.read");
+        assert_eq!(cap.namespace(), Some("drug-database"));
+        assert_eq!(cap.action_part(), "read");
+        assert!(cap.is_under("drug-database"));
+
+        // The split is on the last separator, of either kind.
+        let nested = Capability::new("phi.notes:read");
+        assert_eq!(nested.namespace(), Some("phi.notes"));
+        assert_eq!(nested.action_part(), "read");
+        assert!(nested.is_under("phi"));
+        assert!(nested.is_under("phi.notes"));
+
+        let bare = Capability::new("audit");
+        assert_eq!(bare.namespace(), None);
+        assert_eq!(bare.action_part(), "audit");
+    }
+
+    #[test]
+    fn capability_is_under_needs_a_separator_boundary() {
+        let cap = Capability::new("drug-database.read");
+        assert!(!cap.is_under("drug"));
+        assert!(!cap.is_under("drug-database.read"));
+        assert!(!Capability::new("drug-databases.read").is_under("drug-database"));
+        assert!(!Capability::new("drug-database.").is_under("drug-database"));
+        assert!(Capability::new("phi:read").is_under("phi"));
+    }
+
     // ── CapabilitySet ────────────────────────────────────────────────────────
 
     #[test]
@@ -57,16 +90,131 @@ mod tests {
         assert!(names.contains("c"));
     }
 
+    #[test]
+    fn capability_set_all_is_sorted_regardless_of_grant_order() {
+        let orders = [
+            ["phi:write", "audit:write", "phi:read", "order:submit"],
+            ["order:submit", "phi:read", "phi:write", "audit:write"],
+            ["audit:write", "order:submit", "phi:read", "phi:write"],
+        ];
+
+        for order in orders {
+            let mut caps = CapabilitySet::default();
+            for name in order {
+                caps.grant(Capability::new(name));
+            }
+            let names: Vec<&str> = caps.all().map(|c| c.0.as_str()).collect();
+            assert_eq!(names, ["audit:write", "order:submit", "phi:read", "phi:write"]);
+        }
+    }
+
     #[test]
     fn capability_set_duplicate_grant_is_idempotent() {
         let mut caps = CapabilitySet::default();
         caps.grant(Capability::new("phi:read"));
         caps.grant(Capability::new("phi:read"));
 
-        // HashSet semantics: duplicates are silently dropped.
+        // Set semantics: duplicates are silently dropped.
         assert_eq!(caps.all().count(), 1);
     }
 
+    #[test]
+    fn capability_set_grant_with_reason_exposes_justification() {
+        let mut caps = CapabilitySet::default();
+        let phi_read = Capability::new("phi:read");
+        let phi_write = Capability::new("phi:write");
+
+        caps.grant_with_reason(phi_read.clone(), "attending physician for encounter E-17");
+        caps.grant(phi_write.clone());
+
+        assert!(caps.has(&phi_read));
+        assert_eq!(
+            caps.justification(&phi_read),
+            Some("attending physician for encounter E-17")
+        );
+        // Plain grant records no justification; unheld capabilities have none.
+        assert_eq!(caps.justification(&phi_write), None);
+        assert_eq!(caps.justification(&Capability::new("order:submit")), None);
+    }
+
+    fn at(rfc3339: &str) -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&chrono::Utc)
+    }
+
+    fn names(caps: &CapabilitySet) -> Vec<String> {
+        let mut names: Vec<String> = caps.all().map(|c| c.0.clone()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn capability_set_intersection_keeps_common() {
+        let mut user = CapabilitySet::default();
+        user.grant_with_reason(Capability::new("phi:read"), "delegated by Dr. Ortiz");
+        user.grant(Capability::new("order:submit"));
+        let mut org = CapabilitySet::default();
+        org.grant(Capability::new("phi:read"));
+        org.grant(Capability::new("audit:write"));
+
+        let both = user.intersection(&org);
+        assert_eq!(names(&both), vec!["phi:read"]);
+        assert_eq!(
+            both.justification(&Capability::new("phi:read")),
+            Some("delegated by Dr. Ortiz")
+        );
+
+        assert_eq!(names(&user.difference(&org)), vec!["order:submit"]);
+    }
+
+    #[test]
+    fn capability_set_union_merges() {
+        let mut a = CapabilitySet::default();
+        a.grant(Capability::new("phi:read"));
+        let mut b = CapabilitySet::default();
+        b.grant_with_reason(Capability::new("phi:read"), "on call");
+        b.grant(Capability::new("audit:write"));
+
+        let merged = a.union(&b);
+        assert_eq!(names(&merged), vec!["audit:write", "phi:read"]);
+        assert_eq!(merged.justification(&Capability::new("phi:read")), Some("on call"));
+    }
+
+    #[test]
+    fn capability_set_intersection_takes_earliest_expiry() {
+        let phi_read = Capability::new("phi:read");
+        let mut user = CapabilitySet::default();
+        user.grant_until(phi_read.clone(), at("2026-03-01T12:00:00Z"));
+        let mut org = CapabilitySet::default();
+        org.grant_until(phi_read.clone(), at("2026-03-01T09:00:00Z"));
+        let mut forever = CapabilitySet::default();
+        forever.grant(phi_read.clone());
+
+        let both = user.intersection(&org);
+        assert_eq!(both.expires_at(&phi_read), Some(at("2026-03-01T09:00:00Z")));
+        assert!(both.has_at(&phi_read, at("2026-03-01T08:59:59Z")));
+        assert!(!both.has_at(&phi_read, at("2026-03-01T09:00:00Z")));
+
+        // A grant that never expires does not lift the other's expiry.
+        assert_eq!(
+            user.intersection(&forever).expires_at(&phi_read),
+            Some(at("2026-03-01T12:00:00Z"))
+        );
+        // In a union, the longer-lived grant wins.
+        assert_eq!(user.union(&org).expires_at(&phi_read), Some(at("2026-03-01T12:00:00Z")));
+        assert_eq!(user.union(&forever).expires_at(&phi_read), None);
+
+        // Only grants unexpired at the given instant stay live.
+        let mut mixed = org.clone();
+        mixed.grant(Capability::new("audit:write"));
+        assert_eq!(
+            names(&mixed.live_at(at("2026-03-01T08:59:59Z"))),
+            vec!["audit:write", "phi:read"]
+        );
+        assert_eq!(names(&mixed.live_at(at("2026-03-01T09:00:00Z"))), vec!["audit:write"]);
+    }
+
     // ── PolicyVerdict serde round-trip ───────────────────────────────────────
 
     #[test]
@@ -81,17 +229,43 @@ mod tests {
     fn policy_verdict_deny_round_trips() {
         let original = PolicyVerdict::Deny {
             reason: "patient data access outside care team".to_string(),
+            remediation: None,
         };
         let json = serde_json::to_string(&original).unwrap();
         let decoded: PolicyVerdict = serde_json::from_str(&json).unwrap();
         assert_eq!(original, decoded);
     }
 
+    #[test]
+    fn policy_verdict_deny_remediation_round_trips() {
+        let original = PolicyVerdict::Deny {
+            reason: "patient has not consented".to_string(),
+            remediation: Some("obtain patient consent".to_string()),
+        };
+        let json = serde_json::to_string(&original).unwrap();
+        assert!(json.contains("\"remediation\":\"obtain patient consent\""), "{json}");
+        let decoded: PolicyVerdict = serde_json::from_str(&json).unwrap();
+        assert_eq!(original, decoded);
+
+        // Without a hint the field is left out, and older JSON still loads.
+        let plain = r#"{"Deny":{"reason":"no"}}"#;
+        let decoded: PolicyVerdict = serde_json::from_str(plain).unwrap();
+        assert_eq!(
+            decoded,
+            PolicyVerdict::Deny {
+                reason: "no".to_string(),
+                remediation: None,
+            }
+        );
+        assert_eq!(serde_json::to_string(&decoded).unwrap(), plain);
+    }
+
     #[test]
     fn policy_verdict_require_approval_round_trips() {
         let original = PolicyVerdict::RequireApproval {
             reason: "high-risk prescription".to_string(),
             approver_role: "attending_physician".to_string(),
+            required_attributes: [("specialty".to_string(), "cardiology".to_string())].into(),
         };
         let json = serde_json::to_string(&original).unwrap();
         let decoded: PolicyVerdict = serde_json::from_str(&json).unwrap();
@@ -108,6 +282,45 @@ mod tests {
         assert_eq!(original, decoded);
     }
 
+    #[test]
+    fn policy_verdict_require_both_round_trips() {
+        let original = PolicyVerdict::RequireBoth {
+            reason: "controlled substance order".to_string(),
+            approver_role: "pharmacist".to_string(),
+            required_attributes: Default::default(),
+            check_ids: vec!["dose-range".to_string(), "phi-content-scan".to_string()],
+        };
+        let json = serde_json::to_string(&original).unwrap();
+        let decoded: PolicyVerdict = serde_json::from_str(&json).unwrap();
+        assert_eq!(original, decoded);
+
+        assert_eq!(original.verification_checks(), ["dose-range", "phi-content-scan"]);
+        let (_, role, _) = original.approval_requirement().unwrap();
+        assert_eq!(role, "pharmacist");
+    }
+
+    // ── ActionKind ───────────────────────────────────────────────────────────
+
+    #[test]
+    fn action_kind_inferred_from_leading_verb() {
+        use policy::ActionKind;
+
+        assert_eq!(ActionKind::infer("read_record"), Some(ActionKind::Read));
+        assert_eq!(ActionKind::infer("Update-Chart"), Some(ActionKind::Write));
+        assert_eq!(ActionKind::infer("delete_record"), Some(ActionKind::Delete));
+        assert_eq!(ActionKind::infer("submit.order"), Some(ActionKind::Execute));
+        assert_eq!(ActionKind::infer("reconcile_record"), None);
+        assert_eq!(ActionKind::infer(""), None);
+
+        // A delete counts as a write, not the other way around.
+        assert!(ActionKind::Delete.is_a(ActionKind::Write));
+        assert!(!ActionKind::Write.is_a(ActionKind::Delete));
+        assert!(!ActionKind::Read.is_a(ActionKind::Write));
+
+        assert_eq!(serde_json::to_string(&ActionKind::Delete).unwrap(), "\"delete\"");
+        assert_eq!(ActionKind::Execute.as_str(), "execute");
+    }
+
     // ── ExecutionId ──────────────────────────────────────────────────────────
 
     #[test]
@@ -120,6 +333,248 @@ mod tests {
         assert_eq!(unique.len(), 100);
     }
 
+    #[test]
+    fn execution_group_vends_stable_distinct_child_ids() {
+        let group = ExecutionGroup::new("pipeline-run-7");
+        let children: Vec<ExecutionId> = (0..4).map(|i| group.child(i)).collect();
+
+        // The same group and index always yield the same id...
+        assert_eq!(children[2], ExecutionGroup::new("pipeline-run-7").child(2));
+        // ...every stage gets its own id...
+        let unique: std::collections::HashSet<&ExecutionId> = children.iter().collect();
+        assert_eq!(unique.len(), 4);
+        // ...and another group's children differ.
+        assert_ne!(children[0], ExecutionGroup::new("pipeline-run-8").child(0));
+    }
+
+    // ── Output provenance ────────────────────────────────────────────────────
+
+    fn signed_summary() -> (agent::AgentOutput, provenance::SigningKey) {
+        let key = provenance::SigningKey::from_bytes(&[7u8; 32]);
+        let output = agent::AgentOutput {
+            kind: "clinical-summary".to_string(),
+            payload: serde_json::json!({ "patient_id": "P-001", "summary": "stable" }),
+            provenance: None,
+            advisories: vec![],
+        };
+        let agent_id = agent::AgentId("note-summarizer-agent".to_string());
+        (provenance::sign_output(output, &key, &agent_id), key)
+    }
+
+    #[test]
+    fn provenance_valid_signature_verifies() {
+        let (output, key) = signed_summary();
+        let proof = output.provenance.as_ref().expect("sign_output attaches provenance");
+
+        assert_eq!(proof.agent_id.0, "note-summarizer-agent");
+        assert!(proof.verify(&output.payload, &key.verifying_key()));
+
+        // Provenance survives a serde round-trip of the output.
+        let json = serde_json::to_string(&output).unwrap();
+        let decoded: agent::AgentOutput = serde_json::from_str(&json).unwrap();
+        assert!(decoded
+            .provenance
+            .unwrap()
+            .verify(&decoded.payload, &key.verifying_key()));
+    }
+
+    #[test]
+    fn provenance_tampered_payload_fails() {
+        let (mut output, key) = signed_summary();
+        output.payload["summary"] = serde_json::json!("critical");
+
+        let proof = output.provenance.as_ref().unwrap();
+        assert!(!proof.verify(&output.payload, &key.verifying_key()));
+    }
+
+    #[test]
+    fn provenance_wrong_key_or_agent_fails() {
+        let (output, key) = signed_summary();
+        let proof = output.provenance.clone().unwrap();
+
+        let other_key = provenance::SigningKey::from_bytes(&[9u8; 32]);
+        assert!(!proof.verify(&output.payload, &other_key.verifying_key()));
+
+        let impersonated = provenance::Provenance {
+            agent_id: agent::AgentId("other-agent".to_string()),
+            ..proof
+        };
+        assert!(!impersonated.verify(&output.payload, &key.verifying_key()));
+    }
+
+    // ── Output advisories ────────────────────────────────────────────────────
+
+    #[test]
+    fn advisories_round_trip_and_default_to_empty() {
+        let output = agent::AgentOutput {
+            kind: "medication-plan".to_string(),
+            payload: serde_json::json!({ "drug": "metformin" }),
+            provenance: None,
+            advisories: vec!["dose adjusted for renal function".to_string()],
+        };
+
+        let json = serde_json::to_string(&output).unwrap();
+        let decoded: agent::AgentOutput = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.advisories, output.advisories);
+
+        // Outputs serialized before advisories existed still deserialize, and
+        // an empty list is left out of the serialized form.
+        let legacy: agent::AgentOutput =
+            serde_json::from_str(r#"{"kind":"message","payload":null}"#).unwrap();
+        assert!(legacy.advisories.is_empty());
+        assert!(!serde_json::to_string(&legacy).unwrap().contains("advisories"));
+    }
+
+    // ── StepOutcome ──────────────────────────────────────────────────────────
+
+    fn test_state() -> agent::AgentState {
+        agent::AgentState {
+            agent_id: agent::AgentId("outcome-agent".to_string()),
+            execution_id: ExecutionId::new(),
+            phase: "active".to_string(),
+            context: serde_json::Value::Null,
+            step: 0,
+            correlation_id: None,
+        }
+    }
+
+    fn test_output() -> agent::AgentOutput {
+        agent::AgentOutput {
+            kind: "result".to_string(),
+            payload: serde_json::json!({}),
+            provenance: None,
+            advisories: vec![],
+        }
+    }
+
+    #[test]
+    fn step_outcome_from_step_results() {
+        use execution::{StepOutcome, StepResult};
+
+        let cases = [
+            (
+                StepResult::Transitioned {
+                    next_state: test_state(),
+                    output: test_output(),
+                    tags: Vec::new(),
+                },
+                StepOutcome::Allowed,
+            ),
+            (
+                StepResult::Complete {
+                    final_state: test_state(),
+                    output: test_output(),
+                    tags: Vec::new(),
+                },
+                StepOutcome::Allowed,
+            ),
+            (
+                StepResult::Denied {
+                    reason: "no".to_string(),
+                    code: policy::DenyCode::RuleDenied,
+                    rule_id: Some("deny-all".to_string()),
+                    remediation: None,
+                    tags: Vec::new(),
+                    final_state: test_state(),
+                },
+                StepOutcome::Denied,
+            ),
+            (
+                StepResult::AwaitingApproval {
+                    reason: "sign-off".to_string(),
+                    approver_role: "physician".to_string(),
+                    suspended_state: test_state(),
+                    tags: Vec::new(),
+                },
+                StepOutcome::AwaitingApproval,
+            ),
+        ];
+
+        for (result, expected) in cases {
+            assert_eq!(StepOutcome::from_result(&Ok(result)), Some(expected));
+        }
+    }
+
+    #[test]
+    fn step_outcome_from_errors() {
+        use execution::StepOutcome;
+
+        let outcome = |err: VeritasError| StepOutcome::from_result(&Err(err));
+
+        assert_eq!(
+            outcome(VeritasError::PolicyDenied { reason: "no".to_string() }),
+            Some(StepOutcome::Denied)
+        );
+        assert_eq!(
+            outcome(VeritasError::CapabilityMissing {
+                capability: "phi:read".to_string(),
+                action: "read".to_string(),
+            }),
+            Some(StepOutcome::CapabilityMissing)
+        );
+        assert_eq!(
+            outcome(VeritasError::VerificationFailed { reason: "bad".to_string() }),
+            Some(StepOutcome::VerificationRejected)
+        );
+        assert_eq!(
+            outcome(VeritasError::AuditWriteFailed { reason: "disk".to_string() }),
+            None
+        );
+
+        assert_eq!(StepOutcome::CapabilityMissing.as_str(), "capability-missing");
+        assert_eq!(
+            serde_json::to_string(&StepOutcome::VerificationRejected).unwrap(),
+            "\"verification-rejected\""
+        );
+    }
+
+    // ── Request fingerprint ──────────────────────────────────────────────────
+
+    #[test]
+    fn identical_requests_share_a_fingerprint() {
+        use execution::request_fingerprint;
+
+        let mut caps_a = CapabilitySet::default();
+        caps_a.grant(Capability::new("phi:read"));
+        caps_a.grant(Capability::new("ehr:write"));
+        let mut caps_b = CapabilitySet::default();
+        caps_b.grant(Capability::new("ehr:write"));
+        caps_b.grant(Capability::new("phi:read"));
+
+        // Same request, capabilities granted and keys written in another order.
+        let a = request_fingerprint(
+            "read",
+            "patient-101",
+            &caps_a,
+            &serde_json::json!({ "patient_id": "101", "fields": { "labs": true, "notes": false } }),
+        );
+        let b = request_fingerprint(
+            "read",
+            "patient-101",
+            &caps_b,
+            &serde_json::json!({ "fields": { "notes": false, "labs": true }, "patient_id": "101" }),
+        );
+
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 64);
+    }
+
+    #[test]
+    fn differing_requests_have_different_fingerprints() {
+        use execution::request_fingerprint;
+
+        let mut caps = CapabilitySet::default();
+        caps.grant(Capability::new("phi:read"));
+        let payload = serde_json::json!({ "patient_id": "101" });
+        let base = request_fingerprint("read", "patient-101", &caps, &payload);
+
+        let other_input = serde_json::json!({ "patient_id": "102" });
+        assert_ne!(base, request_fingerprint("read", "patient-101", &caps, &other_input));
+        assert_ne!(base, request_fingerprint("write", "patient-101", &caps, &payload));
+        assert_ne!(base, request_fingerprint("read", "patient-102", &caps, &payload));
+        assert_ne!(base, request_fingerprint("read", "patient-101", &CapabilitySet::default(), &payload));
+    }
+
     // ── VeritasError display messages ────────────────────────────────────────
 
     #[test]
@@ -173,6 +628,16 @@ mod tests {
         assert!(msg.contains("illegal transition"));
     }
 
+    #[test]
+    fn error_approval_rejected_display() {
+        let err = VeritasError::ApprovalRejected {
+            reason: "approval by 'dr-reyes' expired".to_string(),
+        };
+        let msg = err.to_string();
+        assert!(msg.contains("approval rejected"));
+        assert!(msg.contains("dr-reyes"));
+    }
+
     #[test]
     fn error_config_error_display() {
         let err = VeritasError::ConfigError {
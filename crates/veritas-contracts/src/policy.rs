@@ -2,7 +2,20 @@
 //!
 //! The policy engine consumes a `PolicyContext` and produces a `PolicyVerdict`.
 //! VERITAS is deny-by-default: any verdict other than `Allow` blocks the agent.
+//!
+//! An `ApprovalRecord` is the human sign-off that lets a step suspended by
+//! `RequireApproval` resume.  Every approval is time-boxed (`granted_at` +
+//! `valid_for`) so a stale sign-off cannot be replayed indefinitely.
+//!
+//! Engines that can say *why* they decided return a `PolicyDecision`: the
+//! verdict plus the deciding rule and, for denials, a `DenyCode`.
+//!
+//! An `ActionKind` classifies the action as a read, write, delete or
+//! execute, so policies can match on the kind instead of on action names.
 
+use std::{collections::BTreeMap, time::Duration};
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// The decision emitted by the policy engine for a single agent action.
@@ -18,18 +31,27 @@ pub enum PolicyVerdict {
     Deny {
         /// Human-readable explanation, written to the audit log.
         reason: String,
+        /// How the agent or user can proceed, e.g. "request physician
+        /// approval".  Set from the deciding rule's `remediation`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        remediation: Option<String>,
     },
 
     /// The action is suspended pending human approval.
     ///
     /// The executor returns `StepResult::AwaitingApproval` and the caller
-    /// must resume with an approval input (kind = "approval_granted") after
-    /// obtaining sign-off from the specified role.
+    /// must resume the step via `Executor::resume()` with an `ApprovalRecord`
+    /// from an approver holding `approver_role` and every
+    /// `required_attributes` pair.
     RequireApproval {
         /// Why approval is required.
         reason: String,
         /// The role that must approve (e.g. "attending_physician", "compliance_officer").
         approver_role: String,
+        /// Attributes the approver must hold in addition to the role, e.g.
+        /// `specialty = "cardiology"`.  Empty when the role alone suffices.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        required_attributes: BTreeMap<String, String>,
     },
 
     /// The action requires an external verification check before proceeding.
@@ -39,6 +61,242 @@ pub enum PolicyVerdict {
         /// Identifier for the verification check to run.
         check_id: String,
     },
+
+    /// The action needs both a content check and a human sign-off.
+    ///
+    /// The executor lets the agent propose and runs every check in
+    /// `check_ids` on the output; only once they pass does it suspend with
+    /// `StepResult::AwaitingApproval`.  `Executor::resume()` with a
+    /// sufficient `ApprovalRecord` re-runs the checks on the fresh proposal
+    /// and proceeds only if they pass again.
+    RequireBoth {
+        /// Why verification and approval are required.
+        reason: String,
+        /// The role that must approve.
+        approver_role: String,
+        /// Attributes the approver must hold in addition to the role.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        required_attributes: BTreeMap<String, String>,
+        /// Identifiers of the verification checks to run, in order.
+        check_ids: Vec<String>,
+    },
+}
+
+impl PolicyVerdict {
+    /// The sign-off this verdict demands, as `(reason, approver_role,
+    /// required_attributes)`: set for `RequireApproval` and `RequireBoth`.
+    pub fn approval_requirement(&self) -> Option<(&str, &str, &BTreeMap<String, String>)> {
+        match self {
+            PolicyVerdict::RequireApproval { reason, approver_role, required_attributes }
+            | PolicyVerdict::RequireBoth { reason, approver_role, required_attributes, .. } => {
+                Some((reason, approver_role, required_attributes))
+            }
+            PolicyVerdict::Allow
+            | PolicyVerdict::Deny { .. }
+            | PolicyVerdict::RequireVerification { .. } => None,
+        }
+    }
+
+    /// The verification checks this verdict names, in order: the `check_id`
+    /// of `RequireVerification` or the `check_ids` of `RequireBoth`.
+    pub fn verification_checks(&self) -> &[String] {
+        match self {
+            PolicyVerdict::RequireVerification { check_id } => std::slice::from_ref(check_id),
+            PolicyVerdict::RequireBoth { check_ids, .. } => check_ids,
+            PolicyVerdict::Allow
+            | PolicyVerdict::Deny { .. }
+            | PolicyVerdict::RequireApproval { .. } => &[],
+        }
+    }
+}
+
+/// The machine-readable category of a denial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DenyCode {
+    /// A rule matched and its verdict was `deny`.
+    RuleDenied,
+    /// A rule matched but the agent lacks one of its required capabilities.
+    MissingCapability,
+    /// No rule matched and the default verdict denied.
+    NoRuleMatched,
+    /// A rule matched but has already fired its
+    /// `max_invocations_per_window` times in the current window.
+    RateLimited,
+    /// The executor's abort condition stopped the step before policy ran.
+    Aborted,
+    /// The engine denied without saying why.
+    Unspecified,
+}
+
+impl DenyCode {
+    /// The kebab-case tag for this code, e.g. `"no-rule-matched"`.
+    ///
+    /// Matches the serde representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DenyCode::RuleDenied => "rule-denied",
+            DenyCode::MissingCapability => "missing-capability",
+            DenyCode::NoRuleMatched => "no-rule-matched",
+            DenyCode::RateLimited => "rate-limited",
+            DenyCode::Aborted => "aborted",
+            DenyCode::Unspecified => "unspecified",
+        }
+    }
+}
+
+/// A policy verdict together with how the engine reached it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyDecision {
+    /// The verdict itself.
+    pub verdict: PolicyVerdict,
+    /// The id of the rule that decided, if the engine has rules and one
+    /// decided.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_id: Option<String>,
+    /// Why the verdict is a denial; `None` unless `verdict` is `Deny`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deny_code: Option<DenyCode>,
+    /// A structured explanation of how the engine reached the verdict, for
+    /// engines that can give one.  The executor copies it into the audit
+    /// record when built `with_decision_trace_in_audit(true)`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<serde_json::Value>,
+    /// Labels the engine attached to the request on its way to the verdict,
+    /// e.g. by policy rules that tag without deciding.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl PolicyDecision {
+    /// A decision carrying nothing but `verdict`; a `Deny` gets
+    /// `DenyCode::Unspecified`.
+    pub fn from_verdict(verdict: PolicyVerdict) -> Self {
+        let deny_code =
+            matches!(verdict, PolicyVerdict::Deny { .. }).then_some(DenyCode::Unspecified);
+        Self {
+            verdict,
+            rule_id: None,
+            deny_code,
+            trace: None,
+            tags: Vec::new(),
+        }
+    }
+}
+
+/// A human sign-off for a step suspended by `PolicyVerdict::RequireApproval`
+/// or `PolicyVerdict::RequireBoth`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalRecord {
+    /// Who approved (e.g. a staff identifier).
+    pub approver_id: String,
+    /// The role the approver acted in.
+    pub approver_role: String,
+    /// Further attributes of the approver, e.g. `specialty` or `seniority`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, String>,
+    /// When the approver signed off.
+    pub granted_at: DateTime<Utc>,
+    /// How long after `granted_at` the approval may be presented.
+    pub valid_for: Duration,
+}
+
+impl ApprovalRecord {
+    /// The instant after which this approval is stale: `granted_at +
+    /// valid_for`.  `None` if that lies beyond the representable range, i.e.
+    /// the approval never goes stale in practice.
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        chrono::Duration::from_std(self.valid_for)
+            .ok()
+            .and_then(|valid_for| self.granted_at.checked_add_signed(valid_for))
+    }
+
+    /// True if the approval is stale at `now`.  An approval is still valid
+    /// at exactly its expiry instant.
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at().is_some_and(|expires_at| expires_at < now)
+    }
+
+    /// The requirements of an approval request this record does not meet.
+    ///
+    /// The role must match exactly and every `required_attributes` pair must
+    /// appear in `attributes` with the same value.  An empty result means the
+    /// approval is sufficient; each entry names one unmet requirement, e.g.
+    /// `"specialty = 'cardiology'"`.
+    pub fn unmet_requirements(
+        &self,
+        approver_role: &str,
+        required_attributes: &BTreeMap<String, String>,
+    ) -> Vec<String> {
+        let mut unmet = Vec::new();
+        if self.approver_role != approver_role {
+            unmet.push(format!("role '{approver_role}'"));
+        }
+        for (key, value) in required_attributes {
+            if self.attributes.get(key) != Some(value) {
+                unmet.push(format!("{key} = '{value}'"));
+            }
+        }
+        unmet
+    }
+}
+
+/// What an action does to its resource, as far as policy is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActionKind {
+    /// Reads the resource without changing it.
+    Read,
+    /// Creates or modifies the resource.
+    Write,
+    /// Removes the resource.  A delete is also a write: see `is_a`.
+    Delete,
+    /// Runs or sends something, e.g. submitting an order.
+    Execute,
+}
+
+impl ActionKind {
+    /// The kind's name as written in policy files: `"read"`, `"write"`,
+    /// `"delete"` or `"execute"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActionKind::Read => "read",
+            ActionKind::Write => "write",
+            ActionKind::Delete => "delete",
+            ActionKind::Execute => "execute",
+        }
+    }
+
+    /// True if an action of this kind counts as a `kind` action: the same
+    /// kind, or a `Delete` counted as a `Write`, since it changes the
+    /// resource too.  A policy scoped to writes therefore covers deletes.
+    pub fn is_a(self, kind: ActionKind) -> bool {
+        self == kind || (self == ActionKind::Delete && kind == ActionKind::Write)
+    }
+
+    /// Infer the kind from the verb an action name starts with — the text
+    /// before the first `_`, `-`, `.` or `:`, compared case-insensitively.
+    /// `read_record` is a `Read`, `delete_record` a `Delete`.  `None` when
+    /// the verb is not one of the known ones.
+    pub fn infer(action: &str) -> Option<Self> {
+        let verb = action
+            .split(['_', '-', '.', ':'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match verb.as_str() {
+            "read" | "get" | "list" | "view" | "fetch" | "query" | "search" | "lookup" => {
+                Some(ActionKind::Read)
+            }
+            "write" | "create" | "update" | "add" | "set" | "put" | "edit" | "modify"
+            | "insert" => Some(ActionKind::Write),
+            "delete" | "remove" | "purge" | "erase" => Some(ActionKind::Delete),
+            "execute" | "exec" | "run" | "invoke" | "send" | "submit" => {
+                Some(ActionKind::Execute)
+            }
+            _ => None,
+        }
+    }
 }
 
 /// Everything the policy engine needs to make a decision.
@@ -58,8 +316,49 @@ pub struct PolicyContext {
     pub action: String,
     /// The resource the action targets (from `Agent::describe_action()`).
     pub resource: String,
-    /// All capabilities the agent holds in this execution.
+    /// What the action does to the resource (from
+    /// `Agent::describe_action_kind()`), or `None` when it could not be
+    /// classified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action_kind: Option<ActionKind>,
+    /// All capabilities the agent holds in this execution, sorted by name.
     pub capabilities: Vec<String>,
-    /// Arbitrary additional metadata the agent provides for richer policy evaluation.
+    /// Arbitrary additional metadata the agent provides for richer policy
+    /// evaluation (from `Agent::action_metadata()`; `Null` by default).
     pub metadata: serde_json::Value,
 }
+
+/// Generates contexts for fuzzing the policy matcher.  Every string is
+/// arbitrary; `metadata` is an object of null, boolean, integer or string
+/// values, the shapes `Agent::action_metadata` typically produces.
+#[cfg(feature = "fuzz")]
+impl<'a> arbitrary::Arbitrary<'a> for PolicyContext {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut metadata = serde_json::Map::new();
+        for _ in 0..u.int_in_range(0..=4)? {
+            let value = match u.int_in_range(0..=3)? {
+                0 => serde_json::Value::Null,
+                1 => serde_json::Value::Bool(u.arbitrary()?),
+                2 => serde_json::Value::from(u.arbitrary::<i64>()?),
+                _ => serde_json::Value::String(u.arbitrary()?),
+            };
+            metadata.insert(u.arbitrary()?, value);
+        }
+        Ok(Self {
+            agent_id: u.arbitrary()?,
+            execution_id: u.arbitrary()?,
+            current_phase: u.arbitrary()?,
+            action: u.arbitrary()?,
+            resource: u.arbitrary()?,
+            action_kind: *u.choose(&[
+                None,
+                Some(ActionKind::Read),
+                Some(ActionKind::Write),
+                Some(ActionKind::Delete),
+                Some(ActionKind::Execute),
+            ])?,
+            capabilities: u.arbitrary()?,
+            metadata: serde_json::Value::Object(metadata),
+        })
+    }
+}
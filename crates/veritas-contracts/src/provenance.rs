@@ -0,0 +1,76 @@
+//! Agent output provenance.
+//!
+//! A `Provenance` binds an `AgentOutput` payload to the agent that produced
+//! it: an Ed25519 signature, made with the agent's key, over the agent id and
+//! the canonical JSON of the payload.  Anyone holding the agent's public key
+//! can later prove the payload is exactly what that agent emitted.
+//!
+//! This is independent of the audit hash chain, which proves the *log* was
+//! not altered after the fact; provenance proves *who* produced an output.
+//!
+//! The signed message is
+//! `b"veritas-output-provenance/v1\0" ‖ agent_id ‖ b"\0" ‖ canonical payload`,
+//! where the canonical payload is `serde_json::to_vec` of the payload (object
+//! keys sorted, no whitespace).
+
+use ed25519_dalek::{Signature, Signer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub use ed25519_dalek::{SigningKey, VerifyingKey};
+
+use crate::agent::{AgentId, AgentOutput};
+
+/// Domain separator prepended to every provenance message so a provenance
+/// signature can never be replayed as a signature over anything else.
+const DOMAIN: &[u8] = b"veritas-output-provenance/v1\0";
+
+/// Proof that a specific agent produced a specific output payload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// The agent that signed the payload.
+    pub agent_id: AgentId,
+    /// Hex-encoded 64-byte Ed25519 signature.
+    pub signature: String,
+}
+
+impl Provenance {
+    /// Return true if this provenance is a valid signature by `public_key`
+    /// over `payload` and `self.agent_id`.
+    ///
+    /// Any change to the payload, the agent id or the signature — or a
+    /// signature made with a different key — fails verification.
+    pub fn verify(&self, payload: &Value, public_key: &VerifyingKey) -> bool {
+        let Ok(bytes) = hex::decode(&self.signature) else {
+            return false;
+        };
+        let Ok(signature) = Signature::from_slice(&bytes) else {
+            return false;
+        };
+        public_key
+            .verify_strict(&signing_message(&self.agent_id, payload), &signature)
+            .is_ok()
+    }
+}
+
+/// Sign `output`'s payload as `agent_id` with `key` and attach the resulting
+/// `Provenance`, replacing any existing one.
+pub fn sign_output(mut output: AgentOutput, key: &SigningKey, agent_id: &AgentId) -> AgentOutput {
+    let signature = key.sign(&signing_message(agent_id, &output.payload));
+    output.provenance = Some(Provenance {
+        agent_id: agent_id.clone(),
+        signature: hex::encode(signature.to_bytes()),
+    });
+    output
+}
+
+/// The exact bytes a provenance signature covers.
+fn signing_message(agent_id: &AgentId, payload: &Value) -> Vec<u8> {
+    let mut message = Vec::with_capacity(DOMAIN.len() + agent_id.0.len() + 64);
+    message.extend_from_slice(DOMAIN);
+    message.extend_from_slice(agent_id.0.as_bytes());
+    message.push(0);
+    // serde_json::to_vec on a Value never fails: every key is a string.
+    message.extend(serde_json::to_vec(payload).unwrap_or_default());
+    message
+}
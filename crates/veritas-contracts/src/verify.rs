@@ -3,10 +3,47 @@
 //! Before any agent output is delivered or used to advance state, the
 //! verifier runs it against an `OutputSchema`. Only a passing
 //! `VerificationReport` allows the step to proceed.
+//!
+//! Schemas can be built in code or loaded from TOML with
+//! `OutputSchema::from_toml_str` / `from_file`:
+//!
+//! ```toml
+//! schema_id = "clinical-summary-v1"
+//! json_schema = { type = "object", required = ["patient_id", "summary"] }
+//!
+//! [[rules]]
+//! rule_id = "req-patient-id"
+//! description = "Output must identify the patient"
+//! rule_type = { RequiredField = { field_path = "patient_id" } }
+//!
+//! [[rules]]
+//! rule_id = "no-pii-labels"
+//! description = "Summary must not contain PII labels"
+//! rule_type = { Custom = { function_name = "no-pii-labels" } }
+//!
+//! [[defaults]]
+//! field_path = "requires_prior_auth"
+//! value = true
+//! ```
+//!
+//! `Custom` rules name a function that must still be registered in code.
+//!
+//! Rules default to `severity = "error"`.  A `"warn"` rule is still reported
+//! when it fails but does not fail the report: `passed` is true exactly when
+//! no error-severity rule failed.
+//!
+//! `defaults` fill optional fields the agent left out.  They are applied by
+//! `Verifier::normalize` — which the executor calls before verification only
+//! when built `with_output_normalization(true)` — never by `verify` itself.
 
-use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{de, Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
+use crate::error::{VeritasError, VeritasResult};
+
 /// The full specification the verifier checks agent outputs against.
 ///
 /// Schemas are defined at runtime startup and passed to the Executor.
@@ -17,9 +54,52 @@ pub struct OutputSchema {
     /// Unique identifier for this schema (e.g. "patient-intake-v1").
     pub schema_id: String,
     /// A JSON Schema document used for structural validation.
+    /// `Value::Null` (or omitting it from TOML) disables structural checks.
+    #[serde(default)]
     pub json_schema: Value,
     /// Additional domain rules evaluated after structural validation.
+    #[serde(default)]
     pub rules: Vec<VerificationRule>,
+    /// Values for optional fields the output may omit, filled in by
+    /// `Verifier::normalize` before verification.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub defaults: Vec<FieldDefault>,
+}
+
+/// A default for one optional output field.
+///
+/// Applied only when the field at `field_path` is absent or JSON `null`; a
+/// value the agent did produce is never replaced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldDefault {
+    /// The field to fill, in the same dot-notation or JSON Pointer form as
+    /// a `RequiredField` rule's `field_path`.
+    pub field_path: String,
+    /// The value to fill it with.
+    pub value: Value,
+}
+
+impl OutputSchema {
+    /// Parse `s` as a TOML output schema.
+    ///
+    /// Returns `VeritasError::ConfigError` if the TOML is malformed or does
+    /// not match the `OutputSchema` layout.
+    pub fn from_toml_str(s: &str) -> VeritasResult<Self> {
+        toml::from_str(s).map_err(|e| VeritasError::ConfigError {
+            reason: format!("failed to parse output schema TOML: {}", e),
+        })
+    }
+
+    /// Read the file at `path` and parse it as a TOML output schema.
+    ///
+    /// Returns `VeritasError::ConfigError` if the file cannot be read or its
+    /// contents are not a valid schema.
+    pub fn from_file(path: &Path) -> VeritasResult<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|e| VeritasError::ConfigError {
+            reason: format!("failed to read output schema file '{}': {}", path.display(), e),
+        })?;
+        Self::from_toml_str(&contents)
+    }
 }
 
 /// A single verification rule applied to an agent output.
@@ -29,14 +109,56 @@ pub struct VerificationRule {
     pub rule_id: String,
     /// Human-readable description for audit logs and operator tooling.
     pub description: String,
+    /// How serious a failure of this rule is.  Defaults to `Error`; `Pass`
+    /// is refused when a schema is loaded, and a rule built with it in code
+    /// fails as `Error`.
+    #[serde(default, deserialize_with = "rule_severity")]
+    pub severity: RuleSeverity,
     /// The verification logic to apply.
     pub rule_type: VerificationRuleType,
 }
 
+/// How serious a rule failure is.
+///
+/// Ordered by seriousness, so the most serious of several severities is
+/// their `max()`.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum RuleSeverity {
+    /// Nothing failed: the lowest level, and the `max_severity` of a report
+    /// without failures.  Not a severity a rule can be given.
+    Pass,
+    /// Reported, but does not fail verification.
+    Warn,
+    /// Fails verification.
+    #[default]
+    Error,
+}
+
+/// Deserialize a rule's `severity`, refusing `pass`: failures of such a rule
+/// would count as neither errors nor warnings, and the rule would fail open.
+fn rule_severity<'de, D: Deserializer<'de>>(deserializer: D) -> Result<RuleSeverity, D::Error> {
+    match RuleSeverity::deserialize(deserializer)? {
+        RuleSeverity::Pass => Err(de::Error::custom(
+            "a rule's severity must be \"warn\" or \"error\", not \"pass\"",
+        )),
+        severity => Ok(severity),
+    }
+}
+
 /// The kinds of verification checks VERITAS supports out of the box.
 ///
 /// `Custom` allows domain adapters to hook in arbitrary logic by name,
 /// keeping the core verifier free of healthcare-specific knowledge.
+///
+/// Every field path accepts two forms.  A path starting with `/` is an
+/// RFC 6901 JSON Pointer (`"/safety_report/details/0/severity"`), which can
+/// index arrays and address keys containing dots; any other path is
+/// dot-notation (`"patient.id"`).  The leading `/` decides — a path is never
+/// read both ways — so a top-level key that itself starts with `/` must be
+/// written as a pointer (`"/~1key"`).  `*` wildcards are dot-notation only.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum VerificationRuleType {
     /// The field at `field_path` must be present and non-null.
@@ -45,6 +167,13 @@ pub enum VerificationRuleType {
         field_path: String,
     },
 
+    /// The field at `field_path` must be absent or null — for fields that
+    /// must never be delivered, such as an internal `debug_prompt`.
+    ForbiddenField {
+        /// JSONPath-style dotted path.
+        field_path: String,
+    },
+
     /// The field at `field_path` must equal one of `allowed`.
     AllowedValues {
         /// JSONPath-style dotted path.
@@ -53,12 +182,168 @@ pub enum VerificationRuleType {
         allowed: Vec<Value>,
     },
 
+    /// Like `AllowedValues`, but the permitted values come from a set the
+    /// hosting application registers under `set_name` — for sets too large
+    /// or too dynamic to embed in the rule, such as a drug formulary.
+    AllowedFrom {
+        /// JSONPath-style dotted path.
+        field_path: String,
+        /// Name of the registered value set.
+        set_name: String,
+    },
+
     /// The field at `field_path` must not match `pattern` (regex or substring).
     ForbiddenPattern {
         /// JSONPath-style dotted path.
         field_path: String,
-        /// The forbidden pattern (implementation-defined matching semantics).
+        /// The forbidden pattern: a literal substring, or a regular
+        /// expression searched anywhere in the value when `is_regex` is set.
         pattern: String,
+        /// Treat `pattern` as a regular expression.  Defaults to `false`.
+        #[serde(default)]
+        is_regex: bool,
+    },
+
+    /// Like `ForbiddenPattern`, but only checked when the field at
+    /// `when_field` equals `when_equals` (e.g. scan for SSNs only in
+    /// summaries with `shared_externally = true`).  Passes whenever the
+    /// condition does not hold.
+    ForbiddenPatternWhen {
+        /// JSONPath-style dotted path.
+        field_path: String,
+        /// The forbidden pattern, as in `ForbiddenPattern`.
+        pattern: String,
+        /// Treat `pattern` as a regular expression.  Defaults to `false`.
+        #[serde(default)]
+        is_regex: bool,
+        /// JSONPath-style dotted path to the condition field.
+        when_field: String,
+        /// The value that switches the check on.  A missing condition field
+        /// compares as `null`.
+        when_equals: Value,
+    },
+
+    /// The value at `value_field` must equal one of the elements of the array
+    /// at `array_field` (e.g. a `primary_medication` listed in `medications`).
+    MemberOfArray {
+        /// JSONPath-style dotted path to the value being checked.
+        value_field: String,
+        /// JSONPath-style dotted path to the array the value must belong to.
+        array_field: String,
+    },
+
+    /// When the field at `when_field` equals `when_equals`, the field at
+    /// `must_be_null_field` must be absent or null (e.g. an uncovered
+    /// procedure must not carry a copay).  Passes whenever the condition
+    /// does not hold.
+    ImpliesNull {
+        /// JSONPath-style dotted path to the condition field.
+        when_field: String,
+        /// The value that triggers the rule.  A missing condition field
+        /// compares as `null`.
+        when_equals: Value,
+        /// JSONPath-style dotted path to the field that must then be null.
+        must_be_null_field: String,
+    },
+
+    /// The string at `recommendation_field` must contain, ignoring case, at
+    /// least one of the phrases `mapping` lists for the value at
+    /// `severity_field` (e.g. a `"HIGH"` interaction must say `"avoid"` or
+    /// `"monitor"`), so an agent cannot pair a serious finding with a
+    /// benign note.
+    ///
+    /// Passes when the severity field is absent or its value has no entry
+    /// in `mapping`; a non-string severity is looked up by its JSON text.
+    /// Fails when a mapped severity comes without a string recommendation.
+    SeverityRecommendationConsistency {
+        /// JSONPath-style dotted path to the severity.
+        severity_field: String,
+        /// JSONPath-style dotted path to the recommendation text.
+        recommendation_field: String,
+        /// For each severity value, the phrases of which the recommendation
+        /// must contain one.  Severities and phrases match case-insensitively.
+        mapping: BTreeMap<String, Vec<String>>,
+    },
+
+    /// The object at `object_path` must contain no keys outside
+    /// `allowed_keys`.
+    ///
+    /// Unlike JSON Schema `additionalProperties: false`, the object can sit
+    /// at any nested path; an empty path refers to the whole payload.
+    OnlyAllowedKeys {
+        /// JSONPath-style dotted path to the object.
+        object_path: String,
+        /// The exhaustive list of permitted keys.
+        allowed_keys: Vec<String>,
+    },
+
+    /// The array at `field_path` must hold at most `max` elements (e.g. at
+    /// most five differential diagnoses).
+    ///
+    /// An absent field passes — pair with `RequiredField` to demand it — but
+    /// a present non-array value fails.
+    MaxArrayLength {
+        /// JSONPath-style dotted path to the array.
+        field_path: String,
+        /// The largest permitted number of elements.
+        max: usize,
+    },
+
+    /// The number at `field_path` must lie within `max_pct_delta` percent
+    /// of the number at `baseline_field` (e.g. a follow-up dose within ±20%
+    /// of the baseline dose).
+    ///
+    /// The delta is `|value - baseline| / |baseline| * 100`.  Fails if
+    /// either field is missing or not a number; a zero baseline only admits
+    /// a zero value.
+    RelativeRange {
+        /// JSONPath-style dotted path to the value being checked.
+        field_path: String,
+        /// JSONPath-style dotted path to the baseline value.
+        baseline_field: String,
+        /// The largest permitted difference, in percent of the baseline.
+        max_pct_delta: f64,
+    },
+
+    /// The number at `field` must increase from each element of the array
+    /// at `array_path` to the next (e.g. the `step` of a sequenced
+    /// treatment plan).
+    ///
+    /// With `strict`, equal neighbours fail too.  An absent array passes —
+    /// pair with `RequiredField` to demand it — but a present non-array
+    /// value, or an element whose `field` is missing or not a number, fails.
+    /// Only the first violation is reported.
+    MonotonicField {
+        /// JSONPath-style dotted path to the array.
+        array_path: String,
+        /// Path to the number within each element.
+        field: String,
+        /// Whether each value must exceed, not merely equal, the one before.
+        strict: bool,
+    },
+
+    /// The string at `field_path` must be a dose of the form
+    /// `<number> <unit>` (e.g. `"500 mg"`) with a positive quantity and a
+    /// unit from `allowed_units`.
+    ///
+    /// A `*` segment matches every element of an array, so
+    /// `"medications.*.dose"` checks the dose of each medication.
+    DoseFormat {
+        /// JSONPath-style dotted path; `*` segments expand over arrays.
+        field_path: String,
+        /// The exhaustive list of permitted units, matched case-sensitively.
+        allowed_units: Vec<String>,
+    },
+
+    /// Every element of the array at `array_path` must pass `element_rules`.
+    ///
+    /// Element rules resolve their field paths against the element itself;
+    /// an empty path refers to the whole element.
+    EachElement {
+        /// JSONPath-style dotted path to the array.
+        array_path: String,
+        /// Rules applied to each element in turn.
+        element_rules: Vec<VerificationRule>,
     },
 
     /// Delegate to a named custom function registered by the hosting application.
@@ -69,12 +354,56 @@ pub enum VerificationRuleType {
 }
 
 /// The result of running all rules in an `OutputSchema` against an output.
+///
+/// Build reports with `VerificationReport::from_failures` so the aggregate
+/// fields stay consistent with `failures`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VerificationReport {
-    /// True only if all rules passed.
+    /// True only if no error-severity rule failed (`error_count == 0`).
     pub passed: bool,
-    /// All failures collected during this verification run. Empty on pass.
+    /// All failures collected during this verification run, warnings
+    /// included. Empty on a clean pass.
     pub failures: Vec<VerificationFailure>,
+    /// The most serious severity among `failures`; `Pass` when empty.
+    #[serde(default = "no_failures")]
+    pub max_severity: RuleSeverity,
+    /// Number of error-severity failures.
+    #[serde(default)]
+    pub error_count: usize,
+    /// Number of warn-severity failures.
+    #[serde(default)]
+    pub warn_count: usize,
+}
+
+impl VerificationReport {
+    /// Build a report from the collected `failures`, deriving `passed`, the
+    /// counts and `max_severity` from their severities.
+    pub fn from_failures(failures: Vec<VerificationFailure>) -> Self {
+        let error_count = failures
+            .iter()
+            .filter(|f| f.severity == RuleSeverity::Error)
+            .count();
+        let warn_count = failures
+            .iter()
+            .filter(|f| f.severity == RuleSeverity::Warn)
+            .count();
+        Self {
+            passed: error_count == 0,
+            max_severity: failures
+                .iter()
+                .map(|f| f.severity)
+                .max()
+                .unwrap_or(RuleSeverity::Pass),
+            error_count,
+            warn_count,
+            failures,
+        }
+    }
+}
+
+/// The `max_severity` of a report serialized without one.
+fn no_failures() -> RuleSeverity {
+    RuleSeverity::Pass
 }
 
 /// A single rule failure within a `VerificationReport`.
@@ -84,4 +413,7 @@ pub struct VerificationFailure {
     pub rule_id: String,
     /// Human-readable explanation of why the rule failed.
     pub message: String,
+    /// The severity of the failed rule.
+    #[serde(default)]
+    pub severity: RuleSeverity,
 }
@@ -0,0 +1,201 @@
+//! Parking suspended executions until an approver acts on them.
+//!
+//! A step that returns `StepResult::AwaitingApproval` must be re-run with
+//! `Executor::resume()` once a human signs off.  In an interactive
+//! deployment the approver is usually not the caller that ran the step, so
+//! the suspension has to be parked somewhere both can reach.  An
+//! `ApprovalQueue` holds one `PendingApproval` per suspended execution: a UI
+//! lists the entries for the signed-in approver's role, and `take`s the one
+//! the approver acts on, which carries everything `resume()` needs.
+
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use veritas_contracts::{
+    agent::{AgentInput, AgentState, ExecutionId},
+    execution::StepResult,
+};
+
+/// A suspended execution waiting for approval.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingApproval {
+    /// The suspended execution.
+    pub execution_id: ExecutionId,
+    /// The state to pass back to `Executor::resume()`.
+    pub suspended_state: AgentState,
+    /// Why approval is required.
+    pub reason: String,
+    /// The role that must provide approval.
+    pub approver_role: String,
+    /// The input of the suspended step, to pass back to `resume()`.
+    pub input: AgentInput,
+}
+
+impl PendingApproval {
+    /// The pending approval for a step that returned `result` given `input`,
+    /// or `None` if `result` is not `StepResult::AwaitingApproval`.
+    pub fn from_result(result: &StepResult, input: &AgentInput) -> Option<Self> {
+        match result {
+            StepResult::AwaitingApproval {
+                reason,
+                approver_role,
+                suspended_state,
+                ..
+            } => Some(Self {
+                execution_id: suspended_state.execution_id.clone(),
+                suspended_state: suspended_state.clone(),
+                reason: reason.clone(),
+                approver_role: approver_role.clone(),
+                input: input.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Suspended executions awaiting approval, in the order they were parked.
+///
+/// Safe to share between the thread running executions and the one serving
+/// approvers.
+#[derive(Debug, Default)]
+pub struct ApprovalQueue {
+    pending: Mutex<Vec<PendingApproval>>,
+}
+
+impl ApprovalQueue {
+    /// Create an empty queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Park `pending`.  An execution is suspended at one step at a time, so
+    /// an entry already queued for the same execution is replaced.
+    pub fn enqueue(&self, pending: PendingApproval) {
+        let mut queue = self.pending.lock().expect("approval queue lock poisoned");
+        queue.retain(|p| p.execution_id != pending.execution_id);
+        queue.push(pending);
+    }
+
+    /// The entries awaiting an approver of `role`, oldest first.
+    pub fn list_for_role(&self, role: &str) -> Vec<PendingApproval> {
+        let queue = self.pending.lock().expect("approval queue lock poisoned");
+        queue
+            .iter()
+            .filter(|p| p.approver_role == role)
+            .cloned()
+            .collect()
+    }
+
+    /// Remove and return the entry for `execution_id`, if one is queued.
+    pub fn take(&self, execution_id: &ExecutionId) -> Option<PendingApproval> {
+        let mut queue = self.pending.lock().expect("approval queue lock poisoned");
+        let index = queue.iter().position(|p| &p.execution_id == execution_id)?;
+        Some(queue.remove(index))
+    }
+
+    /// Number of queued entries.
+    pub fn len(&self) -> usize {
+        self.pending
+            .lock()
+            .expect("approval queue lock poisoned")
+            .len()
+    }
+
+    /// True if nothing is awaiting approval.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use veritas_contracts::{
+        agent::{AgentId, AgentInput, AgentState, ExecutionId},
+        execution::StepResult,
+    };
+
+    use super::{ApprovalQueue, PendingApproval};
+
+    fn suspended(role: &str, text: &str) -> (StepResult, AgentInput) {
+        let result = StepResult::AwaitingApproval {
+            reason: format!("{role} sign-off required"),
+            approver_role: role.to_string(),
+            suspended_state: AgentState {
+                agent_id: AgentId("order-agent".to_string()),
+                execution_id: ExecutionId::new(),
+                phase: "ordering".to_string(),
+                context: serde_json::Value::Null,
+                step: 2,
+                correlation_id: None,
+            },
+            tags: Vec::new(),
+        };
+        let input = AgentInput {
+            kind: "user_message".to_string(),
+            payload: json!({ "text": text }),
+        };
+        (result, input)
+    }
+
+    #[test]
+    fn test_list_for_role_filters_and_take_removes() {
+        let queue = ApprovalQueue::new();
+        let (result, input) = suspended("attending-physician", "start anticoagulant");
+        let physician = PendingApproval::from_result(&result, &input).unwrap();
+        let (result, input) = suspended("pharmacist", "substitute generic");
+        let pharmacist = PendingApproval::from_result(&result, &input).unwrap();
+        queue.enqueue(physician.clone());
+        queue.enqueue(pharmacist.clone());
+
+        let ids = |role: &str| -> Vec<ExecutionId> {
+            queue
+                .list_for_role(role)
+                .into_iter()
+                .map(|p| p.execution_id)
+                .collect()
+        };
+        assert_eq!(
+            ids("attending-physician"),
+            vec![physician.execution_id.clone()]
+        );
+        assert_eq!(ids("pharmacist"), vec![pharmacist.execution_id.clone()]);
+        assert!(ids("nurse").is_empty());
+
+        // The taken entry carries what resume() needs.
+        let taken = queue.take(&physician.execution_id).unwrap();
+        assert_eq!(taken.suspended_state.step, 2);
+        assert_eq!(taken.input.payload["text"], "start anticoagulant");
+        assert!(ids("attending-physician").is_empty());
+        assert!(queue.take(&physician.execution_id).is_none());
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[test]
+    fn test_from_result_ignores_other_results_and_enqueue_replaces() {
+        let (result, input) = suspended("pharmacist", "first");
+        let pending = PendingApproval::from_result(&result, &input).unwrap();
+        let denied = StepResult::Denied {
+            reason: "no".to_string(),
+            code: veritas_contracts::policy::DenyCode::RuleDenied,
+            rule_id: None,
+            remediation: None,
+            tags: Vec::new(),
+            final_state: pending.suspended_state.clone(),
+        };
+        assert!(PendingApproval::from_result(&denied, &input).is_none());
+
+        let queue = ApprovalQueue::new();
+        queue.enqueue(pending.clone());
+        queue.enqueue(PendingApproval {
+            reason: "re-suspended".to_string(),
+            ..pending
+        });
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.list_for_role("pharmacist")[0].reason, "re-suspended");
+    }
+}
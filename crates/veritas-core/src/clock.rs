@@ -0,0 +1,61 @@
+//! Time sources for the VERITAS runtime.
+//!
+//! Every timestamp the runtime produces — `StepRecord.timestamp`,
+//! `AuditLog.finalized_at` — is read from a `Clock`.  Production code uses
+//! `SystemClock`; tests inject a `FixedClock` so audit records and their
+//! hashes are reproducible across runs.
+
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, Utc};
+
+/// A source of the current time.
+pub trait Clock: Send + Sync {
+    /// Return the current time (UTC).
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The wall clock.  This is the default everywhere a clock is accepted.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that always returns the same instant until told otherwise.
+///
+/// Clones share the instant, so a single `FixedClock` can be handed to both
+/// the executor and the audit writer and advanced from the test body.
+#[derive(Debug, Clone)]
+pub struct FixedClock {
+    instant: Arc<Mutex<DateTime<Utc>>>,
+}
+
+impl FixedClock {
+    /// Create a clock frozen at `instant`.
+    pub fn new(instant: DateTime<Utc>) -> Self {
+        Self {
+            instant: Arc::new(Mutex::new(instant)),
+        }
+    }
+
+    /// Move the clock to `instant`.
+    pub fn set(&self, instant: DateTime<Utc>) {
+        *self.instant.lock().expect("clock lock poisoned") = instant;
+    }
+
+    /// Move the clock forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        let mut instant = self.instant.lock().expect("clock lock poisoned");
+        *instant += by;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.instant.lock().expect("clock lock poisoned")
+    }
+}
@@ -4,35 +4,92 @@
 //!
 //!   State → Policy → Capability → [Agent::propose] → Verify → Transition → Audit
 //!
+//! An optional abort condition (`set_abort_condition`) is checked on the
+//! state before the policy, so an execution can be stopped between steps —
+//! e.g. when a patient revokes consent mid-run.  A step limit
+//! (`with_max_steps`) bounds how far any one execution can run.
+//!
 //! The security invariant is absolute: `Agent::propose()` is NEVER called
-//! unless `PolicyEngine::evaluate()` returns `PolicyVerdict::Allow` AND all
+//! unless `PolicyEngine::decide()` returns `PolicyVerdict::Allow` AND all
 //! required capabilities are present. This is enforced structurally — the
 //! code path to `propose()` is only reachable after both checks pass.
+//!
+//! A step suspended by `PolicyVerdict::RequireApproval` is re-run with
+//! `resume()`, which lets it past the approval gate only if the supplied
+//! `ApprovalRecord` meets the verdict's role and attribute requirements.
+//!
+//! Executors are assembled with `Executor::new()` / `new_with_registry()` or,
+//! when optional components such as an `ExecutorObserver` or a `Clock` are
+//! involved, with the named setters of `ExecutorBuilder`.
+//!
+//! Every step runs inside a `veritas_step` tracing span (fields
+//! `execution_id`, `step`, `action`) with one child span per phase:
+//! `policy`, `capability`, `propose`, `verify` and `audit`.
+
+use std::{
+    collections::BTreeMap,
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+    time::Instant,
+};
 
-use chrono::Utc;
-use tracing::{debug, info, warn};
+use chrono::{DateTime, Utc};
+use tracing::{debug, debug_span, field, info, info_span, warn};
 
 use veritas_contracts::{
-    agent::{AgentState, AgentInput},
+    agent::{AgentInput, AgentState, ExecutionId},
     capability::{Capability, CapabilitySet},
     error::{VeritasError, VeritasResult},
-    execution::{StepRecord, StepResult},
-    policy::{PolicyContext, PolicyVerdict},
+    execution::{
+        request_fingerprint, BreakGlass, PreflightResult, RejectionReason, StepOutcome, StepRecord,
+        StepResult,
+    },
+    policy::{
+        ActionKind, ApprovalRecord, DenyCode, PolicyContext, PolicyDecision, PolicyVerdict,
+    },
     verify::OutputSchema,
 };
 
-use crate::traits::{Agent, AuditWriter, PolicyEngine, Verifier};
+use crate::{
+    clock::{Clock, SystemClock},
+    registry::AuditRegistry,
+    traits::{Agent, AuditWriter, ExecutorObserver, PolicyEngine, Verifier},
+};
+
+/// A check run on the agent state at the top of every step.
+///
+/// Returns `Some(reason)` to abort the execution: the step is audited as a
+/// denial and returned as `StepResult::Denied` without consulting the policy.
+pub type AbortCondition = Box<dyn Fn(&AgentState) -> Option<String> + Send + Sync>;
+
+/// Where an executor sends its audit records.
+enum AuditSink {
+    /// One writer for every step — the one-executor-per-execution model.
+    Single(Box<dyn AuditWriter>),
+    /// A writer per execution, looked up by `state.execution_id`.
+    Registry(Arc<AuditRegistry>),
+}
 
-/// The central executor that drives a single agent execution.
+/// The central executor that drives agent executions.
 ///
-/// Construct one executor per agent execution (i.e. per `ExecutionId`).
-/// The executor owns the trusted components — policy, audit, verifier — and
-/// enforces the pipeline ordering on every call to `step()`.
+/// Construct one executor per agent execution (i.e. per `ExecutionId`) with
+/// `new()`, or share one executor across executions with
+/// `new_with_registry()`.  The executor owns the trusted components —
+/// policy, audit, verifier — and enforces the pipeline ordering on every
+/// call to `step()`.
 pub struct Executor {
     policy: Box<dyn PolicyEngine>,
-    audit: Box<dyn AuditWriter>,
+    shadow_policy: Option<Box<dyn PolicyEngine>>,
+    audit: AuditSink,
     verifier: Box<dyn Verifier>,
     schema: OutputSchema,
+    clock: Box<dyn Clock>,
+    observer: Option<Box<dyn ExecutorObserver>>,
+    abort_condition: Option<AbortCondition>,
+    max_steps: Option<u64>,
+    timing_in_audit: bool,
+    decision_trace_in_audit: bool,
+    output_normalization: bool,
 }
 
 impl Executor {
@@ -43,23 +100,185 @@ impl Executor {
         verifier: Box<dyn Verifier>,
         schema: OutputSchema,
     ) -> Self {
-        Self { policy, audit, verifier, schema }
+        Self {
+            policy,
+            shadow_policy: None,
+            audit: AuditSink::Single(audit),
+            verifier,
+            schema,
+            clock: Box::new(SystemClock),
+            observer: None,
+            abort_condition: None,
+            max_steps: None,
+            timing_in_audit: false,
+            decision_trace_in_audit: false,
+            output_normalization: false,
+        }
+    }
+
+    /// Create an executor that routes each step's audit records to the
+    /// writer `registry` holds for the step's `state.execution_id`.
+    ///
+    /// A single executor built this way can serve any number of executions;
+    /// each still gets its own chain.
+    pub fn new_with_registry(
+        policy: Box<dyn PolicyEngine>,
+        registry: Arc<AuditRegistry>,
+        verifier: Box<dyn Verifier>,
+        schema: OutputSchema,
+    ) -> Self {
+        Self {
+            policy,
+            shadow_policy: None,
+            audit: AuditSink::Registry(registry),
+            verifier,
+            schema,
+            clock: Box::new(SystemClock),
+            observer: None,
+            abort_condition: None,
+            max_steps: None,
+            timing_in_audit: false,
+            decision_trace_in_audit: false,
+            output_normalization: false,
+        }
+    }
+
+    /// Replace the clock used to timestamp audit records.
+    ///
+    /// Defaults to `SystemClock`.  Inject a `FixedClock` for reproducible
+    /// records in tests.
+    pub fn with_clock(mut self, clock: Box<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The current time by the executor's clock — the instant against which
+    /// it checks grant and approval expiry.
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    /// Record how long the policy evaluation, `propose()` and verification
+    /// took in each step's audit record (`StepRecord::policy_eval_micros`,
+    /// `propose_micros`, `verify_micros`).
+    ///
+    /// Off by default.  The timings are part of the hashed record, so they
+    /// need chain version 2 (`veritas_audit::CHAIN_VERSION`); with timing
+    /// off the fields stay `None` and are left out of the canonical hash
+    /// input entirely, so records hash as they would without the fields.
+    pub fn with_timing_in_audit(mut self, enabled: bool) -> Self {
+        self.timing_in_audit = enabled;
+        self
+    }
+
+    /// Record the policy engine's explanation of each verdict
+    /// (`PolicyDecision::trace`) in the step's audit record as
+    /// `StepRecord::decision_trace`, so the record states which rule decided
+    /// and why earlier rules were skipped.
+    ///
+    /// Off by default.  The trace is part of the hashed record, so it needs
+    /// chain version 2 (`veritas_audit::CHAIN_VERSION`); with tracing off
+    /// the field stays `None` and is left out of the canonical hash input.
+    /// Engines that give no trace leave it `None` either way.
+    pub fn with_decision_trace_in_audit(mut self, enabled: bool) -> Self {
+        self.decision_trace_in_audit = enabled;
+        self
+    }
+
+    /// Pass each proposed output through `Verifier::normalize()` before
+    /// verifying it, so optional fields the agent omitted are filled from
+    /// the schema's `defaults`.
+    ///
+    /// Off by default: the output is verified exactly as proposed.  When on,
+    /// the normalized output is the one verified, audited and delivered, and
+    /// `StepRecord::defaulted_fields` names the fields the runtime filled.
+    pub fn with_output_normalization(mut self, enabled: bool) -> Self {
+        self.output_normalization = enabled;
+        self
+    }
+
+    /// Evaluate `policy` alongside the enforced policy on every step and
+    /// record what it would have decided, without enforcing it.
+    ///
+    /// For rolling out a stricter policy safely: each audited step carries
+    /// the shadow verdict as `StepRecord::shadow_verdict`, and a shadow
+    /// verdict that differs from the enforced one is logged at `info`.  The
+    /// enforced policy alone decides the step; a shadow evaluation error is
+    /// logged at `warn` and leaves the field `None`.  Shadow verdicts need
+    /// chain version 2 (`veritas_audit::CHAIN_VERSION`).
+    pub fn with_shadow_policy(mut self, policy: Box<dyn PolicyEngine>) -> Self {
+        self.shadow_policy = Some(policy);
+        self
+    }
+
+    /// Attach an observer notified of policy verdicts and step outcomes.
+    pub fn with_observer(mut self, observer: Box<dyn ExecutorObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Install a condition checked on the agent state before every step.
+    ///
+    /// When it returns `Some(reason)`, the step is audited as a denial with
+    /// that reason and returned as `StepResult::Denied`; the policy, the
+    /// capability check and the agent are never reached.  Replaces any
+    /// previously installed condition.
+    pub fn set_abort_condition(&mut self, condition: AbortCondition) {
+        self.abort_condition = Some(condition);
+    }
+
+    /// Reject any step whose `state.step` has reached `limit`.
+    ///
+    /// A safety bound against an agent looping forever through valid
+    /// transitions: the rejection is checked before the policy, audited as a
+    /// denial and returned as `VeritasError::StateMachineError`.  Steps
+    /// `0..limit` run normally.  Unlimited by default.
+    pub fn with_max_steps(mut self, limit: u64) -> Self {
+        self.max_steps = Some(limit);
+        self
+    }
+
+    /// Start building an executor with named setters instead of positional
+    /// arguments.
+    pub fn builder() -> ExecutorBuilder {
+        ExecutorBuilder::default()
     }
 
     /// Execute one step of the agent's state machine.
     ///
     /// # Pipeline
     ///
-    /// 1. Build `PolicyContext` from `agent.describe_action()`
-    /// 2. Call `policy.evaluate()`:
-    ///    - `Deny` → audit the denial, return `StepResult::Denied`
+    /// 0. If an abort condition is set and fires, audit the denial and return
+    ///    `StepResult::Denied`
+    /// 1. Build `PolicyContext` from `agent.describe_action()` and
+    ///    `agent.action_metadata()`
+    /// 2. Call `policy.decide()` — `policy.decide_uncounted()` for a retry.
+    ///    A shadow policy, if set, is evaluated on the same context; its
+    ///    verdict is only recorded in the step's audit records. Then:
+    ///    - `Deny` → audit the denial, return `StepResult::Denied` with the
+    ///      decision's `DenyCode` and rule id
     ///    - `RequireApproval` → audit, return `StepResult::AwaitingApproval`
-    ///    - `RequireVerification` / `Allow` → continue
+    ///    - `RequireVerification` / `RequireBoth` / `Allow` → continue
     /// 3. Check that the agent holds all `required_capabilities()`; if not,
-    ///    audit a synthetic denial and return `VeritasError::CapabilityMissing`
-    /// 4. Call `agent.propose()` — **only reachable after steps 2 & 3 pass**
-    /// 5. Call `verifier.verify()`; if failed, return `VeritasError::VerificationFailed`
-    /// 6. Call `agent.transition()` to advance state
+    ///    audit a synthetic denial and return `VeritasError::CapabilityMissing`.
+    ///    Then call `agent.validate_input()`; a rejection is audited as a
+    ///    denial and its error returned
+    /// 4. Call `agent.propose()` — **only reachable after steps 2 & 3 pass**.
+    ///    A panic in `propose()` is caught, audited as a denial and returned
+    ///    as `VeritasError::StateMachineError`
+    /// 5. If built `with_output_normalization(true)`, fill schema defaults
+    ///    with `verifier.normalize()`. Call `verifier.verify()` and, for
+    ///    `RequireVerification` and `RequireBoth`, `verifier.run_check()` for
+    ///    each named check; if any fails, offer the rejection to
+    ///    `agent.on_rejected()`. A returned input is audited as a rejection
+    ///    and the step is retried once with it; otherwise return
+    ///    `VeritasError::VerificationFailed`. A verified `RequireBoth` output
+    ///    is then audited and the step returns `StepResult::AwaitingApproval`,
+    ///    undelivered, until `resume()`
+    /// 6. Call `agent.transition()` to advance state, carrying the state's
+    ///    `correlation_id` over to the next state. If it fails, audit the
+    ///    verified output with a denial naming the failure and return
+    ///    `VeritasError::StateMachineError`
     /// 7. Audit the completed step
     /// 8. If `agent.is_terminal()`, finalize the audit and return `StepResult::Complete`
     /// 9. Otherwise return `StepResult::Transitioned`
@@ -75,10 +294,245 @@ impl Executor {
         state: AgentState,
         input: AgentInput,
         capabilities: &CapabilitySet,
+    ) -> VeritasResult<StepResult> {
+        self.observed(state, |state| {
+            self.run_step(agent, state, input, capabilities, &self.schema, None, None, true)
+        })
+    }
+
+    /// Execute one step exactly as `step()` does, but verify the output
+    /// against `schema` instead of the executor's own.
+    ///
+    /// Lets a multi-step agent hold a different verification contract per
+    /// phase — e.g. an intake phase emitting a structured history and a
+    /// recommendation phase emitting a plan — with one executor.  The
+    /// override applies to this step only, including its `on_rejected()`
+    /// retry; the stored schema is untouched.
+    pub fn step_with_schema(
+        &self,
+        agent: &dyn Agent,
+        state: AgentState,
+        input: AgentInput,
+        capabilities: &CapabilitySet,
+        schema: &OutputSchema,
+    ) -> VeritasResult<StepResult> {
+        self.observed(state, |state| {
+            self.run_step(agent, state, input, capabilities, schema, None, None, true)
+        })
+    }
+
+    /// Re-run a step that returned `StepResult::AwaitingApproval`, presenting
+    /// a human sign-off.
+    ///
+    /// The policy is evaluated afresh.  If it again returns
+    /// `RequireApproval`, the step proceeds through the rest of the pipeline
+    /// only when `approval` holds the required role and every required
+    /// attribute; a partial match is audited and returned as
+    /// `StepResult::AwaitingApproval` again, its reason naming the unmet
+    /// requirements.  `RequireBoth` is gated the same way, after the fresh
+    /// proposal has passed its checks again.  Any other verdict is handled
+    /// exactly as in `step()` — an approval never overrides a `Deny` or a
+    /// missing capability.
+    ///
+    /// An approval whose `granted_at + valid_for` lies before the executor's
+    /// clock is stale, and one from an approver of the wrong role cannot
+    /// satisfy the verdict at all: either rejection is audited as a denial
+    /// and returned as `VeritasError::ApprovalRejected`, so an old or
+    /// misdirected sign-off cannot be replayed.
+    pub fn resume(
+        &self,
+        agent: &dyn Agent,
+        state: AgentState,
+        input: AgentInput,
+        capabilities: &CapabilitySet,
+        approval: &ApprovalRecord,
+    ) -> VeritasResult<StepResult> {
+        self.observed(state, |state| {
+            self.run_step(agent, state, input, capabilities, &self.schema, Some(approval), None, true)
+        })
+    }
+
+    /// Execute one step as `step()` does, except that an output failing
+    /// verification is delivered anyway — the break-glass path for an
+    /// emergency in which withholding the output is worse than delivering it.
+    ///
+    /// Everything before verification is unchanged: a policy `Deny`, a
+    /// missing capability or a rejected input stops the step as usual, and a
+    /// `RequireBoth` output still waits for approval.  When verification
+    /// fails, the failures are logged with `tracing::warn!`, the agent is not
+    /// offered a retry, and the step's audit record carries a `BreakGlass`
+    /// annotation naming the `authorizer`, the `justification` and the
+    /// overridden failures.  A step whose output passes verification is
+    /// recorded exactly as by `step()`, without the annotation.
+    ///
+    /// # Errors
+    ///
+    /// As `step()`, except for verification failures.  Returns
+    /// `VeritasError::ConfigError`, without running the step, if
+    /// `justification` or `authorizer` is blank: an override must say who
+    /// authorized it and why.
+    pub fn step_break_glass(
+        &self,
+        agent: &dyn Agent,
+        state: AgentState,
+        input: AgentInput,
+        capabilities: &CapabilitySet,
+        justification: String,
+        authorizer: String,
+    ) -> VeritasResult<StepResult> {
+        if justification.trim().is_empty() || authorizer.trim().is_empty() {
+            return Err(VeritasError::ConfigError {
+                reason: "break-glass override requires a justification and an authorizer"
+                    .to_string(),
+            });
+        }
+        let break_glass = BreakGlass::new(justification, authorizer);
+        self.observed(state, |state| {
+            self.run_step(
+                agent,
+                state,
+                input,
+                capabilities,
+                &self.schema,
+                None,
+                Some(&break_glass),
+                true,
+            )
+        })
+    }
+
+    /// Predict whether `step()` would let the agent act, without acting.
+    ///
+    /// Applies the same abort condition and step limit, then runs the same
+    /// policy evaluation and capability check as `step()` with the same
+    /// arguments, but never calls `agent.propose()`, the verifier, or the
+    /// audit writer — nothing is recorded.  The policy is asked through
+    /// `evaluate`, so a preview spends no rate-limit budget.  Useful for UI
+    /// previews and plan validation.
+    ///
+    /// A firing abort condition or an exceeded step limit is reported as
+    /// `Denied`.  Fails closed: a policy evaluation error is reported as
+    /// `Denied` too.
+    pub fn preflight(
+        &self,
+        agent: &dyn Agent,
+        state: &AgentState,
+        input: &AgentInput,
+        capabilities: &CapabilitySet,
+    ) -> PreflightResult {
+        if let Some(reason) = self.abort_condition.as_ref().and_then(|check| check(state)) {
+            return PreflightResult::Denied { reason, remediation: None };
+        }
+        if self.max_steps.is_some_and(|limit| state.step >= limit) {
+            return PreflightResult::Denied {
+                reason: "max steps exceeded".to_string(),
+                remediation: None,
+            };
+        }
+
+        let (action, resource) = agent.describe_action(state, input);
+        let action_kind = agent.describe_action_kind(state, input, &action);
+        let metadata = agent.action_metadata(state, input);
+        let live = capabilities.live_at(self.clock.now());
+        let policy_ctx = policy_context(state, &action, &resource, action_kind, &live, metadata);
+
+        let verdict = match self.policy.evaluate(&policy_ctx) {
+            Ok(verdict) => verdict,
+            Err(e) => {
+                return PreflightResult::Denied {
+                    reason: format!("policy evaluation failed: {}", e),
+                    remediation: None,
+                };
+            }
+        };
+
+        match verdict {
+            PolicyVerdict::Deny { reason, remediation } => {
+                PreflightResult::Denied { reason, remediation }
+            }
+            PolicyVerdict::RequireApproval { reason, approver_role, .. } => {
+                PreflightResult::RequiresApproval { reason, approver_role }
+            }
+            PolicyVerdict::Allow
+            | PolicyVerdict::RequireVerification { .. }
+            | PolicyVerdict::RequireBoth { .. } => {
+                let required = agent.required_capabilities(state, input);
+                let now = self.clock.now();
+                match required
+                    .into_iter()
+                    .find(|name| !capabilities.has_at(&Capability::new(name.as_str()), now))
+                {
+                    Some(capability) => PreflightResult::CapabilityMissing { capability, action },
+                    // RequireBoth checks capabilities before it suspends.
+                    None => match verdict {
+                        PolicyVerdict::RequireBoth { reason, approver_role, .. } => {
+                            PreflightResult::RequiresApproval { reason, approver_role }
+                        }
+                        _ => PreflightResult::Allowed,
+                    },
+                }
+            }
+        }
+    }
+
+    /// Decide `ctx` for `run_step`, counting the pass toward the policy's
+    /// rate limits unless it is a retry, which was counted on its first pass.
+    /// A resume counts like a fresh step, so presenting one approval again
+    /// and again cannot run the agent past the limit.
+    fn decide_once(&self, ctx: &PolicyContext, allow_retry: bool) -> VeritasResult<PolicyDecision> {
+        if allow_retry {
+            self.policy.decide(ctx)
+        } else {
+            self.policy.decide_uncounted(ctx)
+        }
+    }
+
+    /// Run `run` on `state` and report its outcome to the observer, if any.
+    fn observed(
+        &self,
+        state: AgentState,
+        run: impl FnOnce(AgentState) -> VeritasResult<StepResult>,
+    ) -> VeritasResult<StepResult> {
+        let execution_id = state.execution_id.clone();
+        let step = state.step;
+        let result = run(state);
+        if let Some(observer) = &self.observer {
+            observer.on_step_finished(&execution_id, step, StepOutcome::from_result(&result));
+        }
+        result
+    }
+
+    /// The pipeline behind `step()`, `step_with_schema()`, `resume()` and
+    /// `step_break_glass()`. `schema` is the contract the output is verified
+    /// against. `approval` is the sign-off passed to `resume()`.
+    /// `break_glass` is the override passed to `step_break_glass()`, under
+    /// which a failed verification is recorded instead of rejected.
+    /// `allow_retry` is cleared on the
+    /// retry requested through `Agent::on_rejected()`, so an agent gets one
+    /// retry.
+    #[allow(clippy::too_many_arguments)]
+    fn run_step(
+        &self,
+        agent: &dyn Agent,
+        state: AgentState,
+        input: AgentInput,
+        capabilities: &CapabilitySet,
+        schema: &OutputSchema,
+        approval: Option<&ApprovalRecord>,
+        break_glass: Option<&BreakGlass>,
+        allow_retry: bool,
     ) -> VeritasResult<StepResult> {
         let execution_id = state.execution_id.0.to_string();
         let step_num = state.step;
 
+        let step_span = info_span!(
+            "veritas_step",
+            execution_id = %execution_id,
+            step = step_num,
+            action = field::Empty
+        );
+        let _step_entered = step_span.enter();
+
         debug!(
             execution_id = %execution_id,
             step = step_num,
@@ -87,26 +541,170 @@ impl Executor {
             "executor step starting"
         );
 
-        // ── Step 1: Describe the action the agent wants to take ──────────────
-        let (action, resource) = agent.describe_action(&state, &input);
-
-        let policy_ctx = PolicyContext {
-            agent_id: state.agent_id.0.clone(),
-            execution_id: execution_id.clone(),
-            current_phase: state.phase.clone(),
-            action: action.clone(),
-            resource: resource.clone(),
-            capabilities: capabilities.all().map(|c| c.0.clone()).collect(),
-            metadata: serde_json::Value::Null,
+        // The audit record every exit writes, each with its own verdict and
+        // timestamp.  The other fields are filled in here as the step gets
+        // that far, so no exit can leave one out.
+        let mut record = StepRecord {
+            step: step_num,
+            input,
+            verdict: PolicyVerdict::Allow,
+            output: None,
+            timestamp: self.clock.now(),
+            capability_justifications: BTreeMap::new(),
+            advisories: Vec::new(),
+            policy_eval_micros: None,
+            propose_micros: None,
+            verify_micros: None,
+            request_fingerprint: String::new(),
+            derived_from: None,
+            decision_trace: None,
+            action: String::new(),
+            resource: String::new(),
+            break_glass: None,
+            correlation_id: state.correlation_id.clone(),
+            shadow_verdict: None,
+            policy_tags: Vec::new(),
+            defaulted_fields: Vec::new(),
         };
+        let input = &record.input;
+
+        // ── Step 0: Abort condition ──────────────────────────────────────────
+        //
+        // Checked before anything else so a revoked execution cannot reach
+        // even the policy engine.
+        if let Some(reason) = self.abort_condition.as_ref().and_then(|check| check(&state)) {
+            warn!(
+                execution_id = %execution_id,
+                step = step_num,
+                reason = %reason,
+                "abort condition triggered, execution stopped"
+            );
+
+            let record = StepRecord {
+                verdict: PolicyVerdict::Deny { reason: reason.clone(), remediation: None },
+                timestamp: self.clock.now(),
+                ..record
+            };
+            self.write_audit(&state.execution_id, &record)?;
+
+            return Ok(StepResult::Denied {
+                reason,
+                code: DenyCode::Aborted,
+                rule_id: None,
+                remediation: None,
+                tags: Vec::new(),
+                final_state: state,
+            });
+        }
+
+        // The step limit is likewise enforced before the policy: an execution
+        // past its bound gets no further decisions.
+        if self.max_steps.is_some_and(|limit| step_num >= limit) {
+            let reason = "max steps exceeded".to_string();
+            warn!(
+                execution_id = %execution_id,
+                step = step_num,
+                max_steps = self.max_steps,
+                "step limit reached, execution stopped"
+            );
+
+            let record = StepRecord {
+                verdict: PolicyVerdict::Deny { reason: reason.clone(), remediation: None },
+                timestamp: self.clock.now(),
+                ..record
+            };
+            self.write_audit(&state.execution_id, &record)?;
+
+            return Err(VeritasError::StateMachineError { reason });
+        }
+
+        // ── Step 1: Describe the action the agent wants to take ──────────────
+        let (action, resource) = agent.describe_action(&state, input);
+        step_span.record("action", action.as_str());
+
+        let action_kind = agent.describe_action_kind(&state, input, &action);
+        let metadata = agent.action_metadata(&state, input);
+        let live = capabilities.live_at(self.clock.now());
+        let policy_ctx = policy_context(&state, &action, &resource, action_kind, &live, metadata);
+        record.request_fingerprint =
+            request_fingerprint(&action, &resource, &live, &input.payload);
+        record.action = action.clone();
+        record.resource = resource;
 
         // ── Step 2: Policy evaluation ────────────────────────────────────────
         //
         // This is the primary trust gate. No agent logic runs until Allow.
-        let verdict = self.policy.evaluate(&policy_ctx)?;
+        let started = Instant::now();
+        let PolicyDecision { verdict, rule_id, deny_code, trace, tags } = debug_span!("policy")
+            .in_scope(|| self.decide_once(&policy_ctx, allow_retry))?;
+        record.policy_eval_micros = self.audit_micros(started);
+        record.decision_trace = trace.filter(|_| self.decision_trace_in_audit);
+        record.policy_tags = tags;
+        if let Some(observer) = &self.observer {
+            observer.on_policy_evaluated(&policy_ctx, &verdict, started.elapsed());
+        }
+        record.shadow_verdict =
+            self.shadow_verdict(&policy_ctx, &verdict, &execution_id, step_num);
+        record.verdict = verdict.clone();
+
+        // An approval presented to `resume()` must still be fresh and come
+        // from the required role; otherwise it is rejected outright rather
+        // than treated as merely insufficient.
+        if let (Some((_, approver_role, _)), Some(approval)) =
+            (verdict.approval_requirement(), approval)
+        {
+            let now = self.clock.now();
+            if approval.is_expired_at(now) {
+                let reason = format!(
+                    "approval by '{}' granted at {} expired at {}",
+                    approval.approver_id,
+                    approval.granted_at.to_rfc3339(),
+                    approval.expires_at().map(|at| at.to_rfc3339()).unwrap_or_default()
+                );
+                warn!(
+                    execution_id = %execution_id,
+                    step = step_num,
+                    reason = %reason,
+                    "expired approval rejected"
+                );
+
+                let record = StepRecord {
+                    verdict: PolicyVerdict::Deny { reason: reason.clone(), remediation: None },
+                    timestamp: now,
+                    ..record
+                };
+                self.write_audit(&state.execution_id, &record)?;
+
+                return Err(VeritasError::ApprovalRejected { reason });
+            }
+
+            // An approver of the wrong role cannot satisfy the verdict at all;
+            // only a missing attribute leaves the step awaiting a better match.
+            if approval.approver_role != approver_role {
+                let reason = format!(
+                    "approval by '{}' holds role '{}', but '{}' is required",
+                    approval.approver_id, approval.approver_role, approver_role
+                );
+                warn!(
+                    execution_id = %execution_id,
+                    step = step_num,
+                    reason = %reason,
+                    "approval from wrong role rejected"
+                );
+
+                let record = StepRecord {
+                    verdict: PolicyVerdict::Deny { reason: reason.clone(), remediation: None },
+                    timestamp: now,
+                    ..record
+                };
+                self.write_audit(&state.execution_id, &record)?;
+
+                return Err(VeritasError::ApprovalRejected { reason });
+            }
+        }
 
         match &verdict {
-            PolicyVerdict::Deny { reason } => {
+            PolicyVerdict::Deny { reason, remediation } => {
                 warn!(
                     execution_id = %execution_id,
                     step = step_num,
@@ -116,21 +714,35 @@ impl Executor {
 
                 // Audit the denial so every denied step is on record.
                 let record = StepRecord {
-                    step: step_num,
-                    input,
-                    verdict: verdict.clone(),
-                    output: None,
-                    timestamp: Utc::now(),
+                    timestamp: self.clock.now(),
+                    ..record
                 };
-                self.audit.write(&record)?;
+                self.write_audit(&state.execution_id, &record)?;
 
                 return Ok(StepResult::Denied {
                     reason: reason.clone(),
+                    code: deny_code.unwrap_or(DenyCode::Unspecified),
+                    rule_id,
+                    remediation: remediation.clone(),
+                    tags: record.policy_tags,
                     final_state: state,
                 });
             }
 
-            PolicyVerdict::RequireApproval { reason, approver_role } => {
+            PolicyVerdict::RequireApproval { approver_role, required_attributes, .. }
+                if approval.is_some_and(|a| {
+                    a.unmet_requirements(approver_role, required_attributes).is_empty()
+                }) =>
+            {
+                info!(
+                    execution_id = %execution_id,
+                    step = step_num,
+                    approver_role = %approver_role,
+                    "approval requirements met, resuming step"
+                );
+            }
+
+            PolicyVerdict::RequireApproval { reason, approver_role, required_attributes } => {
                 info!(
                     execution_id = %execution_id,
                     step = step_num,
@@ -138,23 +750,25 @@ impl Executor {
                     "execution suspended awaiting approval"
                 );
 
+                let reason =
+                    suspension_reason(reason, approver_role, required_attributes, approval);
+
                 let record = StepRecord {
-                    step: step_num,
-                    input,
-                    verdict: verdict.clone(),
-                    output: None,
-                    timestamp: Utc::now(),
+                    timestamp: self.clock.now(),
+                    ..record
                 };
-                self.audit.write(&record)?;
+                self.write_audit(&state.execution_id, &record)?;
 
                 return Ok(StepResult::AwaitingApproval {
-                    reason: reason.clone(),
+                    reason,
                     approver_role: approver_role.clone(),
                     suspended_state: state,
+                    tags: record.policy_tags,
                 });
             }
 
-            // Allow and RequireVerification both proceed to capability check.
+            // Allow and RequireVerification both proceed to capability check,
+            // as does RequireApproval with a sufficient approval (above).
             PolicyVerdict::Allow | PolicyVerdict::RequireVerification { .. } => {
                 debug!(
                     execution_id = %execution_id,
@@ -162,16 +776,29 @@ impl Executor {
                     "policy allowed action, checking capabilities"
                 );
             }
+
+            // RequireBoth verifies before it asks for approval: the approval
+            // gate runs after Step 5, once the output has passed its checks.
+            PolicyVerdict::RequireBoth { .. } => {
+                debug!(
+                    execution_id = %execution_id,
+                    step = step_num,
+                    "policy requires verification and approval, checking capabilities"
+                );
+            }
         }
 
         // ── Step 3: Capability check ─────────────────────────────────────────
         //
-        // Even after Allow, the agent must hold every declared capability.
-        // This enforces principle of least privilege at the runtime level.
-        let required = agent.required_capabilities(&state, &input);
+        // Even after Allow, the agent must hold every declared capability,
+        // unexpired as of the executor's clock.  This enforces principle of
+        // least privilege at the runtime level.
+        let capability_span = debug_span!("capability").entered();
+        let required = agent.required_capabilities(&state, input);
+        let now = self.clock.now();
         for cap_name in &required {
             let cap = Capability::new(cap_name.as_str());
-            if !capabilities.has(&cap) {
+            if !capabilities.has_at(&cap, now) {
                 warn!(
                     execution_id = %execution_id,
                     step = step_num,
@@ -186,15 +813,14 @@ impl Executor {
                         "capability '{}' required for action '{}' is not granted",
                         cap_name, action
                     ),
+                    remediation: None,
                 };
                 let record = StepRecord {
-                    step: step_num,
-                    input,
                     verdict: denial_verdict,
-                    output: None,
-                    timestamp: Utc::now(),
+                    timestamp: self.clock.now(),
+                    ..record
                 };
-                self.audit.write(&record)?;
+                self.write_audit(&state.execution_id, &record)?;
 
                 return Err(VeritasError::CapabilityMissing {
                     capability: cap_name.clone(),
@@ -202,29 +828,121 @@ impl Executor {
                 });
             }
         }
+        record.capability_justifications = capability_justifications(capabilities, &required);
+        drop(capability_span);
+
+        // ── Step 3b: Input preconditions ─────────────────────────────────────
+        //
+        // The agent vets its own input before doing any work.  A rejection is
+        // audited like any other denial and its error returned unchanged.
+        if let Err(e) = agent.validate_input(&state, input) {
+            warn!(
+                execution_id = %execution_id,
+                step = step_num,
+                error = %e,
+                "agent rejected its input, step denied"
+            );
+
+            let record = StepRecord {
+                verdict: PolicyVerdict::Deny {
+                    reason: format!("agent rejected input: {}", e),
+                    remediation: None,
+                },
+                timestamp: self.clock.now(),
+                ..record
+            };
+            self.write_audit(&state.execution_id, &record)?;
+
+            return Err(e);
+        }
 
         // ── Step 4: Agent proposal ───────────────────────────────────────────
         //
         // Only reachable if policy returned Allow AND all capabilities present.
         // This is the ONLY call site for agent.propose() in the runtime.
+        //
+        // The agent is untrusted: a panic inside propose() is contained here,
+        // audited as a denial and reported as an error, and state does not
+        // advance.
         debug!(
             execution_id = %execution_id,
             step = step_num,
             "capabilities verified, calling agent.propose()"
         );
-        let output = agent.propose(&state, &input)?;
+        let started = Instant::now();
+        let proposed = debug_span!("propose").in_scope(|| {
+            panic::catch_unwind(AssertUnwindSafe(|| agent.propose(&state, input)))
+        });
+        record.propose_micros = self.audit_micros(started);
+        let output = match proposed {
+            Ok(result) => result?,
+            Err(panic) => {
+                let detail = panic
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| panic.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".to_string());
+                warn!(
+                    execution_id = %execution_id,
+                    step = step_num,
+                    panic = %detail,
+                    "agent panicked during propose, step denied"
+                );
+
+                let record = StepRecord {
+                    verdict: PolicyVerdict::Deny {
+                        reason: format!("agent panicked during propose: {}", detail),
+                        remediation: None,
+                    },
+                    timestamp: self.clock.now(),
+                    ..record
+                };
+                self.write_audit(&state.execution_id, &record)?;
+
+                return Err(VeritasError::StateMachineError {
+                    reason: "agent panicked during propose".to_string(),
+                });
+            }
+        };
 
         // ── Step 5: Output verification ──────────────────────────────────────
         //
         // The verifier inspects the raw LLM/agent output before it touches state.
-        let report = self.verifier.verify(&output, &self.schema)?;
+        //
+        // With output normalization on, schema defaults are filled in first
+        // and the normalized output replaces the proposed one from here on.
+        // The record names the fields the runtime filled, so the audit tells
+        // them apart from what the agent produced.
+        let output = if self.output_normalization {
+            let normalized = self.verifier.normalize(&output, schema);
+            record.defaulted_fields = filled_fields(&output.payload, &normalized.payload);
+            normalized
+        } else {
+            output
+        };
+
+        // A RequireVerification or RequireBoth verdict adds the named checks
+        // on top of the schema: all must pass, and the first failure stops
+        // the rest.
+        let started = Instant::now();
+        let report = debug_span!("verify").in_scope(|| {
+            let mut report = self.verifier.verify(&output, schema)?;
+            for check_id in verdict.verification_checks() {
+                if !report.passed {
+                    break;
+                }
+                report = self.verifier.run_check(check_id, &output)?;
+            }
+            Ok::<_, VeritasError>(report)
+        })?;
+        record.verify_micros = self.audit_micros(started);
         if !report.passed {
-            let failure_summary = report
+            let failures: Vec<String> = report
                 .failures
                 .iter()
                 .map(|f| format!("[{}] {}", f.rule_id, f.message))
-                .collect::<Vec<_>>()
-                .join("; ");
+                .collect();
+            let failure_summary = failures.join("; ");
 
             warn!(
                 execution_id = %execution_id,
@@ -232,23 +950,152 @@ impl Executor {
                 failures = %failure_summary,
                 "output verification failed"
             );
-            return Err(VeritasError::VerificationFailed {
-                reason: failure_summary,
-            });
+
+            if let Some(break_glass) = break_glass {
+                warn!(
+                    execution_id = %execution_id,
+                    step = step_num,
+                    authorizer = %break_glass.authorizer,
+                    justification = %break_glass.justification,
+                    failures = %failure_summary,
+                    "BREAK-GLASS: delivering output despite failed verification"
+                );
+                record.break_glass = Some(BreakGlass {
+                    overridden_failures: failures,
+                    ..break_glass.clone()
+                });
+            } else if allow_retry {
+                let rejection = RejectionReason::VerificationFailed {
+                    failures: report.failures.clone(),
+                };
+                if let Some(retry_input) = agent.on_rejected(&state, &rejection) {
+                    info!(
+                        execution_id = %execution_id,
+                        step = step_num,
+                        "agent requested a retry after verification failure"
+                    );
+
+                    // Keep the first rejection on record before retrying. The
+                    // rejected output is left out: it may carry the very
+                    // content the verifier refused to deliver.
+                    let record = StepRecord {
+                        verdict: PolicyVerdict::Deny {
+                            reason: format!("output verification failed: {}", failure_summary),
+                            remediation: None,
+                        },
+                        timestamp: self.clock.now(),
+                        ..record
+                    };
+                    self.write_audit(&state.execution_id, &record)?;
+
+                    return self.run_step(
+                        agent,
+                        state,
+                        retry_input,
+                        capabilities,
+                        schema,
+                        approval,
+                        None,
+                        false,
+                    );
+                }
+            }
+
+            if record.break_glass.is_none() {
+                return Err(VeritasError::VerificationFailed {
+                    reason: failure_summary,
+                });
+            }
+        }
+
+        // ── Step 5b: Approval after verification ─────────────────────────────
+        //
+        // A RequireBoth output that passed its checks still waits for a
+        // sufficient sign-off.  The suspension is recorded but the output is
+        // withheld (`output: None`): the resumed step proposes and verifies
+        // afresh.
+        if let PolicyVerdict::RequireBoth { reason, approver_role, required_attributes, .. } =
+            &verdict
+        {
+            if !approval.is_some_and(|a| {
+                a.unmet_requirements(approver_role, required_attributes).is_empty()
+            }) {
+                info!(
+                    execution_id = %execution_id,
+                    step = step_num,
+                    approver_role = %approver_role,
+                    "output verified, execution suspended awaiting approval"
+                );
+
+                let reason =
+                    suspension_reason(reason, approver_role, required_attributes, approval);
+                let record = StepRecord {
+                    timestamp: self.clock.now(),
+                    ..record
+                };
+                self.write_audit(&state.execution_id, &record)?;
+
+                return Ok(StepResult::AwaitingApproval {
+                    reason,
+                    approver_role: approver_role.clone(),
+                    suspended_state: state,
+                    tags: record.policy_tags,
+                });
+            }
+
+            info!(
+                execution_id = %execution_id,
+                step = step_num,
+                approver_role = %approver_role,
+                "output verified and approval requirements met, resuming step"
+            );
         }
 
         // ── Step 6: State transition ─────────────────────────────────────────
-        let next_state = agent.transition(&state, &output)?;
+        //
+        // The output is already verified, so a failed transition must still
+        // leave a record: the audit shows the output was produced even though
+        // state could not advance.
+        let mut next_state = match agent.transition(&state, &output) {
+            Ok(next_state) => next_state,
+            Err(e) => {
+                warn!(
+                    execution_id = %execution_id,
+                    step = step_num,
+                    error = %e,
+                    "state transition failed after verification"
+                );
+
+                let record = StepRecord {
+                    verdict: PolicyVerdict::Deny {
+                        reason: format!("state transition failed: {}", e),
+                        remediation: None,
+                    },
+                    output: Some(output.clone()),
+                    timestamp: self.clock.now(),
+                    advisories: output.advisories.clone(),
+                    ..record
+                };
+                self.write_audit(&state.execution_id, &record)?;
+
+                return Err(VeritasError::StateMachineError {
+                    reason: format!("state transition failed after verification: {}", e),
+                });
+            }
+        };
+
+        // The correlation id belongs to the caller, not the agent: carry it
+        // over even when `transition()` builds a fresh state.
+        next_state.correlation_id = state.correlation_id.clone();
 
         // ── Step 7: Audit the completed step ─────────────────────────────────
         let record = StepRecord {
-            step: step_num,
-            input,
-            verdict,
             output: Some(output.clone()),
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
+            advisories: output.advisories.clone(),
+            ..record
         };
-        self.audit.write(&record)?;
+        self.write_audit(&state.execution_id, &record)?;
 
         // ── Steps 8 & 9: Terminal check ──────────────────────────────────────
         if agent.is_terminal(&next_state) {
@@ -257,36 +1104,366 @@ impl Executor {
                 step = step_num,
                 "agent reached terminal state, finalizing audit"
             );
-            self.audit.finalize(&execution_id)?;
+            self.finalize_audit(&state.execution_id)?;
             Ok(StepResult::Complete {
                 final_state: next_state,
                 output,
+                tags: record.policy_tags,
             })
         } else {
             Ok(StepResult::Transitioned {
                 next_state,
                 output,
+                tags: record.policy_tags,
             })
         }
     }
+
+    /// What the shadow policy, if any, decides for `ctx`.  Logs a verdict
+    /// that diverges from the `enforced` one; never affects the step.
+    fn shadow_verdict(
+        &self,
+        ctx: &PolicyContext,
+        enforced: &PolicyVerdict,
+        execution_id: &str,
+        step_num: u64,
+    ) -> Option<PolicyVerdict> {
+        let shadow = self.shadow_policy.as_ref()?;
+        match debug_span!("shadow_policy").in_scope(|| shadow.evaluate(ctx)) {
+            Ok(verdict) => {
+                if verdict != *enforced {
+                    info!(
+                        execution_id = %execution_id,
+                        step = step_num,
+                        enforced = ?enforced,
+                        shadow = ?verdict,
+                        "shadow policy diverges from enforced policy"
+                    );
+                }
+                Some(verdict)
+            }
+            Err(e) => {
+                warn!(
+                    execution_id = %execution_id,
+                    step = step_num,
+                    error = %e,
+                    "shadow policy evaluation failed"
+                );
+                None
+            }
+        }
+    }
+
+    /// Microseconds since `started` when timings are recorded in audit
+    /// (`with_timing_in_audit`), otherwise `None`.
+    fn audit_micros(&self, started: Instant) -> Option<u64> {
+        self.timing_in_audit
+            .then(|| u64::try_from(started.elapsed().as_micros()).unwrap_or(u64::MAX))
+    }
+
+    // ── Audit routing ─────────────────────────────────────────────────────────
+
+    /// Append `record` to the audit chain of `execution_id`.
+    fn write_audit(&self, execution_id: &ExecutionId, record: &StepRecord) -> VeritasResult<()> {
+        let _span = debug_span!("audit").entered();
+        match &self.audit {
+            AuditSink::Single(writer) => writer.write(record),
+            AuditSink::Registry(registry) => registry.writer_for(execution_id)?.write(record),
+        }
+    }
+
+    /// Finalize the audit chain of `execution_id`, evicting a registry's
+    /// writer for it.
+    fn finalize_audit(&self, execution_id: &ExecutionId) -> VeritasResult<()> {
+        let _span = debug_span!("audit").entered();
+        match &self.audit {
+            AuditSink::Single(writer) => writer.finalize(&execution_id.0.to_string()),
+            AuditSink::Registry(registry) => registry.finalize(execution_id),
+        }
+    }
+}
+
+// ── Builder ───────────────────────────────────────────────────────────────────
+
+/// Assembles an `Executor` from named parts.
+///
+/// `policy`, `verifier`, `schema` and one of `audit` / `audit_registry` are
+/// required; `observer`, `clock`, `abort_condition` and `max_steps` are
+/// optional.
+///
+/// ```rust,ignore
+/// let executor = Executor::builder()
+///     .policy(Box::new(policy))
+///     .audit(Box::new(audit))
+///     .verifier(Box::new(verifier))
+///     .schema(schema)
+///     .clock(Box::new(FixedClock::new(start)))
+///     .build()?;
+/// ```
+#[derive(Default)]
+pub struct ExecutorBuilder {
+    policy: Option<Box<dyn PolicyEngine>>,
+    shadow_policy: Option<Box<dyn PolicyEngine>>,
+    audit: Option<AuditSink>,
+    verifier: Option<Box<dyn Verifier>>,
+    schema: Option<OutputSchema>,
+    clock: Option<Box<dyn Clock>>,
+    observer: Option<Box<dyn ExecutorObserver>>,
+    abort_condition: Option<AbortCondition>,
+    max_steps: Option<u64>,
+    timing_in_audit: bool,
+    decision_trace_in_audit: bool,
+    output_normalization: bool,
+}
+
+impl ExecutorBuilder {
+    /// Set the policy engine.
+    pub fn policy(mut self, policy: Box<dyn PolicyEngine>) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Evaluate `policy` in shadow mode, as `Executor::with_shadow_policy()`
+    /// does.  None by default.
+    pub fn shadow_policy(mut self, policy: Box<dyn PolicyEngine>) -> Self {
+        self.shadow_policy = Some(policy);
+        self
+    }
+
+    /// Send every step's audit records to `audit`.
+    pub fn audit(mut self, audit: Box<dyn AuditWriter>) -> Self {
+        self.audit = Some(AuditSink::Single(audit));
+        self
+    }
+
+    /// Route audit records per execution through `registry`, as
+    /// `Executor::new_with_registry()` does.  Replaces any `audit` writer.
+    pub fn audit_registry(mut self, registry: Arc<AuditRegistry>) -> Self {
+        self.audit = Some(AuditSink::Registry(registry));
+        self
+    }
+
+    /// Set the output verifier.
+    pub fn verifier(mut self, verifier: Box<dyn Verifier>) -> Self {
+        self.verifier = Some(verifier);
+        self
+    }
+
+    /// Set the schema every output is verified against.
+    pub fn schema(mut self, schema: OutputSchema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Set the clock used to timestamp audit records.  Defaults to
+    /// `SystemClock`.
+    pub fn clock(mut self, clock: Box<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Attach an observer.  None by default.
+    pub fn observer(mut self, observer: Box<dyn ExecutorObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Install an abort condition, as `Executor::set_abort_condition()`
+    /// does.  None by default.
+    pub fn abort_condition(mut self, condition: AbortCondition) -> Self {
+        self.abort_condition = Some(condition);
+        self
+    }
+
+    /// Limit each execution to `limit` steps, as `Executor::with_max_steps()`
+    /// does.  Unlimited by default.
+    pub fn max_steps(mut self, limit: u64) -> Self {
+        self.max_steps = Some(limit);
+        self
+    }
+
+    /// Record phase timings in audit records, as
+    /// `Executor::with_timing_in_audit()` does.  Off by default.
+    pub fn timing_in_audit(mut self, enabled: bool) -> Self {
+        self.timing_in_audit = enabled;
+        self
+    }
+
+    /// Record policy decision traces in audit records, as
+    /// `Executor::with_decision_trace_in_audit()` does.  Off by default.
+    pub fn decision_trace_in_audit(mut self, enabled: bool) -> Self {
+        self.decision_trace_in_audit = enabled;
+        self
+    }
+
+    /// Normalize outputs before verification, as
+    /// `Executor::with_output_normalization()` does.  Off by default.
+    pub fn output_normalization(mut self, enabled: bool) -> Self {
+        self.output_normalization = enabled;
+        self
+    }
+
+    /// Build the executor.
+    ///
+    /// Returns `VeritasError::ConfigError` naming the first required
+    /// component that was never set.
+    pub fn build(self) -> VeritasResult<Executor> {
+        fn required<T>(part: Option<T>, name: &str) -> VeritasResult<T> {
+            part.ok_or_else(|| VeritasError::ConfigError {
+                reason: format!("executor builder is missing a {name}"),
+            })
+        }
+
+        Ok(Executor {
+            policy: required(self.policy, "policy engine")?,
+            shadow_policy: self.shadow_policy,
+            audit: required(self.audit, "audit writer")?,
+            verifier: required(self.verifier, "verifier")?,
+            schema: required(self.schema, "output schema")?,
+            clock: self.clock.unwrap_or_else(|| Box::new(SystemClock)),
+            observer: self.observer,
+            abort_condition: self.abort_condition,
+            max_steps: self.max_steps,
+            timing_in_audit: self.timing_in_audit,
+            decision_trace_in_audit: self.decision_trace_in_audit,
+            output_normalization: self.output_normalization,
+        })
+    }
+}
+
+/// Build the `PolicyContext` for `action` (of `action_kind`) on `resource` in
+/// `state`, carrying the agent's action `metadata`.  `capabilities` should
+/// be the grants live at the executor's clock (`CapabilitySet::live_at`), so
+/// a lapsed grant cannot satisfy a rule.
+fn policy_context(
+    state: &AgentState,
+    action: &str,
+    resource: &str,
+    action_kind: Option<ActionKind>,
+    capabilities: &CapabilitySet,
+    metadata: serde_json::Value,
+) -> PolicyContext {
+    PolicyContext {
+        agent_id: state.agent_id.0.clone(),
+        execution_id: state.execution_id.0.to_string(),
+        current_phase: state.phase.clone(),
+        action: action.to_string(),
+        resource: resource.to_string(),
+        action_kind,
+        // `all()` yields names sorted, so the context is deterministic.
+        capabilities: capabilities.all().map(|c| c.0.clone()).collect(),
+        metadata,
+    }
+}
+
+/// The reason reported for a step suspended awaiting approval: the policy's
+/// `reason`, extended — when an insufficient `approval` was presented to
+/// `resume()` — with what it lacks, so the caller can find the right
+/// approver.
+fn suspension_reason(
+    reason: &str,
+    approver_role: &str,
+    required_attributes: &BTreeMap<String, String>,
+    approval: Option<&ApprovalRecord>,
+) -> String {
+    match approval {
+        Some(a) => format!(
+            "{}; approval by '{}' does not satisfy: {}",
+            reason,
+            a.approver_id,
+            a.unmet_requirements(approver_role, required_attributes).join(", ")
+        ),
+        None => reason.to_string(),
+    }
+}
+
+/// The justifications of the `required` capabilities, keyed by name.
+///
+/// Capabilities granted without a justification are omitted.
+fn capability_justifications(
+    capabilities: &CapabilitySet,
+    required: &[String],
+) -> BTreeMap<String, String> {
+    required
+        .iter()
+        .filter_map(|name| {
+            capabilities
+                .justification(&Capability::new(name.as_str()))
+                .map(|reason| (name.clone(), reason.to_string()))
+        })
+        .collect()
+}
+
+/// JSON Pointers to the values `normalized` holds where `proposed` has none
+/// — the key is absent or `null` — outermost first, in key order.
+///
+/// A filled-in object is reported as a whole, not field by field.
+fn filled_fields(proposed: &serde_json::Value, normalized: &serde_json::Value) -> Vec<String> {
+    fn walk(
+        proposed: &serde_json::Value,
+        normalized: &serde_json::Value,
+        pointer: &str,
+        filled: &mut Vec<String>,
+    ) {
+        if normalized.is_null() {
+            return;
+        }
+        if proposed.is_null() {
+            filled.push(pointer.to_string());
+            return;
+        }
+        let mut descend = |key: &str, before: &serde_json::Value, after: &serde_json::Value| {
+            let key = key.replace('~', "~0").replace('/', "~1");
+            walk(before, after, &format!("{pointer}/{key}"), filled);
+        };
+        match (proposed, normalized) {
+            (serde_json::Value::Object(before), serde_json::Value::Object(after)) => {
+                for (key, value) in after {
+                    descend(key, before.get(key).unwrap_or(&serde_json::Value::Null), value);
+                }
+            }
+            (serde_json::Value::Array(before), serde_json::Value::Array(after)) => {
+                for (i, (b, a)) in before.iter().zip(after).enumerate() {
+                    descend(&i.to_string(), b, a);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut filled = Vec::new();
+    walk(proposed, normalized, "", &mut filled);
+    filled
 }
 
 // ── Tests ────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
-    use std::sync::{Arc, Mutex};
+    use std::{
+        collections::BTreeMap,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
 
     use veritas_contracts::{
         agent::{AgentId, AgentInput, AgentOutput, AgentState, ExecutionId},
-        capability::CapabilitySet,
+        capability::{Capability, CapabilitySet},
         error::{VeritasError, VeritasResult},
-        execution::{StepRecord, StepResult},
-        policy::{PolicyContext, PolicyVerdict},
-        verify::{OutputSchema, VerificationFailure, VerificationReport},
+        execution::{
+            request_fingerprint, PreflightResult, RejectionReason, StepOutcome, StepRecord,
+            StepResult,
+        },
+        policy::{ActionKind, ApprovalRecord, PolicyContext, PolicyDecision, PolicyVerdict},
+        verify::{
+            FieldDefault, OutputSchema, RuleSeverity, VerificationFailure, VerificationReport,
+        },
     };
 
-    use crate::traits::{Agent, AuditWriter, PolicyEngine, Verifier};
+    use crate::{
+        clock::FixedClock,
+        traits::{Agent, AuditWriter, ExecutorObserver, PolicyEngine, Verifier},
+    };
 
     use super::Executor;
 
@@ -299,6 +1476,7 @@ mod tests {
             phase: phase.to_string(),
             context: serde_json::Value::Null,
             step: 0,
+            correlation_id: None,
         }
     }
 
@@ -314,6 +1492,7 @@ mod tests {
             schema_id: "test-schema-v1".to_string(),
             json_schema: serde_json::Value::Null,
             rules: vec![],
+            defaults: vec![],
         }
     }
 
@@ -328,6 +1507,34 @@ mod tests {
         }
     }
 
+    /// A policy returning a pre-configured verdict that counts the
+    /// decisions spending its budget, as a rate-limited engine would.
+    struct CountingPolicy {
+        verdict: PolicyVerdict,
+        counted: Arc<Mutex<u32>>,
+    }
+
+    impl CountingPolicy {
+        fn new(verdict: PolicyVerdict) -> Self {
+            Self { verdict, counted: Arc::new(Mutex::new(0)) }
+        }
+    }
+
+    impl PolicyEngine for CountingPolicy {
+        fn evaluate(&self, _ctx: &PolicyContext) -> VeritasResult<PolicyVerdict> {
+            Ok(self.verdict.clone())
+        }
+
+        fn decide(&self, ctx: &PolicyContext) -> VeritasResult<PolicyDecision> {
+            *self.counted.lock().unwrap() += 1;
+            self.decide_uncounted(ctx)
+        }
+
+        fn decide_uncounted(&self, ctx: &PolicyContext) -> VeritasResult<PolicyDecision> {
+            self.evaluate(ctx).map(PolicyDecision::from_verdict)
+        }
+    }
+
     /// An audit writer that records every call for later inspection.
     struct MockAudit {
         records: Arc<Mutex<Vec<StepRecord>>>,
@@ -367,15 +1574,13 @@ mod tests {
             _schema: &OutputSchema,
         ) -> VeritasResult<VerificationReport> {
             if self.pass {
-                Ok(VerificationReport { passed: true, failures: vec![] })
+                Ok(VerificationReport::from_failures(vec![]))
             } else {
-                Ok(VerificationReport {
-                    passed: false,
-                    failures: vec![VerificationFailure {
-                        rule_id: "required-field".to_string(),
-                        message: "field 'patient_id' is missing".to_string(),
-                    }],
-                })
+                Ok(VerificationReport::from_failures(vec![VerificationFailure {
+                    rule_id: "required-field".to_string(),
+                    message: "field 'patient_id' is missing".to_string(),
+                    severity: RuleSeverity::Error,
+                }]))
             }
         }
     }
@@ -409,6 +1614,8 @@ mod tests {
             Ok(AgentOutput {
                 kind: "response".to_string(),
                 payload: serde_json::json!({ "text": "ok" }),
+                provenance: None,
+                advisories: vec![],
             })
         }
 
@@ -484,6 +1691,150 @@ mod tests {
         }
     }
 
+    /// An agent that requires a fixed list of capabilities and proposes normally
+    /// once they are held.
+    struct ScopedAgent {
+        required: Vec<String>,
+    }
+
+    impl Agent for ScopedAgent {
+        fn propose(&self, _state: &AgentState, _input: &AgentInput) -> VeritasResult<AgentOutput> {
+            Ok(AgentOutput {
+                kind: "response".to_string(),
+                payload: serde_json::json!({ "text": "ok" }),
+                provenance: None,
+                advisories: vec![],
+            })
+        }
+
+        fn transition(
+            &self,
+            state: &AgentState,
+            _output: &AgentOutput,
+        ) -> VeritasResult<AgentState> {
+            Ok(AgentState { step: state.step + 1, ..state.clone() })
+        }
+
+        fn required_capabilities(
+            &self,
+            _state: &AgentState,
+            _input: &AgentInput,
+        ) -> Vec<String> {
+            self.required.clone()
+        }
+
+        fn describe_action(
+            &self,
+            _state: &AgentState,
+            _input: &AgentInput,
+        ) -> (String, String) {
+            ("read_phi".to_string(), "patient_record".to_string())
+        }
+
+        fn is_terminal(&self, _state: &AgentState) -> bool {
+            false
+        }
+    }
+
+    /// A verifier that rejects any summary containing an SSN label, or every
+    /// summary when `reject_all` is set.
+    struct PiiVerifier {
+        reject_all: bool,
+    }
+
+    impl Verifier for PiiVerifier {
+        fn verify(
+            &self,
+            output: &AgentOutput,
+            _schema: &OutputSchema,
+        ) -> VeritasResult<VerificationReport> {
+            let summary = output.payload["summary"].as_str().unwrap_or("");
+            if self.reject_all || summary.contains("SSN:") {
+                Ok(VerificationReport::from_failures(vec![VerificationFailure {
+                    rule_id: "no-pii-labels".to_string(),
+                    message: "summary contains forbidden PII label 'SSN:'".to_string(),
+                    severity: RuleSeverity::Error,
+                }]))
+            } else {
+                Ok(VerificationReport::from_failures(vec![]))
+            }
+        }
+    }
+
+    /// A summarizer that leaks PII unless asked for a redacted summary, and
+    /// asks for one when its output is rejected.
+    struct SelfCorrectingAgent {
+        propose_count: Arc<Mutex<u32>>,
+        /// When false, on_rejected() never asks for a redacted retry.
+        corrects: bool,
+    }
+
+    impl SelfCorrectingAgent {
+        fn new(corrects: bool) -> Self {
+            Self { propose_count: Arc::new(Mutex::new(0)), corrects }
+        }
+    }
+
+    impl Agent for SelfCorrectingAgent {
+        fn propose(&self, _state: &AgentState, input: &AgentInput) -> VeritasResult<AgentOutput> {
+            *self.propose_count.lock().unwrap() += 1;
+            let summary = if input.kind == "summarize-redacted" {
+                "Patient presents with mild anemia."
+            } else {
+                "Patient SSN: 123-45-6789 presents with mild anemia."
+            };
+            Ok(AgentOutput {
+                kind: "clinical-summary".to_string(),
+                payload: serde_json::json!({ "summary": summary }),
+                provenance: None,
+                advisories: vec![],
+            })
+        }
+
+        fn transition(
+            &self,
+            state: &AgentState,
+            _output: &AgentOutput,
+        ) -> VeritasResult<AgentState> {
+            Ok(AgentState {
+                step: state.step + 1,
+                phase: "complete".to_string(),
+                ..state.clone()
+            })
+        }
+
+        fn required_capabilities(
+            &self,
+            _state: &AgentState,
+            _input: &AgentInput,
+        ) -> Vec<String> {
+            vec![]
+        }
+
+        fn describe_action(
+            &self,
+            _state: &AgentState,
+            _input: &AgentInput,
+        ) -> (String, String) {
+            ("summarize".to_string(), "clinical-notes".to_string())
+        }
+
+        fn is_terminal(&self, state: &AgentState) -> bool {
+            state.phase == "complete"
+        }
+
+        fn on_rejected(&self, _state: &AgentState, reason: &RejectionReason) -> Option<AgentInput> {
+            let RejectionReason::VerificationFailed { failures } = reason;
+            if !self.corrects || !failures.iter().any(|f| f.rule_id == "no-pii-labels") {
+                return None;
+            }
+            Some(AgentInput {
+                kind: "summarize-redacted".to_string(),
+                payload: serde_json::json!({}),
+            })
+        }
+    }
+
     // ── Test cases ────────────────────────────────────────────────────────────
 
     /// Core security test: a policy Deny must prevent agent.propose() from
@@ -499,7 +1850,12 @@ mod tests {
         let audit_records = audit.records.clone();
 
         let executor = Executor::new(
-            Box::new(MockPolicy { verdict: PolicyVerdict::Deny { reason: "not allowed".to_string() } }),
+            Box::new(MockPolicy {
+                verdict: PolicyVerdict::Deny {
+                    reason: "not allowed".to_string(),
+                    remediation: None,
+                },
+            }),
             Box::new(audit),
             Box::new(MockVerifier { pass: true }),
             make_schema(),
@@ -529,6 +1885,7 @@ mod tests {
                 verdict: PolicyVerdict::RequireApproval {
                     reason: "high risk action".to_string(),
                     approver_role: "attending_physician".to_string(),
+                    required_attributes: BTreeMap::new(),
                 },
             }),
             Box::new(MockAudit::new()),
@@ -550,85 +1907,465 @@ mod tests {
         }
     }
 
-    /// A missing capability blocks the step even when policy says Allow.
-    #[test]
-    fn test_capability_missing_blocks() {
-        let agent = CapRequiringAgent { required: "phi:read".to_string() };
-
-        let executor = Executor::new(
-            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
-            Box::new(MockAudit::new()),
+    /// An executor whose policy requires a cardiology attending's approval.
+    fn cardiology_approval_executor(audit: MockAudit) -> Executor {
+        Executor::new(
+            Box::new(MockPolicy {
+                verdict: PolicyVerdict::RequireApproval {
+                    reason: "cardiac procedure".to_string(),
+                    approver_role: "attending-physician".to_string(),
+                    required_attributes: [("specialty".to_string(), "cardiology".to_string())]
+                        .into(),
+                },
+            }),
+            Box::new(audit),
             Box::new(MockVerifier { pass: true }),
             make_schema(),
-        );
-
-        // No capabilities granted.
-        let caps = CapabilitySet::default();
-        let result = executor.step(&agent, make_state("active"), make_input(), &caps);
+        )
+    }
 
-        match result {
-            Err(VeritasError::CapabilityMissing { capability, .. }) => {
-                assert_eq!(capability, "phi:read");
-            }
-            other => panic!("expected CapabilityMissing, got {:?}", other),
+    fn approval(specialty: Option<&str>) -> ApprovalRecord {
+        ApprovalRecord {
+            approver_id: "dr-reyes".to_string(),
+            approver_role: "attending-physician".to_string(),
+            attributes: specialty
+                .map(|s| ("specialty".to_string(), s.to_string()))
+                .into_iter()
+                .collect(),
+            granted_at: chrono::Utc::now(),
+            valid_for: Duration::from_secs(3600),
         }
     }
 
-    /// A successful step: policy allows, capabilities present, verifier passes.
-    /// Audit must contain one record. Result must be Transitioned.
-    #[test]
-    fn test_successful_step() {
+    /// A cardiology approval granted at 09:00 and valid for 15 minutes,
+    /// presented to an executor whose clock reads `now`.
+    fn resume_at(now: &str) -> (VeritasResult<StepResult>, Vec<StepRecord>, u32) {
         let agent = MockAgent::new();
         let propose_count = agent.propose_count.clone();
         let audit = MockAudit::new();
         let audit_records = audit.records.clone();
+        let executor = cardiology_approval_executor(audit)
+            .with_clock(Box::new(FixedClock::new(now.parse().unwrap())));
 
-        let executor = Executor::new(
-            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
-            Box::new(audit),
-            Box::new(MockVerifier { pass: true }),
-            make_schema(),
+        let approval = ApprovalRecord {
+            granted_at: "2026-03-01T09:00:00Z".parse().unwrap(),
+            valid_for: Duration::from_secs(15 * 60),
+            ..approval(Some("cardiology"))
+        };
+        let result = executor.resume(
+            &agent,
+            make_state("active"),
+            make_input(),
+            &CapabilitySet::default(),
+            &approval,
         );
 
-        let caps = CapabilitySet::default();
-        let result = executor.step(&agent, make_state("active"), make_input(), &caps).unwrap();
+        let records = audit_records.lock().unwrap().clone();
+        let proposed = *propose_count.lock().unwrap();
+        (result, records, proposed)
+    }
 
-        // propose() must have been called exactly once.
-        assert_eq!(*propose_count.lock().unwrap(), 1);
+    /// An approval presented inside its window, up to and including the
+    /// expiry instant, lets the step run.
+    #[test]
+    fn test_resume_with_in_window_approval_proceeds() {
+        for now in ["2026-03-01T09:05:00Z", "2026-03-01T09:15:00Z"] {
+            let (result, records, proposed) = resume_at(now);
+            assert!(matches!(result, Ok(StepResult::Transitioned { .. })), "{now}: {:?}", result);
+            assert_eq!(proposed, 1);
+            assert_eq!(records.len(), 1);
+            assert!(records[0].output.is_some());
+        }
+    }
 
-        // One audit record for the completed step.
-        assert_eq!(audit_records.lock().unwrap().len(), 1);
+    /// A stale approval is rejected with an error naming the expiry, the
+    /// rejection is audited, and the agent never proposes.
+    #[test]
+    fn test_resume_with_expired_approval_is_rejected() {
+        let (result, records, proposed) = resume_at("2026-03-01T09:15:01Z");
 
         match result {
-            StepResult::Transitioned { next_state, output } => {
-                assert_eq!(next_state.step, 1);
-                assert_eq!(next_state.phase, "next");
-                assert_eq!(output.kind, "response");
+            Err(VeritasError::ApprovalRejected { reason }) => {
+                assert!(reason.contains("dr-reyes"), "reason: {reason}");
+                assert!(reason.contains("expired at 2026-03-01T09:15:00"), "reason: {reason}");
             }
-            other => panic!("expected Transitioned, got {:?}", other),
+            other => panic!("expected ApprovalRejected, got {:?}", other),
         }
+        assert_eq!(proposed, 0);
+        assert_eq!(records.len(), 1);
+        assert!(records[0].output.is_none());
+        assert!(matches!(
+            &records[0].verdict,
+            PolicyVerdict::Deny { reason, .. } if reason.contains("expired")
+        ));
     }
 
-    /// When is_terminal() returns true, the executor returns Complete and
-    /// finalizes the audit.
+    /// An approver holding the role and every required attribute lets the
+    /// resumed step run to completion.
     #[test]
-    fn test_terminal_state() {
-        let agent = MockAgent::terminal();
+    fn test_resume_with_matching_approval_proceeds() {
+        let agent = MockAgent::new();
+        let propose_count = agent.propose_count.clone();
         let audit = MockAudit::new();
-        let was_finalized = audit.finalized.clone();
+        let audit_records = audit.records.clone();
+        let executor = cardiology_approval_executor(audit);
+
+        let result = executor
+            .resume(
+                &agent,
+                make_state("active"),
+                make_input(),
+                &CapabilitySet::default(),
+                &approval(Some("cardiology")),
+            )
+            .unwrap();
+
+        assert!(matches!(result, StepResult::Transitioned { .. }), "got {:?}", result);
+        assert_eq!(*propose_count.lock().unwrap(), 1);
+        let records = audit_records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        assert!(records[0].output.is_some());
+    }
 
-        let executor = Executor::new(
-            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
-            Box::new(audit),
-            Box::new(MockVerifier { pass: true }),
-            make_schema(),
-        );
+    /// An approver with the right role but without the required specialty
+    /// leaves the step suspended, and the agent never proposes.
+    #[test]
+    fn test_resume_with_partial_approval_stays_suspended() {
+        let agent = MockAgent::new();
+        let propose_count = agent.propose_count.clone();
+        let executor = cardiology_approval_executor(MockAudit::new());
+
+        let result = executor
+            .resume(
+                &agent,
+                make_state("active"),
+                make_input(),
+                &CapabilitySet::default(),
+                &approval(None),
+            )
+            .unwrap();
+
+        assert_eq!(*propose_count.lock().unwrap(), 0);
+        match result {
+            StepResult::AwaitingApproval { reason, approver_role, .. } => {
+                assert_eq!(approver_role, "attending-physician");
+                assert!(reason.contains("specialty = 'cardiology'"), "reason: {reason}");
+                assert!(reason.contains("dr-reyes"), "reason: {reason}");
+            }
+            other => panic!("expected AwaitingApproval, got {:?}", other),
+        }
+
+        // A different specialty is no better than none.
+        let result = executor
+            .resume(
+                &agent,
+                make_state("active"),
+                make_input(),
+                &CapabilitySet::default(),
+                &approval(Some("neurology")),
+            )
+            .unwrap();
+        assert!(matches!(result, StepResult::AwaitingApproval { .. }));
+    }
+
+    /// An approver of the wrong role is rejected with an error, the
+    /// rejection is audited as a denial, and the agent never proposes.
+    #[test]
+    fn test_resume_with_wrong_role_is_rejected() {
+        let agent = MockAgent::new();
+        let propose_count = agent.propose_count.clone();
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+        let executor = cardiology_approval_executor(audit);
+
+        let result = executor.resume(
+            &agent,
+            make_state("active"),
+            make_input(),
+            &CapabilitySet::default(),
+            &ApprovalRecord {
+                approver_role: "nurse-practitioner".to_string(),
+                ..approval(Some("cardiology"))
+            },
+        );
+
+        match result {
+            Err(VeritasError::ApprovalRejected { reason }) => {
+                assert!(reason.contains("nurse-practitioner"), "reason: {reason}");
+                assert!(reason.contains("'attending-physician' is required"), "reason: {reason}");
+            }
+            other => panic!("expected ApprovalRejected, got {:?}", other),
+        }
+        assert_eq!(*propose_count.lock().unwrap(), 0);
+        let records = audit_records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        assert!(matches!(records[0].verdict, PolicyVerdict::Deny { .. }));
+    }
+
+    /// A verifier whose schema check always passes and whose named checks
+    /// pass unless listed in `failing`, recording every check it runs.
+    struct NamedChecksVerifier {
+        failing: Vec<&'static str>,
+        runs: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Verifier for NamedChecksVerifier {
+        fn verify(
+            &self,
+            _output: &AgentOutput,
+            _schema: &OutputSchema,
+        ) -> VeritasResult<VerificationReport> {
+            Ok(VerificationReport::from_failures(vec![]))
+        }
+
+        fn run_check(&self, check_id: &str, _output: &AgentOutput) -> VeritasResult<VerificationReport> {
+            self.runs.lock().unwrap().push(check_id.to_string());
+            let failures = if self.failing.contains(&check_id) {
+                vec![VerificationFailure {
+                    rule_id: check_id.to_string(),
+                    message: format!("check '{}' failed", check_id),
+                    severity: RuleSeverity::Error,
+                }]
+            } else {
+                vec![]
+            };
+            Ok(VerificationReport::from_failures(failures))
+        }
+    }
+
+    fn require_both_executor(
+        audit: MockAudit,
+        failing: Vec<&'static str>,
+    ) -> (Executor, Arc<Mutex<Vec<String>>>) {
+        let runs = Arc::new(Mutex::new(vec![]));
+        let executor = Executor::new(
+            Box::new(MockPolicy {
+                verdict: PolicyVerdict::RequireBoth {
+                    reason: "controlled substance order".to_string(),
+                    approver_role: "attending-physician".to_string(),
+                    required_attributes: BTreeMap::new(),
+                    check_ids: vec!["dose-range".to_string(), "phi-scan".to_string()],
+                },
+            }),
+            Box::new(audit),
+            Box::new(NamedChecksVerifier { failing, runs: runs.clone() }),
+            make_schema(),
+        );
+        (executor, runs)
+    }
+
+    /// A RequireBoth step runs its checks first and only then suspends for
+    /// approval; resuming re-runs the checks on a fresh proposal and
+    /// completes.
+    #[test]
+    fn test_require_both_verifies_then_awaits_approval() {
+        let agent = MockAgent::new();
+        let propose_count = agent.propose_count.clone();
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+        let (executor, runs) = require_both_executor(audit, vec![]);
+        let caps = CapabilitySet::default();
+
+        let suspended = match executor.step(&agent, make_state("active"), make_input(), &caps) {
+            Ok(StepResult::AwaitingApproval { reason, approver_role, suspended_state, .. }) => {
+                assert_eq!(reason, "controlled substance order");
+                assert_eq!(approver_role, "attending-physician");
+                suspended_state
+            }
+            other => panic!("expected AwaitingApproval, got {:?}", other),
+        };
+        assert_eq!(*propose_count.lock().unwrap(), 1);
+        assert_eq!(*runs.lock().unwrap(), ["dose-range", "phi-scan"]);
+        {
+            let records = audit_records.lock().unwrap();
+            assert_eq!(records.len(), 1);
+            assert!(matches!(records[0].verdict, PolicyVerdict::RequireBoth { .. }));
+            assert!(records[0].output.is_none(), "a suspended output is not recorded");
+        }
+
+        let result =
+            executor.resume(&agent, suspended, make_input(), &caps, &approval(None)).unwrap();
+
+        assert!(matches!(result, StepResult::Transitioned { .. }), "got {:?}", result);
+        assert_eq!(*propose_count.lock().unwrap(), 2);
+        assert_eq!(runs.lock().unwrap().len(), 4, "checks re-run on resume");
+        let records = audit_records.lock().unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records[1].output.is_some());
+    }
+
+    /// A RequireBoth step whose check fails is rejected outright: approval is
+    /// never requested, and later checks do not run.
+    #[test]
+    fn test_require_both_failed_check_skips_approval() {
+        let agent = MockAgent::new();
+        let (executor, runs) = require_both_executor(MockAudit::new(), vec!["dose-range"]);
+
+        let result =
+            executor.step(&agent, make_state("active"), make_input(), &CapabilitySet::default());
+
+        match result {
+            Err(VeritasError::VerificationFailed { reason }) => {
+                assert!(reason.contains("[dose-range]"), "reason: {}", reason);
+            }
+            other => panic!("expected VerificationFailed, got {:?}", other),
+        }
+        assert_eq!(*runs.lock().unwrap(), ["dose-range"]);
+    }
+
+    /// A missing capability blocks the step even when policy says Allow.
+    #[test]
+    fn test_capability_missing_blocks() {
+        let agent = CapRequiringAgent { required: "phi:read".to_string() };
+
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(MockAudit::new()),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
+
+        // No capabilities granted.
+        let caps = CapabilitySet::default();
+        let result = executor.step(&agent, make_state("active"), make_input(), &caps);
+
+        match result {
+            Err(VeritasError::CapabilityMissing { capability, .. }) => {
+                assert_eq!(capability, "phi:read");
+            }
+            other => panic!("expected CapabilityMissing, got {:?}", other),
+        }
+    }
+
+    /// A policy that allows everything and keeps the capabilities it was
+    /// shown.
+    struct CapabilitySeeingPolicy {
+        seen: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl PolicyEngine for CapabilitySeeingPolicy {
+        fn evaluate(&self, ctx: &PolicyContext) -> VeritasResult<PolicyVerdict> {
+            *self.seen.lock().unwrap() = ctx.capabilities.clone();
+            Ok(PolicyVerdict::Allow)
+        }
+    }
+
+    /// A grant expired at the executor's clock never reaches the policy, so
+    /// it cannot satisfy a rule's required capabilities.
+    #[test]
+    fn test_expired_grant_left_out_of_policy_context() {
+        let seen = Arc::new(Mutex::new(vec![]));
+        let executor = Executor::new(
+            Box::new(CapabilitySeeingPolicy { seen: seen.clone() }),
+            Box::new(MockAudit::new()),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        )
+        .with_clock(Box::new(FixedClock::new("2026-03-01T09:00:00Z".parse().unwrap())));
+
+        let mut caps = CapabilitySet::default();
+        caps.grant(Capability::new("audit:write"));
+        caps.grant_until(Capability::new("phi:read"), "2026-03-01T09:00:00Z".parse().unwrap());
+
+        executor.step(&MockAgent::new(), make_state("active"), make_input(), &caps).unwrap();
+        assert_eq!(*seen.lock().unwrap(), vec!["audit:write".to_string()]);
+    }
+
+    /// A successful step: policy allows, capabilities present, verifier passes.
+    /// Audit must contain one record. Result must be Transitioned.
+    #[test]
+    fn test_successful_step() {
+        let agent = MockAgent::new();
+        let propose_count = agent.propose_count.clone();
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
+
+        let caps = CapabilitySet::default();
+        let result = executor.step(&agent, make_state("active"), make_input(), &caps).unwrap();
+
+        // propose() must have been called exactly once.
+        assert_eq!(*propose_count.lock().unwrap(), 1);
+
+        // One audit record for the completed step.
+        assert_eq!(audit_records.lock().unwrap().len(), 1);
+
+        match result {
+            StepResult::Transitioned { next_state, output, .. } => {
+                assert_eq!(next_state.step, 1);
+                assert_eq!(next_state.phase, "next");
+                assert_eq!(output.kind, "response");
+            }
+            other => panic!("expected Transitioned, got {:?}", other),
+        }
+    }
+
+    /// Identical requests in two executions are recorded with the same
+    /// fingerprint; a different input payload changes it.
+    #[test]
+    fn test_step_records_request_fingerprint() {
+        let agent = MockAgent::new();
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
+
+        let caps = CapabilitySet::default();
+        let other_input = AgentInput {
+            kind: "user_message".to_string(),
+            payload: serde_json::json!({ "text": "goodbye" }),
+        };
+        executor.step(&agent, make_state("active"), make_input(), &caps).unwrap();
+        executor.step(&agent, make_state("active"), make_input(), &caps).unwrap();
+        executor.step(&agent, make_state("active"), other_input, &caps).unwrap();
+
+        // A lapsed grant adds no authority, so it leaves the fingerprint alone.
+        let mut with_expired = caps.clone();
+        let lapsed = "2000-01-01T00:00:00Z".parse().unwrap();
+        with_expired.grant_until(Capability::new("phi:read"), lapsed);
+        executor.step(&agent, make_state("active"), make_input(), &with_expired).unwrap();
+
+        let records = audit_records.lock().unwrap();
+        assert_eq!(
+            records[0].request_fingerprint,
+            request_fingerprint("respond", "user", &caps, &make_input().payload)
+        );
+        assert_eq!(records[0].request_fingerprint, records[1].request_fingerprint);
+        assert_ne!(records[0].request_fingerprint, records[2].request_fingerprint);
+        assert_eq!(records[0].request_fingerprint, records[3].request_fingerprint);
+    }
+
+    /// When is_terminal() returns true, the executor returns Complete and
+    /// finalizes the audit.
+    #[test]
+    fn test_terminal_state() {
+        let agent = MockAgent::terminal();
+        let audit = MockAudit::new();
+        let was_finalized = audit.finalized.clone();
+
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
 
         let caps = CapabilitySet::default();
         let result = executor.step(&agent, make_state("active"), make_input(), &caps).unwrap();
 
         match result {
-            StepResult::Complete { final_state, output } => {
+            StepResult::Complete { final_state, output, .. } => {
                 assert_eq!(output.kind, "response");
                 // The transitioned state has step = 1.
                 assert_eq!(final_state.step, 1);
@@ -667,4 +2404,1463 @@ mod tests {
             other => panic!("expected VerificationFailed, got {:?}", other),
         }
     }
+
+    /// A failed verification is delivered only through `step_break_glass()`,
+    /// and the override is recorded with its authorizer and justification.
+    #[test]
+    fn test_break_glass_overrides_verification_failure() {
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(MockVerifier { pass: false }),
+            make_schema(),
+        );
+        let caps = CapabilitySet::default();
+
+        // The normal path still rejects, and records nothing.
+        let result = executor.step(&MockAgent::new(), make_state("active"), make_input(), &caps);
+        assert!(matches!(result, Err(VeritasError::VerificationFailed { .. })));
+        assert!(audit_records.lock().unwrap().is_empty());
+
+        let result = executor.step_break_glass(
+            &MockAgent::new(),
+            make_state("active"),
+            make_input(),
+            &caps,
+            "rule outdated, patient in resuscitation".to_string(),
+            "dr-on-call".to_string(),
+        );
+        assert!(matches!(result, Ok(StepResult::Transitioned { .. })), "got {:?}", result);
+
+        let records = audit_records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        assert!(records[0].output.is_some());
+        let glass = records[0].break_glass.as_ref().expect("override must be recorded");
+        assert_eq!(glass.authorizer, "dr-on-call");
+        assert_eq!(glass.justification, "rule outdated, patient in resuscitation");
+        assert_eq!(glass.overridden_failures, ["[required-field] field 'patient_id' is missing"]);
+        let json = serde_json::to_value(&records[0]).unwrap();
+        assert_eq!(json["break_glass"]["authorizer"], "dr-on-call");
+    }
+
+    /// A break-glass step whose output passes verification records no
+    /// override, and one without an authorizer never runs.
+    #[test]
+    fn test_break_glass_annotates_only_overrides() {
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
+        let caps = CapabilitySet::default();
+
+        executor
+            .step_break_glass(
+                &MockAgent::new(),
+                make_state("active"),
+                make_input(),
+                &caps,
+                "precaution".to_string(),
+                "dr-on-call".to_string(),
+            )
+            .unwrap();
+        assert!(!audit_records.lock().unwrap()[0].has_break_glass());
+
+        let agent = MockAgent::new();
+        let propose_count = agent.propose_count.clone();
+        let result = executor.step_break_glass(
+            &agent,
+            make_state("active"),
+            make_input(),
+            &caps,
+            "precaution".to_string(),
+            "  ".to_string(),
+        );
+        assert!(matches!(result, Err(VeritasError::ConfigError { .. })));
+        assert_eq!(*propose_count.lock().unwrap(), 0);
+        assert_eq!(audit_records.lock().unwrap().len(), 1);
+    }
+
+    /// A verifier that fails any output missing a payload key listed in the
+    /// schema's `required` array, naming the schema in the failure, and
+    /// normalizes by filling top-level `defaults`.
+    struct RequiredKeysVerifier;
+
+    impl Verifier for RequiredKeysVerifier {
+        fn verify(
+            &self,
+            output: &AgentOutput,
+            schema: &OutputSchema,
+        ) -> VeritasResult<VerificationReport> {
+            let required = schema.json_schema["required"].as_array().cloned().unwrap_or_default();
+            let failures = required
+                .iter()
+                .filter_map(|key| key.as_str())
+                .filter(|key| output.payload.get(key).is_none())
+                .map(|key| VerificationFailure {
+                    rule_id: schema.schema_id.clone(),
+                    message: format!("field '{}' is missing", key),
+                    severity: RuleSeverity::Error,
+                })
+                .collect();
+            Ok(VerificationReport::from_failures(failures))
+        }
+
+        fn normalize(&self, output: &AgentOutput, schema: &OutputSchema) -> AgentOutput {
+            let mut normalized = output.clone();
+            for default in &schema.defaults {
+                if let Some(payload) = normalized.payload.as_object_mut() {
+                    payload
+                        .entry(default.field_path.clone())
+                        .or_insert_with(|| default.value.clone());
+                }
+            }
+            normalized
+        }
+    }
+
+    fn schema_requiring(schema_id: &str, key: &str) -> OutputSchema {
+        OutputSchema {
+            schema_id: schema_id.to_string(),
+            json_schema: serde_json::json!({ "required": [key] }),
+            rules: vec![],
+            defaults: vec![],
+        }
+    }
+
+    /// The same agent stepped against two per-step schemas is verified
+    /// against each one's contract, not the executor's stored schema.
+    #[test]
+    fn test_step_with_schema_verifies_against_override() {
+        let agent = MockAgent::new();
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(MockAudit::new()),
+            Box::new(RequiredKeysVerifier),
+            schema_requiring("stored-v1", "plan"),
+        );
+        let caps = CapabilitySet::default();
+
+        // MockAgent emits `{ "text": "ok" }`: a contract asking for `text`
+        // passes even though the stored schema would reject it.
+        let intake = schema_requiring("intake-v1", "text");
+        let next_state = match executor
+            .step_with_schema(&agent, make_state("intake"), make_input(), &caps, &intake)
+            .unwrap()
+        {
+            StepResult::Transitioned { next_state, .. } => next_state,
+            other => panic!("expected Transitioned, got {:?}", other),
+        };
+
+        let recommend = schema_requiring("recommend-v1", "recommendation");
+        match executor.step_with_schema(&agent, next_state, make_input(), &caps, &recommend) {
+            Err(VeritasError::VerificationFailed { reason }) => {
+                assert!(reason.contains("[recommend-v1]"), "reason: {}", reason);
+                assert!(reason.contains("recommendation"), "reason: {}", reason);
+            }
+            other => panic!("expected VerificationFailed, got {:?}", other),
+        }
+    }
+
+    /// `step()` keeps verifying against the stored schema after an override.
+    #[test]
+    fn test_step_uses_stored_schema_after_override() {
+        let agent = MockAgent::new();
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(MockAudit::new()),
+            Box::new(RequiredKeysVerifier),
+            schema_requiring("stored-v1", "plan"),
+        );
+        let caps = CapabilitySet::default();
+
+        let lenient = schema_requiring("lenient-v1", "text");
+        assert!(executor
+            .step_with_schema(&agent, make_state("active"), make_input(), &caps, &lenient)
+            .is_ok());
+
+        match executor.step(&agent, make_state("active"), make_input(), &caps) {
+            Err(VeritasError::VerificationFailed { reason }) => {
+                assert!(reason.contains("[stored-v1]"), "reason: {}", reason);
+            }
+            other => panic!("expected VerificationFailed, got {:?}", other),
+        }
+    }
+
+    /// With output normalization on, a missing `requires_prior_auth` is
+    /// defaulted to `true` before verification, so the step passes and the
+    /// audited and delivered output carry the default.  Without it the same
+    /// output fails verification.
+    #[test]
+    fn test_output_normalization_fills_defaults_before_verify() {
+        let schema = OutputSchema {
+            defaults: vec![FieldDefault {
+                field_path: "requires_prior_auth".to_string(),
+                value: serde_json::json!(true),
+            }],
+            ..schema_requiring("prior-auth-v1", "requires_prior_auth")
+        };
+        let caps = CapabilitySet::default();
+
+        let strict = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(MockAudit::new()),
+            Box::new(RequiredKeysVerifier),
+            schema.clone(),
+        );
+        assert!(matches!(
+            strict.step(&MockAgent::new(), make_state("active"), make_input(), &caps),
+            Err(VeritasError::VerificationFailed { .. })
+        ));
+
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+        let executor = Executor::builder()
+            .policy(Box::new(MockPolicy { verdict: PolicyVerdict::Allow }))
+            .audit(Box::new(audit))
+            .verifier(Box::new(RequiredKeysVerifier))
+            .schema(schema)
+            .output_normalization(true)
+            .build()
+            .unwrap();
+        match executor
+            .step(&MockAgent::new(), make_state("active"), make_input(), &caps)
+            .unwrap()
+        {
+            StepResult::Transitioned { output, .. } => {
+                assert_eq!(output.payload["requires_prior_auth"], serde_json::json!(true));
+                assert_eq!(output.payload["text"], serde_json::json!("ok"));
+            }
+            other => panic!("expected Transitioned, got {:?}", other),
+        }
+        let records = audit_records.lock().unwrap();
+        let audited = records[0].output.as_ref().unwrap();
+        assert_eq!(audited.payload["requires_prior_auth"], serde_json::json!(true));
+        // The record tells the runtime's default apart from the agent's fields.
+        assert_eq!(records[0].defaulted_fields, ["/requires_prior_auth"]);
+        assert!(records[0].has_optional_fields());
+    }
+
+    /// `filled_fields` names each value the normalized payload gained, as a
+    /// JSON Pointer, and reports a filled-in object as a whole.
+    #[test]
+    fn test_filled_fields_names_only_new_values() {
+        let proposed = serde_json::json!({
+            "text": "ok",
+            "flags": null,
+            "items": [{ "code": "a" }],
+        });
+        let normalized = serde_json::json!({
+            "text": "ok",
+            "flags": { "urgent": false },
+            "items": [{ "code": "a", "unit/dose": 1 }],
+            "site": { "ward": "4B" },
+        });
+        assert_eq!(
+            super::filled_fields(&proposed, &normalized),
+            ["/flags", "/items/0/unit~1dose", "/site"]
+        );
+        assert!(super::filled_fields(&proposed, &proposed).is_empty());
+    }
+
+    /// Behaves like `MockAgent`, but its transition() builds a fresh state
+    /// without the correlation id.
+    struct ForgetfulAgent;
+
+    impl Agent for ForgetfulAgent {
+        fn propose(&self, state: &AgentState, input: &AgentInput) -> VeritasResult<AgentOutput> {
+            MockAgent::new().propose(state, input)
+        }
+
+        fn transition(
+            &self,
+            state: &AgentState,
+            _output: &AgentOutput,
+        ) -> VeritasResult<AgentState> {
+            Ok(AgentState {
+                agent_id: state.agent_id.clone(),
+                execution_id: state.execution_id.clone(),
+                phase: "next".to_string(),
+                context: serde_json::Value::Null,
+                step: state.step + 1,
+                correlation_id: None,
+            })
+        }
+
+        fn required_capabilities(
+            &self,
+            _state: &AgentState,
+            _input: &AgentInput,
+        ) -> Vec<String> {
+            vec![]
+        }
+
+        fn describe_action(
+            &self,
+            state: &AgentState,
+            input: &AgentInput,
+        ) -> (String, String) {
+            MockAgent::new().describe_action(state, input)
+        }
+
+        fn is_terminal(&self, _state: &AgentState) -> bool {
+            false
+        }
+    }
+
+    /// The state's correlation id is copied into every audit record and
+    /// carried onto the next state, even when the agent drops it.
+    #[test]
+    fn test_correlation_id_copied_to_records_and_next_state() {
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
+        let caps = CapabilitySet::default();
+
+        let state = AgentState {
+            correlation_id: Some("req-42".to_string()),
+            ..make_state("active")
+        };
+        let next_state = match executor.step(&ForgetfulAgent, state, make_input(), &caps).unwrap() {
+            StepResult::Transitioned { next_state, .. } => next_state,
+            other => panic!("expected Transitioned, got {:?}", other),
+        };
+        assert_eq!(next_state.correlation_id.as_deref(), Some("req-42"));
+
+        executor.step(&ForgetfulAgent, next_state, make_input(), &caps).unwrap();
+        let records = audit_records.lock().unwrap();
+        assert_eq!(records.len(), 2);
+        for record in records.iter() {
+            assert_eq!(record.correlation_id.as_deref(), Some("req-42"));
+            assert!(record.has_correlation_id());
+        }
+    }
+
+    /// A denying shadow policy is recorded on every audit record while the
+    /// enforced Allow still runs the agent and delivers its output.
+    #[test]
+    fn test_shadow_policy_recorded_but_not_enforced() {
+        let agent = MockAgent::new();
+        let propose_count = agent.propose_count.clone();
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+        let shadow = PolicyVerdict::Deny {
+            reason: "stricter policy would deny".to_string(),
+            remediation: None,
+        };
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        )
+        .with_shadow_policy(Box::new(MockPolicy { verdict: shadow.clone() }));
+
+        let result = executor
+            .step(&agent, make_state("active"), make_input(), &CapabilitySet::default())
+            .unwrap();
+
+        assert!(matches!(result, StepResult::Transitioned { .. }), "got {:?}", result);
+        assert_eq!(*propose_count.lock().unwrap(), 1);
+        let records = audit_records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].verdict, PolicyVerdict::Allow);
+        assert_eq!(records[0].shadow_verdict, Some(shadow));
+        assert!(records[0].has_shadow_verdict());
+    }
+
+    /// An allowing shadow policy cannot lift an enforced denial, and an
+    /// executor without a shadow policy records none.
+    #[test]
+    fn test_shadow_policy_cannot_override_enforced_deny() {
+        let deny = PolicyVerdict::Deny {
+            reason: "enforced deny".to_string(),
+            remediation: None,
+        };
+        let agent = MockAgent::new();
+        let propose_count = agent.propose_count.clone();
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+        let executor = Executor::builder()
+            .policy(Box::new(MockPolicy { verdict: deny.clone() }))
+            .shadow_policy(Box::new(MockPolicy { verdict: PolicyVerdict::Allow }))
+            .audit(Box::new(audit))
+            .verifier(Box::new(MockVerifier { pass: true }))
+            .schema(make_schema())
+            .build()
+            .unwrap();
+
+        let result = executor
+            .step(&agent, make_state("active"), make_input(), &CapabilitySet::default())
+            .unwrap();
+
+        assert!(matches!(result, StepResult::Denied { .. }), "got {:?}", result);
+        assert_eq!(*propose_count.lock().unwrap(), 0);
+        {
+            let records = audit_records.lock().unwrap();
+            assert_eq!(records[0].verdict, deny);
+            assert_eq!(records[0].shadow_verdict, Some(PolicyVerdict::Allow));
+        }
+
+        let plain_audit = MockAudit::new();
+        let plain_records = plain_audit.records.clone();
+        let plain = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(plain_audit),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
+        plain
+            .step(&MockAgent::new(), make_state("active"), make_input(), &CapabilitySet::default())
+            .unwrap();
+        assert_eq!(plain_records.lock().unwrap()[0].shadow_verdict, None);
+    }
+
+    /// Each pass counts once toward the policy's budget: a preflight and the
+    /// shadow policy spend none, nor does the retry after a rejection, but
+    /// every resume does, however often one approval is presented.
+    #[test]
+    fn test_step_counted_once_toward_policy_budget() {
+        let caps = CapabilitySet::default();
+
+        let policy = CountingPolicy::new(PolicyVerdict::Allow);
+        let counted = policy.counted.clone();
+        let shadow = CountingPolicy::new(PolicyVerdict::Allow);
+        let shadow_counted = shadow.counted.clone();
+        let executor = Executor::new(
+            Box::new(policy),
+            Box::new(MockAudit::new()),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        )
+        .with_shadow_policy(Box::new(shadow));
+        let agent = MockAgent::new();
+        let state = make_state("active");
+        assert_eq!(
+            executor.preflight(&agent, &state, &make_input(), &caps),
+            PreflightResult::Allowed
+        );
+        assert_eq!(*counted.lock().unwrap(), 0, "preflight must not spend budget");
+        executor.step(&agent, state, make_input(), &caps).unwrap();
+        assert_eq!(*counted.lock().unwrap(), 1);
+        assert_eq!(*shadow_counted.lock().unwrap(), 0);
+
+        let policy = CountingPolicy::new(PolicyVerdict::RequireApproval {
+            reason: "high risk action".to_string(),
+            approver_role: "attending-physician".to_string(),
+            required_attributes: Default::default(),
+        });
+        let counted = policy.counted.clone();
+        let executor = Executor::new(
+            Box::new(policy),
+            Box::new(MockAudit::new()),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
+        let suspended_state = match executor
+            .step(&agent, make_state("active"), make_input(), &caps)
+            .unwrap()
+        {
+            StepResult::AwaitingApproval { suspended_state, .. } => suspended_state,
+            other => panic!("expected AwaitingApproval, got {:?}", other),
+        };
+        for resumes in 2..=3 {
+            executor
+                .resume(&agent, suspended_state.clone(), make_input(), &caps, &approval(None))
+                .unwrap();
+            assert_eq!(*counted.lock().unwrap(), resumes, "every resume counts");
+        }
+
+        let policy = CountingPolicy::new(PolicyVerdict::Allow);
+        let counted = policy.counted.clone();
+        let executor = Executor::new(
+            Box::new(policy),
+            Box::new(MockAudit::new()),
+            Box::new(PiiVerifier { reject_all: false }),
+            make_schema(),
+        );
+        let retrying = SelfCorrectingAgent::new(true);
+        executor.step(&retrying, make_state("active"), make_input(), &caps).unwrap();
+        assert_eq!(*retrying.propose_count.lock().unwrap(), 2);
+        assert_eq!(*counted.lock().unwrap(), 1, "a retry must not count again");
+    }
+
+    /// An agent that regenerates a clean summary after a PII rejection
+    /// completes on the retry, and the first rejection stays on record.
+    #[test]
+    fn test_on_rejected_retry_completes() {
+        let agent = SelfCorrectingAgent::new(true);
+        let propose_count = agent.propose_count.clone();
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(PiiVerifier { reject_all: false }),
+            make_schema(),
+        );
+
+        let caps = CapabilitySet::default();
+        let result = executor.step(&agent, make_state("active"), make_input(), &caps).unwrap();
+
+        assert_eq!(*propose_count.lock().unwrap(), 2, "propose() runs once per attempt");
+        match result {
+            StepResult::Complete { output, .. } => {
+                assert!(!output.payload["summary"].as_str().unwrap().contains("SSN:"));
+            }
+            other => panic!("expected Complete on retry, got {:?}", other),
+        }
+
+        let records = audit_records.lock().unwrap();
+        assert_eq!(records.len(), 2, "rejection and retry must both be audited");
+        match &records[0].verdict {
+            PolicyVerdict::Deny { reason, .. } => assert!(reason.contains("no-pii-labels"), "{}", reason),
+            other => panic!("expected the rejection to be audited as Deny, got {:?}", other),
+        }
+        assert!(records[0].output.is_none(), "rejected output must not be audited");
+        assert_eq!(records[1].input.kind, "summarize-redacted");
+        assert_eq!(records[1].verdict, PolicyVerdict::Allow);
+    }
+
+    /// An agent that declines to correct itself gets the original error and
+    /// no extra audit record.
+    #[test]
+    fn test_on_rejected_none_surfaces_failure() {
+        let agent = SelfCorrectingAgent::new(false);
+        let propose_count = agent.propose_count.clone();
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(PiiVerifier { reject_all: false }),
+            make_schema(),
+        );
+
+        let caps = CapabilitySet::default();
+        let result = executor.step(&agent, make_state("active"), make_input(), &caps);
+
+        assert!(matches!(result, Err(VeritasError::VerificationFailed { .. })));
+        assert_eq!(*propose_count.lock().unwrap(), 1);
+        assert!(audit_records.lock().unwrap().is_empty());
+    }
+
+    /// The retry is granted once: a second rejection is returned as an error.
+    #[test]
+    fn test_on_rejected_retries_only_once() {
+        let agent = SelfCorrectingAgent::new(true);
+        let propose_count = agent.propose_count.clone();
+
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(MockAudit::new()),
+            Box::new(PiiVerifier { reject_all: true }),
+            make_schema(),
+        );
+
+        let caps = CapabilitySet::default();
+        let result = executor.step(&agent, make_state("active"), make_input(), &caps);
+
+        assert!(matches!(result, Err(VeritasError::VerificationFailed { .. })));
+        assert_eq!(*propose_count.lock().unwrap(), 2, "exactly one retry is allowed");
+    }
+
+    /// Preflight reports the same gating decision `step()` makes — for every
+    /// verdict and for a missing capability — without writing audit or
+    /// calling propose.
+    #[test]
+    fn test_preflight_mirrors_step_gating() {
+        let deny = PolicyVerdict::Deny {
+            reason: "not on my watch".to_string(),
+            remediation: Some("ask the charge nurse".to_string()),
+        };
+        let approval = PolicyVerdict::RequireApproval {
+            reason: "high-risk order".to_string(),
+            approver_role: "attending_physician".to_string(),
+            required_attributes: BTreeMap::new(),
+        };
+
+        let cases = vec![
+            (PolicyVerdict::Allow, true, PreflightResult::Allowed),
+            (
+                deny,
+                true,
+                PreflightResult::Denied {
+                    reason: "not on my watch".to_string(),
+                    remediation: Some("ask the charge nurse".to_string()),
+                },
+            ),
+            (
+                approval,
+                true,
+                PreflightResult::RequiresApproval {
+                    reason: "high-risk order".to_string(),
+                    approver_role: "attending_physician".to_string(),
+                },
+            ),
+            (
+                PolicyVerdict::Allow,
+                false,
+                PreflightResult::CapabilityMissing {
+                    capability: "phi:read".to_string(),
+                    action: "read_phi".to_string(),
+                },
+            ),
+        ];
+
+        for (verdict, granted, expected) in cases {
+            let agent = CapRequiringAgent { required: "phi:read".to_string() };
+            let audit = MockAudit::new();
+            let audit_records = audit.records.clone();
+            let executor = Executor::new(
+                Box::new(MockPolicy { verdict: verdict.clone() }),
+                Box::new(audit),
+                Box::new(MockVerifier { pass: true }),
+                make_schema(),
+            );
+
+            let mut caps = CapabilitySet::default();
+            if granted {
+                caps.grant(Capability::new("phi:read"));
+            }
+
+            // CapRequiringAgent panics if propose() is reached.
+            let result = executor.preflight(&agent, &make_state("active"), &make_input(), &caps);
+            assert_eq!(result, expected, "verdict {:?}", verdict);
+            assert!(
+                audit_records.lock().unwrap().is_empty(),
+                "preflight must not write audit for verdict {:?}",
+                verdict
+            );
+
+            // step() must reach the same decision for the blocking cases.
+            if expected != PreflightResult::Allowed {
+                let step = executor.step(&agent, make_state("active"), make_input(), &caps);
+                let agrees = matches!(
+                    (&expected, &step),
+                    (PreflightResult::Denied { .. }, Ok(StepResult::Denied { .. }))
+                        | (
+                            PreflightResult::RequiresApproval { .. },
+                            Ok(StepResult::AwaitingApproval { .. })
+                        )
+                        | (
+                            PreflightResult::CapabilityMissing { .. },
+                            Err(VeritasError::CapabilityMissing { .. })
+                        )
+                );
+                assert!(agrees, "preflight {:?} disagrees with step {:?}", expected, step);
+            }
+        }
+    }
+
+    /// Preflight of an allowed step leaves propose uncalled; step then runs it.
+    #[test]
+    fn test_preflight_allowed_does_not_propose() {
+        let agent = MockAgent::new();
+        let propose_count = agent.propose_count.clone();
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
+
+        let caps = CapabilitySet::default();
+        let state = make_state("active");
+        let result = executor.preflight(&agent, &state, &make_input(), &caps);
+
+        assert_eq!(result, PreflightResult::Allowed);
+        assert_eq!(*propose_count.lock().unwrap(), 0);
+        assert!(audit_records.lock().unwrap().is_empty());
+
+        executor.step(&agent, state, make_input(), &caps).unwrap();
+        assert_eq!(*propose_count.lock().unwrap(), 1);
+    }
+
+    /// Preflight refuses a step that `step()` would stop before the policy —
+    /// one whose abort condition fires or one at the step limit — and still
+    /// records nothing.
+    #[test]
+    fn test_preflight_applies_abort_condition_and_step_limit() {
+        let agent = MockAgent::new();
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+        let mut executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        )
+        .with_max_steps(2);
+        executor.set_abort_condition(Box::new(|state| {
+            (state.context["consent_revoked"] == true)
+                .then(|| "patient revoked consent for AI queries".to_string())
+        }));
+        let caps = CapabilitySet::default();
+
+        let revoked = AgentState {
+            context: serde_json::json!({ "consent_revoked": true }),
+            ..make_state("active")
+        };
+        assert_eq!(
+            executor.preflight(&agent, &revoked, &make_input(), &caps),
+            PreflightResult::Denied {
+                reason: "patient revoked consent for AI queries".to_string(),
+                remediation: None,
+            }
+        );
+
+        let at_limit = AgentState { step: 2, ..make_state("active") };
+        assert_eq!(
+            executor.preflight(&agent, &at_limit, &make_input(), &caps),
+            PreflightResult::Denied {
+                reason: "max steps exceeded".to_string(),
+                remediation: None,
+            }
+        );
+
+        let below_limit = AgentState { step: 1, ..make_state("active") };
+        assert_eq!(
+            executor.preflight(&agent, &below_limit, &make_input(), &caps),
+            PreflightResult::Allowed
+        );
+        assert!(audit_records.lock().unwrap().is_empty());
+    }
+
+    /// The step record carries the justification of every capability the step
+    /// used — and only those that were granted with a reason.
+    #[test]
+    fn test_capability_justifications_flow_into_step_record() {
+        let agent = ScopedAgent {
+            required: vec!["phi:read".to_string(), "phi:write".to_string()],
+        };
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
+
+        let mut caps = CapabilitySet::default();
+        caps.grant_with_reason(Capability::new("phi:read"), "treating clinician");
+        caps.grant(Capability::new("phi:write"));
+        caps.grant_with_reason(Capability::new("order:submit"), "unused by this step");
+
+        executor.step(&agent, make_state("active"), make_input(), &caps).unwrap();
+
+        let records = audit_records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        let justifications = &records[0].capability_justifications;
+        assert_eq!(justifications.len(), 1, "got {:?}", justifications);
+        assert_eq!(justifications["phi:read"], "treating clinician");
+    }
+
+    /// An agent that attaches a fixed advisory to every output.
+    struct AdvisingAgent;
+
+    impl Agent for AdvisingAgent {
+        fn propose(&self, _state: &AgentState, _input: &AgentInput) -> VeritasResult<AgentOutput> {
+            Ok(AgentOutput {
+                kind: "response".to_string(),
+                payload: serde_json::json!({ "text": "ok" }),
+                provenance: None,
+                advisories: vec!["dose adjusted for renal function".to_string()],
+            })
+        }
+
+        fn transition(
+            &self,
+            state: &AgentState,
+            _output: &AgentOutput,
+        ) -> VeritasResult<AgentState> {
+            Ok(AgentState { step: state.step + 1, ..state.clone() })
+        }
+
+        fn required_capabilities(
+            &self,
+            _state: &AgentState,
+            _input: &AgentInput,
+        ) -> Vec<String> {
+            vec![]
+        }
+
+        fn describe_action(
+            &self,
+            _state: &AgentState,
+            _input: &AgentInput,
+        ) -> (String, String) {
+            ("read_phi".to_string(), "patient_record".to_string())
+        }
+
+        fn is_terminal(&self, _state: &AgentState) -> bool {
+            false
+        }
+    }
+
+    /// Advisories the agent attaches to its output are copied into the
+    /// audited step record; they never affect the outcome.
+    #[test]
+    fn test_advisories_flow_into_step_record() {
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
+
+        let result = executor
+            .step(&AdvisingAgent, make_state("active"), make_input(), &CapabilitySet::default())
+            .unwrap();
+        assert!(matches!(result, StepResult::Transitioned { .. }));
+
+        let records = audit_records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].advisories, vec!["dose adjusted for renal function".to_string()]);
+    }
+
+    /// A policy that allows only emergent actions, read from the metadata.
+    struct UrgencyPolicy;
+
+    impl PolicyEngine for UrgencyPolicy {
+        fn evaluate(&self, ctx: &PolicyContext) -> VeritasResult<PolicyVerdict> {
+            if ctx.metadata["urgency"] == "emergent" {
+                Ok(PolicyVerdict::Allow)
+            } else {
+                Ok(PolicyVerdict::Deny {
+                    reason: format!("urgency is {}", ctx.metadata["urgency"]),
+                    remediation: None,
+                })
+            }
+        }
+    }
+
+    /// Behaves like `AdvisingAgent`, declaring the urgency it was built with
+    /// as action metadata.
+    struct UrgentAgent(&'static str);
+
+    impl Agent for UrgentAgent {
+        fn propose(&self, state: &AgentState, input: &AgentInput) -> VeritasResult<AgentOutput> {
+            AdvisingAgent.propose(state, input)
+        }
+
+        fn transition(
+            &self,
+            state: &AgentState,
+            output: &AgentOutput,
+        ) -> VeritasResult<AgentState> {
+            AdvisingAgent.transition(state, output)
+        }
+
+        fn required_capabilities(
+            &self,
+            state: &AgentState,
+            input: &AgentInput,
+        ) -> Vec<String> {
+            AdvisingAgent.required_capabilities(state, input)
+        }
+
+        fn describe_action(
+            &self,
+            state: &AgentState,
+            input: &AgentInput,
+        ) -> (String, String) {
+            AdvisingAgent.describe_action(state, input)
+        }
+
+        fn action_metadata(
+            &self,
+            _state: &AgentState,
+            _input: &AgentInput,
+        ) -> serde_json::Value {
+            serde_json::json!({ "urgency": self.0 })
+        }
+
+        fn is_terminal(&self, state: &AgentState) -> bool {
+            AdvisingAgent.is_terminal(state)
+        }
+    }
+
+    /// The agent's action metadata reaches the policy engine.
+    #[test]
+    fn test_action_metadata_reaches_policy() {
+        let executor = Executor::new(
+            Box::new(UrgencyPolicy),
+            Box::new(MockAudit::new()),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
+        let caps = CapabilitySet::default();
+
+        let emergent =
+            executor.step(&UrgentAgent("emergent"), make_state("active"), make_input(), &caps);
+        assert!(matches!(emergent, Ok(StepResult::Transitioned { .. })));
+
+        match executor.step(&UrgentAgent("routine"), make_state("active"), make_input(), &caps) {
+            Ok(StepResult::Denied { reason, .. }) => assert_eq!(reason, "urgency is \"routine\""),
+            other => panic!("expected Denied, got {:?}", other),
+        }
+    }
+
+    /// A policy that denies every action classified as a write.
+    struct ReadOnlyPolicy;
+
+    impl PolicyEngine for ReadOnlyPolicy {
+        fn evaluate(&self, ctx: &PolicyContext) -> VeritasResult<PolicyVerdict> {
+            if ctx.action_kind.is_some_and(|kind| kind.is_a(ActionKind::Write)) {
+                Ok(PolicyVerdict::Deny {
+                    reason: format!("{} is a write", ctx.action),
+                    remediation: None,
+                })
+            } else {
+                Ok(PolicyVerdict::Allow)
+            }
+        }
+    }
+
+    /// Behaves like `AdvisingAgent` under the action name it was built with.
+    struct NamedActionAgent(&'static str);
+
+    impl Agent for NamedActionAgent {
+        fn propose(&self, state: &AgentState, input: &AgentInput) -> VeritasResult<AgentOutput> {
+            AdvisingAgent.propose(state, input)
+        }
+
+        fn transition(
+            &self,
+            state: &AgentState,
+            output: &AgentOutput,
+        ) -> VeritasResult<AgentState> {
+            AdvisingAgent.transition(state, output)
+        }
+
+        fn required_capabilities(
+            &self,
+            state: &AgentState,
+            input: &AgentInput,
+        ) -> Vec<String> {
+            AdvisingAgent.required_capabilities(state, input)
+        }
+
+        fn describe_action(
+            &self,
+            _state: &AgentState,
+            _input: &AgentInput,
+        ) -> (String, String) {
+            (self.0.to_string(), "patient/42".to_string())
+        }
+
+        fn is_terminal(&self, state: &AgentState) -> bool {
+            AdvisingAgent.is_terminal(state)
+        }
+    }
+
+    /// The kind inferred from the action name reaches the policy engine.
+    #[test]
+    fn test_action_kind_reaches_policy() {
+        let executor = Executor::new(
+            Box::new(ReadOnlyPolicy),
+            Box::new(MockAudit::new()),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
+        let caps = CapabilitySet::default();
+
+        let step = |action| {
+            executor.step(&NamedActionAgent(action), make_state("active"), make_input(), &caps)
+        };
+
+        assert!(matches!(step("read_record"), Ok(StepResult::Transitioned { .. })));
+        match step("delete_record") {
+            Ok(StepResult::Denied { reason, .. }) => assert_eq!(reason, "delete_record is a write"),
+            other => panic!("expected Denied, got {:?}", other),
+        }
+    }
+
+    /// An agent whose propose() panics instead of returning an error.
+    struct PanickingAgent;
+
+    impl Agent for PanickingAgent {
+        fn propose(&self, _state: &AgentState, _input: &AgentInput) -> VeritasResult<AgentOutput> {
+            panic!("model backend returned garbage")
+        }
+
+        fn transition(
+            &self,
+            _state: &AgentState,
+            _output: &AgentOutput,
+        ) -> VeritasResult<AgentState> {
+            unreachable!("transition must not run after a panicking propose")
+        }
+
+        fn required_capabilities(
+            &self,
+            _state: &AgentState,
+            _input: &AgentInput,
+        ) -> Vec<String> {
+            vec![]
+        }
+
+        fn describe_action(
+            &self,
+            _state: &AgentState,
+            _input: &AgentInput,
+        ) -> (String, String) {
+            ("read_phi".to_string(), "patient_record".to_string())
+        }
+
+        fn is_terminal(&self, _state: &AgentState) -> bool {
+            false
+        }
+    }
+
+    /// A panic in propose() is contained: the step errors, a denial is
+    /// audited, and nothing unwinds out of the executor.
+    #[test]
+    fn test_panicking_propose_is_audited_as_denial() {
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
+
+        let caps = CapabilitySet::default();
+        match executor.step(&PanickingAgent, make_state("active"), make_input(), &caps) {
+            Err(VeritasError::StateMachineError { reason }) => {
+                assert_eq!(reason, "agent panicked during propose");
+            }
+            other => panic!("expected StateMachineError, got {:?}", other),
+        }
+
+        let records = audit_records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        assert!(records[0].output.is_none());
+        match &records[0].verdict {
+            PolicyVerdict::Deny { reason, .. } => {
+                assert!(reason.contains("model backend returned garbage"), "reason: {}", reason);
+            }
+            other => panic!("expected Deny, got {:?}", other),
+        }
+    }
+
+    /// Behaves like `MockAgent`, but rejects input without a `text` field.
+    struct PickyAgent(MockAgent);
+
+    impl Agent for PickyAgent {
+        fn propose(&self, state: &AgentState, input: &AgentInput) -> VeritasResult<AgentOutput> {
+            self.0.propose(state, input)
+        }
+
+        fn transition(
+            &self,
+            state: &AgentState,
+            output: &AgentOutput,
+        ) -> VeritasResult<AgentState> {
+            self.0.transition(state, output)
+        }
+
+        fn required_capabilities(
+            &self,
+            state: &AgentState,
+            input: &AgentInput,
+        ) -> Vec<String> {
+            self.0.required_capabilities(state, input)
+        }
+
+        fn validate_input(&self, _state: &AgentState, input: &AgentInput) -> VeritasResult<()> {
+            if input.payload["text"].is_string() {
+                Ok(())
+            } else {
+                Err(VeritasError::SchemaValidation { reason: "input has no 'text'".to_string() })
+            }
+        }
+
+        fn describe_action(
+            &self,
+            state: &AgentState,
+            input: &AgentInput,
+        ) -> (String, String) {
+            self.0.describe_action(state, input)
+        }
+
+        fn is_terminal(&self, state: &AgentState) -> bool {
+            self.0.is_terminal(state)
+        }
+    }
+
+    /// Input failing validate_input() never reaches propose(): the agent's
+    /// error is returned and the rejection audited as a denial.
+    #[test]
+    fn test_rejected_input_skips_propose() {
+        let agent = PickyAgent(MockAgent::new());
+        let propose_count = agent.0.propose_count.clone();
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
+        let caps = CapabilitySet::default();
+
+        let bad_input = AgentInput {
+            kind: "user_message".to_string(),
+            payload: serde_json::json!({}),
+        };
+        match executor.step(&agent, make_state("active"), bad_input, &caps) {
+            Err(VeritasError::SchemaValidation { reason }) => assert_eq!(reason, "input has no 'text'"),
+            other => panic!("expected SchemaValidation, got {:?}", other),
+        }
+        assert_eq!(*propose_count.lock().unwrap(), 0);
+        {
+            let records = audit_records.lock().unwrap();
+            assert_eq!(records.len(), 1);
+            match &records[0].verdict {
+                PolicyVerdict::Deny { reason, .. } => assert!(reason.starts_with("agent rejected input")),
+                other => panic!("expected Deny, got {:?}", other),
+            }
+        }
+
+        let result = executor.step(&agent, make_state("active"), make_input(), &caps);
+        assert!(matches!(result, Ok(StepResult::Transitioned { .. })));
+        assert_eq!(*propose_count.lock().unwrap(), 1);
+    }
+
+    /// Behaves like `MockAgent`, but its transition() always fails.
+    struct StuckAgent;
+
+    impl Agent for StuckAgent {
+        fn propose(&self, state: &AgentState, input: &AgentInput) -> VeritasResult<AgentOutput> {
+            MockAgent::new().propose(state, input)
+        }
+
+        fn transition(
+            &self,
+            _state: &AgentState,
+            _output: &AgentOutput,
+        ) -> VeritasResult<AgentState> {
+            Err(VeritasError::StateMachineError { reason: "no route from 'active'".to_string() })
+        }
+
+        fn required_capabilities(
+            &self,
+            _state: &AgentState,
+            _input: &AgentInput,
+        ) -> Vec<String> {
+            vec![]
+        }
+
+        fn describe_action(
+            &self,
+            state: &AgentState,
+            input: &AgentInput,
+        ) -> (String, String) {
+            MockAgent::new().describe_action(state, input)
+        }
+
+        fn is_terminal(&self, _state: &AgentState) -> bool {
+            false
+        }
+    }
+
+    /// A transition failure after verification still audits the verified
+    /// output, with a denial naming the failure.
+    #[test]
+    fn test_transition_failure_audits_verified_output() {
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
+
+        let caps = CapabilitySet::default();
+        match executor.step(&StuckAgent, make_state("active"), make_input(), &caps) {
+            Err(VeritasError::StateMachineError { reason }) => {
+                assert!(reason.contains("no route from 'active'"), "reason: {}", reason);
+            }
+            other => panic!("expected StateMachineError, got {:?}", other),
+        }
+
+        let records = audit_records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        let output = records[0].output.as_ref().expect("verified output must be audited");
+        assert_eq!(output.payload["text"], "ok");
+        match &records[0].verdict {
+            PolicyVerdict::Deny { reason, .. } => {
+                assert!(reason.starts_with("state transition failed"), "reason: {}", reason);
+            }
+            other => panic!("expected Deny, got {:?}", other),
+        }
+    }
+
+    /// With a FixedClock, two identical runs produce identical audit records.
+    #[test]
+    fn test_fixed_clock_records_are_reproducible() {
+        let instant = chrono::DateTime::parse_from_rfc3339("2026-02-18T10:30:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let state = make_state("active");
+
+        let run = || {
+            let audit = MockAudit::new();
+            let audit_records = audit.records.clone();
+            let executor = Executor::new(
+                Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+                Box::new(audit),
+                Box::new(MockVerifier { pass: true }),
+                make_schema(),
+            )
+            .with_clock(Box::new(FixedClock::new(instant)));
+
+            executor
+                .step(&MockAgent::new(), state.clone(), make_input(), &CapabilitySet::default())
+                .unwrap();
+            let records = audit_records.lock().unwrap();
+            records.clone()
+        };
+
+        let first = run();
+        let second = run();
+
+        assert_eq!(first[0].timestamp, instant);
+        assert_eq!(
+            serde_json::to_string(&first).unwrap(),
+            serde_json::to_string(&second).unwrap(),
+            "records must be byte-identical across runs"
+        );
+    }
+
+    /// An abort condition firing on `consent_revoked` in the state context
+    /// stops the step before the policy is consulted, and audits it.
+    #[test]
+    fn test_abort_condition_stops_execution() {
+        let agent = MockAgent::new();
+        let propose_count = agent.propose_count.clone();
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+
+        let mut executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
+        executor.set_abort_condition(Box::new(|state| {
+            (state.context["consent_revoked"] == true)
+                .then(|| "patient revoked consent for AI queries".to_string())
+        }));
+
+        let caps = CapabilitySet::default();
+
+        // Consent intact: the step proceeds normally.
+        let result = executor.step(&agent, make_state("active"), make_input(), &caps).unwrap();
+        assert!(matches!(result, StepResult::Transitioned { .. }));
+
+        // Consent revoked: denied before policy, propose never called again.
+        let revoked = AgentState {
+            context: serde_json::json!({ "consent_revoked": true }),
+            ..make_state("active")
+        };
+        let result = executor.step(&agent, revoked, make_input(), &caps).unwrap();
+
+        match result {
+            StepResult::Denied { reason, .. } => {
+                assert_eq!(reason, "patient revoked consent for AI queries");
+            }
+            other => panic!("expected Denied, got {:?}", other),
+        }
+        assert_eq!(*propose_count.lock().unwrap(), 1);
+
+        let records = audit_records.lock().unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records[1].output.is_none());
+        assert!(matches!(&records[1].verdict, PolicyVerdict::Deny { reason, .. } if reason.contains("revoked")));
+    }
+
+    /// With a step limit, steps below it run and the step at the limit is
+    /// rejected before the policy, with the rejection audited.
+    #[test]
+    fn test_max_steps_rejects_step_at_limit() {
+        let agent = MockAgent::new();
+        let propose_count = agent.propose_count.clone();
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(audit),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        )
+        .with_max_steps(2);
+        let caps = CapabilitySet::default();
+
+        let mut state = make_state("active");
+        for _ in 0..2 {
+            match executor.step(&agent, state, make_input(), &caps).unwrap() {
+                StepResult::Transitioned { next_state, .. } => state = next_state,
+                other => panic!("expected Transitioned, got {:?}", other),
+            }
+        }
+        assert_eq!(state.step, 2);
+
+        match executor.step(&agent, state, make_input(), &caps) {
+            Err(VeritasError::StateMachineError { reason }) => {
+                assert_eq!(reason, "max steps exceeded");
+            }
+            other => panic!("expected StateMachineError, got {:?}", other),
+        }
+        assert_eq!(*propose_count.lock().unwrap(), 2);
+
+        let records = audit_records.lock().unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[2].step, 2);
+        assert!(records[2].output.is_none());
+        assert!(matches!(
+            &records[2].verdict,
+            PolicyVerdict::Deny { reason, .. } if reason == "max steps exceeded"
+        ));
+    }
+
+    /// An observer that records every notification it receives.
+    #[derive(Default)]
+    struct RecordingObserver {
+        verdicts: Arc<Mutex<Vec<PolicyVerdict>>>,
+        outcomes: Arc<Mutex<Vec<Option<StepOutcome>>>>,
+    }
+
+    impl ExecutorObserver for RecordingObserver {
+        fn on_policy_evaluated(&self, _ctx: &PolicyContext, verdict: &PolicyVerdict, _elapsed: Duration) {
+            self.verdicts.lock().unwrap().push(verdict.clone());
+        }
+
+        fn on_step_finished(&self, _execution_id: &ExecutionId, _step: u64, outcome: Option<StepOutcome>) {
+            self.outcomes.lock().unwrap().push(outcome);
+        }
+    }
+
+    /// A builder with every required part produces a working executor that
+    /// uses the optional clock and observer it was given.
+    #[test]
+    fn test_builder_builds_working_executor() {
+        let instant = chrono::DateTime::parse_from_rfc3339("2026-02-18T10:30:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let audit = MockAudit::new();
+        let audit_records = audit.records.clone();
+        let observer = RecordingObserver::default();
+        let verdicts = observer.verdicts.clone();
+        let outcomes = observer.outcomes.clone();
+
+        let executor = Executor::builder()
+            .policy(Box::new(MockPolicy { verdict: PolicyVerdict::Allow }))
+            .audit(Box::new(audit))
+            .verifier(Box::new(MockVerifier { pass: true }))
+            .schema(make_schema())
+            .clock(Box::new(FixedClock::new(instant)))
+            .observer(Box::new(observer))
+            .build()
+            .unwrap();
+
+        let result = executor
+            .step(&MockAgent::new(), make_state("active"), make_input(), &CapabilitySet::default())
+            .unwrap();
+
+        assert!(matches!(result, StepResult::Transitioned { .. }));
+        assert_eq!(audit_records.lock().unwrap()[0].timestamp, instant);
+        assert_eq!(*verdicts.lock().unwrap(), vec![PolicyVerdict::Allow]);
+        assert_eq!(*outcomes.lock().unwrap(), vec![Some(StepOutcome::Allowed)]);
+    }
+
+    /// Leaving out a required component is a configuration error naming it.
+    #[test]
+    fn test_builder_without_policy_fails() {
+        let result = Executor::builder()
+            .audit(Box::new(MockAudit::new()))
+            .verifier(Box::new(MockVerifier { pass: true }))
+            .schema(make_schema())
+            .build();
+
+        match result {
+            Err(VeritasError::ConfigError { reason }) => {
+                assert!(reason.contains("policy engine"), "reason: {reason}");
+            }
+            Err(other) => panic!("expected ConfigError, got {:?}", other),
+            Ok(_) => panic!("expected ConfigError, got an executor"),
+        }
+    }
+
+    /// A step runs inside a `veritas_step` span carrying the execution id,
+    /// step number and action, with one child span per pipeline phase.
+    #[test]
+    fn test_step_emits_phase_spans() {
+        use crate::testing::SpanRecorder;
+
+        let executor = Executor::new(
+            Box::new(MockPolicy { verdict: PolicyVerdict::Allow }),
+            Box::new(MockAudit::new()),
+            Box::new(MockVerifier { pass: true }),
+            make_schema(),
+        );
+
+        let state = make_state("active");
+        let execution_id = state.execution_id.0.to_string();
+        let recorder = SpanRecorder::default();
+
+        tracing::subscriber::with_default(recorder.clone(), || {
+            executor
+                .step(&MockAgent::new(), state, make_input(), &CapabilitySet::default())
+                .unwrap();
+        });
+
+        let spans = recorder.spans();
+        let root = spans
+            .iter()
+            .find(|s| s.name == "veritas_step")
+            .expect("step must open a veritas_step span");
+        assert_eq!(root.field("execution_id"), Some(execution_id.as_str()));
+        assert_eq!(root.field("step"), Some("0"));
+        assert_eq!(root.field("action"), Some("respond"));
+
+        // Span ids are assigned in creation order, so they must ascend phase by phase.
+        let mut previous_id = root.id;
+        for phase in ["policy", "capability", "propose", "verify", "audit"] {
+            let span = spans
+                .iter()
+                .find(|s| s.name == phase)
+                .unwrap_or_else(|| panic!("missing '{}' span", phase));
+            assert_eq!(span.parent, Some(root.id), "'{}' must nest under veritas_step", phase);
+            assert!(span.id > previous_id, "'{}' span opened out of order", phase);
+            previous_id = span.id;
+        }
+    }
 }
@@ -4,7 +4,17 @@
 //!
 //! This crate provides:
 //! - The four core traits (`Agent`, `PolicyEngine`, `AuditWriter`, `Verifier`)
-//! - The `Executor` that wires them together in the correct trust order
+//! - The `Executor` that wires them together in the correct trust order,
+//!   and an `ExecutorBuilder` for assembling it from named parts
+//! - The optional `ExecutorObserver` hook for metrics and logging
+//! - The `ApprovalQueue` that parks suspended executions until an approver
+//!   resumes them
+//! - The `Clock` abstraction every runtime timestamp is read from
+//! - The `AuditRegistry` that routes audit writes per execution
+//! - `testing::RecordingExecutor`, which records the executor's phase order,
+//!   and stand-in components for tests (behind the `testing` feature)
+//! - `metrics::PrometheusObserver`, which exports policy evaluation metrics
+//!   to Prometheus (behind the `prometheus` feature)
 //!
 //! ## Usage
 //!
@@ -12,7 +22,14 @@
 //! use veritas_core::{Executor, traits::{Agent, PolicyEngine, AuditWriter, Verifier}};
 //! ```
 
+pub mod approval;
+pub mod clock;
 pub mod executor;
+#[cfg(feature = "prometheus")]
+pub mod metrics;
+pub mod registry;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod traits;
 
-pub use executor::Executor;
+pub use executor::{Executor, ExecutorBuilder};
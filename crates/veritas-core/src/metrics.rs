@@ -0,0 +1,195 @@
+//! Prometheus export of executor metrics.
+//!
+//! `PrometheusObserver` is an `ExecutorObserver` that records every policy
+//! evaluation the executor reports:
+//!
+//! - `veritas_policy_evaluations_total{verdict}` — a counter per verdict
+//!   (`allow`, `deny`, `require-approval`, `require-verification`)
+//! - `veritas_policy_evaluation_seconds` — a histogram of evaluation latency
+//!
+//! The observer owns a `prometheus::Registry`; the host serves it on
+//! `/metrics` by encoding `registry().gather()` with a `TextEncoder`.
+//!
+//! Available behind the `prometheus` feature.
+
+use std::time::Duration;
+
+use prometheus::{Histogram, HistogramOpts, IntCounterVec, Opts, Registry};
+
+use veritas_contracts::{
+    error::{VeritasError, VeritasResult},
+    policy::{PolicyContext, PolicyVerdict},
+};
+
+use crate::traits::ExecutorObserver;
+
+/// An `ExecutorObserver` exporting policy evaluation counts and latency to a
+/// Prometheus registry.
+pub struct PrometheusObserver {
+    registry: Registry,
+    evaluations: IntCounterVec,
+    latency: Histogram,
+}
+
+impl PrometheusObserver {
+    /// Create an observer with its metrics registered in a fresh registry.
+    pub fn new() -> VeritasResult<Self> {
+        Self::with_registry(Registry::new())
+    }
+
+    /// Create an observer whose metrics are registered in `registry`, so
+    /// they are served alongside the host's own metrics.
+    ///
+    /// Returns `VeritasError::ConfigError` if the registry already holds
+    /// metrics with the same names.
+    pub fn with_registry(registry: Registry) -> VeritasResult<Self> {
+        let evaluations = IntCounterVec::new(
+            Opts::new(
+                "veritas_policy_evaluations_total",
+                "Policy evaluations performed by the executor, by verdict",
+            ),
+            &["verdict"],
+        )
+        .map_err(config_error)?;
+        let latency = Histogram::with_opts(HistogramOpts::new(
+            "veritas_policy_evaluation_seconds",
+            "Time taken by the policy engine to reach a verdict",
+        ))
+        .map_err(config_error)?;
+
+        registry.register(Box::new(evaluations.clone())).map_err(config_error)?;
+        registry.register(Box::new(latency.clone())).map_err(config_error)?;
+
+        Ok(Self {
+            registry,
+            evaluations,
+            latency,
+        })
+    }
+
+    /// The registry holding this observer's metrics.
+    ///
+    /// `Registry` is cheap to clone; clones share the same metrics.
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+}
+
+impl ExecutorObserver for PrometheusObserver {
+    fn on_policy_evaluated(&self, _ctx: &PolicyContext, verdict: &PolicyVerdict, elapsed: Duration) {
+        self.evaluations.with_label_values(&[verdict_label(verdict)]).inc();
+        self.latency.observe(elapsed.as_secs_f64());
+    }
+}
+
+/// The `verdict` label value for `verdict`, matching its serialized name.
+fn verdict_label(verdict: &PolicyVerdict) -> &'static str {
+    match verdict {
+        PolicyVerdict::Allow => "allow",
+        PolicyVerdict::Deny { .. } => "deny",
+        PolicyVerdict::RequireApproval { .. } => "require-approval",
+        PolicyVerdict::RequireVerification { .. } => "require-verification",
+        PolicyVerdict::RequireBoth { .. } => "require-both",
+    }
+}
+
+fn config_error(e: prometheus::Error) -> VeritasError {
+    VeritasError::ConfigError {
+        reason: format!("failed to register Prometheus metrics: {}", e),
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use veritas_contracts::capability::CapabilitySet;
+
+    use super::*;
+    use crate::{
+        executor::Executor,
+        testing::{action_input, agent_state, empty_schema, ActionAgent, NullAudit, PassVerifier},
+        traits::PolicyEngine,
+    };
+
+    /// Allows reads, denies deletes and sends orders for approval.
+    struct ActionPolicy;
+
+    impl PolicyEngine for ActionPolicy {
+        fn evaluate(&self, ctx: &PolicyContext) -> VeritasResult<PolicyVerdict> {
+            Ok(match ctx.action.as_str() {
+                "read" => PolicyVerdict::Allow,
+                "order" => PolicyVerdict::RequireApproval {
+                    reason: "orders need sign-off".to_string(),
+                    approver_role: "attending-physician".to_string(),
+                    required_attributes: Default::default(),
+                },
+                _ => PolicyVerdict::Deny {
+                    reason: "not permitted".to_string(),
+                    remediation: None,
+                },
+            })
+        }
+    }
+
+    /// The value of `veritas_policy_evaluations_total{verdict}` in `registry`.
+    fn evaluations(registry: &Registry, verdict: &str) -> f64 {
+        registry
+            .gather()
+            .iter()
+            .filter(|family| family.get_name() == "veritas_policy_evaluations_total")
+            .flat_map(|family| family.get_metric())
+            .find(|metric| {
+                metric
+                    .get_label()
+                    .iter()
+                    .any(|label| label.get_name() == "verdict" && label.get_value() == verdict)
+            })
+            .map(|metric| metric.get_counter().get_value())
+            .unwrap_or(0.0)
+    }
+
+    /// Stepped verdicts are counted by label and every evaluation is timed.
+    #[test]
+    fn test_stepped_verdicts_are_counted() {
+        let observer = PrometheusObserver::new().unwrap();
+        let registry = observer.registry().clone();
+        let executor = Executor::new(
+            Box::new(ActionPolicy),
+            Box::new(NullAudit),
+            Box::new(PassVerifier),
+            empty_schema("test-v1"),
+        )
+        .with_observer(Box::new(observer));
+
+        let agent = ActionAgent::on("records");
+        for action in ["read", "read", "delete", "order", "read"] {
+            let state = agent_state("metrics-agent");
+            executor.step(&agent, state, action_input(action), &CapabilitySet::default()).unwrap();
+        }
+
+        assert_eq!(evaluations(&registry, "allow"), 3.0);
+        assert_eq!(evaluations(&registry, "deny"), 1.0);
+        assert_eq!(evaluations(&registry, "require-approval"), 1.0);
+        assert_eq!(evaluations(&registry, "require-verification"), 0.0);
+
+        let latency = registry
+            .gather()
+            .into_iter()
+            .find(|family| family.get_name() == "veritas_policy_evaluation_seconds")
+            .expect("latency histogram registered");
+        assert_eq!(latency.get_metric()[0].get_histogram().get_sample_count(), 5);
+    }
+
+    /// Registering twice in one registry is a configuration error.
+    #[test]
+    fn test_duplicate_registration_is_config_error() {
+        let registry = Registry::new();
+        PrometheusObserver::with_registry(registry.clone()).unwrap();
+
+        assert!(matches!(
+            PrometheusObserver::with_registry(registry),
+            Err(VeritasError::ConfigError { .. })
+        ));
+    }
+}
@@ -0,0 +1,98 @@
+//! Per-execution routing of audit writes.
+//!
+//! An `Executor` built with `Executor::new_with_registry` does not own a
+//! single audit writer.  Instead it asks an `AuditRegistry` for the writer
+//! belonging to each step's `state.execution_id`, creating one on first use.
+//! This lets one long-lived executor serve many executions while every
+//! execution still gets its own, independently verifiable chain.  A writer
+//! is evicted once its execution is finalized.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use veritas_contracts::{
+    agent::ExecutionId,
+    error::{VeritasError, VeritasResult},
+};
+
+use crate::traits::AuditWriter;
+
+/// Creates the audit writer for an execution seen for the first time.
+pub type AuditWriterFactory = Box<dyn Fn(&ExecutionId) -> Arc<dyn AuditWriter> + Send + Sync>;
+
+/// Maps each `ExecutionId` to the audit writer that holds its chain.
+pub struct AuditRegistry {
+    factory: AuditWriterFactory,
+    writers: Mutex<HashMap<ExecutionId, Arc<dyn AuditWriter>>>,
+}
+
+impl AuditRegistry {
+    /// Create an empty registry that builds writers with `factory`.
+    pub fn new(factory: AuditWriterFactory) -> Self {
+        Self {
+            factory,
+            writers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Return the writer for `execution_id`, creating it if this is the
+    /// execution's first audit write.
+    ///
+    /// Returns `VeritasError::AuditWriteFailed` if the registry lock is poisoned.
+    pub fn writer_for(&self, execution_id: &ExecutionId) -> VeritasResult<Arc<dyn AuditWriter>> {
+        let mut writers = self.writers()?;
+        let writer = writers
+            .entry(execution_id.clone())
+            .or_insert_with(|| (self.factory)(execution_id));
+        Ok(Arc::clone(writer))
+    }
+
+    /// Finalize the chain of `execution_id` and evict its writer, so a
+    /// long-lived registry holds only executions still in progress.
+    ///
+    /// The executor calls this when an execution reaches a terminal state.
+    /// A writer whose `finalize` fails stays registered, and its error is
+    /// returned.
+    pub fn finalize(&self, execution_id: &ExecutionId) -> VeritasResult<()> {
+        self.writer_for(execution_id)?.finalize(&execution_id.0.to_string())?;
+        self.remove(execution_id)?;
+        Ok(())
+    }
+
+    /// Evict the writer for `execution_id` without finalizing it, returning
+    /// it if there was one.
+    pub fn remove(
+        &self,
+        execution_id: &ExecutionId,
+    ) -> VeritasResult<Option<Arc<dyn AuditWriter>>> {
+        Ok(self.writers()?.remove(execution_id))
+    }
+
+    /// Return the writer for `execution_id` if one has been created and not
+    /// yet evicted.
+    pub fn get(&self, execution_id: &ExecutionId) -> VeritasResult<Option<Arc<dyn AuditWriter>>> {
+        Ok(self.writers()?.get(execution_id).map(Arc::clone))
+    }
+
+    /// Number of executions with a writer in the registry.
+    pub fn len(&self) -> VeritasResult<usize> {
+        Ok(self.writers()?.len())
+    }
+
+    /// True if no execution currently has a writer in the registry.
+    pub fn is_empty(&self) -> VeritasResult<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Lock the writer map, reporting a poisoned lock as
+    /// `VeritasError::AuditWriteFailed`.
+    fn writers(
+        &self,
+    ) -> VeritasResult<MutexGuard<'_, HashMap<ExecutionId, Arc<dyn AuditWriter>>>> {
+        self.writers.lock().map_err(|e| VeritasError::AuditWriteFailed {
+            reason: format!("audit registry lock poisoned: {}", e),
+        })
+    }
+}
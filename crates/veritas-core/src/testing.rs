@@ -0,0 +1,625 @@
+//! Test utilities for asserting the executor's phase ordering.
+//!
+//! `RecordingExecutor` wraps a real `Executor` and records, in a shared
+//! `PhaseLog`, every call the executor makes into the trusted components and
+//! the agent.  Tests can then assert the security invariant directly —
+//! `propose` never runs before a passing policy and capability check —
+//! however the executor's internals are refactored.
+//!
+//! The fixtures below — `NullAudit`, `MemoryAudit`, `PassVerifier`,
+//! `ActionAgent` and the `agent_state`/`action_input`/`empty_schema`
+//! builders — stand in for the parts a test of some other component does
+//! not care about.
+//! `SpanRecorder` captures the spans and events emitted while it is the
+//! `tracing` subscriber.
+//!
+//! Available to this crate's own tests and, to downstream crates, behind the
+//! `testing` feature.
+
+use std::sync::{Arc, Mutex};
+
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Level, Metadata, Subscriber,
+};
+
+use veritas_contracts::{
+    agent::{AgentId, AgentInput, AgentOutput, AgentState, ExecutionId},
+    capability::CapabilitySet,
+    error::VeritasResult,
+    execution::{RejectionReason, StepRecord, StepResult},
+    policy::{ActionKind, PolicyContext, PolicyDecision, PolicyVerdict},
+    verify::{OutputSchema, VerificationReport},
+};
+
+use crate::{
+    executor::Executor,
+    traits::{Agent, AuditWriter, PolicyEngine, Verifier},
+};
+
+/// One pipeline phase, as observed through the component it calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// `PolicyEngine::evaluate`, `evaluate_all`, `decide` or
+    /// `decide_uncounted`.
+    Policy,
+    /// `Agent::required_capabilities` — the executor's capability check.
+    Capability,
+    /// `Agent::propose`.
+    Propose,
+    /// `Verifier::verify` or `Verifier::run_check`.
+    Verify,
+    /// `Agent::transition`.
+    Transition,
+    /// `AuditWriter::write`.  `finalize` is not recorded.
+    Audit,
+}
+
+/// The ordered list of phases a `RecordingExecutor` has observed.
+///
+/// Cheap to clone; clones share the same underlying log.
+#[derive(Debug, Clone, Default)]
+pub struct PhaseLog(Arc<Mutex<Vec<Phase>>>);
+
+impl PhaseLog {
+    /// Create an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `phase` to the log.
+    pub fn record(&self, phase: Phase) {
+        self.0.lock().expect("phase log poisoned").push(phase);
+    }
+
+    /// Every recorded phase, in call order.
+    pub fn phases(&self) -> Vec<Phase> {
+        self.0.lock().expect("phase log poisoned").clone()
+    }
+
+    /// True if `phase` was recorded at least once.
+    pub fn contains(&self, phase: Phase) -> bool {
+        self.phases().contains(&phase)
+    }
+
+    /// Index of the first occurrence of `phase`, if recorded.
+    pub fn position(&self, phase: Phase) -> Option<usize> {
+        self.phases().iter().position(|p| *p == phase)
+    }
+
+    /// Forget every recorded phase.
+    pub fn clear(&self) {
+        self.0.lock().expect("phase log poisoned").clear();
+    }
+}
+
+/// An `Executor` whose component and agent calls are recorded in a
+/// `PhaseLog`.
+pub struct RecordingExecutor {
+    executor: Executor,
+    log: PhaseLog,
+}
+
+impl RecordingExecutor {
+    /// Build an executor from the given components, each wrapped so its
+    /// calls are recorded.
+    pub fn new(
+        policy: Box<dyn PolicyEngine>,
+        audit: Box<dyn AuditWriter>,
+        verifier: Box<dyn Verifier>,
+        schema: OutputSchema,
+    ) -> Self {
+        let log = PhaseLog::new();
+        let executor = Executor::new(
+            Box::new(RecordingPolicy { inner: policy, log: log.clone() }),
+            Box::new(RecordingAudit { inner: audit, log: log.clone() }),
+            Box::new(RecordingVerifier { inner: verifier, log: log.clone() }),
+            schema,
+        );
+        Self { executor, log }
+    }
+
+    /// Run `Executor::step`, recording the agent's calls alongside the
+    /// components'.
+    pub fn step(
+        &self,
+        agent: &dyn Agent,
+        state: AgentState,
+        input: AgentInput,
+        capabilities: &CapabilitySet,
+    ) -> VeritasResult<StepResult> {
+        let agent = RecordingAgent { inner: agent, log: self.log.clone() };
+        self.executor.step(&agent, state, input, capabilities)
+    }
+
+    /// The log of every phase observed so far.
+    pub fn log(&self) -> &PhaseLog {
+        &self.log
+    }
+}
+
+// ── Fixtures ──────────────────────────────────────────────────────────────────
+
+/// An `AuditWriter` that discards every record.
+pub struct NullAudit;
+
+impl AuditWriter for NullAudit {
+    fn write(&self, _record: &StepRecord) -> VeritasResult<()> {
+        Ok(())
+    }
+
+    fn finalize(&self, _execution_id: &str) -> VeritasResult<()> {
+        Ok(())
+    }
+}
+
+/// An `AuditWriter` that keeps every record it is given, in order.
+///
+/// Cheap to clone; clones share the same records, so a test can keep one
+/// and hand the other to the executor.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryAudit(Arc<Mutex<Vec<StepRecord>>>);
+
+impl MemoryAudit {
+    /// Create a writer holding no records.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every record written so far.
+    pub fn records(&self) -> Vec<StepRecord> {
+        self.0.lock().expect("audit records poisoned").clone()
+    }
+}
+
+impl AuditWriter for MemoryAudit {
+    fn write(&self, record: &StepRecord) -> VeritasResult<()> {
+        self.0.lock().expect("audit records poisoned").push(record.clone());
+        Ok(())
+    }
+
+    fn finalize(&self, _execution_id: &str) -> VeritasResult<()> {
+        Ok(())
+    }
+}
+
+/// A `Verifier` that passes every output.
+pub struct PassVerifier;
+
+impl Verifier for PassVerifier {
+    fn verify(
+        &self,
+        _output: &AgentOutput,
+        _schema: &OutputSchema,
+    ) -> VeritasResult<VerificationReport> {
+        Ok(VerificationReport::from_failures(vec![]))
+    }
+}
+
+/// An agent whose action is its input's `kind`, taken on a fixed resource.
+///
+/// It needs no capabilities, proposes `{"ok": true}`, advances the state's
+/// `step` by one and never reaches a terminal state.
+pub struct ActionAgent {
+    resource: String,
+}
+
+impl ActionAgent {
+    /// An agent acting on `resource`.
+    pub fn on(resource: impl Into<String>) -> Self {
+        Self { resource: resource.into() }
+    }
+}
+
+impl Agent for ActionAgent {
+    fn propose(&self, _state: &AgentState, _input: &AgentInput) -> VeritasResult<AgentOutput> {
+        Ok(AgentOutput {
+            kind: "result".to_string(),
+            payload: serde_json::json!({ "ok": true }),
+            provenance: None,
+            advisories: vec![],
+        })
+    }
+
+    fn transition(&self, state: &AgentState, _output: &AgentOutput) -> VeritasResult<AgentState> {
+        Ok(AgentState { step: state.step + 1, ..state.clone() })
+    }
+
+    fn required_capabilities(&self, _state: &AgentState, _input: &AgentInput) -> Vec<String> {
+        vec![]
+    }
+
+    fn describe_action(&self, _state: &AgentState, input: &AgentInput) -> (String, String) {
+        (input.kind.clone(), self.resource.clone())
+    }
+
+    fn is_terminal(&self, _state: &AgentState) -> bool {
+        false
+    }
+}
+
+/// A new execution of `agent_id` at step 0, in phase "active".
+pub fn agent_state(agent_id: &str) -> AgentState {
+    AgentState {
+        agent_id: AgentId(agent_id.to_string()),
+        execution_id: ExecutionId::new(),
+        phase: "active".to_string(),
+        context: serde_json::Value::Null,
+        step: 0,
+        correlation_id: None,
+    }
+}
+
+/// An input of `kind` with a null payload — for an `ActionAgent`, a request
+/// to take the action `kind`.
+pub fn action_input(kind: &str) -> AgentInput {
+    AgentInput {
+        kind: kind.to_string(),
+        payload: serde_json::Value::Null,
+    }
+}
+
+/// A schema with no JSON Schema, rules or defaults: every output passes.
+pub fn empty_schema(schema_id: &str) -> OutputSchema {
+    OutputSchema {
+        schema_id: schema_id.to_string(),
+        json_schema: serde_json::Value::Null,
+        rules: vec![],
+        defaults: vec![],
+    }
+}
+
+// ── Tracing capture ───────────────────────────────────────────────────────────
+
+/// A span opened while a `SpanRecorder` was installed.
+#[derive(Debug, Clone)]
+pub struct CapturedSpan {
+    /// The span's id; ids ascend in creation order, starting at 1.
+    pub id: u64,
+    /// The span's name, e.g. `"veritas_step"`.
+    pub name: &'static str,
+    /// The id of the span it was opened in, if any.
+    pub parent: Option<u64>,
+    /// Every field recorded on the span, as `(name, value)`.
+    pub fields: Vec<(String, String)>,
+}
+
+impl CapturedSpan {
+    /// The value recorded for the field `name`, if any.
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// An event emitted while a `SpanRecorder` was installed.
+#[derive(Debug, Clone)]
+pub struct CapturedEvent {
+    /// The event's level.
+    pub level: Level,
+    /// The event's message, as its `Debug` rendering.
+    pub message: String,
+}
+
+#[derive(Default)]
+struct CaptureState {
+    spans: Vec<CapturedSpan>,
+    events: Vec<CapturedEvent>,
+    stack: Vec<u64>,
+}
+
+/// A minimal `tracing` subscriber that records every span, with its parent
+/// and fields, and every event, with its level and message.
+///
+/// Cheap to clone; clones share what was recorded, so a test can install one
+/// with `tracing::subscriber::with_default` and read the other afterwards.
+#[derive(Clone, Default)]
+pub struct SpanRecorder {
+    state: Arc<Mutex<CaptureState>>,
+}
+
+impl SpanRecorder {
+    /// Every span opened so far, in creation order.
+    pub fn spans(&self) -> Vec<CapturedSpan> {
+        self.state.lock().expect("span recorder poisoned").spans.clone()
+    }
+
+    /// Every event emitted so far, in order.
+    pub fn events(&self) -> Vec<CapturedEvent> {
+        self.state.lock().expect("span recorder poisoned").events.clone()
+    }
+
+    /// The messages of the events emitted at `level`, in order.
+    pub fn messages_at(&self, level: Level) -> Vec<String> {
+        self.events()
+            .into_iter()
+            .filter(|event| event.level == level)
+            .map(|event| event.message)
+            .collect()
+    }
+}
+
+struct FieldVisitor<'a>(&'a mut Vec<(String, String)>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name().to_string(), value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.push((field.name().to_string(), format!("{:?}", value)));
+    }
+}
+
+impl Subscriber for SpanRecorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attrs: &span::Attributes<'_>) -> span::Id {
+        let mut state = self.state.lock().expect("span recorder poisoned");
+        let parent = match attrs.parent() {
+            Some(id) => Some(id.into_u64()),
+            None if attrs.is_contextual() => state.stack.last().copied(),
+            None => None,
+        };
+        let mut fields = Vec::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        let id = state.spans.len() as u64 + 1;
+        state.spans.push(CapturedSpan {
+            id,
+            name: attrs.metadata().name(),
+            parent,
+            fields,
+        });
+        span::Id::from_u64(id)
+    }
+
+    fn record(&self, span: &span::Id, values: &span::Record<'_>) {
+        let mut state = self.state.lock().expect("span recorder poisoned");
+        let index = span.into_u64() as usize - 1;
+        values.record(&mut FieldVisitor(&mut state.spans[index].fields));
+    }
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Vec::new();
+        event.record(&mut FieldVisitor(&mut fields));
+        let message = fields
+            .into_iter()
+            .find(|(name, _)| name == "message")
+            .map(|(_, value)| value)
+            .unwrap_or_default();
+        self.state.lock().expect("span recorder poisoned").events.push(CapturedEvent {
+            level: *event.metadata().level(),
+            message,
+        });
+    }
+
+    fn enter(&self, span: &span::Id) {
+        self.state.lock().expect("span recorder poisoned").stack.push(span.into_u64());
+    }
+
+    fn exit(&self, _span: &span::Id) {
+        self.state.lock().expect("span recorder poisoned").stack.pop();
+    }
+}
+
+// ── Recording wrappers ────────────────────────────────────────────────────────
+
+struct RecordingPolicy {
+    inner: Box<dyn PolicyEngine>,
+    log: PhaseLog,
+}
+
+impl PolicyEngine for RecordingPolicy {
+    fn evaluate(&self, ctx: &PolicyContext) -> VeritasResult<PolicyVerdict> {
+        self.log.record(Phase::Policy);
+        self.inner.evaluate(ctx)
+    }
+
+    fn evaluate_all(&self, ctxs: &[PolicyContext]) -> VeritasResult<Vec<PolicyVerdict>> {
+        self.log.record(Phase::Policy);
+        self.inner.evaluate_all(ctxs)
+    }
+
+    fn decide(&self, ctx: &PolicyContext) -> VeritasResult<PolicyDecision> {
+        self.log.record(Phase::Policy);
+        self.inner.decide(ctx)
+    }
+
+    fn decide_uncounted(&self, ctx: &PolicyContext) -> VeritasResult<PolicyDecision> {
+        self.log.record(Phase::Policy);
+        self.inner.decide_uncounted(ctx)
+    }
+}
+
+struct RecordingAudit {
+    inner: Box<dyn AuditWriter>,
+    log: PhaseLog,
+}
+
+impl AuditWriter for RecordingAudit {
+    fn write(&self, record: &StepRecord) -> VeritasResult<()> {
+        self.log.record(Phase::Audit);
+        self.inner.write(record)
+    }
+
+    fn finalize(&self, execution_id: &str) -> VeritasResult<()> {
+        self.inner.finalize(execution_id)
+    }
+}
+
+struct RecordingVerifier {
+    inner: Box<dyn Verifier>,
+    log: PhaseLog,
+}
+
+impl Verifier for RecordingVerifier {
+    fn verify(&self, output: &AgentOutput, schema: &OutputSchema) -> VeritasResult<VerificationReport> {
+        self.log.record(Phase::Verify);
+        self.inner.verify(output, schema)
+    }
+
+    fn run_check(&self, check_id: &str, output: &AgentOutput) -> VeritasResult<VerificationReport> {
+        self.log.record(Phase::Verify);
+        self.inner.run_check(check_id, output)
+    }
+
+    fn normalize(&self, output: &AgentOutput, schema: &OutputSchema) -> AgentOutput {
+        self.inner.normalize(output, schema)
+    }
+}
+
+struct RecordingAgent<'a> {
+    inner: &'a dyn Agent,
+    log: PhaseLog,
+}
+
+impl Agent for RecordingAgent<'_> {
+    fn propose(&self, state: &AgentState, input: &AgentInput) -> VeritasResult<AgentOutput> {
+        self.log.record(Phase::Propose);
+        self.inner.propose(state, input)
+    }
+
+    fn transition(&self, state: &AgentState, output: &AgentOutput) -> VeritasResult<AgentState> {
+        self.log.record(Phase::Transition);
+        self.inner.transition(state, output)
+    }
+
+    fn required_capabilities(&self, state: &AgentState, input: &AgentInput) -> Vec<String> {
+        self.log.record(Phase::Capability);
+        self.inner.required_capabilities(state, input)
+    }
+
+    fn validate_input(&self, state: &AgentState, input: &AgentInput) -> VeritasResult<()> {
+        self.inner.validate_input(state, input)
+    }
+
+    fn describe_action(&self, state: &AgentState, input: &AgentInput) -> (String, String) {
+        self.inner.describe_action(state, input)
+    }
+
+    fn describe_action_kind(
+        &self,
+        state: &AgentState,
+        input: &AgentInput,
+        action: &str,
+    ) -> Option<ActionKind> {
+        self.inner.describe_action_kind(state, input, action)
+    }
+
+    fn action_metadata(&self, state: &AgentState, input: &AgentInput) -> serde_json::Value {
+        self.inner.action_metadata(state, input)
+    }
+
+    fn is_terminal(&self, state: &AgentState) -> bool {
+        self.inner.is_terminal(state)
+    }
+
+    fn on_rejected(&self, state: &AgentState, reason: &RejectionReason) -> Option<AgentInput> {
+        self.inner.on_rejected(state, reason)
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use veritas_contracts::capability::Capability;
+
+    use super::*;
+
+    struct FixedPolicy(PolicyVerdict);
+
+    impl PolicyEngine for FixedPolicy {
+        fn evaluate(&self, _ctx: &PolicyContext) -> VeritasResult<PolicyVerdict> {
+            Ok(self.0.clone())
+        }
+    }
+
+    struct ReadAgent;
+
+    impl Agent for ReadAgent {
+        fn propose(&self, _state: &AgentState, _input: &AgentInput) -> VeritasResult<AgentOutput> {
+            Ok(AgentOutput {
+                kind: "result".to_string(),
+                payload: serde_json::json!({ "ok": true }),
+                provenance: None,
+                advisories: vec![],
+            })
+        }
+
+        fn transition(&self, state: &AgentState, _output: &AgentOutput) -> VeritasResult<AgentState> {
+            Ok(AgentState { step: state.step + 1, ..state.clone() })
+        }
+
+        fn required_capabilities(&self, _state: &AgentState, _input: &AgentInput) -> Vec<String> {
+            vec!["records.read".to_string()]
+        }
+
+        fn describe_action(&self, _state: &AgentState, _input: &AgentInput) -> (String, String) {
+            ("read".to_string(), "records".to_string())
+        }
+
+        fn is_terminal(&self, _state: &AgentState) -> bool {
+            false
+        }
+    }
+
+    fn recording_executor(verdict: PolicyVerdict) -> RecordingExecutor {
+        RecordingExecutor::new(
+            Box::new(FixedPolicy(verdict)),
+            Box::new(NullAudit),
+            Box::new(PassVerifier),
+            empty_schema("test-v1"),
+        )
+    }
+
+    fn run(executor: &RecordingExecutor) -> VeritasResult<StepResult> {
+        let mut capabilities = CapabilitySet::default();
+        capabilities.grant(Capability::new("records.read"));
+        executor.step(
+            &ReadAgent,
+            agent_state("recording-agent"),
+            action_input("query"),
+            &capabilities,
+        )
+    }
+
+    /// A successful step calls each phase exactly once, in the canonical
+    /// order.
+    #[test]
+    fn test_successful_step_follows_canonical_order() {
+        let executor = recording_executor(PolicyVerdict::Allow);
+
+        assert!(matches!(run(&executor), Ok(StepResult::Transitioned { .. })));
+        assert_eq!(
+            executor.log().phases(),
+            vec![
+                Phase::Policy,
+                Phase::Capability,
+                Phase::Propose,
+                Phase::Verify,
+                Phase::Transition,
+                Phase::Audit,
+            ]
+        );
+    }
+
+    /// A policy denial is audited without the agent ever proposing.
+    #[test]
+    fn test_deny_never_reaches_propose() {
+        let executor = recording_executor(PolicyVerdict::Deny {
+            reason: "not today".to_string(),
+            remediation: None,
+        });
+
+        assert!(matches!(run(&executor), Ok(StepResult::Denied { .. })));
+        assert!(!executor.log().contains(Phase::Propose));
+        assert_eq!(executor.log().phases(), vec![Phase::Policy, Phase::Audit]);
+    }
+}
@@ -9,13 +9,19 @@
 //!
 //! The executor wires them together in the correct order. Implementations
 //! of `Agent` are never called unless the policy engine first returns Allow.
+//!
+//! `ExecutorObserver` sits outside the trust boundary: an optional hook for
+//! metrics and logging that sees what the executor decided but cannot
+//! influence it.
+
+use std::time::Duration;
 
 use veritas_contracts::{
-    agent::{AgentInput, AgentOutput, AgentState},
+    agent::{AgentInput, AgentOutput, AgentState, ExecutionId},
     error::VeritasResult,
-    execution::StepRecord,
-    policy::{PolicyContext, PolicyVerdict},
-    verify::{OutputSchema, VerificationReport},
+    execution::{RejectionReason, StepOutcome, StepRecord},
+    policy::{ActionKind, PolicyContext, PolicyDecision, PolicyVerdict},
+    verify::{OutputSchema, RuleSeverity, VerificationFailure, VerificationReport},
 };
 
 /// An agent that proposes outputs and manages its own state transitions.
@@ -30,8 +36,10 @@ pub trait Agent: Send + Sync {
     /// and input, produces an output, and does nothing else. The executor
     /// calls `transition()` separately to advance state after verification.
     ///
-    /// The executor guarantees this is only called after `PolicyEngine::evaluate()`
-    /// returns `PolicyVerdict::Allow`.
+    /// The executor guarantees this is only called once the policy's decision
+    /// (`PolicyEngine::decide()`) lets the step proceed: `Allow`,
+    /// `RequireVerification`, or an approval requirement met by the approval
+    /// passed to `resume()`.
     fn propose(&self, state: &AgentState, input: &AgentInput) -> VeritasResult<AgentOutput>;
 
     /// Apply `output` to `state` and return the next state.
@@ -47,6 +55,17 @@ pub trait Agent: Send + Sync {
     /// the agent's logic.
     fn required_capabilities(&self, state: &AgentState, input: &AgentInput) -> Vec<String>;
 
+    /// Check the input's preconditions before any work is done.
+    ///
+    /// The executor calls this after the capability check and before
+    /// `propose()`.  Returning `Err` ends the step: the rejection is audited
+    /// as a denial, `propose()` is never called, and the error is returned
+    /// as-is — so an agent can refuse a malformed request precisely instead
+    /// of defaulting missing fields.  The default accepts every input.
+    fn validate_input(&self, _state: &AgentState, _input: &AgentInput) -> VeritasResult<()> {
+        Ok(())
+    }
+
     /// Describe the action and resource this step would affect.
     ///
     /// Returns `(action, resource)` — plain strings the policy engine uses
@@ -55,11 +74,47 @@ pub trait Agent: Send + Sync {
     /// Example: `("read_patient_record", "patient/12345")`
     fn describe_action(&self, state: &AgentState, input: &AgentInput) -> (String, String);
 
+    /// Classify `action` — the action `describe_action` returned for this
+    /// step — as a read, write, delete or execute, passed through as
+    /// `PolicyContext::action_kind` so rules can match on the kind.
+    ///
+    /// The default infers the kind from the action's leading verb with
+    /// `ActionKind::infer` (`"delete_record"` is a `Delete`); override it
+    /// when the action names do not follow that convention.  `None` leaves
+    /// the action unclassified, and rules scoped to a kind never match it.
+    fn describe_action_kind(
+        &self,
+        _state: &AgentState,
+        _input: &AgentInput,
+        action: &str,
+    ) -> Option<ActionKind> {
+        ActionKind::infer(action)
+    }
+
+    /// Extra facts about this action for the policy engine, passed through
+    /// as `PolicyContext::metadata` (e.g. `{"urgency": "routine"}`).
+    ///
+    /// Like `describe_action`, this is the agent's own claim; policies should
+    /// treat it as context, not proof.  The default is `Value::Null`.
+    fn action_metadata(&self, _state: &AgentState, _input: &AgentInput) -> serde_json::Value {
+        serde_json::Value::Null
+    }
+
     /// Return true if the agent has reached a terminal state.
     ///
     /// When this returns true after a step completes, the executor calls
     /// `AuditWriter::finalize()` and returns `StepResult::Complete`.
     fn is_terminal(&self, state: &AgentState) -> bool;
+
+    /// React to the executor rejecting this step's output.
+    ///
+    /// Called when the proposed output fails verification. Returning
+    /// `Some(input)` asks the executor to retry the step once with that input;
+    /// the retry goes through the full pipeline again, starting at policy.
+    /// The default returns `None`, which surfaces the rejection as an error.
+    fn on_rejected(&self, _state: &AgentState, _reason: &RejectionReason) -> Option<AgentInput> {
+        None
+    }
 }
 
 /// The policy engine: the first and most critical gate in the execution pipeline.
@@ -72,6 +127,37 @@ pub trait PolicyEngine: Send + Sync {
     /// The executor calls this before any agent logic runs. A non-`Allow`
     /// verdict prevents `Agent::propose()` from being called.
     fn evaluate(&self, ctx: &PolicyContext) -> VeritasResult<PolicyVerdict>;
+
+    /// Evaluate a batch of contexts, e.g. every step of a proposed pipeline.
+    ///
+    /// Verdicts are returned in input order.  The first evaluation error
+    /// aborts the batch.  The default calls `evaluate` once per context;
+    /// engines with per-call setup can override it to share that work.
+    fn evaluate_all(&self, ctxs: &[PolicyContext]) -> VeritasResult<Vec<PolicyVerdict>> {
+        ctxs.iter().map(|ctx| self.evaluate(ctx)).collect()
+    }
+
+    /// Evaluate `ctx` and report how the verdict was reached.
+    ///
+    /// The executor calls this instead of `evaluate` so a denial can carry
+    /// its `DenyCode` and deciding rule into `StepResult::Denied`.  The
+    /// default wraps `evaluate` with `PolicyDecision::from_verdict`; engines
+    /// that know which rule decided should override it.  Engines that keep
+    /// per-invocation budgets, such as rate limits, spend them here and not
+    /// in `evaluate`, which previews and shadow evaluations call.
+    fn decide(&self, ctx: &PolicyContext) -> VeritasResult<PolicyDecision> {
+        self.evaluate(ctx).map(PolicyDecision::from_verdict)
+    }
+
+    /// Decide `ctx` as `decide` does, without counting it toward rate limits
+    /// or any other per-invocation budget.
+    ///
+    /// The executor calls this for a step `decide` has already counted: the
+    /// retry after `Agent::on_rejected()`.  The default calls `decide`;
+    /// engines whose `decide` records state should override it.
+    fn decide_uncounted(&self, ctx: &PolicyContext) -> VeritasResult<PolicyDecision> {
+        self.decide(ctx)
+    }
 }
 
 /// The audit writer: the immutable execution record.
@@ -104,4 +190,61 @@ pub trait Verifier: Send + Sync {
     /// Return a `VerificationReport` with `passed = true` if all rules pass,
     /// or `passed = false` with populated `failures` if any rule fails.
     fn verify(&self, output: &AgentOutput, schema: &OutputSchema) -> VeritasResult<VerificationReport>;
+
+    /// Run the named check `check_id` against `output`.
+    ///
+    /// The executor calls this after `verify()` passes when the policy verdict
+    /// was `RequireVerification { check_id }`, and for each of the
+    /// `check_ids` of a `RequireBoth`; a failing report blocks the step
+    /// exactly like a failed `verify()`.
+    ///
+    /// The default fails closed: a verifier that does not implement named
+    /// checks reports every check as failed.
+    fn run_check(&self, check_id: &str, _output: &AgentOutput) -> VeritasResult<VerificationReport> {
+        Ok(VerificationReport::from_failures(vec![VerificationFailure {
+            rule_id: check_id.to_string(),
+            message: format!("verifier does not implement check '{}'", check_id),
+            severity: RuleSeverity::Error,
+        }]))
+    }
+
+    /// Fill the optional fields `output` omits from `schema.defaults`.
+    ///
+    /// The executor calls this between `propose()` and `verify()` when built
+    /// `with_output_normalization(true)`; the returned output is what gets
+    /// verified, audited and delivered.  The default returns `output`
+    /// unchanged.
+    fn normalize(&self, output: &AgentOutput, _schema: &OutputSchema) -> AgentOutput {
+        output.clone()
+    }
+}
+
+/// A passive hook notified as the executor runs steps.
+///
+/// Observers are for metrics and logging: they receive copies of the
+/// executor's decisions and return nothing, so they cannot alter a step.
+/// Every method defaults to a no-op.
+pub trait ExecutorObserver: Send + Sync {
+    /// Called after the policy engine decides a step (`PolicyEngine::decide()`),
+    /// with the verdict and the time the decision took.  Not called when
+    /// evaluation errors.
+    fn on_policy_evaluated(
+        &self,
+        _ctx: &PolicyContext,
+        _verdict: &PolicyVerdict,
+        _elapsed: Duration,
+    ) {
+    }
+
+    /// Called once per `Executor::step()` or `Executor::resume()` call, after
+    /// the step has finished.  `outcome` is `StepOutcome::from_result` of
+    /// the value returned to the caller — `None` for errors that are not a
+    /// step decision.
+    fn on_step_finished(
+        &self,
+        _execution_id: &ExecutionId,
+        _step: u64,
+        _outcome: Option<StepOutcome>,
+    ) {
+    }
 }
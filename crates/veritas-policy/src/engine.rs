@@ -5,25 +5,75 @@
 //!
 //! Evaluation algorithm:
 //!
-//! 1. Iterate rules in declaration order.
-//! 2. For the first rule whose `action` and `resource` patterns match:
-//!    a. Verify the agent holds every capability listed in `required_capabilities`.
-//!       If any are missing → `Deny` (defense-in-depth; the `allow` verdict is
-//!       overridden by missing capabilities, not by the rule order).
-//!    b. Convert `RuleVerdict` → `PolicyVerdict` and return.
-//! 3. If no rule matched → `Deny` with "denied by default" (deny-by-default policy).
+//! 1. Iterate rules in declaration order.  A matching rule with
+//!    `verdict = "continue"` adds its `tags` to the decision and evaluation
+//!    moves on; its capabilities and rate limit are not checked.
+//! 2. For the first other rule whose `action` and `resource` patterns match,
+//!    whose `action_kind` (if any) covers the context's, and whose
+//!    `metadata_match` pairs (if any) all appear in the context's metadata:
+//!    - a. Verify the agent holds every capability listed in `required_capabilities`.
+//!      If any are missing → `Deny` (defense-in-depth; the `allow` verdict is
+//!      overridden by missing capabilities, not by the rule order).
+//!      A requirement ending in `.*` (e.g. `drug-database.*`) is satisfied by
+//!      any held capability under that namespace.
+//!      Capabilities from `capability_conditions` entries whose resource
+//!      pattern matches are checked after these base requirements.
+//!    - b. With `capability_mode = "restrictive"`, also verify the requirements
+//!      of every later rule that matches; any missing capability → `Deny`.
+//!    - c. Convert `RuleVerdict` → `PolicyVerdict` and return.
+//! 3. If no rule decided → `Deny` with "denied by default" (deny-by-default
+//!    policy), unless the config sets `default_verdict = "allow"`, in which
+//!    case → `Allow` with a `warn!` on every such decision.  A policy whose
+//!    only matching rules continue therefore denies by default.
+//!
+//! Whatever decides, the trace and the `PolicyDecision` carry the tags
+//! accumulated from the `continue` rules matched on the way.
+//!
+//! Between steps 2a/2b and 2c, a rule with `max_invocations_per_window` is
+//! counted in the engine's `RateCounterStore`; once it has fired that many
+//! times for the agent in the current window → `Deny`.  Only `decide` —
+//! the call `Executor::step()` enforces — counts.  `evaluate`,
+//! `evaluate_explain` and `decide_uncounted` read the count and deny when
+//! the next counted firing would pass the limit, so previews, shadow
+//! evaluations and resumed steps spend no budget.
+//!
+//! `evaluate_explain` runs the same algorithm and also returns a `PolicyTrace`
+//! recording why each rule was skipped or fired.
+//!
+//! `evaluate_config` runs the algorithm over a bare `PolicyConfig`, without
+//! an engine and therefore without rate limits or approver resolvers.
+//!
+//! An `approver_role` may hold `{name}` placeholders, each replaced in step
+//! 2c by the `ApproverResolver` registered under `name` applied to the
+//! context's resource — so one rule can route each procedure to the
+//! specialty that must approve it.
 
+use std::collections::HashMap;
+use std::fmt;
 use std::path::Path;
+use std::sync::Arc;
 
 use tracing::{debug, warn};
 
 use veritas_contracts::{
+    capability::CapabilityCatalog,
     error::{VeritasError, VeritasResult},
-    policy::{PolicyContext, PolicyVerdict},
+    policy::{PolicyContext, PolicyDecision, PolicyVerdict},
+};
+use veritas_core::{
+    clock::{Clock, SystemClock},
+    traits::PolicyEngine,
+};
+
+use crate::{
+    rate::{window_key, InMemoryRateCounterStore, RateCounterStore, DEFAULT_WINDOW_SECS},
+    rule::{CapabilityMode, DefaultVerdict, PolicyConfig, PolicyRule, RuleVerdict},
+    trace::{PolicyTrace, RuleEvaluation, RuleOutcome},
 };
-use veritas_core::traits::PolicyEngine;
 
-use crate::rule::{PolicyConfig, RuleVerdict};
+/// Maps the resource under evaluation to an approver role, for the `{name}`
+/// placeholders of a rule's `approver_role`.
+pub type ApproverResolver = Box<dyn Fn(&str) -> String + Send + Sync>;
 
 /// A `PolicyEngine` implementation that reads rules from a TOML document.
 ///
@@ -34,21 +84,100 @@ use crate::rule::{PolicyConfig, RuleVerdict};
 ///
 /// let engine = TomlPolicyEngine::from_file(Path::new("policies/healthcare.toml"))?;
 /// ```
-#[derive(Debug)]
 pub struct TomlPolicyEngine {
     config: PolicyConfig,
+    /// Counts firings of rules with `max_invocations_per_window`.
+    rate_store: Arc<dyn RateCounterStore>,
+    /// Decides which rate window an evaluation falls in.
+    clock: Arc<dyn Clock>,
+    /// Named resolvers for `{name}` placeholders in approver roles.
+    approver_resolvers: HashMap<String, ApproverResolver>,
+}
+
+impl fmt::Debug for TomlPolicyEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TomlPolicyEngine")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
 }
 
 impl TomlPolicyEngine {
     /// Parse `s` as TOML and build a `TomlPolicyEngine`.
     ///
-    /// Returns `VeritasError::ConfigError` if the TOML is malformed or does
-    /// not match the expected `PolicyConfig` schema.
+    /// Rules that name a `[[groups]]` entry inherit its requirements here.
+    ///
+    /// Returns `VeritasError::ConfigError` if the TOML is malformed, does
+    /// not match the expected `PolicyConfig` schema, a rule references an
+    /// undefined group, or a rule's `metadata_match` is not a table.
     pub fn from_toml_str(s: &str) -> VeritasResult<Self> {
-        let config: PolicyConfig = toml::from_str(s).map_err(|e| VeritasError::ConfigError {
+        let mut config: PolicyConfig = toml::from_str(s).map_err(|e| VeritasError::ConfigError {
             reason: format!("failed to parse policy TOML: {}", e),
         })?;
-        Ok(Self { config })
+        config.resolve_groups()?;
+        config.check_metadata_matches()?;
+        Ok(Self {
+            config,
+            rate_store: Arc::new(InMemoryRateCounterStore::new()),
+            clock: Arc::new(SystemClock),
+            approver_resolvers: HashMap::new(),
+        })
+    }
+
+    /// Keep rate-limit counts in `store` instead of the default
+    /// `InMemoryRateCounterStore`, e.g. to share them between engines or
+    /// across a server's executions.
+    pub fn with_rate_store(mut self, store: Arc<dyn RateCounterStore>) -> Self {
+        self.rate_store = store;
+        self
+    }
+
+    /// Read the time that places evaluations in rate windows from `clock`.
+    /// Defaults to `SystemClock`.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Register `resolver` for the `{name}` placeholder in approver roles.
+    ///
+    /// When a rule with `approver_role = "{name}"` decides, the placeholder
+    /// is replaced with `resolver(resource)`, so `cardiac-mri` can need a
+    /// cardiologist while other procedures need an attending.  Text around a
+    /// placeholder is kept.  Registering the same name twice replaces the
+    /// earlier resolver.
+    pub fn register_approver_resolver(
+        &mut self,
+        name: impl Into<String>,
+        resolver: ApproverResolver,
+    ) {
+        self.approver_resolvers.insert(name.into(), resolver);
+    }
+
+    /// Parse `s` like `from_toml_str`, then check every capability the rules
+    /// require against `catalog`.
+    ///
+    /// Each requirement missing from the catalog is logged at `warn`; with a
+    /// strict catalog the first one is instead returned as
+    /// `VeritasError::ConfigError` naming the rule and the capability.
+    pub fn from_toml_str_validated(s: &str, catalog: &CapabilityCatalog) -> VeritasResult<Self> {
+        let engine = Self::from_toml_str(s)?;
+        for (rule_id, capability) in engine.config.unknown_capabilities(catalog) {
+            if catalog.is_strict() {
+                return Err(VeritasError::ConfigError {
+                    reason: format!(
+                        "rule '{}' requires capability '{}' which is not in the capability catalog",
+                        rule_id, capability
+                    ),
+                });
+            }
+            warn!(
+                rule_id = %rule_id,
+                capability = %capability,
+                "policy rule requires a capability absent from the catalog"
+            );
+        }
+        Ok(engine)
     }
 
     /// Read the file at `path` and parse it as TOML policy configuration.
@@ -61,6 +190,62 @@ impl TomlPolicyEngine {
         })?;
         Self::from_toml_str(&contents)
     }
+
+    /// The configuration this engine evaluates.
+    pub fn config(&self) -> &PolicyConfig {
+        &self.config
+    }
+
+    /// The capabilities the policy requires for `action` on `resource`.
+    ///
+    /// Returns the `required_capabilities` of the first rule matching the
+    /// pair — including any inherited from its group, then any from
+    /// `capability_conditions` matching `resource` — in declaration order,
+    /// without duplicates.  In restrictive capability mode the
+    /// requirements of every matching rule are included.  Empty when no rule
+    /// matches or the matching rules require nothing.  Lets a UI tell a user
+    /// up front what an action needs; it does not evaluate the rule's verdict,
+    /// and rules with a `metadata_match` or `action_kind` are considered as if
+    /// it held.
+    pub fn required_capabilities_for(&self, action: &str, resource: &str) -> Vec<String> {
+        let mut required: Vec<String> = Vec::new();
+        let deciding = self
+            .config
+            .rules
+            .iter()
+            .filter(|r| r.verdict != RuleVerdict::Continue && r.matches(action, resource));
+        for rule in deciding {
+            for cap in rule.capabilities_required_on(resource) {
+                if !required.contains(cap) {
+                    required.push(cap.clone());
+                }
+            }
+            if self.config.capability_mode == CapabilityMode::FirstMatch {
+                break;
+            }
+        }
+        required
+    }
+
+    /// Evaluate `ctx` like `PolicyEngine::evaluate` and explain the decision.
+    ///
+    /// The returned `PolicyTrace` lists every rule considered, in order, with
+    /// why it was skipped or how it was applied, plus the final verdict.
+    /// Like `evaluate`, it does not count toward rate limits.
+    pub fn evaluate_explain(&self, ctx: &PolicyContext) -> VeritasResult<PolicyTrace> {
+        Ok(self.explain(ctx, false))
+    }
+
+    /// Trace `ctx`, counting this evaluation toward its rule's rate limit
+    /// when `count` is set.
+    fn explain(&self, ctx: &PolicyContext, count: bool) -> PolicyTrace {
+        let rate = RateCheck {
+            store: self.rate_store.as_ref(),
+            clock: self.clock.as_ref(),
+            count,
+        };
+        explain_config(&self.config, ctx, Some(rate), &self.approver_resolvers)
+    }
 }
 
 impl PolicyEngine for TomlPolicyEngine {
@@ -73,92 +258,384 @@ impl PolicyEngine for TomlPolicyEngine {
     /// produces `Deny`, even for an `allow` rule.
     ///
     /// If no rule matches, returns `PolicyVerdict::Deny` with the message
-    /// "denied by default: no policy rule matched action '…' on resource '…'".
+    /// "denied by default: no policy rule matched action '…' on resource '…'",
+    /// or `PolicyVerdict::Allow` when the config sets `default_verdict = "allow"`.
+    ///
+    /// Does not count toward rate limits; only `decide` does.
     fn evaluate(&self, ctx: &PolicyContext) -> VeritasResult<PolicyVerdict> {
+        self.evaluate_explain(ctx).map(|trace| trace.verdict)
+    }
+
+    /// Evaluate `ctx` and report the deciding rule and, for a denial, its
+    /// `DenyCode` — taken from the same trace `evaluate_explain` returns.
+    /// The one evaluation that counts toward rate limits.
+    fn decide(&self, ctx: &PolicyContext) -> VeritasResult<PolicyDecision> {
+        Ok(self.explain(ctx, true).to_decision())
+    }
+
+    /// Decide `ctx` as `decide` does, without counting it toward rate limits.
+    fn decide_uncounted(&self, ctx: &PolicyContext) -> VeritasResult<PolicyDecision> {
+        Ok(self.explain(ctx, false).to_decision())
+    }
+}
+
+/// Evaluate `ctx` against `config` without building a `TomlPolicyEngine`.
+///
+/// Runs the same algorithm as `PolicyEngine::evaluate` but keeps no state:
+/// `max_invocations_per_window` is ignored, since there is nothing to count
+/// firings in.  `config` is used as given, so rules naming a group should be
+/// resolved with `PolicyConfig::resolve_groups` first.  No approver
+/// resolvers are registered, so `{name}` placeholders in approver roles are
+/// returned as written.  A pure function of its arguments, suited to fuzzing
+/// the matcher directly.
+pub fn evaluate_config(config: &PolicyConfig, ctx: &PolicyContext) -> PolicyVerdict {
+    explain_config(config, ctx, None, &HashMap::new()).verdict
+}
+
+/// Where `explain_config` checks rate limits, and whether the evaluation
+/// counts toward them.
+struct RateCheck<'a> {
+    store: &'a dyn RateCounterStore,
+    clock: &'a dyn Clock,
+    count: bool,
+}
+
+/// Evaluate `ctx` against `config` and trace the decision.  Rate limits are
+/// checked in `rate`'s store, in the window its clock reports, when given,
+/// and ignored otherwise; approver role placeholders are filled from
+/// `resolvers`.
+fn explain_config(
+    config: &PolicyConfig,
+    ctx: &PolicyContext,
+    rate: Option<RateCheck<'_>>,
+    resolvers: &HashMap<String, ApproverResolver>,
+) -> PolicyTrace {
+    debug!(
+        agent_id = %ctx.agent_id,
+        action = %ctx.action,
+        resource = %ctx.resource,
+        "evaluating policy"
+    );
+
+    let mut evaluations = Vec::new();
+    let mut tags: Vec<String> = Vec::new();
+
+    for (index, rule) in config.rules.iter().enumerate() {
+        if !rule.matches_action(&ctx.action) {
+            evaluations.push(RuleEvaluation {
+                rule_id: rule.id.clone(),
+                outcome: RuleOutcome::ActionMismatch,
+            });
+            continue;
+        }
+        if !rule.matches_action_kind(ctx.action_kind) {
+            evaluations.push(RuleEvaluation {
+                rule_id: rule.id.clone(),
+                outcome: RuleOutcome::ActionKindMismatch,
+            });
+            continue;
+        }
+        if !rule.matches_resource(&ctx.resource) {
+            evaluations.push(RuleEvaluation {
+                rule_id: rule.id.clone(),
+                outcome: RuleOutcome::ResourceMismatch,
+            });
+            continue;
+        }
+        if !rule.matches_metadata(&ctx.metadata) {
+            evaluations.push(RuleEvaluation {
+                rule_id: rule.id.clone(),
+                outcome: RuleOutcome::MetadataMismatch,
+            });
+            continue;
+        }
+
         debug!(
-            agent_id = %ctx.agent_id,
+            rule_id = %rule.id,
             action = %ctx.action,
             resource = %ctx.resource,
-            "evaluating policy"
+            "rule matched"
         );
 
-        for rule in &self.config.rules {
-            if !rule.matches(&ctx.action, &ctx.resource) {
-                continue;
+        // A continue rule only tags the request; a later rule decides.
+        if rule.verdict == RuleVerdict::Continue {
+            for tag in &rule.tags {
+                if !tags.contains(tag) {
+                    tags.push(tag.clone());
+                }
             }
+            evaluations.push(RuleEvaluation {
+                rule_id: rule.id.clone(),
+                outcome: RuleOutcome::Continued {
+                    tags: rule.tags.clone(),
+                },
+            });
+            continue;
+        }
 
-            debug!(
-                rule_id = %rule.id,
-                action = %ctx.action,
-                resource = %ctx.resource,
-                "rule matched"
-            );
-
-            // Defense-in-depth capability check: even a matching allow rule is
-            // overridden if the agent lacks a required capability.
-            for required_cap in &rule.required_capabilities {
-                if !ctx.capabilities.contains(required_cap) {
-                    warn!(
-                        rule_id = %rule.id,
-                        capability = %required_cap,
-                        agent_id = %ctx.agent_id,
-                        "matched rule requires capability agent does not hold"
-                    );
-                    return Ok(PolicyVerdict::Deny {
-                        reason: format!(
-                            "rule '{}' requires capability '{}' which is not granted to agent '{}'",
-                            rule.id, required_cap, ctx.agent_id
-                        ),
-                    });
-                }
+        // Defense-in-depth capability check: even a matching allow rule is
+        // overridden if the agent lacks a required capability.  In
+        // restrictive mode every later matching rule's requirements apply
+        // too, and a rule failing them becomes the deciding rule.
+        let mut checked = vec![rule];
+        if config.capability_mode == CapabilityMode::Restrictive {
+            checked.extend(config.rules.iter().skip(index + 1).filter(|r| {
+                r.verdict != RuleVerdict::Continue
+                    && r.matches(&ctx.action, &ctx.resource)
+                    && r.matches_action_kind(ctx.action_kind)
+                    && r.matches_metadata(&ctx.metadata)
+            }));
+        }
+        for checked_rule in checked {
+            if let Some(trace) = capability_override(checked_rule, ctx, &mut evaluations) {
+                return PolicyTrace { tags, ..trace };
+            }
+        }
+        if let Some(rate) = &rate {
+            if let Some(trace) = rate_limit_override(rule, ctx, rate, &mut evaluations) {
+                return PolicyTrace { tags, ..trace };
             }
+        }
 
-            // Capability check passed — convert RuleVerdict to PolicyVerdict.
-            let verdict = match rule.verdict {
-                RuleVerdict::Allow => PolicyVerdict::Allow,
+        // Capability check passed — convert RuleVerdict to PolicyVerdict.
+        let verdict = match rule.verdict {
+            RuleVerdict::Allow => PolicyVerdict::Allow,
 
-                RuleVerdict::Deny => PolicyVerdict::Deny {
-                    reason: rule
-                        .deny_reason
-                        .clone()
-                        .unwrap_or_else(|| format!("denied by rule '{}'", rule.id)),
-                },
+            RuleVerdict::Deny => PolicyVerdict::Deny {
+                reason: rule
+                    .deny_reason
+                    .clone()
+                    .unwrap_or_else(|| format!("denied by rule '{}'", rule.id)),
+                remediation: rule.remediation.clone(),
+            },
 
-                RuleVerdict::RequireApproval => PolicyVerdict::RequireApproval {
-                    reason: rule
-                        .approval_reason
-                        .clone()
-                        .unwrap_or_else(|| format!("approval required by rule '{}'", rule.id)),
-                    approver_role: rule
-                        .approver_role
-                        .clone()
-                        .unwrap_or_else(|| "unspecified".to_string()),
-                },
+            RuleVerdict::RequireApproval => PolicyVerdict::RequireApproval {
+                reason: rule
+                    .approval_reason
+                    .clone()
+                    .unwrap_or_else(|| format!("approval required by rule '{}'", rule.id)),
+                approver_role: approver_role_for(rule, &ctx.resource, resolvers),
+                required_attributes: rule.approver_attributes.clone(),
+            },
 
-                RuleVerdict::RequireVerification => PolicyVerdict::RequireVerification {
-                    check_id: rule
-                        .verification_check_id
-                        .clone()
-                        .unwrap_or_else(|| format!("check-{}", rule.id)),
-                },
-            };
+            RuleVerdict::RequireVerification => PolicyVerdict::RequireVerification {
+                check_id: rule
+                    .verification_check_id
+                    .clone()
+                    .unwrap_or_else(|| format!("check-{}", rule.id)),
+            },
+
+            RuleVerdict::RequireBoth => PolicyVerdict::RequireBoth {
+                reason: rule.approval_reason.clone().unwrap_or_else(|| {
+                    format!("verification and approval required by rule '{}'", rule.id)
+                }),
+                approver_role: approver_role_for(rule, &ctx.resource, resolvers),
+                required_attributes: rule.approver_attributes.clone(),
+                check_ids: require_both_checks(rule),
+            },
+
+            RuleVerdict::Continue => unreachable!("continue rules never decide"),
+        };
 
-            return Ok(verdict);
+        evaluations.push(RuleEvaluation {
+            rule_id: rule.id.clone(),
+            outcome: RuleOutcome::Fired,
+        });
+        return PolicyTrace {
+            evaluations,
+            verdict,
+            tags,
+        };
+    }
+
+    // No rule matched — apply the default verdict.
+    let verdict = match config.default_verdict {
+        DefaultVerdict::Deny => {
+            warn!(
+                action = %ctx.action,
+                resource = %ctx.resource,
+                agent_id = %ctx.agent_id,
+                "no policy rule matched; denying by default"
+            );
+            PolicyVerdict::Deny {
+                reason: format!(
+                    "denied by default: no policy rule matched action '{}' on resource '{}'",
+                    ctx.action, ctx.resource
+                ),
+                remediation: None,
+            }
+        }
+        DefaultVerdict::Allow => {
+            warn!(
+                action = %ctx.action,
+                resource = %ctx.resource,
+                agent_id = %ctx.agent_id,
+                "FAIL-OPEN: no policy rule matched; allowing by default \
+                 (default_verdict = \"allow\" must not be used in production)"
+            );
+            PolicyVerdict::Allow
         }
+    };
 
-        // No rule matched — deny by default.
-        warn!(
-            action = %ctx.action,
-            resource = %ctx.resource,
-            agent_id = %ctx.agent_id,
-            "no policy rule matched; denying by default"
-        );
+    PolicyTrace {
+        evaluations,
+        verdict,
+        tags,
+    }
+}
 
-        Ok(PolicyVerdict::Deny {
+/// Count this firing of `rule` if it is rate-limited and `rate` counts —
+/// otherwise only look at what the count would become — and return the
+/// deny trace if that takes the agent past the rule's limit for the
+/// current window, recording the override in `evaluations`.
+fn rate_limit_override(
+    rule: &PolicyRule,
+    ctx: &PolicyContext,
+    rate: &RateCheck<'_>,
+    evaluations: &mut Vec<RuleEvaluation>,
+) -> Option<PolicyTrace> {
+    let max_invocations = rule.max_invocations_per_window?;
+    let window_secs = rule.window_secs.unwrap_or(DEFAULT_WINDOW_SECS);
+    let key = window_key(&rule.id, &ctx.agent_id, window_secs, rate.clock.now());
+    let count = if rate.count {
+        rate.store.increment(&key)
+    } else {
+        rate.store.get(&key) + 1
+    };
+    if count <= max_invocations {
+        return None;
+    }
+    warn!(
+        rule_id = %rule.id,
+        agent_id = %ctx.agent_id,
+        count,
+        max_invocations,
+        "matched rule exceeded its rate limit"
+    );
+    evaluations.push(RuleEvaluation {
+        rule_id: rule.id.clone(),
+        outcome: RuleOutcome::RateLimited {
+            max_invocations,
+            window_secs,
+        },
+    });
+    Some(PolicyTrace {
+        evaluations: std::mem::take(evaluations),
+        verdict: PolicyVerdict::Deny {
             reason: format!(
-                "denied by default: no policy rule matched action '{}' on resource '{}'",
-                ctx.action, ctx.resource
+                "rule '{}' allows at most {} invocations per {}s window and agent '{}' has reached the limit",
+                rule.id, max_invocations, window_secs, ctx.agent_id
             ),
-        })
+            remediation: rule.remediation.clone(),
+        },
+        tags: Vec::new(),
+    })
+}
+
+/// The deny trace for `rule` if the agent lacks one of the capabilities it
+/// requires on `ctx.resource`, recording the override in `evaluations`.
+fn capability_override(
+    rule: &PolicyRule,
+    ctx: &PolicyContext,
+    evaluations: &mut Vec<RuleEvaluation>,
+) -> Option<PolicyTrace> {
+    let required_cap = rule
+        .capabilities_required_on(&ctx.resource)
+        .find(|cap| !capability_satisfied(cap, &ctx.capabilities))?;
+    warn!(
+        rule_id = %rule.id,
+        capability = %required_cap,
+        agent_id = %ctx.agent_id,
+        "matched rule requires capability agent does not hold"
+    );
+    evaluations.push(RuleEvaluation {
+        rule_id: rule.id.clone(),
+        outcome: RuleOutcome::CapabilityOverride {
+            missing_capability: required_cap.clone(),
+        },
+    });
+    Some(PolicyTrace {
+        evaluations: std::mem::take(evaluations),
+        verdict: PolicyVerdict::Deny {
+            reason: format!(
+                "rule '{}' requires capability '{}' which is not granted to agent '{}'",
+                rule.id, required_cap, ctx.agent_id
+            ),
+            remediation: rule.remediation.clone(),
+        },
+        tags: Vec::new(),
+    })
+}
+
+/// Return true if `held` satisfies the `required` capability.
+///
+/// A requirement ending in `.*` is a namespace wildcard: `drug-database.*` is
+/// satisfied by `drug-database.read` or `drug-database.write`, but not by
+/// `drug-database` itself or `drug-databases.read`.  Anything else must be
+/// held exactly.
+fn capability_satisfied(required: &str, held: &[String]) -> bool {
+    match required.strip_suffix(".*") {
+        Some(namespace) => held.iter().any(|cap| {
+            cap.strip_prefix(namespace)
+                .is_some_and(|rest| rest.starts_with('.') && rest.len() > 1)
+        }),
+        None => held.iter().any(|cap| cap == required),
+    }
+}
+
+/// The checks a require-both `rule` runs: its `verification_check_id`, if
+/// set, then each of its `verification_check_ids` not already listed.  A
+/// rule naming neither runs `"check-<rule id>"`.
+fn require_both_checks(rule: &PolicyRule) -> Vec<String> {
+    let mut check_ids: Vec<String> = rule.verification_check_id.iter().cloned().collect();
+    for check_id in &rule.verification_check_ids {
+        if !check_ids.contains(check_id) {
+            check_ids.push(check_id.clone());
+        }
+    }
+    if check_ids.is_empty() {
+        check_ids.push(format!("check-{}", rule.id));
+    }
+    check_ids
+}
+
+/// The approver role `rule` requires for `resource`: its `approver_role`
+/// (`"unspecified"` when unset) with every `{name}` placeholder replaced by
+/// the resolver registered under `name`.
+///
+/// A placeholder without a resolver is kept as written and logged at `warn`;
+/// no approver holds a role like `"{specialty}"`, so the approval fails
+/// closed until the resolver is registered.
+fn approver_role_for(
+    rule: &PolicyRule,
+    resource: &str,
+    resolvers: &HashMap<String, ApproverResolver>,
+) -> String {
+    let Some(template) = rule.approver_role.as_deref() else {
+        return "unspecified".to_string();
+    };
+    let mut role = String::new();
+    let mut rest = template;
+    while let Some((before, after)) = rest.split_once('{') {
+        let Some((name, tail)) = after.split_once('}') else {
+            break;
+        };
+        role.push_str(before);
+        match resolvers.get(name) {
+            Some(resolve) => role.push_str(&resolve(resource)),
+            None => {
+                warn!(
+                    rule_id = %rule.id,
+                    resolver = %name,
+                    "no approver resolver registered; placeholder left unresolved"
+                );
+                role.push('{');
+                role.push_str(name);
+                role.push('}');
+            }
+        }
+        rest = tail;
     }
+    role.push_str(rest);
+    role
 }
@@ -7,7 +7,9 @@
 //! This crate provides [`TomlPolicyEngine`], which implements the
 //! [`PolicyEngine`](veritas_core::traits::PolicyEngine) trait.  Rules are
 //! declared in a TOML file, evaluated in order, and the first matching rule
-//! wins.  If no rule matches, the request is denied.
+//! wins.  If no rule matches, the request is denied — unless the policy sets
+//! the top-level `default_verdict = "allow"`, a fail-open mode meant only
+//! for development that logs a warning on every default-allow.
 //!
 //! ## Quick start
 //!
@@ -22,23 +24,92 @@
 //! ## Rule matching
 //!
 //! Each rule specifies an `action` and `resource` pattern.  Both support the
-//! wildcard `"*"` which matches any value.  Rules are applied in declaration
-//! order; the first match wins.
+//! wildcard `"*"` which matches any value.  A resource may also be
+//! `"prefix/**"` (everything under `prefix/`) or be negated with a leading
+//! `!` — `"!patient-records-no-consent"` matches every resource but that
+//! one.  Rules are applied in declaration order; the first match wins.
+//!
+//! A rule's `required_capabilities` may name a whole namespace with a trailing
+//! `.*` — `"drug-database.*"` is satisfied by any `drug-database.` capability.
+//! Rules sharing requirements can name a `[[groups]]` entry in their `group`
+//! field and inherit its `required_capabilities` at load time.  A rule with
+//! `metadata_match = { urgency = "routine" }` only matches when the agent's
+//! `Agent::action_metadata` carries those key/value pairs, and one with
+//! `action_kind = "write"` only when the action is classified as a write or a
+//! delete (`Agent::describe_action_kind`, inferred from the action's leading
+//! verb by default).  Kinds are `read`, `write`, `delete` and `execute`.  Repeated
+//! `[[rules.capability_conditions]]` entries add requirements that apply only
+//! when the resource matches their own `resource` pattern.
+//!
+//! `TomlPolicyEngine::from_toml_str_validated` also checks every required
+//! capability against a `CapabilityCatalog`, warning about — or, with a
+//! strict catalog, rejecting — names the catalog does not know.  With the
+//! top-level `capability_mode = "restrictive"`, the requirements of every
+//! matching rule apply, not only those of the rule that decides the verdict.
+//!
+//! ## Continue rules
+//!
+//! A rule with `verdict = "continue"` does not decide: when it matches, its
+//! `tags` are added to the decision and evaluation carries on, so a logging
+//! or tagging rule can sit in front of the rule that allows.  The tags
+//! accumulated on the way reach `PolicyTrace::tags` and
+//! `PolicyDecision::tags`.  A policy with only `continue` rules denies by
+//! default, like one with no rules at all.
+//!
+//! ## Rate limits
+//!
+//! A rule with `max_invocations_per_window = 3` (and optionally
+//! `window_secs`, default 60) fires at most three times per agent in each
+//! window; further matches are denied with `DenyCode::RateLimited`.  The
+//! counts live in a [`RateCounterStore`] — in memory by default, or shared
+//! through `TomlPolicyEngine::with_rate_store`.  Only `decide`, the call
+//! `Executor::step()` enforces, counts; `evaluate` and `evaluate_explain`
+//! report whether the next firing would be allowed without spending it.
+//!
+//! ## Approver resolution
+//!
+//! The appropriate approver can depend on the resource: with
+//! `approver_role = "{specialty}"`, the role comes from the resolver
+//! registered with `TomlPolicyEngine::register_approver_resolver("specialty",
+//! ...)`, which maps `cardiac-mri` to `cardiologist`, say, and anything else
+//! to `attending-physician`.
+//!
+//! ## Explaining decisions
+//!
+//! `TomlPolicyEngine::evaluate_explain` returns a [`PolicyTrace`] recording
+//! why each rule was skipped or fired; `PolicyTrace::to_json` renders it for
+//! auditors.
+//!
+//! ## Fuzzing
+//!
+//! [`evaluate_config`] evaluates a context against a `PolicyConfig` directly,
+//! as a pure function with no engine, TOML or rate-limit state.  The `fuzz`
+//! feature implements `arbitrary::Arbitrary` for `PolicyContext`, so a fuzz
+//! target can feed it generated contexts.
 
 pub mod engine;
+pub mod rate;
 pub mod rule;
+pub mod trace;
 
-pub use engine::TomlPolicyEngine;
-pub use rule::{PolicyConfig, PolicyRule, RuleVerdict};
+pub use engine::{evaluate_config, ApproverResolver, TomlPolicyEngine};
+pub use rate::{InMemoryRateCounterStore, RateCounterStore};
+pub use rule::{
+    CapabilityCondition, CapabilityMode, DefaultVerdict, PolicyConfig, PolicyGroup, PolicyRule,
+    RuleVerdict,
+};
+pub use trace::{PolicyTrace, RuleEvaluation, RuleOutcome};
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
-    use veritas_contracts::policy::{PolicyContext, PolicyVerdict};
+    use veritas_contracts::policy::{ActionKind, PolicyContext, PolicyVerdict};
     use veritas_core::traits::PolicyEngine;
 
-    use crate::TomlPolicyEngine;
+    use crate::{
+        DefaultVerdict, PolicyConfig, PolicyRule, RuleOutcome, RuleVerdict, TomlPolicyEngine,
+    };
 
     // ── Helpers ───────────────────────────────────────────────────────────────
 
@@ -51,6 +122,7 @@ mod tests {
             current_phase: "active".to_string(),
             action: action.to_string(),
             resource: resource.to_string(),
+            action_kind: ActionKind::infer(action),
             capabilities: capabilities.iter().map(|s| s.to_string()).collect(),
             metadata: serde_json::Value::Null,
         }
@@ -69,7 +141,7 @@ mod tests {
         let verdict = engine.evaluate(&ctx("read_record", "patient/42", &[])).unwrap();
 
         match verdict {
-            PolicyVerdict::Deny { reason } => {
+            PolicyVerdict::Deny { reason, .. } => {
                 assert!(
                     reason.contains("denied by default"),
                     "expected 'denied by default' in reason, got: {reason}"
@@ -118,7 +190,7 @@ mod tests {
         let verdict = engine.evaluate(&ctx("delete_record", "patient/99", &[])).unwrap();
 
         match verdict {
-            PolicyVerdict::Deny { reason } => {
+            PolicyVerdict::Deny { reason, .. } => {
                 assert!(
                     reason.contains("deletion of patient records is prohibited"),
                     "unexpected reason: {reason}"
@@ -149,12 +221,13 @@ mod tests {
         let verdict = engine.evaluate(&ctx("prescribe_medication", "patient/7", &[])).unwrap();
 
         match verdict {
-            PolicyVerdict::RequireApproval { reason, approver_role } => {
+            PolicyVerdict::RequireApproval { reason, approver_role, required_attributes } => {
                 assert!(
                     reason.contains("high-risk prescription"),
                     "unexpected reason: {reason}"
                 );
                 assert_eq!(approver_role, "attending_physician");
+                assert!(required_attributes.is_empty());
             }
             other => panic!("expected RequireApproval, got {:?}", other),
         }
@@ -198,7 +271,7 @@ mod tests {
         // Wildcard action: an action not matched by the first rule falls through
         // to the wildcard action rule.
         match engine.evaluate(&ctx("update_record", "patient/1", &[])).unwrap() {
-            PolicyVerdict::Deny { reason } => {
+            PolicyVerdict::Deny { reason, .. } => {
                 assert!(reason.contains("write operations are not permitted"));
             }
             other => panic!("expected Deny from wildcard action rule, got {:?}", other),
@@ -257,7 +330,7 @@ mod tests {
         let verdict = engine.evaluate(&ctx("read_phi", "patient/33", &[])).unwrap();
 
         match verdict {
-            PolicyVerdict::Deny { reason } => {
+            PolicyVerdict::Deny { reason, .. } => {
                 assert!(
                     reason.contains("phi:read"),
                     "deny reason should mention the missing capability: {reason}"
@@ -293,4 +366,1388 @@ mod tests {
             other => panic!("expected ConfigError, got {:?}", other),
         }
     }
+
+    // ── 9. capability namespace wildcard ──────────────────────────────────────
+
+    /// A required capability ending in `.*` is satisfied by any capability in
+    /// that namespace, and only by those.
+    #[test]
+    fn test_capability_namespace_wildcard() {
+        let toml = r#"
+            [[rules]]
+            id = "allow-drug-lookup"
+            description = "Any drug-database capability grants lookups"
+            action = "drug-interaction-check"
+            resource = "drug-database"
+            required_capabilities = ["drug-database.*"]
+            verdict = "allow"
+        "#;
+
+        let engine = TomlPolicyEngine::from_toml_str(toml).unwrap();
+
+        let verdict = engine
+            .evaluate(&ctx("drug-interaction-check", "drug-database", &["drug-database.read"]))
+            .unwrap();
+        assert_eq!(verdict, PolicyVerdict::Allow);
+
+        let verdict = engine
+            .evaluate(&ctx("drug-interaction-check", "drug-database", &["patient-records.read"]))
+            .unwrap();
+        match verdict {
+            PolicyVerdict::Deny { reason, .. } => {
+                assert!(
+                    reason.contains("drug-database.*"),
+                    "deny reason should name the wildcard requirement: {reason}"
+                );
+            }
+            other => panic!("expected Deny without a drug-database capability, got {:?}", other),
+        }
+
+        // The bare namespace is not a capability under it.
+        let verdict = engine
+            .evaluate(&ctx("drug-interaction-check", "drug-database", &["drug-database"]))
+            .unwrap();
+        assert!(matches!(verdict, PolicyVerdict::Deny { .. }));
+    }
+
+    // ── 10. TOML round-trip ───────────────────────────────────────────────────
+
+    /// A config built in code serializes to TOML that loads back into an
+    /// equal config, with unset optional fields left out of the document.
+    #[test]
+    fn test_to_toml_string_round_trip() {
+        let rule = |id: &str, verdict: RuleVerdict| PolicyRule {
+            id: id.to_string(),
+            description: format!("rule {id}"),
+            action: "read_record".to_string(),
+            resource: "*".to_string(),
+            action_kind: None,
+            required_capabilities: vec![],
+            capability_conditions: vec![],
+            group: None,
+            verdict,
+            deny_reason: None,
+            remediation: None,
+            approval_reason: None,
+            approver_role: None,
+            approver_attributes: Default::default(),
+            verification_check_id: None,
+            verification_check_ids: vec![],
+            metadata_match: None,
+            max_invocations_per_window: None,
+            window_secs: None,
+            tags: vec![],
+        };
+
+        let config = PolicyConfig {
+            default_verdict: DefaultVerdict::Deny,
+            capability_mode: Default::default(),
+            groups: vec![],
+            rules: vec![
+                PolicyRule {
+                    required_capabilities: vec!["phi:read".to_string()],
+                    ..rule("allow-read", RuleVerdict::Allow)
+                },
+                PolicyRule {
+                    deny_reason: Some("deletion is prohibited".to_string()),
+                    ..rule("deny-delete", RuleVerdict::Deny)
+                },
+                PolicyRule {
+                    approval_reason: Some("high-risk prescription".to_string()),
+                    approver_role: Some("attending_physician".to_string()),
+                    ..rule("approve-prescribe", RuleVerdict::RequireApproval)
+                },
+            ],
+        };
+
+        let toml = config.to_toml_string().unwrap();
+
+        assert!(!toml.contains("verification_check_id"), "None fields must be omitted:\n{toml}");
+        assert_eq!(toml.matches("deny_reason").count(), 1, "only the deny rule sets deny_reason:\n{toml}");
+        assert_eq!(toml.matches("approver_role").count(), 1, "only one rule sets approver_role:\n{toml}");
+
+        let engine = TomlPolicyEngine::from_toml_str(&toml).unwrap();
+        assert_eq!(engine.config(), &config);
+    }
+
+    // ── 11. explain trace ─────────────────────────────────────────────────────
+
+    /// The JSON trace records each skipped rule with its skip reason, then
+    /// the rule that fired and the final verdict.
+    #[test]
+    fn test_evaluate_explain_json_trace() {
+        let toml = r#"
+[[rules]]
+id = "deny-delete"
+description = "Deletion is never allowed"
+action = "delete_record"
+resource = "*"
+verdict = "deny"
+deny_reason = "deletion is prohibited"
+
+[[rules]]
+id = "allow-read-labs"
+description = "Lab results may be read"
+action = "read_record"
+resource = "lab-results"
+verdict = "allow"
+
+[[rules]]
+id = "allow-read-notes"
+description = "Clinical notes may be read with notes.read"
+action = "read_record"
+resource = "clinical-notes"
+required_capabilities = ["notes.read"]
+verdict = "allow"
+
+[[rules]]
+id = "never-reached"
+description = "Shadowed by the rule above"
+action = "*"
+resource = "*"
+verdict = "deny"
+"#;
+        let engine = TomlPolicyEngine::from_toml_str(toml).unwrap();
+
+        let trace = engine
+            .evaluate_explain(&ctx("read_record", "clinical-notes", &["notes.read"]))
+            .unwrap();
+        let json = trace.to_json();
+
+        assert_eq!(
+            json["rules"],
+            serde_json::json!([
+                { "rule_id": "deny-delete", "outcome": "action-mismatch" },
+                { "rule_id": "allow-read-labs", "outcome": "resource-mismatch" },
+                { "rule_id": "allow-read-notes", "outcome": "fired" }
+            ])
+        );
+        assert_eq!(json["matched_rule"], "allow-read-notes");
+        assert_eq!(json["verdict"], "Allow");
+
+        // A missing capability shows up as an override of the matching rule.
+        let trace = engine
+            .evaluate_explain(&ctx("read_record", "clinical-notes", &[]))
+            .unwrap();
+        let json = trace.to_json();
+        assert_eq!(
+            json["rules"][2],
+            serde_json::json!({
+                "rule_id": "allow-read-notes",
+                "outcome": "capability-override",
+                "missing_capability": "notes.read"
+            })
+        );
+        assert!(matches!(trace.verdict, PolicyVerdict::Deny { .. }));
+
+        // `evaluate` returns exactly the traced verdict.
+        let context = ctx("read_record", "lab-results", &[]);
+        assert_eq!(
+            engine.evaluate(&context).unwrap(),
+            engine.evaluate_explain(&context).unwrap().verdict
+        );
+    }
+
+    // ── 12. default verdict ───────────────────────────────────────────────────
+
+    /// An explicit `default_verdict = "deny"` behaves exactly like omitting it.
+    #[test]
+    fn test_default_verdict_deny_unchanged() {
+        let explicit = TomlPolicyEngine::from_toml_str(
+            r#"
+            default_verdict = "deny"
+            rules = []
+        "#,
+        )
+        .unwrap();
+        let implicit = TomlPolicyEngine::from_toml_str("rules = []").unwrap();
+
+        assert_eq!(explicit.config().default_verdict, DefaultVerdict::Deny);
+        assert_eq!(explicit.config(), implicit.config());
+
+        let verdict = explicit.evaluate(&ctx("read_record", "patient/42", &[])).unwrap();
+        assert!(matches!(verdict, PolicyVerdict::Deny { .. }));
+    }
+
+    /// With `default_verdict = "allow"`, an unmatched request is allowed —
+    /// matched rules still apply — and each default-allow logs a warning.
+    #[test]
+    fn test_default_verdict_allow_warns() {
+        use veritas_core::testing::SpanRecorder;
+
+        let toml = r#"
+            default_verdict = "allow"
+
+            [[rules]]
+            id = "deny-delete"
+            description = "Deletion is never allowed"
+            action = "delete_record"
+            resource = "*"
+            verdict = "deny"
+        "#;
+        let engine = TomlPolicyEngine::from_toml_str(toml).unwrap();
+
+        let recorder = SpanRecorder::default();
+
+        let (unmatched, matched) = tracing::subscriber::with_default(recorder.clone(), || {
+            (
+                engine.evaluate(&ctx("read_record", "patient/42", &[])).unwrap(),
+                engine.evaluate(&ctx("delete_record", "patient/42", &[])).unwrap(),
+            )
+        });
+
+        assert_eq!(unmatched, PolicyVerdict::Allow);
+        assert!(matches!(matched, PolicyVerdict::Deny { .. }));
+
+        let messages = recorder.messages_at(tracing::Level::WARN);
+        assert_eq!(messages.len(), 1, "exactly one default-allow warning: {:?}", messages);
+        assert!(messages[0].contains("FAIL-OPEN"), "got: {}", messages[0]);
+    }
+
+    // ── 13. batch evaluation ──────────────────────────────────────────────────
+
+    /// `evaluate_all` returns one verdict per context, in input order, each
+    /// identical to what `evaluate` returns for that context alone.
+    #[test]
+    fn test_evaluate_all_matches_individual_evaluation() {
+        let toml = r#"
+            [[rules]]
+            id = "approve-prescribe"
+            description = "Prescriptions require physician approval"
+            action = "prescribe_medication"
+            resource = "*"
+            verdict = "require-approval"
+            approval_reason = "prescription requires sign-off"
+            approver_role = "attending_physician"
+
+            [[rules]]
+            id = "phi-read-allow"
+            description = "PHI reads need phi:read"
+            action = "read_record"
+            resource = "*"
+            verdict = "allow"
+            required_capabilities = ["phi:read"]
+        "#;
+        let engine = TomlPolicyEngine::from_toml_str(toml).unwrap();
+
+        let contexts = [
+            ctx("read_record", "patient/1", &["phi:read"]),
+            ctx("read_record", "patient/1", &[]),
+            ctx("prescribe_medication", "patient/1", &[]),
+            ctx("delete_record", "patient/1", &["phi:read"]),
+        ];
+
+        let batch = engine.evaluate_all(&contexts).unwrap();
+        let individual: Vec<PolicyVerdict> =
+            contexts.iter().map(|c| engine.evaluate(c).unwrap()).collect();
+
+        assert_eq!(batch, individual);
+        assert_eq!(batch[0], PolicyVerdict::Allow);
+        assert!(matches!(batch[1], PolicyVerdict::Deny { .. }));
+        assert!(matches!(batch[2], PolicyVerdict::RequireApproval { .. }));
+        assert!(matches!(batch[3], PolicyVerdict::Deny { .. }));
+    }
+
+    // ── 14. rule groups ───────────────────────────────────────────────────────
+
+    /// A rule inherits its group's capabilities: it allows only when the
+    /// agent holds both the inherited and its own capability.
+    #[test]
+    fn test_rule_inherits_group_capabilities() {
+        let toml = r#"
+            [[groups]]
+            name = "clinical-readers"
+            required_capabilities = ["clinical-data.read"]
+
+            [[rules]]
+            id = "allow-analysis"
+            description = "Analysis needs clinical data and the analysis scope"
+            action = "analyze"
+            resource = "*"
+            group = "clinical-readers"
+            required_capabilities = ["analysis.run"]
+            verdict = "allow"
+        "#;
+        let engine = TomlPolicyEngine::from_toml_str(toml).unwrap();
+
+        assert_eq!(
+            engine.config().rules[0].required_capabilities,
+            vec!["analysis.run".to_string(), "clinical-data.read".to_string()]
+        );
+        assert_eq!(
+            engine
+                .evaluate(&ctx("analyze", "symptoms", &["analysis.run", "clinical-data.read"]))
+                .unwrap(),
+            PolicyVerdict::Allow
+        );
+        assert!(matches!(
+            engine.evaluate(&ctx("analyze", "symptoms", &["analysis.run"])).unwrap(),
+            PolicyVerdict::Deny { .. }
+        ));
+
+        // Serializing and reloading does not duplicate inherited capabilities.
+        let reloaded =
+            TomlPolicyEngine::from_toml_str(&engine.config().to_toml_string().unwrap()).unwrap();
+        assert_eq!(reloaded.config(), engine.config());
+    }
+
+    /// A rule naming a group that is not defined is a configuration error.
+    #[test]
+    fn test_unknown_group_is_rejected() {
+        let toml = r#"
+            [[rules]]
+            id = "allow-analysis"
+            description = "References a group that does not exist"
+            action = "analyze"
+            resource = "*"
+            group = "no-such-group"
+            verdict = "allow"
+        "#;
+
+        match TomlPolicyEngine::from_toml_str(toml) {
+            Err(veritas_contracts::error::VeritasError::ConfigError { reason }) => {
+                assert!(reason.contains("no-such-group"), "got: {reason}");
+                assert!(reason.contains("allow-analysis"), "got: {reason}");
+            }
+            other => panic!("expected ConfigError, got {:?}", other),
+        }
+    }
+
+    // ── 15. approver attributes ───────────────────────────────────────────────
+
+    /// `approver_attributes` on a require-approval rule are carried into the
+    /// verdict as `required_attributes`.
+    #[test]
+    fn test_approver_attributes_flow_into_verdict() {
+        let toml = r#"
+            [[rules]]
+            id = "approve-cardiac"
+            description = "Cardiac procedures need a cardiology attending"
+            action = "order_procedure"
+            resource = "cardiac-mri"
+            verdict = "require-approval"
+            approval_reason = "high-cost cardiac imaging"
+            approver_role = "attending-physician"
+            approver_attributes = { specialty = "cardiology" }
+        "#;
+
+        let engine = TomlPolicyEngine::from_toml_str(toml).unwrap();
+        let verdict = engine.evaluate(&ctx("order_procedure", "cardiac-mri", &[])).unwrap();
+
+        match verdict {
+            PolicyVerdict::RequireApproval { approver_role, required_attributes, .. } => {
+                assert_eq!(approver_role, "attending-physician");
+                assert_eq!(
+                    required_attributes.get("specialty").map(String::as_str),
+                    Some("cardiology")
+                );
+            }
+            other => panic!("expected RequireApproval, got {:?}", other),
+        }
+    }
+
+    // ── 16. capability introspection ──────────────────────────────────────────
+
+    /// `required_capabilities_for` reports the matching rule's requirements,
+    /// inherited group capabilities included, and nothing for an unmatched
+    /// action.
+    #[test]
+    fn test_required_capabilities_for() {
+        let toml = r#"
+            [[groups]]
+            name = "phi-readers"
+            required_capabilities = ["phi:read"]
+
+            [[rules]]
+            id = "allow-export"
+            description = "Exports need PHI access and the export scope"
+            action = "export_record"
+            resource = "*"
+            group = "phi-readers"
+            required_capabilities = ["records.export"]
+            verdict = "allow"
+
+            [[rules]]
+            id = "allow-ping"
+            description = "Health checks need nothing"
+            action = "ping"
+            resource = "*"
+            verdict = "allow"
+        "#;
+        let engine = TomlPolicyEngine::from_toml_str(toml).unwrap();
+
+        assert_eq!(
+            engine.required_capabilities_for("export_record", "patient/7"),
+            vec!["records.export".to_string(), "phi:read".to_string()]
+        );
+        assert!(engine.required_capabilities_for("ping", "service").is_empty());
+        assert!(engine.required_capabilities_for("delete_record", "patient/7").is_empty());
+    }
+
+    // ── 17. restrictive capability mode ───────────────────────────────────────
+
+    /// Two rules match; only the second requires a capability the agent
+    /// lacks.  First-match mode applies the first rule's allow, restrictive
+    /// mode denies on the second rule's missing capability.
+    #[test]
+    fn test_restrictive_mode_checks_every_matching_rule() {
+        let rules = r#"
+            [[rules]]
+            id = "allow-read"
+            description = "Reads are allowed"
+            action = "read_record"
+            resource = "*"
+            verdict = "allow"
+
+            [[rules]]
+            id = "phi-guard"
+            description = "Any access to records needs PHI clearance"
+            action = "*"
+            resource = "*"
+            required_capabilities = ["phi:read"]
+            verdict = "allow"
+        "#;
+        let request = ctx("read_record", "patient/9", &[]);
+
+        let first_match = TomlPolicyEngine::from_toml_str(rules).unwrap();
+        assert_eq!(first_match.evaluate(&request).unwrap(), PolicyVerdict::Allow);
+
+        let restrictive =
+            TomlPolicyEngine::from_toml_str(&format!("capability_mode = \"restrictive\"\n{rules}"))
+                .unwrap();
+        let trace = restrictive.evaluate_explain(&request).unwrap();
+        match &trace.verdict {
+            PolicyVerdict::Deny { reason, .. } => {
+                assert!(reason.contains("phi-guard"), "reason: {reason}");
+                assert!(reason.contains("phi:read"), "reason: {reason}");
+            }
+            other => panic!("expected Deny in restrictive mode, got {:?}", other),
+        }
+        assert_eq!(trace.matched_rule(), Some("phi-guard"));
+        assert_eq!(
+            restrictive.required_capabilities_for("read_record", "patient/9"),
+            vec!["phi:read".to_string()]
+        );
+
+        // Holding the capability satisfies every matching rule; the first
+        // rule's verdict applies again.
+        let cleared = ctx("read_record", "patient/9", &["phi:read"]);
+        assert_eq!(restrictive.evaluate(&cleared).unwrap(), PolicyVerdict::Allow);
+    }
+
+    // ── 18. metadata matching ─────────────────────────────────────────────────
+
+    /// A metadata-scoped rule applies only when the context's metadata holds
+    /// its pairs; otherwise it is skipped and evaluation falls through.
+    #[test]
+    fn test_metadata_match_scopes_rule() {
+        let toml = r#"
+            [[rules]]
+            id = "deny-routine-elective"
+            description = "Routine elective procedures are not scheduled by agents"
+            action = "schedule_procedure"
+            resource = "*"
+            verdict = "deny"
+            deny_reason = "routine elective procedures need a human scheduler"
+            metadata_match = { urgency = "routine" }
+
+            [[rules]]
+            id = "allow-schedule"
+            description = "Other procedures may be scheduled"
+            action = "schedule_procedure"
+            resource = "*"
+            verdict = "allow"
+        "#;
+        let engine = TomlPolicyEngine::from_toml_str(toml).unwrap();
+        let with_metadata = |metadata: serde_json::Value| PolicyContext {
+            metadata,
+            ..ctx("schedule_procedure", "procedure/knee-mri", &[])
+        };
+
+        let routine = engine
+            .evaluate_explain(&with_metadata(serde_json::json!({ "urgency": "routine" })))
+            .unwrap();
+        assert!(matches!(routine.verdict, PolicyVerdict::Deny { .. }));
+        assert_eq!(routine.matched_rule(), Some("deny-routine-elective"));
+
+        for metadata in [
+            serde_json::json!({ "urgency": "emergent" }),
+            serde_json::Value::Null,
+        ] {
+            let trace = engine.evaluate_explain(&with_metadata(metadata)).unwrap();
+            assert_eq!(trace.verdict, PolicyVerdict::Allow);
+            assert_eq!(trace.evaluations[0].outcome, RuleOutcome::MetadataMismatch);
+        }
+
+        // A non-table metadata_match is a configuration error.
+        let bad = toml.replace(r#"{ urgency = "routine" }"#, r#""routine""#);
+        assert!(TomlPolicyEngine::from_toml_str(&bad).is_err());
+    }
+
+    // ── 19. negated resource patterns ─────────────────────────────────────────
+
+    /// A `!` resource matches everything the un-negated pattern does not, so
+    /// one allow rule can carve out a single resource or a whole subtree.
+    #[test]
+    fn test_negated_resource_excludes_one_resource() {
+        let toml = r#"
+            [[rules]]
+            id = "allow-read-consented"
+            description = "Reads are allowed everywhere except unconsented records"
+            action = "read"
+            resource = "!patient-records-no-consent"
+            verdict = "allow"
+        "#;
+        let engine = TomlPolicyEngine::from_toml_str(toml).unwrap();
+
+        for resource in ["patient-records", "lab-results", "patient-records-no-consent-archive"] {
+            let trace = engine.evaluate_explain(&ctx("read", resource, &[])).unwrap();
+            assert_eq!(trace.verdict, PolicyVerdict::Allow, "{resource}");
+            assert_eq!(trace.matched_rule(), Some("allow-read-consented"));
+        }
+
+        let excluded = engine
+            .evaluate_explain(&ctx("read", "patient-records-no-consent", &[]))
+            .unwrap();
+        assert!(matches!(excluded.verdict, PolicyVerdict::Deny { .. }));
+        assert_eq!(excluded.matched_rule(), None);
+
+        // `!prefix/**` excludes a subtree; `!*` matches nothing.
+        let rule = |resource: &str| PolicyRule {
+            resource: resource.to_string(),
+            ..engine.config().rules[0].clone()
+        };
+        let outside_psych = rule("!psych/**");
+        assert!(outside_psych.matches_resource("patient/42"));
+        assert!(outside_psych.matches_resource("psych"));
+        assert!(!outside_psych.matches_resource("psych/notes/7"));
+        assert!(!rule("!*").matches_resource("patient-records"));
+    }
+
+    // ── 20. deny codes through the executor ───────────────────────────────────
+
+    /// `StepResult::Denied` carries the engine's deny code and deciding rule:
+    /// `MissingCapability` for a capability override, `NoRuleMatched` for the
+    /// default deny.
+    #[test]
+    fn test_denied_step_carries_deny_code() {
+        use veritas_contracts::{capability::CapabilitySet, execution::StepResult, policy::DenyCode};
+        use veritas_core::{
+            testing::{
+                action_input, agent_state, empty_schema, ActionAgent, NullAudit, PassVerifier,
+            },
+            Executor,
+        };
+
+        let toml = r#"
+            [[rules]]
+            id = "phi-read-allow"
+            description = "PHI reads need phi:read"
+            action = "read_phi"
+            resource = "*"
+            required_capabilities = ["phi:read"]
+            verdict = "allow"
+        "#;
+        let executor = Executor::new(
+            Box::new(TomlPolicyEngine::from_toml_str(toml).unwrap()),
+            Box::new(NullAudit),
+            Box::new(PassVerifier),
+            empty_schema("deny-codes-v1"),
+        );
+        let agent = ActionAgent::on("patient/1");
+        let run = |action: &str| {
+            let state = agent_state("deny-code-agent");
+            executor.step(&agent, state, action_input(action), &CapabilitySet::default()).unwrap()
+        };
+
+        match run("read_phi") {
+            StepResult::Denied { code, rule_id, .. } => {
+                assert_eq!(code, DenyCode::MissingCapability);
+                assert_eq!(rule_id.as_deref(), Some("phi-read-allow"));
+            }
+            other => panic!("expected Denied, got {:?}", other),
+        }
+        match run("delete_phi") {
+            StepResult::Denied { code, rule_id, .. } => {
+                assert_eq!(code, DenyCode::NoRuleMatched);
+                assert_eq!(rule_id, None);
+            }
+            other => panic!("expected Denied, got {:?}", other),
+        }
+    }
+
+    // ── 21. resource-conditional capabilities ─────────────────────────────────
+
+    /// A capability condition applies only to resources matching its pattern;
+    /// other resources need just the rule's base requirements.
+    #[test]
+    fn test_capability_condition_applies_only_to_matching_resource() {
+        let toml = r#"
+            [[rules]]
+            id = "allow-plan-treatment"
+            description = "Planning is allowed; diagnosis data also needs treatment.write"
+            action = "plan-treatment"
+            resource = "*"
+            verdict = "allow"
+
+            [[rules.capability_conditions]]
+            resource = "diagnosis-data"
+            capabilities = ["treatment.write"]
+        "#;
+        let engine = TomlPolicyEngine::from_toml_str(toml).unwrap();
+
+        let trace = engine
+            .evaluate_explain(&ctx("plan-treatment", "diagnosis-data", &[]))
+            .unwrap();
+        assert!(matches!(
+            &trace.verdict,
+            PolicyVerdict::Deny { reason, .. } if reason.contains("treatment.write")
+        ));
+        assert_eq!(
+            trace.evaluations.last().unwrap().outcome,
+            RuleOutcome::CapabilityOverride {
+                missing_capability: "treatment.write".to_string(),
+            }
+        );
+
+        assert_eq!(
+            engine
+                .evaluate(&ctx("plan-treatment", "diagnosis-data", &["treatment.write"]))
+                .unwrap(),
+            PolicyVerdict::Allow
+        );
+        assert_eq!(
+            engine.evaluate(&ctx("plan-treatment", "care-notes", &[])).unwrap(),
+            PolicyVerdict::Allow
+        );
+
+        assert_eq!(
+            engine.required_capabilities_for("plan-treatment", "diagnosis-data"),
+            ["treatment.write"]
+        );
+        assert!(engine.required_capabilities_for("plan-treatment", "care-notes").is_empty());
+    }
+
+    // ── 22. capability catalog validation ─────────────────────────────────────
+
+    /// Requirements are checked against the catalog: known names and
+    /// wildcards over known namespaces load; an unknown name is flagged,
+    /// and a strict catalog refuses the policy.
+    #[test]
+    fn test_policy_validated_against_capability_catalog() {
+        use veritas_contracts::capability::{Capability, CapabilityCatalog};
+
+        let mut catalog = CapabilityCatalog::new();
+        catalog.register(Capability::new("clinical-data.read"), "Read clinical records");
+        catalog.register(Capability::new("drug-database.read"), "Query drug interactions");
+
+        let valid = r#"
+            [[rules]]
+            id = "allow-analysis"
+            description = "Analysis reads clinical data"
+            action = "analyze"
+            resource = "symptom-data"
+            required_capabilities = ["clinical-data.read"]
+            verdict = "allow"
+
+            [[rules]]
+            id = "allow-drug-check"
+            description = "Any drug database capability suffices"
+            action = "check-drug-safety"
+            resource = "drug-database"
+            required_capabilities = ["drug-database.*"]
+            verdict = "allow"
+        "#;
+        let engine = TomlPolicyEngine::from_toml_str_validated(valid, &catalog.clone().strict(true));
+        assert!(engine.is_ok(), "{:?}", engine.err());
+        assert!(engine.unwrap().config().unknown_capabilities(&catalog).is_empty());
+
+        let typo = valid.replace(r#"["clinical-data.read"]"#, r#"["clinical_data.read"]"#);
+        let lenient = TomlPolicyEngine::from_toml_str_validated(&typo, &catalog).unwrap();
+        assert_eq!(
+            lenient.config().unknown_capabilities(&catalog),
+            [("allow-analysis".to_string(), "clinical_data.read".to_string())]
+        );
+
+        match TomlPolicyEngine::from_toml_str_validated(&typo, &catalog.strict(true)) {
+            Err(veritas_contracts::error::VeritasError::ConfigError { reason }) => {
+                assert!(reason.contains("allow-analysis"), "reason: {reason}");
+                assert!(reason.contains("clinical_data.read"), "reason: {reason}");
+            }
+            other => panic!("expected ConfigError, got {:?}", other),
+        }
+    }
+
+    // ── 23. require-both ──────────────────────────────────────────────────────
+
+    /// A require-both rule returns RequireBoth carrying the approval settings
+    /// and its checks in order.
+    #[test]
+    fn test_require_both() {
+        let toml = r#"
+            [[rules]]
+            id = "controlled-substance"
+            description = "Controlled substances need a dose check and pharmacist sign-off"
+            action = "prescribe_medication"
+            resource = "controlled/**"
+            verdict = "require-both"
+            approval_reason = "controlled substance order"
+            approver_role = "pharmacist"
+            verification_check_ids = ["dose-range", "phi-content-scan"]
+        "#;
+
+        let engine = TomlPolicyEngine::from_toml_str(toml).unwrap();
+        let verdict =
+            engine.evaluate(&ctx("prescribe_medication", "controlled/oxycodone", &[])).unwrap();
+
+        match verdict {
+            PolicyVerdict::RequireBoth { reason, approver_role, required_attributes, check_ids } => {
+                assert_eq!(reason, "controlled substance order");
+                assert_eq!(approver_role, "pharmacist");
+                assert!(required_attributes.is_empty());
+                assert_eq!(check_ids, ["dose-range", "phi-content-scan"]);
+            }
+            other => panic!("expected RequireBoth, got {:?}", other),
+        }
+
+        // A single `verification_check_id` is not dropped: it runs first,
+        // and a check named in both fields runs once.
+        let toml = r#"
+            [[rules]]
+            id = "controlled-substance"
+            description = "Controlled substances need checks and pharmacist sign-off"
+            action = "prescribe_medication"
+            resource = "controlled/**"
+            verdict = "require-both"
+            approver_role = "pharmacist"
+            verification_check_id = "allergy-scan"
+            verification_check_ids = ["dose-range", "allergy-scan"]
+        "#;
+        let engine = TomlPolicyEngine::from_toml_str(toml).unwrap();
+        let verdict =
+            engine.evaluate(&ctx("prescribe_medication", "controlled/oxycodone", &[])).unwrap();
+        assert_eq!(verdict.verification_checks(), ["allergy-scan", "dose-range"]);
+    }
+
+    // ── 24. rate limits ───────────────────────────────────────────────────────
+
+    /// A rule limited to 3 invocations per window denies the 4th call in the
+    /// window and allows again once the window rolls over.
+    #[test]
+    fn test_rate_limit_per_window() {
+        use std::sync::Arc;
+
+        use chrono::{Duration, TimeZone, Utc};
+        use veritas_contracts::policy::DenyCode;
+        use veritas_core::clock::FixedClock;
+
+        use crate::{InMemoryRateCounterStore, RateCounterStore};
+
+        let toml = r#"
+            [[rules]]
+            id = "limited-lookup"
+            description = "At most three lookups a minute"
+            action = "lookup"
+            resource = "*"
+            verdict = "allow"
+            max_invocations_per_window = 3
+            window_secs = 60
+        "#;
+
+        let clock = FixedClock::new(Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap());
+        let store = Arc::new(InMemoryRateCounterStore::new());
+        let engine = TomlPolicyEngine::from_toml_str(toml)
+            .unwrap()
+            .with_rate_store(store.clone())
+            .with_clock(Arc::new(clock.clone()));
+
+        for _ in 0..3 {
+            // A preview spends nothing; only the enforced decision counts.
+            assert_eq!(engine.evaluate(&ctx("lookup", "drug/1", &[])).unwrap(), PolicyVerdict::Allow);
+            assert_eq!(
+                engine.decide(&ctx("lookup", "drug/1", &[])).unwrap().verdict,
+                PolicyVerdict::Allow
+            );
+        }
+        assert_eq!(store.get("limited-lookup/test-agent/28401840"), 3);
+        let trace = engine.evaluate_explain(&ctx("lookup", "drug/1", &[])).unwrap();
+        match &trace.verdict {
+            PolicyVerdict::Deny { reason, .. } => assert!(reason.contains("at most 3"), "{reason}"),
+            other => panic!("expected Deny, got {:?}", other),
+        }
+        assert_eq!(trace.deny_code(), Some(DenyCode::RateLimited));
+        assert_eq!(trace.matched_rule(), Some("limited-lookup"));
+        assert_eq!(
+            trace.evaluations.last().unwrap().outcome,
+            RuleOutcome::RateLimited { max_invocations: 3, window_secs: 60 }
+        );
+
+        // The counts are in the shared store: a second engine on the same
+        // store is limited too.
+        let other_engine = TomlPolicyEngine::from_toml_str(toml)
+            .unwrap()
+            .with_rate_store(store.clone())
+            .with_clock(Arc::new(clock.clone()));
+        assert!(matches!(
+            other_engine.evaluate(&ctx("lookup", "drug/1", &[])).unwrap(),
+            PolicyVerdict::Deny { .. }
+        ));
+
+        // Neither the explained nor the previewed denial was counted.
+        assert_eq!(store.get("limited-lookup/test-agent/28401840"), 3);
+
+        // A new window starts from zero.
+        clock.advance(Duration::seconds(60));
+        assert_eq!(
+            engine.decide(&ctx("lookup", "drug/1", &[])).unwrap().verdict,
+            PolicyVerdict::Allow
+        );
+        assert_eq!(store.get("limited-lookup/test-agent/28401841"), 1);
+    }
+
+    /// An `Executor::preflight` before the step it previews leaves the rate
+    /// budget to the step: with one invocation allowed, the step still runs,
+    /// and only then does a preflight report the limit.
+    #[test]
+    fn test_preflight_then_step_counts_once() {
+        use veritas_contracts::{
+            capability::CapabilitySet,
+            execution::{PreflightResult, StepResult},
+        };
+        use veritas_core::{
+            testing::{
+                action_input, agent_state, empty_schema, ActionAgent, NullAudit, PassVerifier,
+            },
+            Executor,
+        };
+
+        let toml = r#"
+            [[rules]]
+            id = "limited-lookup"
+            description = "One lookup a minute"
+            action = "lookup"
+            resource = "*"
+            verdict = "allow"
+            max_invocations_per_window = 1
+        "#;
+        let executor = Executor::new(
+            Box::new(TomlPolicyEngine::from_toml_str(toml).unwrap()),
+            Box::new(NullAudit),
+            Box::new(PassVerifier),
+            empty_schema("lookup-v1"),
+        );
+        let agent = ActionAgent::on("drug/1");
+        let state = agent_state("lookup-agent");
+        let input = action_input("lookup");
+        let caps = CapabilitySet::default();
+
+        assert_eq!(executor.preflight(&agent, &state, &input, &caps), PreflightResult::Allowed);
+        let next_state = match executor.step(&agent, state, input.clone(), &caps).unwrap() {
+            StepResult::Transitioned { next_state, .. } => next_state,
+            other => panic!("expected Transitioned, got {:?}", other),
+        };
+        assert!(matches!(
+            executor.preflight(&agent, &next_state, &input, &caps),
+            PreflightResult::Denied { .. }
+        ));
+    }
+
+    /// Every resume counts toward the rate limit, so presenting one approval
+    /// again and again cannot run the agent past it: with two invocations
+    /// allowed, the suspension and the first resume spend them and the next
+    /// resume is denied.
+    #[test]
+    fn test_resumes_past_rate_limit_are_denied() {
+        use std::time::Duration;
+
+        use veritas_contracts::{
+            capability::CapabilitySet, execution::StepResult, policy::ApprovalRecord,
+        };
+        use veritas_core::{
+            testing::{
+                action_input, agent_state, empty_schema, ActionAgent, NullAudit, PassVerifier,
+            },
+            Executor,
+        };
+
+        let toml = r#"
+            [[rules]]
+            id = "limited-order"
+            description = "Two approved orders a minute"
+            action = "order"
+            resource = "*"
+            verdict = "require-approval"
+            approver_role = "attending_physician"
+            max_invocations_per_window = 2
+        "#;
+        let executor = Executor::new(
+            Box::new(TomlPolicyEngine::from_toml_str(toml).unwrap()),
+            Box::new(NullAudit),
+            Box::new(PassVerifier),
+            empty_schema("order-v1"),
+        );
+        let agent = ActionAgent::on("pharmacy");
+        let caps = CapabilitySet::default();
+        let approval = ApprovalRecord {
+            approver_id: "dr-chen".to_string(),
+            approver_role: "attending_physician".to_string(),
+            attributes: Default::default(),
+            granted_at: chrono::Utc::now(),
+            valid_for: Duration::from_secs(3600),
+        };
+
+        let suspended_state =
+            match executor.step(&agent, agent_state("order-agent"), action_input("order"), &caps) {
+                Ok(StepResult::AwaitingApproval { suspended_state, .. }) => suspended_state,
+                other => panic!("expected AwaitingApproval, got {:?}", other),
+            };
+        let resume = || {
+            let state = suspended_state.clone();
+            executor.resume(&agent, state, action_input("order"), &caps, &approval)
+        };
+        assert!(matches!(resume(), Ok(StepResult::Transitioned { .. })));
+        match resume() {
+            Ok(StepResult::Denied { reason, .. }) => {
+                assert!(reason.contains("has reached the limit"), "reason: {reason}");
+            }
+            other => panic!("expected Denied, got {:?}", other),
+        }
+    }
+
+    // ── 25. decision trace in the audit record ────────────────────────────────
+
+    /// An executor built `with_decision_trace_in_audit(true)` records the
+    /// engine's trace — the deciding rule and why earlier rules were skipped
+    /// — in the step's audit record; without it the record carries none.
+    #[test]
+    fn test_decision_trace_recorded_in_audit() {
+        use veritas_contracts::capability::CapabilitySet;
+        use veritas_core::{
+            testing::{
+                action_input, agent_state, empty_schema, ActionAgent, MemoryAudit, PassVerifier,
+            },
+            Executor,
+        };
+
+        let toml = r#"
+            [[rules]]
+            id = "allow-read"
+            description = "Reads are fine"
+            action = "read_phi"
+            resource = "*"
+            verdict = "allow"
+
+            [[rules]]
+            id = "deny-delete"
+            description = "PHI is never deleted"
+            action = "delete_phi"
+            resource = "*"
+            verdict = "deny"
+            deny_reason = "PHI deletion is forbidden"
+        "#;
+        let run = |trace_in_audit: bool| {
+            let audit = MemoryAudit::new();
+            let executor = Executor::new(
+                Box::new(TomlPolicyEngine::from_toml_str(toml).unwrap()),
+                Box::new(audit.clone()),
+                Box::new(PassVerifier),
+                empty_schema("decision-trace-v1"),
+            )
+            .with_decision_trace_in_audit(trace_in_audit);
+            let agent = ActionAgent::on("patient/1");
+            let state = agent_state("trace-agent");
+            let input = action_input("delete_phi");
+            executor.step(&agent, state, input, &CapabilitySet::default()).unwrap();
+            let records = audit.records();
+            assert_eq!(records.len(), 1);
+            records.into_iter().next().unwrap()
+        };
+
+        let record = run(true);
+        let trace = record.decision_trace.expect("trace recorded when enabled");
+        assert_eq!(trace["matched_rule"], "deny-delete");
+        assert_eq!(
+            trace["rules"],
+            serde_json::json!([
+                { "rule_id": "allow-read", "outcome": "action-mismatch" },
+                { "rule_id": "deny-delete", "outcome": "fired" }
+            ])
+        );
+
+        assert_eq!(run(false).decision_trace, None);
+    }
+
+    // ── 26. pure evaluation entrypoint ────────────────────────────────────────
+
+    /// `evaluate_config` does not panic on arbitrary contexts, agrees with a
+    /// `TomlPolicyEngine` on the same (rate-limit-free) rules, and denies
+    /// every context when the rule set is empty.
+    #[test]
+    fn test_evaluate_config_on_arbitrary_contexts() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        use crate::evaluate_config;
+
+        let toml = r#"
+            capability_mode = "restrictive"
+
+            [[rules]]
+            id = "deny-secret"
+            description = "Nothing touches the vault"
+            action = "*"
+            resource = "vault/**"
+            verdict = "deny"
+
+            [[rules]]
+            id = "routine-read"
+            description = "Routine reads need a namespace capability"
+            action = "*"
+            resource = "!audit-log"
+            required_capabilities = ["records.*"]
+            verdict = "allow"
+            metadata_match = { urgency = "routine" }
+
+            [[rules]]
+            id = "approve-rest"
+            description = "Everything else needs sign-off"
+            action = "*"
+            resource = "*"
+            verdict = "require-approval"
+            approver_role = "supervisor"
+        "#;
+        let engine = TomlPolicyEngine::from_toml_str(toml).unwrap();
+        let empty = TomlPolicyEngine::from_toml_str("rules = []").unwrap();
+
+        // A fixed xorshift stream, so failures reproduce.
+        let mut seed: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        for round in 0..500 {
+            let len = (next() % 256) as usize;
+            let bytes: Vec<u8> = (0..len).map(|_| next() as u8).collect();
+            let Ok(context) = PolicyContext::arbitrary(&mut Unstructured::new(&bytes)) else {
+                continue;
+            };
+
+            assert_eq!(
+                evaluate_config(engine.config(), &context),
+                engine.evaluate(&context).unwrap(),
+                "round {round}: {context:?}"
+            );
+            assert!(
+                matches!(evaluate_config(empty.config(), &context), PolicyVerdict::Deny { .. }),
+                "round {round}: empty rule set allowed {context:?}"
+            );
+        }
+    }
+
+    // ── 27. continue rules ────────────────────────────────────────────────────
+
+    /// A `continue` rule tags the request and lets the next rule decide; a
+    /// policy of only `continue` rules still denies by default.
+    #[test]
+    fn test_continue_rule_tags_and_defers() {
+        use veritas_contracts::policy::DenyCode;
+
+        let tagging = r#"
+            [[rules]]
+            id = "tag-phi"
+            description = "Flag every access to patient data"
+            action = "*"
+            resource = "patient/**"
+            verdict = "continue"
+            tags = ["phi-access"]
+        "#;
+        let allowing = format!(
+            r#"{tagging}
+            [[rules]]
+            id = "allow-read"
+            description = "Reads are allowed"
+            action = "read_record"
+            resource = "*"
+            verdict = "allow"
+        "#
+        );
+
+        let engine = TomlPolicyEngine::from_toml_str(&allowing).unwrap();
+        let decision = engine.decide(&ctx("read_record", "patient/42", &[])).unwrap();
+        assert_eq!(decision.verdict, PolicyVerdict::Allow);
+        assert_eq!(decision.rule_id.as_deref(), Some("allow-read"));
+        assert_eq!(decision.tags, ["phi-access"]);
+
+        let trace = engine.evaluate_explain(&ctx("read_record", "patient/42", &[])).unwrap();
+        assert_eq!(
+            trace.evaluations.iter().map(|e| &e.outcome).collect::<Vec<_>>(),
+            [
+                &RuleOutcome::Continued {
+                    tags: vec!["phi-access".to_string()]
+                },
+                &RuleOutcome::Fired
+            ]
+        );
+
+        // Outside patient data the tagging rule does not match.
+        let untagged = engine.decide(&ctx("read_record", "formulary", &[])).unwrap();
+        assert_eq!(untagged.verdict, PolicyVerdict::Allow);
+        assert!(untagged.tags.is_empty());
+
+        // Nothing but continue rules: the default deny applies, tags intact.
+        let engine = TomlPolicyEngine::from_toml_str(tagging).unwrap();
+        let decision = engine.decide(&ctx("read_record", "patient/42", &[])).unwrap();
+        assert!(matches!(decision.verdict, PolicyVerdict::Deny { .. }));
+        assert_eq!(decision.deny_code, Some(DenyCode::NoRuleMatched));
+        assert_eq!(decision.rule_id, None);
+        assert_eq!(decision.tags, ["phi-access"]);
+    }
+
+    /// The tags a `continue` rule applies reach both the `StepResult` and the
+    /// step's audit record when the engine runs under an executor.
+    #[test]
+    fn test_continue_rule_tags_reach_step() {
+        use veritas_contracts::{capability::CapabilitySet, execution::StepResult};
+        use veritas_core::{
+            testing::{
+                action_input, agent_state, empty_schema, ActionAgent, MemoryAudit, PassVerifier,
+            },
+            Executor,
+        };
+
+        let toml = r#"
+            [[rules]]
+            id = "tag-phi"
+            description = "Flag every access to patient data"
+            action = "*"
+            resource = "patient/**"
+            verdict = "continue"
+            tags = ["phi-access"]
+
+            [[rules]]
+            id = "allow-read"
+            description = "Reads are allowed"
+            action = "read_record"
+            resource = "*"
+            verdict = "allow"
+        "#;
+        let audit = MemoryAudit::new();
+        let executor = Executor::new(
+            Box::new(TomlPolicyEngine::from_toml_str(toml).unwrap()),
+            Box::new(audit.clone()),
+            Box::new(PassVerifier),
+            empty_schema("policy-tags-v1"),
+        );
+        let agent = ActionAgent::on("patient/42");
+        let state = agent_state("tag-agent");
+        let input = action_input("read_record");
+
+        match executor.step(&agent, state, input, &CapabilitySet::default()).unwrap() {
+            StepResult::Transitioned { tags, .. } => assert_eq!(tags, ["phi-access"]),
+            other => panic!("expected Transitioned, got {other:?}"),
+        }
+        let records = audit.records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].policy_tags, ["phi-access"]);
+        assert!(records[0].has_optional_fields());
+    }
+
+    // ── 28. action kinds ──────────────────────────────────────────────────────
+
+    /// A rule scoped to `action_kind = "write"` matches writes and deletes
+    /// and is skipped for reads and unclassified actions.
+    #[test]
+    fn test_action_kind_scopes_rule() {
+        let toml = r#"
+            [[rules]]
+            id = "deny-writes"
+            description = "This agent never changes records"
+            action = "*"
+            resource = "*"
+            action_kind = "write"
+            verdict = "deny"
+            deny_reason = "read-only agent"
+
+            [[rules]]
+            id = "allow-rest"
+            description = "Everything else is allowed"
+            action = "*"
+            resource = "*"
+            verdict = "allow"
+        "#;
+        let engine = TomlPolicyEngine::from_toml_str(toml).unwrap();
+
+        let delete = engine.evaluate_explain(&ctx("delete_record", "patient/42", &[])).unwrap();
+        assert!(matches!(delete.verdict, PolicyVerdict::Deny { .. }));
+        assert_eq!(delete.matched_rule(), Some("deny-writes"));
+
+        let read = engine.evaluate_explain(&ctx("read_record", "patient/42", &[])).unwrap();
+        assert_eq!(read.verdict, PolicyVerdict::Allow);
+        assert_eq!(read.evaluations[0].outcome, RuleOutcome::ActionKindMismatch);
+
+        // An action with no recognizable verb is unclassified: not a write.
+        let unclassified = PolicyContext {
+            action_kind: None,
+            ..ctx("reconcile_record", "patient/42", &[])
+        };
+        assert_eq!(engine.evaluate(&unclassified).unwrap(), PolicyVerdict::Allow);
+
+        // The kind an agent reports wins over the action name.
+        let reported_write = PolicyContext {
+            action_kind: Some(ActionKind::Write),
+            ..ctx("reconcile_record", "patient/42", &[])
+        };
+        assert!(matches!(
+            engine.evaluate(&reported_write).unwrap(),
+            PolicyVerdict::Deny { .. }
+        ));
+    }
+
+    // ── 29. remediation hints ─────────────────────────────────────────────────
+
+    /// A rule's remediation reaches its own denial and the capability
+    /// denial it makes, survives a TOML round trip of the config, and is
+    /// absent from denials no rule gave a hint for.
+    #[test]
+    fn test_remediation_carried_into_deny() {
+        let toml = r#"
+            [[rules]]
+            id = "deny-no-consent"
+            description = "No queries without consent"
+            action = "query"
+            resource = "no-consent/**"
+            verdict = "deny"
+            deny_reason = "patient has not consented"
+            remediation = "obtain patient consent"
+
+            [[rules]]
+            id = "allow-query"
+            description = "Queries need patient-records.read"
+            action = "query"
+            resource = "*"
+            required_capabilities = ["patient-records.read"]
+            verdict = "allow"
+            remediation = "request patient-records.read from an administrator"
+        "#;
+        let engine = TomlPolicyEngine::from_toml_str(toml).unwrap();
+
+        let denied = engine
+            .evaluate(&ctx("query", "no-consent/42", &["patient-records.read"]))
+            .unwrap();
+        assert_eq!(
+            denied,
+            PolicyVerdict::Deny {
+                reason: "patient has not consented".to_string(),
+                remediation: Some("obtain patient consent".to_string()),
+            }
+        );
+        let json = serde_json::to_string(&denied).unwrap();
+        assert_eq!(serde_json::from_str::<PolicyVerdict>(&json).unwrap(), denied);
+
+        match engine.evaluate(&ctx("query", "patient/42", &[])).unwrap() {
+            PolicyVerdict::Deny { remediation, .. } => assert_eq!(
+                remediation.as_deref(),
+                Some("request patient-records.read from an administrator")
+            ),
+            other => panic!("expected a capability denial, got {:?}", other),
+        }
+
+        match engine.evaluate(&ctx("delete", "patient/42", &[])).unwrap() {
+            PolicyVerdict::Deny { remediation, .. } => assert_eq!(remediation, None),
+            other => panic!("expected the default deny, got {:?}", other),
+        }
+
+        let reloaded = TomlPolicyEngine::from_toml_str(&engine.config().to_toml_string().unwrap())
+            .unwrap();
+        assert_eq!(reloaded.config(), engine.config());
+        assert_eq!(
+            reloaded.config().rules[0].remediation.as_deref(),
+            Some("obtain patient consent")
+        );
+    }
+
+    // ── 30. approver resolvers ────────────────────────────────────────────────
+
+    /// One rule routes each procedure to the approver its resolver picks;
+    /// without the resolver the placeholder is left as written.
+    #[test]
+    fn test_approver_role_resolved_from_resource() {
+        let toml = r#"
+            [[rules]]
+            id = "procedures-need-approval"
+            description = "Procedures need sign-off from the matching specialty"
+            action = "schedule"
+            resource = "*"
+            verdict = "require-approval"
+            approval_reason = "procedure requires sign-off"
+            approver_role = "{specialty}"
+        "#;
+        let approver = |engine: &TomlPolicyEngine, resource: &str| {
+            match engine.evaluate(&ctx("schedule", resource, &[])).unwrap() {
+                PolicyVerdict::RequireApproval { approver_role, .. } => approver_role,
+                other => panic!("expected RequireApproval, got {:?}", other),
+            }
+        };
+
+        let unresolved = TomlPolicyEngine::from_toml_str(toml).unwrap();
+        assert_eq!(approver(&unresolved, "cardiac-mri"), "{specialty}");
+
+        let mut engine = TomlPolicyEngine::from_toml_str(toml).unwrap();
+        engine.register_approver_resolver(
+            "specialty",
+            Box::new(|resource| match resource {
+                r if r.starts_with("cardiac-") => "cardiologist".to_string(),
+                _ => "attending-physician".to_string(),
+            }),
+        );
+        assert_eq!(approver(&engine, "cardiac-mri"), "cardiologist");
+        assert_eq!(approver(&engine, "knee-xray"), "attending-physician");
+
+        // The trace explains the same resolved verdict.
+        let trace = engine
+            .evaluate_explain(&ctx("schedule", "cardiac-mri", &[]))
+            .unwrap();
+        assert!(matches!(
+            trace.verdict,
+            PolicyVerdict::RequireApproval { ref approver_role, .. } if approver_role == "cardiologist"
+        ));
+    }
+
+    /// Text around a placeholder is kept, and a rule without placeholders
+    /// is unaffected by registered resolvers.
+    #[test]
+    fn test_approver_role_template_keeps_surrounding_text() {
+        let toml = r#"
+            [[rules]]
+            id = "templated"
+            description = "Templated approver"
+            action = "schedule"
+            resource = "*"
+            verdict = "require-approval"
+            approver_role = "senior-{specialty}"
+
+            [[rules]]
+            id = "static"
+            description = "Static approver"
+            action = "discharge"
+            resource = "*"
+            verdict = "require-approval"
+            approver_role = "attending-physician"
+        "#;
+        let mut engine = TomlPolicyEngine::from_toml_str(toml).unwrap();
+        engine.register_approver_resolver("specialty", Box::new(|_| "cardiologist".to_string()));
+
+        for (action, expected) in [
+            ("schedule", "senior-cardiologist"),
+            ("discharge", "attending-physician"),
+        ] {
+            match engine.evaluate(&ctx(action, "cardiac-mri", &[])).unwrap() {
+                PolicyVerdict::RequireApproval { approver_role, .. } => {
+                    assert_eq!(approver_role, expected, "{action}")
+                }
+                other => panic!("expected RequireApproval, got {:?}", other),
+            }
+        }
+    }
 }
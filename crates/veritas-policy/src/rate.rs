@@ -0,0 +1,68 @@
+//! Invocation counters behind rule-level rate limits.
+//!
+//! A rule with `max_invocations_per_window` fires at most that many times
+//! per agent in each fixed window of `window_secs` seconds.  The counts live
+//! in a `RateCounterStore`, not in the engine, so a server can share them
+//! across executions — or across processes, with a store backed by Redis or
+//! a database.  `InMemoryRateCounterStore` is the default.
+//!
+//! Windows are aligned to the Unix epoch, and each window counts under its
+//! own key (see `window_key`).  A store therefore never has to reset a
+//! counter: a new window simply starts from zero, and stores that support
+//! expiry can drop keys once their window has passed.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+
+/// The window length for a rule that sets `max_invocations_per_window` but
+/// no `window_secs`.
+pub const DEFAULT_WINDOW_SECS: u64 = 60;
+
+/// Storage for rate-limit counters, keyed by opaque strings.
+///
+/// Implementations must be safe to share between threads; `increment` must
+/// be atomic, since concurrent executions may count against the same key.
+pub trait RateCounterStore: Send + Sync {
+    /// Add one to the counter under `key` and return the new count.  A key
+    /// never incremented before counts from zero.
+    fn increment(&self, key: &str) -> u64;
+
+    /// The current count under `key`; zero if it was never incremented.
+    fn get(&self, key: &str) -> u64;
+}
+
+/// A process-local `RateCounterStore`.  Counts are lost when it is dropped.
+#[derive(Debug, Default)]
+pub struct InMemoryRateCounterStore {
+    counts: Mutex<HashMap<String, u64>>,
+}
+
+impl InMemoryRateCounterStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RateCounterStore for InMemoryRateCounterStore {
+    fn increment(&self, key: &str) -> u64 {
+        let mut counts = self.counts.lock().expect("rate counter lock poisoned");
+        let count = counts.entry(key.to_string()).or_insert(0);
+        *count += 1;
+        *count
+    }
+
+    fn get(&self, key: &str) -> u64 {
+        let counts = self.counts.lock().expect("rate counter lock poisoned");
+        counts.get(key).copied().unwrap_or(0)
+    }
+}
+
+/// The counter key for `rule_id` and `agent_id` in the `window_secs` window
+/// containing `now`: `"<rule_id>/<agent_id>/<window index>"`.
+pub fn window_key(rule_id: &str, agent_id: &str, window_secs: u64, now: DateTime<Utc>) -> String {
+    let window = now.timestamp().max(0) as u64 / window_secs.max(1);
+    format!("{}/{}/{}", rule_id, agent_id, window)
+}
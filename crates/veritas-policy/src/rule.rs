@@ -2,10 +2,24 @@
 //!
 //! A `PolicyConfig` is deserialized from TOML and holds an ordered list of
 //! `PolicyRule`s.  Rules are evaluated in declaration order — the first
-//! matching rule wins.  If no rule matches, the engine denies by default.
+//! matching rule wins, except that a `continue` rule only contributes its
+//! `tags` and lets evaluation go on.  If no rule decides, the engine applies
+//! the config's `default_verdict` — deny unless the policy explicitly opts
+//! into allow.
+//!
+//! Rules may join a named `[[groups]]` entry to inherit its
+//! `required_capabilities` instead of repeating them.
+
+use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
+use veritas_contracts::{
+    capability::CapabilityCatalog,
+    error::{VeritasError, VeritasResult},
+    policy::ActionKind,
+};
+
 /// The decision a rule produces when it matches an incoming `PolicyContext`.
 ///
 /// Variants map directly to `PolicyVerdict` in veritas-contracts, but are
@@ -17,7 +31,12 @@ use serde::{Deserialize, Serialize};
 /// verdict = "deny"
 /// verdict = "require-approval"
 /// verdict = "require-verification"
+/// verdict = "require-both"
+/// verdict = "continue"
 /// ```
+///
+/// `continue` has no `PolicyVerdict` counterpart: the rule adds its `tags`
+/// to the decision and evaluation moves on to the next rule.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum RuleVerdict {
@@ -25,17 +44,26 @@ pub enum RuleVerdict {
     Deny,
     RequireApproval,
     RequireVerification,
+    RequireBoth,
+    /// Tag the request and keep evaluating.  A policy whose only matching
+    /// rules continue falls through to `default_verdict`, so it denies by
+    /// default.
+    Continue,
 }
 
 /// A single policy rule loaded from TOML.
 ///
 /// Rules are matched in the order they appear in the policy file.
 /// The first rule whose `action` and `resource` patterns match the incoming
-/// `PolicyContext` wins; subsequent rules are not evaluated.
+/// `PolicyContext` wins; subsequent rules are not evaluated.  A matching
+/// `verdict = "continue"` rule is the exception: it records its `tags` and
+/// leaves the decision to the rules after it.
 ///
 /// Both `action` and `resource` support the special wildcard value `"*"`,
-/// which matches any string.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// which matches any string.  `resource` also accepts `"prefix/**"` and a
+/// leading `!` that negates the rest of the pattern (see
+/// `matches_resource`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PolicyRule {
     /// Stable identifier used in audit logs and error messages.
     pub id: String,
@@ -48,32 +76,105 @@ pub struct PolicyRule {
     pub action: String,
 
     /// The resource pattern to match against `PolicyContext::resource`.
-    /// Use `"*"` to match any resource.
+    /// Use `"*"` to match any resource, `"prefix/**"` for everything under
+    /// `prefix/`, and a leading `!` for "any resource except".
     pub resource: String,
 
+    /// When set, the rule only matches actions of this kind
+    /// (`PolicyContext::action_kind`), e.g. `action_kind = "write"`, which
+    /// also covers deletes (`ActionKind::is_a`).  An unclassified action
+    /// never matches a rule with an `action_kind`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action_kind: Option<ActionKind>,
+
     /// Capability names that the agent MUST hold for this rule to produce its
     /// `verdict`.  If the agent lacks any listed capability, the engine denies
     /// the request regardless of `verdict`.  This is a defense-in-depth check:
     /// even an explicit `allow` rule cannot override a missing capability.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub required_capabilities: Vec<String>,
 
+    /// Further capabilities required only when the resource matches a
+    /// condition's pattern, checked after `required_capabilities`.
+    ///
+    /// ```toml
+    /// [[rules.capability_conditions]]
+    /// resource = "diagnosis-data"
+    /// capabilities = ["treatment.write"]
+    /// ```
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capability_conditions: Vec<CapabilityCondition>,
+
+    /// Name of a `PolicyGroup` whose `required_capabilities` this rule
+    /// inherits.  Merged into `required_capabilities` at load time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+
     /// The decision this rule produces when it matches and capabilities are met.
     pub verdict: RuleVerdict,
 
     /// Mandatory when `verdict = "deny"`.  Written to the audit log.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub deny_reason: Option<String>,
 
-    /// Mandatory when `verdict = "require-approval"`.  Written to the audit log.
+    /// How to proceed after a denial this rule causes, e.g. "obtain patient
+    /// consent".  Carried into the `PolicyVerdict::Deny` of the rule's own
+    /// deny verdict and of the capability and rate-limit denials it makes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+
+    /// Mandatory when `verdict = "require-approval"` or `"require-both"`.
+    /// Written to the audit log.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub approval_reason: Option<String>,
 
-    /// Mandatory when `verdict = "require-approval"`.  Identifies the role
-    /// (e.g. `"attending_physician"`) that must grant sign-off.
+    /// Mandatory when `verdict = "require-approval"` or `"require-both"`.
+    /// Identifies the role (e.g. `"attending_physician"`) that must grant
+    /// sign-off.  A `{name}` placeholder is filled per resource by the
+    /// approver resolver registered under `name`; see
+    /// `TomlPolicyEngine::register_approver_resolver`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub approver_role: Option<String>,
 
+    /// Attributes the approver must hold besides `approver_role`, e.g.
+    /// `approver_attributes = { specialty = "cardiology" }`.  Only used when
+    /// `verdict = "require-approval"` or `"require-both"`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub approver_attributes: BTreeMap<String, String>,
+
     /// Mandatory when `verdict = "require-verification"`.  References the
-    /// check identifier that the verifier will look up.
+    /// check identifier that the verifier will look up.  On a
+    /// `verdict = "require-both"` rule it runs ahead of
+    /// `verification_check_ids`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub verification_check_id: Option<String>,
+
+    /// The checks a `verdict = "require-both"` rule runs before approval,
+    /// in order, e.g. `verification_check_ids = ["dose-range", "phi-scan"]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub verification_check_ids: Vec<String>,
+
+    /// Key/value pairs that `PolicyContext::metadata` must contain for the
+    /// rule to match, e.g. `metadata_match = { urgency = "routine" }`.  Each
+    /// key must be present with an equal value.  Must be a table.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_match: Option<toml::Value>,
+
+    /// The most times this rule may fire for one agent within a window of
+    /// `window_secs`; further matches are denied until the next window.
+    /// Counted in the engine's `RateCounterStore`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_invocations_per_window: Option<u64>,
+
+    /// Length in seconds of the `max_invocations_per_window` window.
+    /// Defaults to `rate::DEFAULT_WINDOW_SECS`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_secs: Option<u64>,
+
+    /// Labels a `verdict = "continue"` rule attaches to the decision when it
+    /// matches, e.g. `tags = ["phi-access"]`.  Ignored for other verdicts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl PolicyRule {
@@ -82,12 +183,163 @@ impl PolicyRule {
     /// Matching logic:
     /// - `"*"` in the rule's `action` field matches any action string.
     /// - `"*"` in the rule's `resource` field matches any resource string.
-    /// - Otherwise, both fields must match exactly (case-sensitive).
+    /// - Otherwise, both fields must match exactly (case-sensitive), subject
+    ///   to the resource forms described on `matches_resource`.
     pub fn matches(&self, action: &str, resource: &str) -> bool {
-        let action_matches = self.action == "*" || self.action == action;
-        let resource_matches = self.resource == "*" || self.resource == resource;
-        action_matches && resource_matches
+        self.matches_action(action) && self.matches_resource(resource)
     }
+
+    /// Return true if this rule's `action` pattern matches `action`.
+    pub fn matches_action(&self, action: &str) -> bool {
+        self.action == "*" || self.action == action
+    }
+
+    /// Return true if `action_kind` counts as this rule's `action_kind`, if
+    /// any (see `ActionKind::is_a`).  A rule without `action_kind` matches
+    /// every action, classified or not.
+    pub fn matches_action_kind(&self, action_kind: Option<ActionKind>) -> bool {
+        self.action_kind
+            .is_none_or(|kind| action_kind.is_some_and(|actual| actual.is_a(kind)))
+    }
+
+    /// Return true if this rule's `resource` pattern matches `resource`.
+    ///
+    /// - `"*"` matches any resource.
+    /// - `"prefix/**"` matches every resource under `prefix/` (not `prefix`
+    ///   itself).
+    /// - A leading `!` negates the rest of the pattern: `"!x"` matches every
+    ///   resource `"*"` would except `x`, and `"!prefix/**"` every resource
+    ///   outside `prefix/`.  `"!*"` therefore matches nothing.  Only one `!`
+    ///   is stripped.
+    /// - Anything else must match exactly (case-sensitive).
+    pub fn matches_resource(&self, resource: &str) -> bool {
+        resource_matches(&self.resource, resource)
+    }
+
+    /// The capabilities this rule requires on `resource`: its
+    /// `required_capabilities` followed by those of every
+    /// `capability_conditions` entry whose pattern matches `resource`.
+    pub fn capabilities_required_on<'a>(
+        &'a self,
+        resource: &'a str,
+    ) -> impl Iterator<Item = &'a String> + 'a {
+        self.required_capabilities.iter().chain(
+            self.capability_conditions
+                .iter()
+                .filter(move |condition| resource_matches(&condition.resource, resource))
+                .flat_map(|condition| &condition.capabilities),
+        )
+    }
+
+    /// Return true if `metadata` holds every key/value pair of this rule's
+    /// `metadata_match`.  A rule without `metadata_match` matches any
+    /// metadata; a non-table `metadata_match` matches none.
+    pub fn matches_metadata(&self, metadata: &serde_json::Value) -> bool {
+        let Some(required) = &self.metadata_match else {
+            return true;
+        };
+        let Some(table) = required.as_table() else {
+            return false;
+        };
+        table.iter().all(|(key, expected)| {
+            match (metadata.get(key), serde_json::to_value(expected)) {
+                (Some(actual), Ok(expected)) => *actual == expected,
+                _ => false,
+            }
+        })
+    }
+}
+
+/// Match a resource pattern, honoring a leading `!` (see
+/// `PolicyRule::matches_resource`).
+fn resource_matches(pattern: &str, resource: &str) -> bool {
+    match pattern.strip_prefix('!') {
+        Some(excluded) => !resource_pattern_matches(excluded, resource),
+        None => resource_pattern_matches(pattern, resource),
+    }
+}
+
+/// Match a resource pattern without a leading `!`: `"*"`, `"prefix/**"` or
+/// an exact resource name.
+fn resource_pattern_matches(pattern: &str, resource: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+    match pattern.strip_suffix("/**") {
+        Some(prefix) => resource
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('/')),
+        None => pattern == resource,
+    }
+}
+
+/// Capabilities a rule requires only on resources matching `resource`.
+///
+/// `resource` takes the same patterns as `PolicyRule::resource`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityCondition {
+    /// The resource pattern the condition applies to.
+    pub resource: String,
+
+    /// Capabilities required, in addition to the rule's own, when
+    /// `resource` matches.
+    pub capabilities: Vec<String>,
+}
+
+/// Settings shared by every rule that names the group in its `group` field.
+///
+/// ```toml
+/// [[groups]]
+/// name = "clinical-readers"
+/// required_capabilities = ["clinical-data.read"]
+///
+/// [[rules]]
+/// id = "allow-symptom-analysis"
+/// group = "clinical-readers"
+/// # ...
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyGroup {
+    /// The name rules refer to.
+    pub name: String,
+
+    /// Capabilities added to the `required_capabilities` of every member rule.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_capabilities: Vec<String>,
+}
+
+/// The verdict applied when no rule matches a request.
+///
+/// ```toml
+/// default_verdict = "deny"   # the default — fail closed
+/// default_verdict = "allow"  # fail open; development and playgrounds only
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DefaultVerdict {
+    /// Deny unmatched requests.  The only safe choice for production.
+    #[default]
+    Deny,
+    /// Allow unmatched requests.  Every default-allow is logged at `warn`.
+    Allow,
+}
+
+/// Which matching rules' `required_capabilities` the engine enforces.
+///
+/// ```toml
+/// capability_mode = "first-match"   # the default
+/// capability_mode = "restrictive"   # every matching rule's requirements apply
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CapabilityMode {
+    /// Only the first matching rule's requirements are checked.
+    #[default]
+    FirstMatch,
+    /// Every rule matching the request must have its requirements met; a
+    /// capability missing for any of them denies, even though the first
+    /// matching rule still decides the verdict otherwise.
+    Restrictive,
 }
 
 /// The top-level structure deserialized from a TOML policy file.
@@ -103,8 +355,101 @@ impl PolicyRule {
 /// resource = "*"
 /// verdict = "allow"
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PolicyConfig {
+    /// The verdict when no rule matches.  Defaults to `deny`.
+    #[serde(default)]
+    pub default_verdict: DefaultVerdict,
+
+    /// Which matching rules' capability requirements are enforced.
+    /// Defaults to `first-match`.
+    #[serde(default, skip_serializing_if = "is_first_match")]
+    pub capability_mode: CapabilityMode,
+
+    /// Named settings that rules can inherit through their `group` field.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<PolicyGroup>,
+
     /// Ordered list of rules.  First match wins.
     pub rules: Vec<PolicyRule>,
 }
+
+fn is_first_match(mode: &CapabilityMode) -> bool {
+    *mode == CapabilityMode::FirstMatch
+}
+
+impl PolicyConfig {
+    /// Merge each rule's group requirements into the rule itself.
+    ///
+    /// Inherited capabilities are appended after the rule's own, skipping
+    /// any the rule already lists, so resolving twice changes nothing.
+    /// Returns `VeritasError::ConfigError` if a rule names an undefined group.
+    pub fn resolve_groups(&mut self) -> VeritasResult<()> {
+        for rule in &mut self.rules {
+            let Some(group_name) = &rule.group else {
+                continue;
+            };
+            let group = self
+                .groups
+                .iter()
+                .find(|g| &g.name == group_name)
+                .ok_or_else(|| VeritasError::ConfigError {
+                    reason: format!(
+                        "rule '{}' references undefined policy group '{}'",
+                        rule.id, group_name
+                    ),
+                })?;
+            for capability in &group.required_capabilities {
+                if !rule.required_capabilities.contains(capability) {
+                    rule.required_capabilities.push(capability.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Every capability requirement absent from `catalog`, as
+    /// `(rule id, capability)` pairs in declaration order.
+    ///
+    /// Covers `required_capabilities` (group requirements included once
+    /// resolved) and `capability_conditions`.
+    pub fn unknown_capabilities(&self, catalog: &CapabilityCatalog) -> Vec<(String, String)> {
+        self.rules
+            .iter()
+            .flat_map(|rule| {
+                rule.required_capabilities
+                    .iter()
+                    .chain(rule.capability_conditions.iter().flat_map(|c| &c.capabilities))
+                    .filter(|cap| !catalog.recognizes(cap))
+                    .map(|cap| (rule.id.clone(), cap.clone()))
+            })
+            .collect()
+    }
+
+    /// Reject any rule whose `metadata_match` is not a table.
+    ///
+    /// Returns `VeritasError::ConfigError` naming the first offending rule.
+    pub fn check_metadata_matches(&self) -> VeritasResult<()> {
+        match self
+            .rules
+            .iter()
+            .find(|r| r.metadata_match.as_ref().is_some_and(|m| !m.is_table()))
+        {
+            Some(rule) => Err(VeritasError::ConfigError {
+                reason: format!("rule '{}' has a metadata_match that is not a table", rule.id),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Serialize this configuration back to a TOML policy document.
+    ///
+    /// Optional fields that are `None` are omitted rather than written empty,
+    /// so the output loads back through `TomlPolicyEngine::from_toml_str` into
+    /// an equal `PolicyConfig`.
+    pub fn to_toml_string(&self) -> VeritasResult<String> {
+        toml::to_string(self).map_err(|e| VeritasError::ConfigError {
+            reason: format!("failed to serialize policy TOML: {}", e),
+        })
+    }
+}
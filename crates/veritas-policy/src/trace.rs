@@ -0,0 +1,172 @@
+//! Structured explanations of policy decisions.
+//!
+//! `TomlPolicyEngine::evaluate_explain` returns a `PolicyTrace` recording how
+//! every rule it considered was handled — skipped because its action,
+//! action kind, resource or metadata pattern did not match, overridden by a missing
+//! capability or an exhausted rate limit, continued past after tagging, or
+//! fired — together with the final verdict and the accumulated tags.
+//! `PolicyTrace::to_json` renders the trace for auditors.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use veritas_contracts::policy::{DenyCode, PolicyDecision, PolicyVerdict};
+
+/// What happened to one rule during evaluation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "kebab-case")]
+pub enum RuleOutcome {
+    /// The rule's `action` pattern did not match; the rule was skipped.
+    ActionMismatch,
+
+    /// The `action` matched but the context's `action_kind` is not covered
+    /// by the rule's; the rule was skipped.
+    ActionKindMismatch,
+
+    /// The `action` matched but the `resource` pattern did not; the rule was
+    /// skipped.
+    ResourceMismatch,
+
+    /// The `action` and `resource` matched but the context's metadata lacks
+    /// a pair from the rule's `metadata_match`; the rule was skipped.
+    MetadataMismatch,
+
+    /// The rule matched but the agent lacks one of its
+    /// `required_capabilities`, so the engine denied instead of applying the
+    /// rule's verdict.
+    CapabilityOverride {
+        /// The first required capability the agent does not hold.
+        missing_capability: String,
+    },
+
+    /// The rule matched but had already fired its
+    /// `max_invocations_per_window` times in the current window, so the
+    /// engine denied instead of applying the rule's verdict.
+    RateLimited {
+        /// The rule's `max_invocations_per_window`.
+        max_invocations: u64,
+        /// The window length in seconds.
+        window_secs: u64,
+    },
+
+    /// The rule matched with `verdict = "continue"`: its tags were added
+    /// and evaluation went on to the next rule.
+    Continued {
+        /// The tags the rule added.
+        tags: Vec<String>,
+    },
+
+    /// The rule matched and its verdict was returned.
+    Fired,
+}
+
+/// The outcome of evaluating one rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleEvaluation {
+    /// The `id` of the rule.
+    pub rule_id: String,
+
+    /// What happened to the rule.
+    #[serde(flatten)]
+    pub outcome: RuleOutcome,
+}
+
+/// The full decision path of one policy evaluation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyTrace {
+    /// One entry per rule considered, in declaration order.  Evaluation stops
+    /// at the first matching rule that does not continue, so rules after it
+    /// do not appear — except,
+    /// in restrictive capability mode, a later matching rule whose missing
+    /// capability overrode the verdict.
+    pub evaluations: Vec<RuleEvaluation>,
+
+    /// The verdict the engine returned.
+    pub verdict: PolicyVerdict,
+
+    /// The tags of every `continue` rule that matched before the verdict was
+    /// reached, in order, without duplicates.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl PolicyTrace {
+    /// The id of the rule that decided the verdict, or `None` when no rule
+    /// matched and the default verdict applied.
+    pub fn matched_rule(&self) -> Option<&str> {
+        self.evaluations
+            .iter()
+            .find(|e| {
+                matches!(
+                    e.outcome,
+                    RuleOutcome::Fired
+                        | RuleOutcome::CapabilityOverride { .. }
+                        | RuleOutcome::RateLimited { .. }
+                )
+            })
+            .map(|e| e.rule_id.as_str())
+    }
+
+    /// Why the verdict is a denial, or `None` if it is not a `Deny`.
+    ///
+    /// A denial with a deciding rule is `MissingCapability` when the rule
+    /// was overridden, `RateLimited` when it exceeded its rate limit and
+    /// `RuleDenied` when it fired; without one it is `NoRuleMatched`.
+    pub fn deny_code(&self) -> Option<DenyCode> {
+        if !matches!(self.verdict, PolicyVerdict::Deny { .. }) {
+            return None;
+        }
+        let deciding = self.evaluations.iter().find(|e| {
+            matches!(
+                e.outcome,
+                RuleOutcome::Fired
+                    | RuleOutcome::CapabilityOverride { .. }
+                    | RuleOutcome::RateLimited { .. }
+            )
+        });
+        Some(match deciding.map(|e| &e.outcome) {
+            Some(RuleOutcome::CapabilityOverride { .. }) => DenyCode::MissingCapability,
+            Some(RuleOutcome::RateLimited { .. }) => DenyCode::RateLimited,
+            Some(_) => DenyCode::RuleDenied,
+            None => DenyCode::NoRuleMatched,
+        })
+    }
+
+    /// The verdict with its deciding rule, deny code, tags and this trace
+    /// rendered by `to_json`, as returned by `PolicyEngine::decide`.
+    pub fn to_decision(&self) -> PolicyDecision {
+        PolicyDecision {
+            verdict: self.verdict.clone(),
+            rule_id: self.matched_rule().map(str::to_string),
+            deny_code: self.deny_code(),
+            trace: Some(self.to_json()),
+            tags: self.tags.clone(),
+        }
+    }
+
+    /// Render the trace as JSON: the ordered per-rule outcomes, the deciding
+    /// rule (`null` for the default verdict), the final verdict and, when
+    /// any, the tags.
+    ///
+    /// ```json
+    /// {
+    ///   "rules": [
+    ///     { "rule_id": "deny-delete", "outcome": "action-mismatch" },
+    ///     { "rule_id": "allow-read", "outcome": "fired" }
+    ///   ],
+    ///   "matched_rule": "allow-read",
+    ///   "verdict": "Allow"
+    /// }
+    /// ```
+    pub fn to_json(&self) -> Value {
+        let mut json = json!({
+            "rules": self.evaluations,
+            "matched_rule": self.matched_rule(),
+            "verdict": self.verdict,
+        });
+        if !self.tags.is_empty() {
+            json["tags"] = json!(self.tags);
+        }
+        json
+    }
+}
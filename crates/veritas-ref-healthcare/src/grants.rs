@@ -0,0 +1,49 @@
+//! Capability grants for scenario runs.
+//!
+//! Each scenario grants its agents the capabilities its story needs.  A
+//! `Grants` passed to a scenario's `capture_scenario_with` either keeps
+//! those defaults or replaces them at every stage, so the demo's `--grant`
+//! and `--deny-all` flags can drive the capability-missing paths without
+//! editing a scenario.
+
+use veritas_contracts::capability::{Capability, CapabilitySet};
+
+/// Which capabilities a scenario run grants its agents.
+#[derive(Debug, Clone, Default)]
+pub enum Grants {
+    /// Every stage is granted what the scenario itself grants.
+    #[default]
+    Scenario,
+
+    /// Every stage is granted exactly this set instead.
+    Only(CapabilitySet),
+}
+
+impl Grants {
+    /// Grant every stage exactly the named capabilities.
+    pub fn only<I>(names: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let mut capabilities = CapabilitySet::default();
+        for name in names {
+            capabilities.grant(Capability::new(name));
+        }
+        Grants::Only(capabilities)
+    }
+
+    /// Grant no capabilities at all.
+    pub fn deny_all() -> Self {
+        Grants::Only(CapabilitySet::default())
+    }
+
+    /// The capabilities a stage runs with, given those the scenario grants
+    /// it by default.
+    pub fn apply(&self, scenario: CapabilitySet) -> CapabilitySet {
+        match self {
+            Grants::Scenario => scenario,
+            Grants::Only(capabilities) => capabilities.clone(),
+        }
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! Healthcare reference runtime for the VERITAS policy-bound AI execution system.
 //!
-//! Demonstrates five clinical AI scenarios using mock data:
+//! Demonstrates six clinical AI scenarios using mock data:
 //!
 //! 1. **Drug Interaction Checker** — capability-gated database query with
 //!    structured output verification.
@@ -16,8 +16,17 @@
 //! 5. **Prior Authorization Workflow** — `RequireApproval` verdict exercised to
 //!    completion: physician approval simulated, then two sub-cases (PA approved
 //!    vs. denied at insurance eligibility).
+//! 6. **Conditional Disclosure** — `RequireVerification` verdict: a patient
+//!    summary is disclosed only if the policy-named PHI content scan passes.
+//!
+//! Every scenario also exposes `capture_scenario()`, returning a
+//! `report::ScenarioCapture` for programmatic assertions, and
+//! `capture_scenario_with()`, which runs it under `grants::Grants` in place
+//! of its own capability grants.
 //!
 //! All data is hardcoded and fictional. No external API calls are made.
 
+pub mod grants;
 pub mod mock_data;
+pub mod report;
 pub mod scenarios;
@@ -4,10 +4,143 @@
 //! contacted. This module acts as a stand-in for real clinical databases in a
 //! production deployment.
 
+use std::{collections::HashMap, sync::OnceLock};
+
 use serde_json::{json, Value};
 
 // ── Drug Interaction Database (mock) ─────────────────────────────────────────
 
+/// Severity of a drug interaction in the mock database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    High,
+    Medium,
+    Low,
+    /// The pair is not in the database.
+    Unknown,
+}
+
+impl Severity {
+    /// The label used in interaction results, e.g. `"HIGH"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::High => "HIGH",
+            Severity::Medium => "MEDIUM",
+            Severity::Low => "LOW",
+            Severity::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+/// Recommendation returned when neither the pair, its severity nor
+/// `Severity::Unknown` has a template.
+const FALLBACK_RECOMMENDATION: &str =
+    "Consult a clinical pharmacist or full drug interaction database before co-prescribing";
+
+/// Recommendation text for interaction results, derived from the severity.
+///
+/// Every interaction of a severity shares that severity's template, so
+/// severity and guidance cannot drift apart pair by pair.  A pair that needs
+/// more specific guidance gets an override, matched order-independently and
+/// case-insensitively.
+#[derive(Debug, Clone)]
+pub struct RecommendationTemplates {
+    by_severity: HashMap<Severity, String>,
+    overrides: HashMap<(String, String), String>,
+}
+
+impl RecommendationTemplates {
+    /// Templates keyed by severity, with no pair overrides.
+    pub fn new(by_severity: HashMap<Severity, String>) -> Self {
+        Self {
+            by_severity,
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Use `recommendation` for the pair `drug_a` + `drug_b` instead of its
+    /// severity's template.
+    pub fn with_override(
+        mut self,
+        drug_a: &str,
+        drug_b: &str,
+        recommendation: impl Into<String>,
+    ) -> Self {
+        self.overrides.insert(pair_key(drug_a, drug_b), recommendation.into());
+        self
+    }
+
+    /// The recommendation for the pair: its override, else the template for
+    /// `severity`, else the `Severity::Unknown` template.
+    pub fn recommendation(&self, drug_a: &str, drug_b: &str, severity: Severity) -> &str {
+        self.overrides
+            .get(&pair_key(drug_a, drug_b))
+            .or_else(|| self.by_severity.get(&severity))
+            .or_else(|| self.by_severity.get(&Severity::Unknown))
+            .map(String::as_str)
+            .unwrap_or(FALLBACK_RECOMMENDATION)
+    }
+}
+
+impl Default for RecommendationTemplates {
+    /// The reference database's guidance: a baseline per severity, and
+    /// overrides for the known pairs with pair-specific monitoring advice.
+    fn default() -> Self {
+        Self::new(HashMap::from([
+            (
+                Severity::High,
+                "Avoid concurrent use; if clinically necessary, monitor closely for adverse effects".to_string(),
+            ),
+            (
+                Severity::Medium,
+                "Use with caution; monitor for adverse effects and adjust doses as needed".to_string(),
+            ),
+            (
+                Severity::Low,
+                "Generally safe for concurrent use; monitor patients with relevant risk factors".to_string(),
+            ),
+            (Severity::Unknown, FALLBACK_RECOMMENDATION.to_string()),
+        ]))
+        .with_override(
+            "warfarin",
+            "aspirin",
+            "Avoid concurrent use; if clinically necessary, monitor INR weekly and for signs of bleeding",
+        )
+        .with_override(
+            "metformin",
+            "contrast-dye",
+            "Withhold metformin 48 hours before and after contrast administration; recheck renal function before resuming",
+        )
+        .with_override(
+            "lisinopril",
+            "potassium",
+            "Monitor serum potassium within 1 week of starting or adjusting doses; avoid potassium supplements unless deficiency confirmed",
+        )
+        .with_override(
+            "amoxicillin",
+            "ibuprofen",
+            "Monitor for reduced antibiotic efficacy in patients with renal impairment; generally safe for short-term concurrent use",
+        )
+    }
+}
+
+/// Order-independent, case-insensitive key for a drug pair.
+fn pair_key(drug_a: &str, drug_b: &str) -> (String, String) {
+    let a = drug_a.to_lowercase();
+    let b = drug_b.to_lowercase();
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// The templates `check_drug_interaction` uses.
+fn default_templates() -> &'static RecommendationTemplates {
+    static TEMPLATES: OnceLock<RecommendationTemplates> = OnceLock::new();
+    TEMPLATES.get_or_init(RecommendationTemplates::default)
+}
+
 /// Look up the interaction severity between two drugs.
 ///
 /// Known pairs (order-independent):
@@ -16,8 +149,18 @@ use serde_json::{json, Value};
 /// - lisinopril + potassium     → MEDIUM
 /// - amoxicillin + ibuprofen    → LOW
 ///
-/// Any other combination returns an UNKNOWN severity.
+/// Any other combination returns an UNKNOWN severity.  The recommendation
+/// comes from `RecommendationTemplates::default()`.
 pub fn check_drug_interaction(drug_a: &str, drug_b: &str) -> Value {
+    check_drug_interaction_with(drug_a, drug_b, default_templates())
+}
+
+/// `check_drug_interaction` with the recommendation taken from `templates`.
+pub fn check_drug_interaction_with(
+    drug_a: &str,
+    drug_b: &str,
+    templates: &RecommendationTemplates,
+) -> Value {
     // Normalize to lowercase for case-insensitive matching.
     let a = drug_a.to_lowercase();
     let b = drug_b.to_lowercase();
@@ -27,35 +170,30 @@ pub fn check_drug_interaction(drug_a: &str, drug_b: &str) -> Value {
         (a == x && b == y) || (a == y && b == x)
     };
 
-    let (severity, mechanism, recommendation) = if is_pair("warfarin", "aspirin") {
+    let (severity, mechanism) = if is_pair("warfarin", "aspirin") {
         (
-            "HIGH",
+            Severity::High,
             "Both agents inhibit platelet function and increase bleeding risk via distinct pathways",
-            "Avoid concurrent use; if clinically necessary, monitor INR weekly and for signs of bleeding",
         )
     } else if is_pair("metformin", "contrast-dye") {
         (
-            "HIGH",
+            Severity::High,
             "Iodinated contrast may cause acute kidney injury, impairing metformin clearance and risking lactic acidosis",
-            "Withhold metformin 48 hours before and after contrast administration; recheck renal function before resuming",
         )
     } else if is_pair("lisinopril", "potassium") {
         (
-            "MEDIUM",
+            Severity::Medium,
             "ACE inhibitors reduce aldosterone secretion, decreasing potassium excretion and risking hyperkalemia",
-            "Monitor serum potassium within 1 week of starting or adjusting doses; avoid potassium supplements unless deficiency confirmed",
         )
     } else if is_pair("amoxicillin", "ibuprofen") {
         (
-            "LOW",
+            Severity::Low,
             "NSAIDs may slightly reduce the renal clearance of amoxicillin at high doses",
-            "Monitor for reduced antibiotic efficacy in patients with renal impairment; generally safe for short-term concurrent use",
         )
     } else {
         (
-            "UNKNOWN",
+            Severity::Unknown,
             "No interaction data available for this drug pair in the reference database",
-            "Consult a clinical pharmacist or full drug interaction database before co-prescribing",
         )
     };
 
@@ -65,10 +203,10 @@ pub fn check_drug_interaction(drug_a: &str, drug_b: &str) -> Value {
             "drug_b": drug_b
         },
         "result": {
-            "severity": severity,
+            "severity": severity.as_str(),
             "mechanism": mechanism
         },
-        "recommendation": recommendation
+        "recommendation": templates.recommendation(drug_a, drug_b, severity)
     })
 }
 
@@ -134,35 +272,140 @@ pub fn get_patient_symptoms(patient_id: &str) -> Value {
 
 // ── Patient Records (mock) ────────────────────────────────────────────────────
 
+/// A fictional patient held in the mock records table.
+struct PatientRecord {
+    age: u32,
+    sex: &'static str,
+    primary_language: &'static str,
+    /// `(ICD-10 code, description)` pairs.
+    conditions: &'static [(&'static str, &'static str)],
+    /// `(name, dose, frequency)` triples.
+    medications: &'static [(&'static str, &'static str, &'static str)],
+    ai_query_consent: bool,
+    last_updated: &'static str,
+}
+
+impl PatientRecord {
+    fn to_json(&self, patient_id: &str) -> Value {
+        let conditions: Vec<Value> = self
+            .conditions
+            .iter()
+            .map(|(code, description)| json!({ "code": code, "description": description }))
+            .collect();
+        let medications: Vec<Value> = self
+            .medications
+            .iter()
+            .map(|(name, dose, frequency)| json!({ "name": name, "dose": dose, "frequency": frequency }))
+            .collect();
+
+        json!({
+            "patient_id": patient_id,
+            "demographics": {
+                "age": self.age,
+                "sex": self.sex,
+                "primary_language": self.primary_language
+            },
+            "conditions": conditions,
+            "medications": medications,
+            "ai_query_consent": self.ai_query_consent,
+            "last_updated": self.last_updated
+        })
+    }
+}
+
+/// The known patients, keyed by patient ID.
+fn patient_table() -> &'static HashMap<&'static str, PatientRecord> {
+    static TABLE: OnceLock<HashMap<&'static str, PatientRecord>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        HashMap::from([
+            (
+                "patient-101",
+                PatientRecord {
+                    age: 58,
+                    sex: "M",
+                    primary_language: "English",
+                    conditions: &[
+                        ("E11.9", "Type 2 diabetes mellitus without complications"),
+                        ("I10", "Essential hypertension"),
+                        ("D50.9", "Iron deficiency anemia, unspecified"),
+                    ],
+                    medications: &[
+                        ("Metformin", "500 mg", "twice daily"),
+                        ("Lisinopril", "10 mg", "once daily"),
+                        ("Ferrous sulfate", "325 mg", "once daily"),
+                    ],
+                    ai_query_consent: true,
+                    last_updated: "2026-02-14",
+                },
+            ),
+            (
+                "patient-042",
+                PatientRecord {
+                    age: 34,
+                    sex: "F",
+                    primary_language: "Spanish",
+                    conditions: &[
+                        ("J45.909", "Unspecified asthma, uncomplicated"),
+                        ("D50.9", "Iron deficiency anemia, unspecified"),
+                    ],
+                    medications: &[
+                        ("Albuterol inhaler", "90 mcg", "as needed"),
+                        ("Ferrous sulfate", "325 mg", "once daily"),
+                    ],
+                    ai_query_consent: true,
+                    last_updated: "2026-02-14",
+                },
+            ),
+            (
+                "patient-201nc",
+                PatientRecord {
+                    age: 71,
+                    sex: "F",
+                    primary_language: "English",
+                    conditions: &[
+                        ("I48.91", "Unspecified atrial fibrillation"),
+                        ("N18.3", "Chronic kidney disease, stage 3"),
+                    ],
+                    medications: &[
+                        ("Warfarin", "5 mg", "once daily"),
+                        ("Furosemide", "20 mg", "once daily"),
+                    ],
+                    ai_query_consent: false,
+                    last_updated: "2026-01-30",
+                },
+            ),
+        ])
+    })
+}
+
+/// The record synthesized for patient IDs not in the table.
+const DEFAULT_PATIENT: PatientRecord = PatientRecord {
+    age: 45,
+    sex: "U",
+    primary_language: "English",
+    conditions: &[("Z00.00", "Encounter for general adult medical examination")],
+    medications: &[],
+    ai_query_consent: true,
+    last_updated: "2026-02-01",
+};
+
 /// Return a mock patient record for the given patient ID.
 ///
 /// The record includes conditions, current medications, and a consent flag that
 /// controls whether AI-assisted queries are permitted.
 ///
-/// Patients with IDs ending in "nc" (no-consent) have `ai_query_consent: false`.
+/// Known IDs (`patient-101`, `patient-042`, `patient-201nc`) return their own
+/// distinct records.  Any other ID gets a synthesized default record, with
+/// `ai_query_consent: false` if the ID ends in "nc" (no-consent).
 pub fn get_patient_record(patient_id: &str) -> Value {
-    let has_consent = !patient_id.ends_with("nc");
-
-    json!({
-        "patient_id": patient_id,
-        "demographics": {
-            "age": 58,
-            "sex": "M",
-            "primary_language": "English"
-        },
-        "conditions": [
-            { "code": "E11.9",  "description": "Type 2 diabetes mellitus without complications" },
-            { "code": "I10",    "description": "Essential hypertension" },
-            { "code": "D50.9",  "description": "Iron deficiency anemia, unspecified" }
-        ],
-        "medications": [
-            { "name": "Metformin",   "dose": "500 mg", "frequency": "twice daily" },
-            { "name": "Lisinopril",  "dose": "10 mg",  "frequency": "once daily"  },
-            { "name": "Ferrous sulfate", "dose": "325 mg", "frequency": "once daily" }
-        ],
-        "ai_query_consent": has_consent,
-        "last_updated": "2026-02-14"
-    })
+    match patient_table().get(patient_id) {
+        Some(record) => record.to_json(patient_id),
+        None => PatientRecord {
+            ai_query_consent: !patient_id.ends_with("nc"),
+            ..DEFAULT_PATIENT
+        }
+        .to_json(patient_id),
+    }
 }
 
 // ── Insurance Coverage (mock) ─────────────────────────────────────────────────
@@ -184,3 +427,83 @@ pub fn get_insurance_coverage(procedure_code: &str) -> Value {
         "checked_date": "2026-02-18"
     })
 }
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A pair not in the database gets the UNKNOWN-severity template.
+    #[test]
+    fn test_unknown_pair_uses_unknown_template() {
+        let templates = RecommendationTemplates::default();
+        let result = check_drug_interaction_with("ibuprofen", "vitamin-c", &templates);
+
+        assert_eq!(result["result"]["severity"], "UNKNOWN");
+        assert_eq!(
+            result["recommendation"],
+            templates.recommendation("x", "y", Severity::Unknown)
+        );
+    }
+
+    /// A known HIGH pair uses its override; without one it falls back to
+    /// the HIGH baseline shared by every HIGH interaction.
+    #[test]
+    fn test_high_pair_uses_override_over_baseline() {
+        let result = check_drug_interaction("Aspirin", "Warfarin");
+        assert_eq!(result["result"]["severity"], "HIGH");
+        assert!(result["recommendation"].as_str().unwrap().contains("monitor INR weekly"));
+
+        let baseline = RecommendationTemplates::new(HashMap::from([(
+            Severity::High,
+            "HIGH baseline".to_string(),
+        )]));
+        for (a, b) in [("warfarin", "aspirin"), ("metformin", "contrast-dye")] {
+            assert_eq!(check_drug_interaction_with(a, b, &baseline)["recommendation"], "HIGH baseline");
+        }
+
+        let overridden = baseline.with_override("ASPIRIN", "warfarin", "pair-specific");
+        assert_eq!(
+            check_drug_interaction_with("warfarin", "aspirin", &overridden)["recommendation"],
+            "pair-specific"
+        );
+        assert_eq!(
+            check_drug_interaction_with("metformin", "contrast-dye", &overridden)["recommendation"],
+            "HIGH baseline"
+        );
+    }
+
+    fn condition_codes(record: &Value) -> Vec<&str> {
+        record["conditions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["code"].as_str().unwrap())
+            .collect()
+    }
+
+    /// Distinct known patients have distinct conditions.
+    #[test]
+    fn test_known_patients_have_distinct_records() {
+        let a = get_patient_record("patient-101");
+        let b = get_patient_record("patient-042");
+
+        assert_eq!(a["patient_id"], "patient-101");
+        assert_eq!(b["patient_id"], "patient-042");
+        assert_ne!(condition_codes(&a), condition_codes(&b));
+        assert_eq!(get_patient_record("patient-201nc")["ai_query_consent"], false);
+    }
+
+    /// Unknown IDs get the default record with consent set by the "nc" suffix.
+    #[test]
+    fn test_unknown_patient_honors_consent_suffix() {
+        let consenting = get_patient_record("patient-999");
+        let refusing = get_patient_record("patient-999nc");
+
+        assert_eq!(consenting["patient_id"], "patient-999");
+        assert_eq!(consenting["ai_query_consent"], true);
+        assert_eq!(refusing["ai_query_consent"], false);
+        assert_eq!(condition_codes(&consenting), condition_codes(&refusing));
+    }
+}
@@ -0,0 +1,424 @@
+//! Structured captures of scenario runs.
+//!
+//! Every scenario module exposes a `capture_scenario()` function that wires
+//! the same components as the demo but returns what happened instead of
+//! printing it.  A run is recorded as one `StageCapture` per executor step —
+//! its outcome plus the exported audit log of that step's execution — so the
+//! integration suite in `tests/` can assert on verdict sequences, chain
+//! integrity and output fields without scraping console output.
+//!
+//! A stage also carries notes: the warnings of warn-severity rules,
+//! collected by a `WarningTap` around the verifier from every attempt the
+//! step made, and the agent's advisories on its output.  `print_stage_notes`
+//! prints them for the demo; `ScenarioCapture::report` condenses a run into a
+//! serializable `ScenarioReport` for its JSON output.
+//!
+//! A scenario that records, with `StageCapture::with_capabilities`, what its
+//! agent declared it needed and what it was granted gets a
+//! `capability_audit` in its report, so a reviewer can see at a glance
+//! whether each step ran with least privilege.
+
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use veritas_audit::{AuditLog, InMemoryAuditWriter};
+use veritas_contracts::{
+    agent::AgentOutput,
+    capability::{Capability, CapabilitySet},
+    error::{VeritasError, VeritasResult},
+    execution::StepResult,
+    policy::{DenyCode, PolicyVerdict},
+    verify::{OutputSchema, RuleSeverity, VerificationReport},
+};
+use veritas_core::traits::Verifier;
+
+/// How one executor step ended.
+#[derive(Debug)]
+pub enum StageOutcome {
+    /// The step passed every check and produced a verified output.
+    Completed { output: AgentOutput },
+
+    /// The policy denied the step before the agent ran, with the denying
+    /// rule's hint on how to proceed, if it gave one.
+    Denied {
+        reason: String,
+        code: DenyCode,
+        remediation: Option<String>,
+    },
+
+    /// The policy suspended the step pending human approval.
+    AwaitingApproval { reason: String, approver_role: String },
+
+    /// The step returned an error (missing capability, failed verification,
+    /// ...).
+    Failed { error: VeritasError },
+}
+
+/// The outcome and audit trail of one executor step.
+#[derive(Debug)]
+pub struct StageCapture {
+    /// Human-readable name of the stage, e.g. `"stage-2/diagnosis-suggester"`.
+    pub label: String,
+
+    /// How the step ended.
+    pub outcome: StageOutcome,
+
+    /// The audit log exported from the step's writer after it returned.
+    pub audit_log: AuditLog,
+
+    /// Result of `InMemoryAuditWriter::verify_integrity` on the step's chain.
+    pub chain_verified: bool,
+
+    /// Warn-severity rule failures reported while verifying the step's
+    /// output, as `"[rule-id] message"`.  Empty unless added with
+    /// `with_warnings`.
+    pub warnings: Vec<String>,
+
+    /// The agent's advisories on its delivered output.
+    pub advisories: Vec<String>,
+
+    /// The capabilities the step's agent declared against those it was
+    /// granted.  `None` unless added with `with_capabilities`.
+    pub capability_audit: Option<CapabilityAudit>,
+}
+
+impl StageCapture {
+    /// Capture a finished step from its result and the writer it audited to.
+    pub fn new(
+        label: impl Into<String>,
+        result: VeritasResult<StepResult>,
+        audit: &Arc<InMemoryAuditWriter>,
+    ) -> Self {
+        let outcome = match result {
+            Ok(StepResult::Complete { output, .. }) | Ok(StepResult::Transitioned { output, .. }) => {
+                StageOutcome::Completed { output }
+            }
+            Ok(StepResult::Denied { reason, code, remediation, .. }) => {
+                StageOutcome::Denied { reason, code, remediation }
+            }
+            Ok(StepResult::AwaitingApproval { reason, approver_role, .. }) => {
+                StageOutcome::AwaitingApproval { reason, approver_role }
+            }
+            Err(error) => StageOutcome::Failed { error },
+        };
+
+        let advisories = match &outcome {
+            StageOutcome::Completed { output } => output.advisories.clone(),
+            _ => Vec::new(),
+        };
+
+        Self {
+            label: label.into(),
+            outcome,
+            audit_log: audit.export_log(),
+            chain_verified: audit.verify_integrity(),
+            warnings: Vec::new(),
+            advisories,
+            capability_audit: None,
+        }
+    }
+
+    /// Attach the verifier warnings collected while the step ran, typically
+    /// `WarningTap::take()`.
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    /// Record the capabilities the step's agent declared, typically
+    /// `Agent::required_capabilities` for the step's state and input, against
+    /// the set it ran with.  Only the grants live at `now` — the executor's
+    /// `Executor::now()` — count, as they do for the executor's own check.
+    pub fn with_capabilities(
+        mut self,
+        required: Vec<String>,
+        granted: &CapabilitySet,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let satisfied = required
+            .iter()
+            .all(|name| granted.has_at(&Capability::new(name.as_str()), now));
+        self.capability_audit = Some(CapabilityAudit {
+            step: self.label.clone(),
+            required,
+            granted: granted.live_at(now).all().map(|c| c.0.clone()).collect(),
+            satisfied,
+        });
+        self
+    }
+
+    /// The verified output, if the step completed.
+    pub fn output(&self) -> Option<&AgentOutput> {
+        match &self.outcome {
+            StageOutcome::Completed { output } => Some(output),
+            _ => None,
+        }
+    }
+
+    /// The policy verdicts recorded in the audit log, in chain order.
+    pub fn verdicts(&self) -> Vec<&PolicyVerdict> {
+        self.audit_log.events.iter().map(|e| &e.record.verdict).collect()
+    }
+
+    /// `"GRANTED"` if the step ran with an unexpired grant of `capability`
+    /// according to its `capability_audit`, `"NOT GRANTED"` otherwise — for
+    /// the demo's capability lines.
+    pub fn capability_status(&self, capability: &str) -> &'static str {
+        let granted = self
+            .capability_audit
+            .as_ref()
+            .is_some_and(|audit| audit.granted.iter().any(|name| name == capability));
+        if granted {
+            "GRANTED"
+        } else {
+            "NOT GRANTED"
+        }
+    }
+}
+
+/// All stages of one scenario run, in execution order.
+#[derive(Debug)]
+pub struct ScenarioCapture {
+    /// Scenario title as printed by the demo.
+    pub name: String,
+
+    /// One entry per executor step, in the order the steps ran.
+    pub stages: Vec<StageCapture>,
+}
+
+impl ScenarioCapture {
+    /// Start an empty capture for the named scenario.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            stages: Vec::new(),
+        }
+    }
+
+    /// Look up a stage by its label.
+    pub fn stage(&self, label: &str) -> Option<&StageCapture> {
+        self.stages.iter().find(|s| s.label == label)
+    }
+
+    /// True if every stage's audit chain verified.
+    pub fn all_chains_verified(&self) -> bool {
+        self.stages.iter().all(|s| s.chain_verified)
+    }
+
+    /// Condense the run into a serializable report.
+    pub fn report(&self) -> ScenarioReport {
+        ScenarioReport {
+            name: self.name.clone(),
+            stages: self
+                .stages
+                .iter()
+                .map(|stage| {
+                    let (outcome, detail) = match &stage.outcome {
+                        StageOutcome::Completed { .. } => ("completed", None),
+                        StageOutcome::Denied { reason, .. } => ("denied", Some(reason.clone())),
+                        StageOutcome::AwaitingApproval { reason, .. } => {
+                            ("awaiting-approval", Some(reason.clone()))
+                        }
+                        StageOutcome::Failed { error } => ("failed", Some(error.to_string())),
+                    };
+                    StageReport {
+                        label: stage.label.clone(),
+                        outcome: outcome.to_string(),
+                        detail,
+                        deny_code: match &stage.outcome {
+                            StageOutcome::Denied { code, .. } => Some(*code),
+                            _ => None,
+                        },
+                        remediation: match &stage.outcome {
+                            StageOutcome::Denied { remediation, .. } => remediation.clone(),
+                            _ => None,
+                        },
+                        audit_events: stage.audit_log.events.len(),
+                        chain_verified: stage.chain_verified,
+                        warnings: stage.warnings.clone(),
+                        advisories: stage.advisories.clone(),
+                    }
+                })
+                .collect(),
+            capability_audit: self
+                .stages
+                .iter()
+                .filter_map(|stage| stage.capability_audit.clone())
+                .collect(),
+        }
+    }
+}
+
+// ── Serializable report ───────────────────────────────────────────────────────
+
+/// A scenario run as printed by the demo's `--format json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScenarioReport {
+    /// Scenario title.
+    pub name: String,
+
+    /// One entry per executor step, in the order the steps ran.
+    pub stages: Vec<StageReport>,
+
+    /// Required versus granted capabilities of every stage that recorded
+    /// them, in the order the steps ran.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capability_audit: Vec<CapabilityAudit>,
+}
+
+/// One stage of a `ScenarioReport`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageReport {
+    /// The stage's label.
+    pub label: String,
+
+    /// `"completed"`, `"denied"`, `"awaiting-approval"` or `"failed"`.
+    pub outcome: String,
+
+    /// The denial or suspension reason, or the error; `None` on completion.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+
+    /// Why a denied stage was denied, e.g. `"missing-capability"`; `None`
+    /// for other outcomes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deny_code: Option<DenyCode>,
+
+    /// How to proceed after a denial, when the denying rule says.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+
+    /// Number of events in the stage's audit chain.
+    pub audit_events: usize,
+
+    /// Whether the stage's audit chain verified.
+    pub chain_verified: bool,
+
+    /// Verifier warnings, as in `StageCapture::warnings`.
+    pub warnings: Vec<String>,
+
+    /// Agent advisories, as in `StageCapture::advisories`.
+    pub advisories: Vec<String>,
+}
+
+/// The capabilities one step required against those it was granted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityAudit {
+    /// The stage's label.
+    pub step: String,
+
+    /// The capabilities the agent declared for the step, in its order.
+    pub required: Vec<String>,
+
+    /// Every unexpired capability the step ran with, sorted by name.
+    pub granted: Vec<String>,
+
+    /// True if every required capability was granted.
+    pub satisfied: bool,
+}
+
+// ── Warning collection ────────────────────────────────────────────────────────
+
+/// Collects the warn-severity failures of every report a wrapped verifier
+/// returns.
+///
+/// The executor delivers an output whose report holds only warnings without
+/// passing them on, so a scenario wraps its verifier with `wrap` and moves
+/// the collected warnings into the stage with `take`.  The tap keeps every
+/// verifier call, so a step that failed verification or was retried before
+/// it passed also contributes the warnings of its earlier attempts.
+#[derive(Debug, Clone, Default)]
+pub struct WarningTap {
+    warnings: Arc<Mutex<Vec<String>>>,
+}
+
+impl WarningTap {
+    /// Create a tap with no warnings collected.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wrap `inner` so its warnings are collected by this tap.
+    pub fn wrap(&self, inner: impl Verifier + 'static) -> Box<dyn Verifier> {
+        Box::new(TappedVerifier {
+            inner: Box::new(inner),
+            warnings: Arc::clone(&self.warnings),
+        })
+    }
+
+    /// Remove and return the warnings collected so far, oldest first.
+    pub fn take(&self) -> Vec<String> {
+        std::mem::take(&mut *self.warnings.lock().expect("warning tap lock poisoned"))
+    }
+}
+
+/// A verifier whose warnings are collected by a `WarningTap`.
+struct TappedVerifier {
+    inner: Box<dyn Verifier>,
+    warnings: Arc<Mutex<Vec<String>>>,
+}
+
+impl TappedVerifier {
+    fn collect(&self, report: VeritasResult<VerificationReport>) -> VeritasResult<VerificationReport> {
+        if let Ok(report) = &report {
+            self.warnings.lock().expect("warning tap lock poisoned").extend(
+                report
+                    .failures
+                    .iter()
+                    .filter(|f| f.severity == RuleSeverity::Warn)
+                    .map(|f| format!("[{}] {}", f.rule_id, f.message)),
+            );
+        }
+        report
+    }
+}
+
+impl Verifier for TappedVerifier {
+    fn verify(&self, output: &AgentOutput, schema: &OutputSchema) -> VeritasResult<VerificationReport> {
+        self.collect(self.inner.verify(output, schema))
+    }
+
+    fn run_check(&self, check_id: &str, output: &AgentOutput) -> VeritasResult<VerificationReport> {
+        self.collect(self.inner.run_check(check_id, output))
+    }
+
+    fn normalize(&self, output: &AgentOutput, schema: &OutputSchema) -> AgentOutput {
+        self.inner.normalize(output, schema)
+    }
+}
+
+// ── Console output ────────────────────────────────────────────────────────────
+
+/// ANSI colour for warnings: yellow, set apart from errors.
+const WARNING_COLOUR: &str = "\x1b[33m";
+/// ANSI colour for advisories: cyan.
+const ADVISORY_COLOUR: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// Print a denial as the scenarios report it, followed by its remediation
+/// hint when there is one.
+pub fn print_denial(code: DenyCode, reason: &str, remediation: Option<&str>) {
+    println!("  DENIED ({}): {}", code.as_str(), reason);
+    if let Some(remediation) = remediation {
+        println!("  {WARNING_COLOUR}Remediation: {remediation}{RESET}");
+    }
+}
+
+/// Print the stage's "Warnings" and "Advisories" sections, each only when
+/// it has entries.
+pub fn print_stage_notes(stage: &StageCapture) {
+    for (heading, colour, notes) in [
+        ("Warnings", WARNING_COLOUR, &stage.warnings),
+        ("Advisories", ADVISORY_COLOUR, &stage.advisories),
+    ] {
+        if notes.is_empty() {
+            continue;
+        }
+        println!("  {colour}{heading}:{RESET}");
+        for note in notes {
+            println!("    {colour}- {note}{RESET}");
+        }
+    }
+}
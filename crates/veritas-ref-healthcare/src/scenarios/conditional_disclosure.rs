@@ -0,0 +1,398 @@
+//! Scenario 6: Conditional Disclosure
+//!
+//! Demonstrates the fourth policy verdict, `RequireVerification`.  Disclosing
+//! a patient summary is allowed only if a named content check passes: the
+//! policy returns `require-verification` with check id "phi-content-scan",
+//! and the executor runs that check on the agent's output after the schema
+//! verification.  A failing check blocks delivery exactly like a failed
+//! schema rule.
+//!
+//! Pipeline walk-through for the demo run:
+//!   1. Policy evaluates (disclose-summary, patient-summary) → RequireVerification
+//!   2. Capability check: agent must hold "clinical-notes.read"
+//!   3. Agent drafts the summary (simulating LLM output)
+//!   4. Verifier checks the output schema, then runs "phi-content-scan"
+//!   5. Clean summary → delivered and audited; PHI-laden summary → blocked
+//!
+//! Sub-case A: the draft is clean → disclosure proceeds.
+//! Sub-case B: the draft leaks an SSN and a phone number → disclosure blocked.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde_json::json;
+
+use veritas_audit::InMemoryAuditWriter;
+use veritas_contracts::{
+    agent::{AgentId, AgentInput, AgentOutput, AgentState, ExecutionId},
+    capability::{Capability, CapabilitySet},
+    error::{VeritasError, VeritasResult},
+    execution::{StepRecord, StepResult},
+    verify::{OutputSchema, RuleSeverity, VerificationRule, VerificationRuleType},
+};
+use veritas_core::{executor::Executor, traits::{Agent, AuditWriter}};
+use veritas_policy::engine::TomlPolicyEngine;
+use veritas_verify::{
+    engine::SchemaVerifier,
+    pii::{self, PiiKind},
+};
+
+use crate::{
+    grants::Grants,
+    report::{
+        print_denial, print_stage_notes, ScenarioCapture, StageCapture, StageOutcome, WarningTap,
+    },
+};
+
+// ── Policy TOML ───────────────────────────────────────────────────────────────
+
+const DISCLOSURE_POLICY: &str = include_str!("../../policies/conditional_disclosure.toml");
+
+/// The check id the policy names in its `require-verification` verdict.
+const PHI_CHECK_ID: &str = "phi-content-scan";
+
+// ── Agent implementation ──────────────────────────────────────────────────────
+
+/// An agent that prepares a patient summary for disclosure.
+///
+/// The summary text comes from the input's `draft` field, standing in for
+/// an LLM-generated draft so the demo can show both a clean and a leaky
+/// summary deterministically.
+pub struct DisclosureAgent;
+
+impl Agent for DisclosureAgent {
+    fn propose(&self, state: &AgentState, input: &AgentInput) -> VeritasResult<AgentOutput> {
+        let patient_id = input.payload["patient_id"].as_str().unwrap_or("unknown");
+        let draft = input.payload["draft"].as_str().unwrap_or("");
+
+        Ok(AgentOutput {
+            kind: "disclosure-summary".to_string(),
+            payload: json!({
+                "patient_id": patient_id,
+                "summary": draft,
+                "prepared_by": state.agent_id.0
+            }),
+            provenance: None,
+            advisories: vec![],
+        })
+    }
+
+    fn transition(&self, state: &AgentState, _output: &AgentOutput) -> VeritasResult<AgentState> {
+        Ok(AgentState {
+            step: state.step + 1,
+            phase: "disclosed".to_string(),
+            ..state.clone()
+        })
+    }
+
+    fn required_capabilities(&self, _state: &AgentState, _input: &AgentInput) -> Vec<String> {
+        vec!["clinical-notes.read".to_string()]
+    }
+
+    fn describe_action(&self, _state: &AgentState, _input: &AgentInput) -> (String, String) {
+        ("disclose-summary".to_string(), "patient-summary".to_string())
+    }
+
+    fn is_terminal(&self, state: &AgentState) -> bool {
+        state.phase == "disclosed"
+    }
+}
+
+// ── Output schema and PHI check ───────────────────────────────────────────────
+
+/// Build the output schema for disclosed summaries.
+///
+/// The schema only checks shape; the PHI scan is not a schema rule — it runs
+/// because the policy demands it for this action.
+fn disclosure_schema() -> OutputSchema {
+    OutputSchema {
+        schema_id: "disclosure-summary-v1".to_string(),
+        json_schema: json!({
+            "type": "object",
+            "required": ["patient_id", "summary"]
+        }),
+        rules: vec![VerificationRule {
+            rule_id: "req-summary".to_string(),
+            description: "Output must contain a summary text".to_string(),
+            severity: RuleSeverity::Error,
+            rule_type: VerificationRuleType::RequiredField {
+                field_path: "summary".to_string(),
+            },
+        }],
+        defaults: vec![],
+    }
+}
+
+/// The "phi-content-scan" check: fail if any string in the payload contains
+/// an SSN, email address or phone number.
+fn phi_content_scan(payload: &serde_json::Value) -> Option<String> {
+    let findings = pii::scan_value(payload, &PiiKind::ALL);
+    if findings.is_empty() {
+        return None;
+    }
+    let located = findings
+        .iter()
+        .map(|(pointer, kind)| format!("{:?} at '{}'", kind, pointer))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(format!("summary contains PHI ({}); disclosure blocked", located))
+}
+
+// ── Arc-wrapped audit writer helper ──────────────────────────────────────────
+
+/// Thin newtype allowing an `Arc<InMemoryAuditWriter>` to be used as
+/// `Box<dyn AuditWriter>`.
+struct ArcAudit(Arc<InMemoryAuditWriter>);
+
+impl AuditWriter for ArcAudit {
+    fn write(&self, record: &StepRecord) -> VeritasResult<()> {
+        self.0.write(record)
+    }
+    fn finalize(&self, execution_id: &str) -> VeritasResult<()> {
+        self.0.finalize(execution_id)
+    }
+}
+
+// ── Scenario runner ───────────────────────────────────────────────────────────
+
+/// One disclosure attempt: the step result, the writer it audited to, and
+/// the capabilities the agent declared against those it was granted as of
+/// `checked_at`, the executor's time once the step ran.
+struct Attempt {
+    result: VeritasResult<StepResult>,
+    audit: Arc<InMemoryAuditWriter>,
+    required: Vec<String>,
+    capabilities: CapabilitySet,
+    checked_at: DateTime<Utc>,
+}
+
+/// Run one disclosure attempt of `draft` for `patient_id` through a freshly
+/// wired executor, granting `clinical-notes.read` unless `grants` says
+/// otherwise.
+///
+/// Returns the step result together with the audit writer so callers can
+/// inspect what was recorded.
+fn attempt_disclosure(
+    patient_id: &str,
+    draft: &str,
+    tap: &WarningTap,
+    grants: &Grants,
+) -> VeritasResult<Attempt> {
+    let policy = TomlPolicyEngine::from_toml_str(DISCLOSURE_POLICY)?;
+
+    let execution_id = ExecutionId::new();
+    let audit_inner = Arc::new(InMemoryAuditWriter::new(execution_id.0.to_string()));
+
+    let mut verifier = SchemaVerifier::new();
+    verifier.register_rule(PHI_CHECK_ID, Box::new(phi_content_scan));
+
+    let executor = Executor::new(
+        Box::new(policy),
+        Box::new(ArcAudit(Arc::clone(&audit_inner))),
+        tap.wrap(verifier),
+        disclosure_schema(),
+    );
+
+    let state = AgentState {
+        agent_id: AgentId("disclosure-agent".to_string()),
+        execution_id,
+        phase: "drafted".to_string(),
+        context: serde_json::Value::Null,
+        step: 0,
+        correlation_id: None,
+    };
+
+    let input = AgentInput {
+        kind: "disclosure-request".to_string(),
+        payload: json!({ "patient_id": patient_id, "draft": draft }),
+    };
+
+    let mut capabilities = CapabilitySet::default();
+    capabilities.grant(Capability::new("clinical-notes.read"));
+    let capabilities = grants.apply(capabilities);
+
+    let required = DisclosureAgent.required_capabilities(&state, &input);
+    let result = executor.step(&DisclosureAgent, state, input, &capabilities);
+    Ok(Attempt {
+        result,
+        audit: audit_inner,
+        required,
+        capabilities,
+        checked_at: executor.now(),
+    })
+}
+
+/// Patient whose summary the scenario discloses.
+const PATIENT_ID: &str = "patient-042";
+
+/// The two sub-cases: stage label, printed title and the drafted summary.
+const CASES: [(&str, &str, &str); 2] = [
+    (
+        "A",
+        "Sub-case A: clean summary",
+        "Stable on metformin and lisinopril; recheck CBC in four weeks.",
+    ),
+    (
+        "B",
+        "Sub-case B: summary leaking PHI",
+        "Stable on metformin. SSN 123-45-6789, call 555-123-4567 with results.",
+    ),
+];
+
+/// Run both disclosure attempts and capture them without printing.
+///
+/// Stage `"A"` is the clean summary, stage `"B"` the one leaking PHI.
+pub fn capture_scenario() -> VeritasResult<ScenarioCapture> {
+    capture_scenario_with(&Grants::Scenario)
+}
+
+/// Run both disclosure attempts as `capture_scenario` does, granting the
+/// agent what `grants` says instead of `clinical-notes.read`.
+pub fn capture_scenario_with(grants: &Grants) -> VeritasResult<ScenarioCapture> {
+    let mut capture = ScenarioCapture::new("Scenario 6: Conditional Disclosure");
+    for (label, _, draft) in CASES {
+        let tap = WarningTap::new();
+        let attempt = attempt_disclosure(PATIENT_ID, draft, &tap, grants)?;
+        capture.stages.push(
+            StageCapture::new(label, attempt.result, &attempt.audit)
+                .with_warnings(tap.take())
+                .with_capabilities(attempt.required, &attempt.capabilities, attempt.checked_at),
+        );
+    }
+    Ok(capture)
+}
+
+/// Run Scenario 6: Conditional Disclosure.
+///
+/// Sub-case A discloses a clean summary; Sub-case B is blocked by the
+/// policy-required PHI content scan.
+pub fn run_scenario() -> VeritasResult<()> {
+    run_scenario_with(&Grants::Scenario)
+}
+
+/// Run Scenario 6 as `run_scenario` does, under `grants`.
+pub fn run_scenario_with(grants: &Grants) -> VeritasResult<()> {
+    println!("=== Scenario 6: Conditional Disclosure ===");
+    println!();
+
+    let capture = capture_scenario_with(grants)?;
+
+    for ((_, title, _), stage) in CASES.iter().zip(&capture.stages) {
+        println!("  {}", title);
+        println!("  Action:   disclose-summary");
+        println!("  Resource: patient-summary");
+        println!("  Policy verdict:         RequireVerification (check '{}')", PHI_CHECK_ID);
+
+        match &stage.outcome {
+            StageOutcome::Completed { output } => {
+                let summary = output.payload["summary"].as_str().unwrap_or("?");
+                println!("  PHI content scan:       PASS");
+                println!("  Disclosed summary:      {}", summary);
+            }
+            StageOutcome::Denied { reason, code, remediation } => {
+                print_denial(*code, reason, remediation.as_deref());
+            }
+            StageOutcome::AwaitingApproval { reason, .. } => {
+                println!("  AWAITING APPROVAL: {}", reason);
+            }
+            StageOutcome::Failed { error: VeritasError::VerificationFailed { reason } } => {
+                println!("  PHI content scan:       FAIL");
+                println!("  Blocked:                {}", reason);
+            }
+            StageOutcome::Failed { error } => {
+                println!("  FAILED: {}", error);
+            }
+        }
+
+        print_stage_notes(stage);
+        println!(
+            "  Audit chain integrity:  {} ({} event(s) in chain)",
+            if stage.chain_verified { "VERIFIED" } else { "FAILED" },
+            stage.audit_log.events.len()
+        );
+        println!();
+    }
+
+    println!("  Scenario 6 complete.");
+    println!();
+
+    Ok(())
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A clean summary passes the policy-required scan and is disclosed.
+    #[test]
+    fn test_clean_summary_is_disclosed() {
+        let Attempt { result, audit, .. } = attempt_disclosure(
+            "patient-042",
+            "Stable; recheck CBC in four weeks.",
+            &WarningTap::new(),
+            &Grants::Scenario,
+        )
+        .unwrap();
+
+        match result.unwrap() {
+            StepResult::Complete { output, .. } => {
+                assert_eq!(output.payload["summary"], "Stable; recheck CBC in four weeks.");
+            }
+            other => panic!("expected Complete, got {:?}", other),
+        }
+        assert_eq!(audit.export_log().events.len(), 1);
+    }
+
+    /// A summary carrying an SSN fails the scan: the step errors and nothing
+    /// is delivered, even though the output schema itself passes.
+    #[test]
+    fn test_phi_laden_summary_is_blocked() {
+        let Attempt { result, .. } = attempt_disclosure(
+            "patient-042",
+            "Stable. SSN 123-45-6789 on file.",
+            &WarningTap::new(),
+            &Grants::Scenario,
+        )
+        .unwrap();
+
+        match result {
+            Err(VeritasError::VerificationFailed { reason }) => {
+                assert!(reason.contains(PHI_CHECK_ID), "reason: {}", reason);
+                assert!(reason.contains("Ssn at '/summary'"), "reason: {}", reason);
+            }
+            other => panic!("expected VerificationFailed, got {:?}", other),
+        }
+    }
+
+    /// Without a registered check the verdict fails closed.
+    #[test]
+    fn test_unregistered_check_fails_closed() {
+        let policy = TomlPolicyEngine::from_toml_str(DISCLOSURE_POLICY).unwrap();
+        let executor = Executor::new(
+            Box::new(policy),
+            Box::new(InMemoryAuditWriter::new("exec-unregistered")),
+            Box::new(SchemaVerifier::new()),
+            disclosure_schema(),
+        );
+
+        let state = AgentState {
+            agent_id: AgentId("disclosure-agent".to_string()),
+            execution_id: ExecutionId::new(),
+            phase: "drafted".to_string(),
+            context: serde_json::Value::Null,
+            step: 0,
+            correlation_id: None,
+        };
+        let input = AgentInput {
+            kind: "disclosure-request".to_string(),
+            payload: json!({ "patient_id": "patient-042", "draft": "clean" }),
+        };
+        let mut capabilities = CapabilitySet::default();
+        capabilities.grant(Capability::new("clinical-notes.read"));
+
+        let result = executor.step(&DisclosureAgent, state, input, &capabilities);
+        assert!(matches!(result, Err(VeritasError::VerificationFailed { .. })));
+    }
+}
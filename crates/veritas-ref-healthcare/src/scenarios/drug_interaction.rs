@@ -4,48 +4,137 @@
 //! interaction database. The agent checks whether two drugs have a known adverse
 //! interaction and returns a structured recommendation.
 //!
+//! The agent also accepts a whole prescription as a `drugs` array, checking
+//! every unique pair with `check_all_interactions` — the same routine the
+//! pipeline's `DrugSafetyCheckerAgent` uses.
+//!
 //! Pipeline walk-through for the demo run:
 //!   1. Policy evaluates (drug-interaction-check, drug-database) → Allow
 //!   2. Capability check: agent must hold "drug-database.read"
 //!   3. Agent calls mock database → structured result with severity
-//!   4. Verifier checks required fields: query, result, recommendation
+//!   4. Verifier checks required fields: query, result, recommendation, and
+//!      warns (without failing) that a HIGH-severity result needs review
 //!   5. State transitions; audit record written to hash chain
 //!   6. Audit log integrity verified at the end
 
 use std::sync::Arc;
 
-use serde_json::json;
+use serde_json::{json, Value};
 
 use veritas_audit::InMemoryAuditWriter;
 use veritas_contracts::{
     agent::{AgentId, AgentInput, AgentOutput, AgentState, ExecutionId},
     capability::{Capability, CapabilitySet},
-    error::VeritasResult,
-    execution::{StepRecord, StepResult},
-    verify::{OutputSchema, VerificationRule, VerificationRuleType},
+    error::{VeritasError, VeritasResult},
+    execution::StepRecord,
+    verify::{OutputSchema, RuleSeverity, VerificationRule, VerificationRuleType},
 };
 use veritas_core::{executor::Executor, traits::{Agent, AuditWriter}};
 use veritas_policy::engine::TomlPolicyEngine;
 use veritas_verify::engine::SchemaVerifier;
 
-use crate::mock_data::check_drug_interaction;
+use crate::{
+    grants::Grants,
+    mock_data::check_drug_interaction,
+    report::{
+        print_denial, print_stage_notes, ScenarioCapture, StageCapture, StageOutcome, WarningTap,
+    },
+};
 
 // ── Policy TOML ───────────────────────────────────────────────────────────────
 
 /// Embedded healthcare policy covering the drug interaction scenario.
 const HEALTHCARE_POLICY: &str = include_str!("../../policies/healthcare.toml");
 
+// ── Pairwise interaction check ────────────────────────────────────────────────
+
+/// The known interactions among a list of drugs.
+#[derive(Debug, Clone)]
+pub struct InteractionSummary {
+    /// One entry per interacting pair: `drug_a`, `drug_b`, `severity`,
+    /// `mechanism` and `recommendation`.  Pairs without interaction data
+    /// are left out.
+    pub interactions: Vec<Value>,
+
+    /// The most severe interaction found — `"HIGH"`, `"MEDIUM"` or `"LOW"` —
+    /// or `"NONE"` when no pair interacts.
+    pub overall_severity: &'static str,
+}
+
+/// Rank of a severity string from the mock database; higher is worse.
+fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "HIGH" => 3,
+        "MEDIUM" => 2,
+        "LOW" => 1,
+        _ => 0,
+    }
+}
+
+/// Check every unique pair of `drugs` with `check_drug_interaction`.
+///
+/// Repeated names (compared case-insensitively) are checked once.
+pub fn check_all_interactions(drugs: &[&str]) -> InteractionSummary {
+    let mut unique: Vec<&str> = Vec::new();
+    for drug in drugs {
+        if !unique.iter().any(|seen| seen.eq_ignore_ascii_case(drug)) {
+            unique.push(*drug);
+        }
+    }
+
+    let mut interactions = Vec::new();
+    let mut overall_severity = "NONE";
+
+    for (i, drug_a) in unique.iter().enumerate() {
+        for drug_b in &unique[i + 1..] {
+            let result = check_drug_interaction(drug_a, drug_b);
+            let severity = match result["result"]["severity"].as_str() {
+                Some("HIGH") => "HIGH",
+                Some("MEDIUM") => "MEDIUM",
+                Some("LOW") => "LOW",
+                _ => continue,
+            };
+            if severity_rank(severity) > severity_rank(overall_severity) {
+                overall_severity = severity;
+            }
+
+            interactions.push(json!({
+                "drug_a": drug_a,
+                "drug_b": drug_b,
+                "severity": severity,
+                "mechanism": result["result"]["mechanism"],
+                "recommendation": result["recommendation"]
+            }));
+        }
+    }
+
+    InteractionSummary { interactions, overall_severity }
+}
+
 // ── Agent implementation ──────────────────────────────────────────────────────
 
 /// An agent that checks for adverse drug interactions using a mock database.
 ///
 /// In a production system this would call a real clinical API. Here it calls
 /// `check_drug_interaction` from the mock data module.
+///
+/// Input is either a `drugs` array, checked pairwise, or the original
+/// `drug_a` / `drug_b` pair.
 pub struct DrugInteractionAgent;
 
 impl Agent for DrugInteractionAgent {
     fn propose(&self, _state: &AgentState, input: &AgentInput) -> VeritasResult<AgentOutput> {
-        // Extract drug names from the input payload.
+        if let Some(drugs) = input.payload["drugs"].as_array() {
+            let drugs: Vec<&str> = drugs.iter().filter_map(Value::as_str).collect();
+            return Ok(AgentOutput {
+                kind: "drug-interaction-result".to_string(),
+                payload: multi_drug_result(&drugs),
+                provenance: None,
+                advisories: vec![],
+            });
+        }
+
+        // Legacy form: extract the drug pair from the input payload.
         let drug_a = input.payload["drug_a"]
             .as_str()
             .unwrap_or("unknown")
@@ -61,6 +150,8 @@ impl Agent for DrugInteractionAgent {
         Ok(AgentOutput {
             kind: "drug-interaction-result".to_string(),
             payload: result,
+            provenance: None,
+            advisories: vec![],
         })
     }
 
@@ -77,6 +168,28 @@ impl Agent for DrugInteractionAgent {
         vec!["drug-database.read".to_string()]
     }
 
+    /// Require a `drugs` list or both of `drug_a` and `drug_b`, so a
+    /// malformed request fails here rather than as an "unknown" pair.
+    fn validate_input(&self, _state: &AgentState, input: &AgentInput) -> VeritasResult<()> {
+        if input.payload["drugs"].is_array() {
+            return Ok(());
+        }
+        let missing: Vec<&str> = ["drug_a", "drug_b"]
+            .into_iter()
+            .filter(|field| !input.payload[*field].is_string())
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(VeritasError::SchemaValidation {
+                reason: format!(
+                    "drug interaction request is missing {} (or a 'drugs' list)",
+                    missing.join(", ")
+                ),
+            })
+        }
+    }
+
     fn describe_action(&self, _state: &AgentState, _input: &AgentInput) -> (String, String) {
         ("drug-interaction-check".to_string(), "drug-database".to_string())
     }
@@ -86,6 +199,30 @@ impl Agent for DrugInteractionAgent {
     }
 }
 
+/// Build the output payload for a `drugs` list.
+///
+/// Keeps the `query` / `result` / `recommendation` shape of the pairwise
+/// result so the same schema verifies both forms; the recommendation is that
+/// of the most severe interaction.
+fn multi_drug_result(drugs: &[&str]) -> Value {
+    let summary = check_all_interactions(drugs);
+    let recommendation = summary
+        .interactions
+        .iter()
+        .max_by_key(|i| severity_rank(i["severity"].as_str().unwrap_or("")))
+        .map(|i| i["recommendation"].clone())
+        .unwrap_or_else(|| json!("No known interactions among the listed drugs"));
+
+    json!({
+        "query": { "drugs": drugs },
+        "result": {
+            "interactions": summary.interactions,
+            "overall_severity": summary.overall_severity
+        },
+        "recommendation": recommendation
+    })
+}
+
 // ── Output schema ─────────────────────────────────────────────────────────────
 
 /// Build the output schema requiring query, result, and recommendation fields.
@@ -101,6 +238,7 @@ pub fn drug_interaction_schema() -> OutputSchema {
             VerificationRule {
                 rule_id: "req-query".to_string(),
                 description: "Output must contain the queried drug pair".to_string(),
+                severity: RuleSeverity::Error,
                 rule_type: VerificationRuleType::RequiredField {
                     field_path: "query".to_string(),
                 },
@@ -108,6 +246,7 @@ pub fn drug_interaction_schema() -> OutputSchema {
             VerificationRule {
                 rule_id: "req-result".to_string(),
                 description: "Output must contain an interaction result with severity".to_string(),
+                severity: RuleSeverity::Error,
                 rule_type: VerificationRuleType::RequiredField {
                     field_path: "result".to_string(),
                 },
@@ -115,11 +254,26 @@ pub fn drug_interaction_schema() -> OutputSchema {
             VerificationRule {
                 rule_id: "req-recommendation".to_string(),
                 description: "Output must contain a clinical recommendation".to_string(),
+                severity: RuleSeverity::Error,
                 rule_type: VerificationRuleType::RequiredField {
                     field_path: "recommendation".to_string(),
                 },
             },
+            // Advisory only: a HIGH-severity interaction is still delivered,
+            // but the report flags it for pharmacist review.
+            VerificationRule {
+                rule_id: "high-severity-review".to_string(),
+                description: "HIGH-severity interactions should be reviewed by a pharmacist"
+                    .to_string(),
+                severity: RuleSeverity::Warn,
+                rule_type: VerificationRuleType::ForbiddenPattern {
+                    field_path: "result.severity".to_string(),
+                    pattern: "HIGH".to_string(),
+                    is_regex: false,
+                },
+            },
         ],
+        defaults: vec![],
     }
 }
 
@@ -141,14 +295,17 @@ impl AuditWriter for ArcAudit {
 
 // ── Scenario runner ───────────────────────────────────────────────────────────
 
-/// Run Scenario 1: Drug Interaction Checker.
+/// Run Scenario 1 and capture its single stage without printing.
 ///
-/// Tests the warfarin + aspirin pair (known HIGH severity).  Prints each
-/// VERITAS pipeline step clearly and verifies the audit chain at the end.
-pub fn run_scenario() -> VeritasResult<()> {
-    println!("=== Scenario 1: Drug Interaction Checker ===");
-    println!();
+/// Checks the warfarin + aspirin pair (known HIGH severity) with the
+/// `drug-database.read` capability granted.
+pub fn capture_scenario() -> VeritasResult<ScenarioCapture> {
+    capture_scenario_with(&Grants::Scenario)
+}
 
+/// Run Scenario 1 as `capture_scenario` does, granting the agent what
+/// `grants` says instead of `drug-database.read`.
+pub fn capture_scenario_with(grants: &Grants) -> VeritasResult<ScenarioCapture> {
     // ── Wire up the VERITAS components ────────────────────────────────────────
 
     let policy = TomlPolicyEngine::from_toml_str(HEALTHCARE_POLICY)?;
@@ -170,18 +327,14 @@ pub fn run_scenario() -> VeritasResult<()> {
         phase: "active".to_string(),
         context: serde_json::Value::Null,
         step: 0,
+        correlation_id: None,
     };
 
     // ── Grant the required capability ─────────────────────────────────────────
 
     let mut capabilities = CapabilitySet::default();
     capabilities.grant(Capability::new("drug-database.read"));
-
-    println!("  Test: warfarin + aspirin (known HIGH severity interaction)");
-    println!("  Action:   drug-interaction-check");
-    println!("  Resource: drug-database");
-    println!("  Agent capability: drug-database.read [GRANTED]");
-    println!();
+    let capabilities = grants.apply(capabilities);
 
     let input = AgentInput {
         kind: "drug-interaction-request".to_string(),
@@ -193,17 +346,53 @@ pub fn run_scenario() -> VeritasResult<()> {
 
     // ── Run the executor step ─────────────────────────────────────────────────
 
+    let tap = WarningTap::new();
     let executor = Executor::new(
         Box::new(policy),
         Box::new(ArcAudit(Arc::clone(&audit))),
-        Box::new(verifier),
+        tap.wrap(verifier),
         drug_interaction_schema(),
     );
 
-    let result = executor.step(&agent, initial_state, input, &capabilities)?;
+    let required = agent.required_capabilities(&initial_state, &input);
+    let result = executor.step(&agent, initial_state, input, &capabilities);
+
+    let mut capture = ScenarioCapture::new("Scenario 1: Drug Interaction Checker");
+    capture.stages.push(
+        StageCapture::new("warfarin+aspirin", result, &audit)
+            .with_warnings(tap.take())
+            .with_capabilities(required, &capabilities, executor.now()),
+    );
+    Ok(capture)
+}
 
-    match &result {
-        StepResult::Complete { output, .. } | StepResult::Transitioned { output, .. } => {
+/// Run Scenario 1: Drug Interaction Checker.
+///
+/// Tests the warfarin + aspirin pair (known HIGH severity).  Prints each
+/// VERITAS pipeline step clearly and verifies the audit chain at the end.
+pub fn run_scenario() -> VeritasResult<()> {
+    run_scenario_with(&Grants::Scenario)
+}
+
+/// Run Scenario 1 as `run_scenario` does, under `grants`.
+pub fn run_scenario_with(grants: &Grants) -> VeritasResult<()> {
+    println!("=== Scenario 1: Drug Interaction Checker ===");
+    println!();
+
+    let capture = capture_scenario_with(grants)?;
+    let stage = &capture.stages[0];
+
+    println!("  Test: warfarin + aspirin (known HIGH severity interaction)");
+    println!("  Action:   drug-interaction-check");
+    println!("  Resource: drug-database");
+    println!(
+        "  Agent capability: drug-database.read [{}]",
+        stage.capability_status("drug-database.read")
+    );
+    println!();
+
+    match &stage.outcome {
+        StageOutcome::Completed { output } => {
             let severity = output.payload["result"]["severity"]
                 .as_str()
                 .unwrap_or("?");
@@ -217,25 +406,26 @@ pub fn run_scenario() -> VeritasResult<()> {
             println!("  Interaction severity:   {}", severity);
             println!("  Recommendation:         {}", recommendation);
         }
-        StepResult::Denied { reason, .. } => {
-            println!("  DENIED: {}", reason);
+        StageOutcome::Denied { reason, code, remediation } => {
+            print_denial(*code, reason, remediation.as_deref());
         }
-        StepResult::AwaitingApproval { reason, .. } => {
+        StageOutcome::AwaitingApproval { reason, .. } => {
             println!("  AWAITING APPROVAL: {}", reason);
         }
+        StageOutcome::Failed { error } => {
+            println!("  FAILED: {}", error);
+        }
     }
 
     println!();
 
     // ── Verify audit chain integrity ──────────────────────────────────────────
 
-    let integrity_ok = audit.verify_integrity();
-    let log = audit.export_log();
-
+    print_stage_notes(stage);
     println!(
         "  Audit chain integrity:  {} ({} event(s) in chain)",
-        if integrity_ok { "VERIFIED" } else { "FAILED" },
-        log.events.len()
+        if stage.chain_verified { "VERIFIED" } else { "FAILED" },
+        stage.audit_log.events.len()
     );
     println!();
     println!("  Scenario 1 complete.");
@@ -243,3 +433,89 @@ pub fn run_scenario() -> VeritasResult<()> {
 
     Ok(())
 }
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> AgentState {
+        AgentState {
+            agent_id: AgentId("drug-interaction-agent".to_string()),
+            execution_id: ExecutionId::new(),
+            phase: "active".to_string(),
+            context: Value::Null,
+            step: 0,
+            correlation_id: None,
+        }
+    }
+
+    fn input(payload: Value) -> AgentInput {
+        AgentInput {
+            kind: "drug-interaction-query".to_string(),
+            payload,
+        }
+    }
+
+    fn propose(payload: Value) -> AgentOutput {
+        DrugInteractionAgent.propose(&state(), &input(payload)).unwrap()
+    }
+
+    /// A request naming neither drug is rejected before `propose`, with
+    /// both missing fields named.
+    #[test]
+    fn test_input_missing_both_drugs_is_rejected() {
+        match DrugInteractionAgent.validate_input(&state(), &input(json!({ "drug": "warfarin" }))) {
+            Err(VeritasError::SchemaValidation { reason }) => {
+                assert!(reason.contains("drug_a, drug_b"), "reason: {}", reason);
+            }
+            other => panic!("expected SchemaValidation, got {:?}", other),
+        }
+    }
+
+    /// A well-formed pair and a `drugs` list are both accepted.
+    #[test]
+    fn test_well_formed_input_is_accepted() {
+        let pair = input(json!({ "drug_a": "warfarin", "drug_b": "aspirin" }));
+        DrugInteractionAgent.validate_input(&state(), &pair).unwrap();
+
+        let list = input(json!({ "drugs": ["warfarin", "aspirin", "metformin"] }));
+        DrugInteractionAgent.validate_input(&state(), &list).unwrap();
+    }
+
+    /// Three drugs yield three pairs, of which only warfarin + aspirin
+    /// interacts.
+    #[test]
+    fn test_drug_list_finds_high_interaction() {
+        let output = propose(json!({ "drugs": ["warfarin", "aspirin", "metformin"] }));
+
+        let interactions = output.payload["result"]["interactions"].as_array().unwrap();
+        assert_eq!(interactions.len(), 1);
+        assert_eq!(interactions[0]["drug_a"], "warfarin");
+        assert_eq!(interactions[0]["drug_b"], "aspirin");
+        assert_eq!(interactions[0]["severity"], "HIGH");
+        assert_eq!(output.payload["result"]["overall_severity"], "HIGH");
+        assert_eq!(output.payload["recommendation"], interactions[0]["recommendation"]);
+    }
+
+    /// The original `drug_a` / `drug_b` input still produces the pairwise
+    /// result.
+    #[test]
+    fn test_legacy_pair_input_still_works() {
+        let output = propose(json!({ "drug_a": "warfarin", "drug_b": "aspirin" }));
+
+        assert_eq!(output.payload["query"]["drug_a"], "warfarin");
+        assert_eq!(output.payload["result"]["severity"], "HIGH");
+    }
+
+    /// Duplicate names are checked once and pairs without data are skipped.
+    #[test]
+    fn test_check_all_interactions_dedupes() {
+        let summary = check_all_interactions(&["Warfarin", "aspirin", "warfarin", "vitamin-c"]);
+
+        assert_eq!(summary.interactions.len(), 1);
+        assert_eq!(summary.overall_severity, "HIGH");
+        assert_eq!(check_all_interactions(&["vitamin-c"]).overall_severity, "NONE");
+    }
+}
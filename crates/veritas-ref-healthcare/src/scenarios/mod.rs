@@ -5,6 +5,7 @@
 //! clinical data and demonstrates a distinct enforcement pattern.
 
 pub mod clinical_pipeline;
+pub mod conditional_disclosure;
 pub mod drug_interaction;
 pub mod note_summarizer;
 pub mod patient_query;
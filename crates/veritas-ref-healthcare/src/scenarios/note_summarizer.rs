@@ -22,14 +22,20 @@ use veritas_contracts::{
     agent::{AgentId, AgentInput, AgentOutput, AgentState, ExecutionId},
     capability::{Capability, CapabilitySet},
     error::VeritasResult,
-    execution::{StepRecord, StepResult},
-    verify::{OutputSchema, VerificationRule, VerificationRuleType},
+    execution::StepRecord,
+    verify::{OutputSchema, RuleSeverity, VerificationRule, VerificationRuleType},
 };
 use veritas_core::{executor::Executor, traits::{Agent, AuditWriter}};
 use veritas_policy::engine::TomlPolicyEngine;
 use veritas_verify::engine::SchemaVerifier;
 
-use crate::mock_data::get_patient_notes;
+use crate::{
+    grants::Grants,
+    mock_data::get_patient_notes,
+    report::{
+        print_denial, print_stage_notes, ScenarioCapture, StageCapture, StageOutcome, WarningTap,
+    },
+};
 
 // ── Policy TOML ───────────────────────────────────────────────────────────────
 
@@ -77,6 +83,8 @@ impl Agent for NoteSummarizerAgent {
                 "summary": summary,
                 "generated_by": state.agent_id.0
             }),
+            provenance: None,
+            advisories: vec![],
         })
     }
 
@@ -118,6 +126,7 @@ fn note_summarizer_schema() -> OutputSchema {
             VerificationRule {
                 rule_id: "req-patient-id".to_string(),
                 description: "Output must identify the patient".to_string(),
+                severity: RuleSeverity::Error,
                 rule_type: VerificationRuleType::RequiredField {
                     field_path: "patient_id".to_string(),
                 },
@@ -125,6 +134,7 @@ fn note_summarizer_schema() -> OutputSchema {
             VerificationRule {
                 rule_id: "req-summary".to_string(),
                 description: "Output must contain a summary text".to_string(),
+                severity: RuleSeverity::Error,
                 rule_type: VerificationRuleType::RequiredField {
                     field_path: "summary".to_string(),
                 },
@@ -134,11 +144,13 @@ fn note_summarizer_schema() -> OutputSchema {
             VerificationRule {
                 rule_id: "no-pii-labels".to_string(),
                 description: "Summary must not contain PII labels such as DOB: or SSN:".to_string(),
+                severity: RuleSeverity::Error,
                 rule_type: VerificationRuleType::Custom {
                     function_name: "no-pii-labels".to_string(),
                 },
             },
         ],
+        defaults: vec![],
     }
 }
 
@@ -160,16 +172,17 @@ impl AuditWriter for ArcAudit {
 
 // ── Scenario runner ───────────────────────────────────────────────────────────
 
-/// Run Scenario 2: Clinical Note Summarizer.
-///
-/// Demonstrates the custom PII verifier rule passing on a clean summary.
-/// Also shows the full VERITAS pipeline and exports the audit log.
-pub fn run_scenario() -> VeritasResult<()> {
-    println!("=== Scenario 2: Clinical Note Summarizer ===");
-    println!();
+/// Patient whose notes the scenario summarizes.
+const PATIENT_ID: &str = "patient-042";
 
-    let patient_id = "patient-042";
+/// Run Scenario 2 and capture its single stage without printing.
+pub fn capture_scenario() -> VeritasResult<ScenarioCapture> {
+    capture_scenario_with(&Grants::Scenario)
+}
 
+/// Run Scenario 2 as `capture_scenario` does, granting the agent what
+/// `grants` says instead of `clinical-notes.read`.
+pub fn capture_scenario_with(grants: &Grants) -> VeritasResult<ScenarioCapture> {
     // ── Wire up the VERITAS components ────────────────────────────────────────
 
     let policy = TomlPolicyEngine::from_toml_str(HEALTHCARE_POLICY)?;
@@ -206,34 +219,66 @@ pub fn run_scenario() -> VeritasResult<()> {
         phase: "active".to_string(),
         context: serde_json::Value::Null,
         step: 0,
+        correlation_id: None,
     };
 
     let mut capabilities = CapabilitySet::default();
     capabilities.grant(Capability::new("clinical-notes.read"));
-
-    println!("  Test: summarize clinical notes for patient '{}'", patient_id);
-    println!("  Action:   summarize");
-    println!("  Resource: clinical-notes");
-    println!("  Agent capability: clinical-notes.read [GRANTED]");
-    println!("  Custom verifier rule: no-pii-labels [REGISTERED]");
-    println!();
+    let capabilities = grants.apply(capabilities);
 
     let input = AgentInput {
         kind: "summarize-request".to_string(),
-        payload: json!({ "patient_id": patient_id }),
+        payload: json!({ "patient_id": PATIENT_ID }),
     };
 
+    let tap = WarningTap::new();
     let executor = Executor::new(
         Box::new(policy),
         Box::new(ArcAudit(Arc::clone(&audit_inner))),
-        Box::new(verifier),
+        tap.wrap(verifier),
         schema,
     );
 
-    let result = executor.step(&agent, initial_state, input, &capabilities)?;
+    let required = agent.required_capabilities(&initial_state, &input);
+    let result = executor.step(&agent, initial_state, input, &capabilities);
 
-    match &result {
-        StepResult::Complete { output, .. } | StepResult::Transitioned { output, .. } => {
+    let mut capture = ScenarioCapture::new("Scenario 2: Clinical Note Summarizer");
+    capture.stages.push(
+        StageCapture::new("summarize", result, &audit_inner)
+            .with_warnings(tap.take())
+            .with_capabilities(required, &capabilities, executor.now()),
+    );
+    Ok(capture)
+}
+
+/// Run Scenario 2: Clinical Note Summarizer.
+///
+/// Demonstrates the custom PII verifier rule passing on a clean summary.
+/// Also shows the full VERITAS pipeline and exports the audit log.
+pub fn run_scenario() -> VeritasResult<()> {
+    run_scenario_with(&Grants::Scenario)
+}
+
+/// Run Scenario 2 as `run_scenario` does, under `grants`.
+pub fn run_scenario_with(grants: &Grants) -> VeritasResult<()> {
+    println!("=== Scenario 2: Clinical Note Summarizer ===");
+    println!();
+
+    let capture = capture_scenario_with(grants)?;
+    let stage = &capture.stages[0];
+
+    println!("  Test: summarize clinical notes for patient '{}'", PATIENT_ID);
+    println!("  Action:   summarize");
+    println!("  Resource: clinical-notes");
+    println!(
+        "  Agent capability: clinical-notes.read [{}]",
+        stage.capability_status("clinical-notes.read")
+    );
+    println!("  Custom verifier rule: no-pii-labels [REGISTERED]");
+    println!();
+
+    match &stage.outcome {
+        StageOutcome::Completed { output } => {
             let summary = output.payload["summary"]
                 .as_str()
                 .unwrap_or("?");
@@ -246,25 +291,26 @@ pub fn run_scenario() -> VeritasResult<()> {
             println!("  Notes summarized:       {}", note_count);
             println!("  Summary preview:        {}...", &summary[..summary.len().min(120)]);
         }
-        StepResult::Denied { reason, .. } => {
-            println!("  DENIED: {}", reason);
+        StageOutcome::Denied { reason, code, remediation } => {
+            print_denial(*code, reason, remediation.as_deref());
         }
-        StepResult::AwaitingApproval { reason, .. } => {
+        StageOutcome::AwaitingApproval { reason, .. } => {
             println!("  AWAITING APPROVAL: {}", reason);
         }
+        StageOutcome::Failed { error } => {
+            println!("  FAILED: {}", error);
+        }
     }
 
     println!();
 
     // ── Verify audit chain integrity ──────────────────────────────────────────
 
-    let integrity_ok = audit_inner.verify_integrity();
-    let log = audit_inner.export_log();
-
+    print_stage_notes(stage);
     println!(
         "  Audit chain integrity:  {} ({} event(s) in chain)",
-        if integrity_ok { "VERIFIED" } else { "FAILED" },
-        log.events.len()
+        if stage.chain_verified { "VERIFIED" } else { "FAILED" },
+        stage.audit_log.events.len()
     );
     println!();
     println!("  Scenario 2 complete.");
@@ -31,14 +31,20 @@ use veritas_contracts::{
     agent::{AgentId, AgentInput, AgentOutput, AgentState, ExecutionId},
     capability::{Capability, CapabilitySet},
     error::{VeritasError, VeritasResult},
-    execution::{StepRecord, StepResult},
-    verify::{OutputSchema, VerificationRule, VerificationRuleType},
+    execution::StepRecord,
+    verify::{OutputSchema, RuleSeverity, VerificationRule, VerificationRuleType},
 };
 use veritas_core::{executor::Executor, traits::{Agent, AuditWriter}};
 use veritas_policy::engine::TomlPolicyEngine;
 use veritas_verify::engine::SchemaVerifier;
 
-use crate::mock_data::get_patient_record;
+use crate::{
+    grants::Grants,
+    mock_data::get_patient_record,
+    report::{
+        print_denial, print_stage_notes, ScenarioCapture, StageCapture, StageOutcome, WarningTap,
+    },
+};
 
 // ── Policy TOML ───────────────────────────────────────────────────────────────
 
@@ -77,6 +83,8 @@ impl Agent for PatientQueryAgent {
         Ok(AgentOutput {
             kind: "patient-record-result".to_string(),
             payload: record,
+            provenance: None,
+            advisories: vec![],
         })
     }
 
@@ -126,11 +134,13 @@ fn patient_query_schema() -> OutputSchema {
             VerificationRule {
                 rule_id: "req-patient-id".to_string(),
                 description: "Output must contain the patient ID".to_string(),
+                severity: RuleSeverity::Error,
                 rule_type: VerificationRuleType::RequiredField {
                     field_path: "patient_id".to_string(),
                 },
             },
         ],
+        defaults: vec![],
     }
 }
 
@@ -149,52 +159,126 @@ impl AuditWriter for ArcAudit {
 
 // ── Scenario runner ───────────────────────────────────────────────────────────
 
+/// Run one patient query for `patient_id` under `policy_toml`, granting
+/// `patient-records.read` only when `granted` is true — or whatever `grants`
+/// says instead.
+fn capture_query(
+    label: &str,
+    policy_toml: &str,
+    patient_id: &str,
+    granted: bool,
+    grants: &Grants,
+) -> VeritasResult<StageCapture> {
+    let policy = TomlPolicyEngine::from_toml_str(policy_toml)?;
+    let execution_id = ExecutionId::new();
+    let audit = Arc::new(InMemoryAuditWriter::new(execution_id.0.to_string()));
+    let verifier = SchemaVerifier::new();
+    let schema = patient_query_schema();
+    let agent = PatientQueryAgent { patient_id: patient_id.to_string() };
+
+    let state = AgentState {
+        agent_id: AgentId("patient-query-agent".to_string()),
+        execution_id: execution_id.clone(),
+        phase: "active".to_string(),
+        context: serde_json::Value::Null,
+        step: 0,
+        correlation_id: None,
+    };
+
+    let mut capabilities = CapabilitySet::default();
+    if granted {
+        capabilities.grant(Capability::new("patient-records.read"));
+    }
+    let capabilities = grants.apply(capabilities);
+
+    let input = AgentInput {
+        kind: "patient-query".to_string(),
+        payload: json!({ "patient_id": patient_id }),
+    };
+
+    let tap = WarningTap::new();
+    let executor = Executor::new(
+        Box::new(policy),
+        Box::new(ArcAudit(Arc::clone(&audit))),
+        tap.wrap(verifier),
+        schema,
+    );
+
+    let required = agent.required_capabilities(&state, &input);
+    let result = executor.step(&agent, state, input, &capabilities);
+    Ok(StageCapture::new(label, result, &audit)
+        .with_warnings(tap.take())
+        .with_capabilities(required, &capabilities, executor.now()))
+}
+
+/// Run the three sub-cases of Scenario 3 and capture them without printing.
+///
+/// Stages are labelled `"A"`, `"B"` and `"C"` after the sub-cases.
+pub fn capture_scenario() -> VeritasResult<ScenarioCapture> {
+    capture_scenario_with(&Grants::Scenario)
+}
+
+/// Run Scenario 3 as `capture_scenario` does, granting every sub-case what
+/// `grants` says instead of its own grants.
+pub fn capture_scenario_with(grants: &Grants) -> VeritasResult<ScenarioCapture> {
+    let mut capture = ScenarioCapture::new("Scenario 3: Patient Data Query");
+
+    // Sub-case A: WITH capability + consent → Allow.
+    capture
+        .stages
+        .push(capture_query("A", HEALTHCARE_POLICY, "patient-101", true, grants)?);
+
+    // Sub-case B: WITHOUT capability → CapabilityMissing.
+    //
+    // Uses OPEN_POLICY_FOR_CAPABILITY_TEST so the policy returns Allow.
+    // The executor then runs its own capability check (Agent::required_capabilities)
+    // and returns CapabilityMissing before calling agent.propose().
+    capture.stages.push(capture_query(
+        "B",
+        OPEN_POLICY_FOR_CAPABILITY_TEST,
+        "patient-101",
+        false,
+        grants,
+    )?);
+
+    // Sub-case C: WITH capability but no consent → Policy Deny.
+    //
+    // Patient ID ending in "nc" → get_patient_record sets consent = false
+    // → describe_action returns resource = "patient-records-no-consent"
+    // → policy rule "deny-patient-query-no-consent" fires.
+    capture
+        .stages
+        .push(capture_query("C", HEALTHCARE_POLICY, "patient-201nc", true, grants)?);
+
+    Ok(capture)
+}
+
 /// Run Scenario 3: Patient Data Query — three sub-cases.
 pub fn run_scenario() -> VeritasResult<()> {
+    run_scenario_with(&Grants::Scenario)
+}
+
+/// Run Scenario 3 as `run_scenario` does, under `grants`.
+pub fn run_scenario_with(grants: &Grants) -> VeritasResult<()> {
     println!("=== Scenario 3: Patient Data Query ===");
     println!();
 
+    let capture = capture_scenario_with(grants)?;
+
     // ── Sub-case A: WITH capability + consent → Allow ─────────────────────────
 
     {
+        let stage = &capture.stages[0];
+
         println!("  Sub-case A: Agent WITH capability + patient consent flag");
         println!("  Patient ID: patient-101 (ai_query_consent = true)");
-        println!("  Capability: patient-records.read [GRANTED]");
-
-        let policy = TomlPolicyEngine::from_toml_str(HEALTHCARE_POLICY)?;
-        let execution_id = ExecutionId::new();
-        let audit = Arc::new(InMemoryAuditWriter::new(execution_id.0.to_string()));
-        let verifier = SchemaVerifier::new();
-        let schema = patient_query_schema();
-        let agent = PatientQueryAgent { patient_id: "patient-101".to_string() };
-
-        let state = AgentState {
-            agent_id: AgentId("patient-query-agent".to_string()),
-            execution_id: execution_id.clone(),
-            phase: "active".to_string(),
-            context: serde_json::Value::Null,
-            step: 0,
-        };
-
-        let mut capabilities = CapabilitySet::default();
-        capabilities.grant(Capability::new("patient-records.read"));
-
-        let input = AgentInput {
-            kind: "patient-query".to_string(),
-            payload: json!({ "patient_id": "patient-101" }),
-        };
-
-        let executor = Executor::new(
-            Box::new(policy),
-            Box::new(ArcAudit(Arc::clone(&audit))),
-            Box::new(verifier),
-            schema,
+        println!(
+            "  Capability: patient-records.read [{}]",
+            stage.capability_status("patient-records.read")
         );
 
-        let result = executor.step(&agent, state, input, &capabilities)?;
-
-        match result {
-            StepResult::Complete { output, .. } | StepResult::Transitioned { output, .. } => {
+        match &stage.outcome {
+            StageOutcome::Completed { output } => {
                 let conditions = output.payload["conditions"]
                     .as_array()
                     .map(|a| a.len())
@@ -204,88 +288,60 @@ pub fn run_scenario() -> VeritasResult<()> {
                 println!("  Verification result:    PASS");
                 println!("  Record conditions:      {} condition(s) returned", conditions);
             }
-            StepResult::Denied { reason, .. } => {
-                println!("  DENIED: {}", reason);
+            StageOutcome::Denied { reason, code, remediation } => {
+                print_denial(*code, reason, remediation.as_deref());
             }
-            _ => {}
+            StageOutcome::Failed { error } => {
+                println!("  Unexpected error: {}", error);
+            }
+            StageOutcome::AwaitingApproval { .. } => {}
         }
 
-        let integrity_ok = audit.verify_integrity();
-        let log = audit.export_log();
+        print_stage_notes(stage);
         println!(
             "  Audit chain integrity:  {} ({} event(s))",
-            if integrity_ok { "VERIFIED" } else { "FAILED" },
-            log.events.len()
+            if stage.chain_verified { "VERIFIED" } else { "FAILED" },
+            stage.audit_log.events.len()
         );
         println!("  RESULT: SUCCESS (expected)");
         println!();
     }
 
     // ── Sub-case B: WITHOUT capability → CapabilityMissing ───────────────────
-    //
-    // Uses OPEN_POLICY_FOR_CAPABILITY_TEST so the policy returns Allow.
-    // The executor then runs its own capability check (Agent::required_capabilities)
-    // and returns CapabilityMissing before calling agent.propose().
 
     {
+        let stage = &capture.stages[1];
+
         println!("  Sub-case B: Agent WITHOUT capability (executor-level enforcement)");
         println!("  Patient ID: patient-101");
-        println!("  Capability: patient-records.read [NOT GRANTED]");
+        println!(
+            "  Capability: patient-records.read [{}]",
+            stage.capability_status("patient-records.read")
+        );
         println!("  Policy: allows query unconditionally (no capability guard in TOML)");
         println!("  Enforcement: executor's own capability check catches the gap");
 
-        // Open policy: returns Allow without checking capabilities.
-        let policy = TomlPolicyEngine::from_toml_str(OPEN_POLICY_FOR_CAPABILITY_TEST)?;
-        let execution_id = ExecutionId::new();
-        let audit = Arc::new(InMemoryAuditWriter::new(execution_id.0.to_string()));
-        let verifier = SchemaVerifier::new();
-        let schema = patient_query_schema();
-        let agent = PatientQueryAgent { patient_id: "patient-101".to_string() };
-
-        let state = AgentState {
-            agent_id: AgentId("patient-query-agent".to_string()),
-            execution_id: execution_id.clone(),
-            phase: "active".to_string(),
-            context: serde_json::Value::Null,
-            step: 0,
-        };
-
-        // Empty capability set — no capabilities granted.
-        let capabilities = CapabilitySet::default();
-
-        let input = AgentInput {
-            kind: "patient-query".to_string(),
-            payload: json!({ "patient_id": "patient-101" }),
-        };
-
-        let executor = Executor::new(
-            Box::new(policy),
-            Box::new(ArcAudit(Arc::clone(&audit))),
-            Box::new(verifier),
-            schema,
-        );
-
-        let result = executor.step(&agent, state, input, &capabilities);
-
-        match result {
-            Err(VeritasError::CapabilityMissing { capability, action }) => {
+        match &stage.outcome {
+            StageOutcome::Failed {
+                error: VeritasError::CapabilityMissing { capability, action },
+            } => {
                 println!("  Policy verdict:         Allow (policy permits the action)");
                 println!("  Capability check:       FAIL — '{}' missing for '{}'", capability, action);
                 println!("  Agent propose() called: NO (executor blocked before agent logic)");
-                let log = audit.export_log();
                 println!(
-                    "  Audit chain integrity:  VERIFIED ({} denial event(s) recorded)",
-                    log.events.len()
+                    "  Audit chain integrity:  {} ({} denial event(s) recorded)",
+                    if stage.chain_verified { "VERIFIED" } else { "FAILED" },
+                    stage.audit_log.events.len()
                 );
                 println!("  RESULT: CapabilityMissing (expected)");
             }
-            Err(e) => {
-                println!("  Unexpected error: {}", e);
+            StageOutcome::Failed { error } => {
+                println!("  Unexpected error: {}", error);
             }
-            Ok(StepResult::Denied { reason, .. }) => {
-                println!("  DENIED by policy: {}", reason);
+            StageOutcome::Denied { reason, code, remediation } => {
+                print_denial(*code, reason, remediation.as_deref());
             }
-            Ok(_) => {
+            _ => {
                 println!("  Unexpectedly succeeded");
             }
         }
@@ -295,66 +351,40 @@ pub fn run_scenario() -> VeritasResult<()> {
     // ── Sub-case C: WITH capability but no consent → Policy Deny ─────────────
 
     {
+        let stage = &capture.stages[2];
+
         println!("  Sub-case C: Agent WITH capability but no patient consent");
         println!("  Patient ID: patient-201nc (ai_query_consent = false)");
-        println!("  Capability: patient-records.read [GRANTED]");
-        println!("  Agent reports resource: patient-records-no-consent");
-
-        let policy = TomlPolicyEngine::from_toml_str(HEALTHCARE_POLICY)?;
-        let execution_id = ExecutionId::new();
-        let audit = Arc::new(InMemoryAuditWriter::new(execution_id.0.to_string()));
-        let verifier = SchemaVerifier::new();
-        let schema = patient_query_schema();
-
-        // Patient ID ending in "nc" → get_patient_record sets consent = false
-        // → describe_action returns resource = "patient-records-no-consent"
-        // → policy rule "deny-patient-query-no-consent" fires.
-        let agent = PatientQueryAgent { patient_id: "patient-201nc".to_string() };
-
-        let state = AgentState {
-            agent_id: AgentId("patient-query-agent".to_string()),
-            execution_id: execution_id.clone(),
-            phase: "active".to_string(),
-            context: serde_json::Value::Null,
-            step: 0,
-        };
-
-        let mut capabilities = CapabilitySet::default();
-        capabilities.grant(Capability::new("patient-records.read"));
-
-        let input = AgentInput {
-            kind: "patient-query".to_string(),
-            payload: json!({ "patient_id": "patient-201nc" }),
-        };
-
-        let executor = Executor::new(
-            Box::new(policy),
-            Box::new(ArcAudit(Arc::clone(&audit))),
-            Box::new(verifier),
-            schema,
+        println!(
+            "  Capability: patient-records.read [{}]",
+            stage.capability_status("patient-records.read")
         );
+        println!("  Agent reports resource: patient-records-no-consent");
 
-        let result = executor.step(&agent, state, input, &capabilities)?;
-
-        match result {
-            StepResult::Denied { reason, .. } => {
-                println!("  Policy verdict:         Deny");
+        match &stage.outcome {
+            StageOutcome::Denied { reason, code, remediation } => {
+                println!("  Policy verdict:         Deny ({})", code.as_str());
                 println!("  Deny reason:            {}", reason);
+                if let Some(remediation) = remediation {
+                    println!("  Remediation:            {}", remediation);
+                }
                 println!("  Agent propose() called: NO (blocked by policy before capability check)");
                 println!("  RESULT: Policy Denied (expected)");
             }
-            StepResult::Complete { .. } | StepResult::Transitioned { .. } => {
+            StageOutcome::Completed { .. } => {
                 println!("  Unexpectedly succeeded — consent enforcement failed");
             }
-            _ => {}
+            StageOutcome::Failed { error } => {
+                println!("  Unexpected error: {}", error);
+            }
+            StageOutcome::AwaitingApproval { .. } => {}
         }
 
-        let integrity_ok = audit.verify_integrity();
-        let log = audit.export_log();
+        print_stage_notes(stage);
         println!(
             "  Audit chain integrity:  {} ({} event(s), denial recorded)",
-            if integrity_ok { "VERIFIED" } else { "FAILED" },
-            log.events.len()
+            if stage.chain_verified { "VERIFIED" } else { "FAILED" },
+            stage.audit_log.events.len()
         );
         println!();
     }
@@ -0,0 +1,413 @@
+//! End-to-end assertions on every healthcare scenario.
+//!
+//! Each test runs a scenario's `capture_scenario()` — real policy TOML, real
+//! `SchemaVerifier` with the scenario's custom rules, real
+//! `InMemoryAuditWriter` and the real agents — and checks the result from the
+//! outside.  Every scenario is held to:
+//!
+//! - **Verdict sequence** — the stage outcomes and the policy verdicts
+//!   recorded in each audit log, in order.
+//! - **Chain integrity** — every stage's chain verifies, both through the
+//!   writer and by re-running `verify_chain` on the exported events, and the
+//!   log's `terminal_hash` is the last event's hash.
+//! - **Output fields** — the fields each completed stage is expected to
+//!   deliver, and that the audited output is exactly the delivered one.
+//!
+//! Per scenario:
+//!
+//! | Scenario | Stages | Expected |
+//! |---|---|---|
+//! | 1 Drug interaction | 1 | Allow; severity HIGH for warfarin + aspirin, flagged by a review warning |
+//! | 2 Note summarizer | 1 | Allow; 2 notes summarized, no PII labels |
+//! | 3 Patient query | A, B, C | A Allow; B CapabilityMissing audited as Deny; C consent Deny |
+//! | 4 Clinical pipeline | 4 | all Allow; each stage's audited input is the previous stage's audited output; HIGH risk reviewed |
+//! | 5 Prior auth | A 1–3, B 1–2 | RequireApproval → Allow → Allow; RequireApproval → coverage Deny, no step 3 |
+//! | 6 Conditional disclosure | A, B | A RequireVerification and delivered; B blocked by the PHI scan, nothing audited |
+
+use veritas_audit::verify_chain;
+use veritas_contracts::{
+    capability::{Capability, CapabilitySet},
+    error::VeritasError,
+    policy::{DenyCode, PolicyVerdict},
+};
+use veritas_ref_healthcare::{
+    grants::Grants,
+    report::{ScenarioCapture, StageCapture, StageOutcome},
+    scenarios::{
+        clinical_pipeline, conditional_disclosure, drug_interaction, note_summarizer,
+        patient_query, prior_auth,
+    },
+};
+
+// ── Helpers ───────────────────────────────────────────────────────────────────
+
+/// Assert that every stage's audit chain is intact and sealed by its
+/// terminal hash.
+fn assert_chains_intact(capture: &ScenarioCapture) {
+    assert!(capture.all_chains_verified(), "{}: chain failed", capture.name);
+    for stage in &capture.stages {
+        let log = &stage.audit_log;
+        assert!(verify_chain(&log.events), "{}: exported chain failed", stage.label);
+        let last_hash = log.events.last().map(|e| e.this_hash.as_str()).unwrap_or("");
+        assert_eq!(log.terminal_hash, last_hash, "{}: terminal hash", stage.label);
+    }
+}
+
+/// Assert that `stage` completed, that its log holds exactly one Allow-like
+/// event, and that the audited output is the delivered output.
+fn completed_output(stage: &StageCapture, verdict: &PolicyVerdict) -> serde_json::Value {
+    let output = stage
+        .output()
+        .unwrap_or_else(|| panic!("{}: expected Completed, got {:?}", stage.label, stage.outcome));
+    assert_eq!(stage.verdicts(), vec![verdict], "{}: verdicts", stage.label);
+    let audited = stage.audit_log.events[0]
+        .record
+        .output
+        .as_ref()
+        .unwrap_or_else(|| panic!("{}: completed step recorded no output", stage.label));
+    assert_eq!(audited.kind, output.kind, "{}: audited output kind", stage.label);
+    assert_eq!(audited.payload, output.payload, "{}: audited payload", stage.label);
+    output.payload.clone()
+}
+
+/// Assert that `stage` left exactly one audited denial with no output, whose
+/// reason contains `needle`.
+fn assert_single_audited_denial(stage: &StageCapture, needle: &str) {
+    assert_eq!(stage.audit_log.events.len(), 1, "{}: event count", stage.label);
+    let record = &stage.audit_log.events[0].record;
+    assert!(record.output.is_none(), "{}: denial must not record output", stage.label);
+    match &record.verdict {
+        PolicyVerdict::Deny { reason, .. } => {
+            assert!(reason.contains(needle), "{}: reason {:?}", stage.label, reason)
+        }
+        other => panic!("{}: expected Deny, got {:?}", stage.label, other),
+    }
+}
+
+fn labels(capture: &ScenarioCapture) -> Vec<&str> {
+    capture.stages.iter().map(|s| s.label.as_str()).collect()
+}
+
+// ── Scenario 1: Drug Interaction Checker ─────────────────────────────────────
+
+#[test]
+fn drug_interaction_allows_and_reports_high_severity() {
+    let capture = drug_interaction::capture_scenario().unwrap();
+
+    assert_eq!(labels(&capture), ["warfarin+aspirin"]);
+    assert_chains_intact(&capture);
+
+    let payload = completed_output(&capture.stages[0], &PolicyVerdict::Allow);
+    assert_eq!(payload["query"]["drug_a"], "warfarin");
+    assert_eq!(payload["query"]["drug_b"], "aspirin");
+    assert_eq!(payload["result"]["severity"], "HIGH");
+    assert!(payload["recommendation"].as_str().is_some_and(|r| !r.is_empty()));
+}
+
+#[test]
+fn drug_interaction_reports_high_severity_warning() {
+    let capture = drug_interaction::capture_scenario().unwrap();
+    let stage = &capture.stages[0];
+
+    // The warn-severity rule does not block delivery...
+    assert!(matches!(stage.outcome, StageOutcome::Completed { .. }));
+    // ...but its warning reaches the stage and the serializable report.
+    assert!(
+        stage.warnings.iter().any(|w| w.contains("high-severity-review")),
+        "warnings: {:?}",
+        stage.warnings
+    );
+    let report = capture.report();
+    assert_eq!(report.stages[0].outcome, "completed");
+    assert_eq!(report.stages[0].warnings, stage.warnings);
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert!(json["stages"][0]["warnings"][0]
+        .as_str()
+        .is_some_and(|w| w.contains("HIGH")));
+}
+
+// ── Scenario 2: Clinical Note Summarizer ─────────────────────────────────────
+
+#[test]
+fn note_summarizer_allows_a_pii_free_summary() {
+    let capture = note_summarizer::capture_scenario().unwrap();
+
+    assert_eq!(labels(&capture), ["summarize"]);
+    assert_chains_intact(&capture);
+
+    let payload = completed_output(&capture.stages[0], &PolicyVerdict::Allow);
+    assert_eq!(payload["patient_id"], "patient-042");
+    assert_eq!(payload["note_count"], 2);
+    let summary = payload["summary"].as_str().unwrap();
+    for label in ["DOB:", "SSN:", "MRN:", "Date of Birth:"] {
+        assert!(!summary.contains(label), "summary leaks '{}'", label);
+    }
+}
+
+// ── Scenario 3: Patient Data Query ───────────────────────────────────────────
+
+#[test]
+fn patient_query_shows_allow_capability_missing_and_consent_deny() {
+    let capture = patient_query::capture_scenario().unwrap();
+
+    assert_eq!(labels(&capture), ["A", "B", "C"]);
+    assert_chains_intact(&capture);
+
+    // A: capability + consent → the record is delivered.
+    let payload = completed_output(&capture.stages[0], &PolicyVerdict::Allow);
+    assert_eq!(payload["patient_id"], "patient-101");
+    assert_eq!(payload["ai_query_consent"], true);
+    assert_eq!(payload["conditions"].as_array().unwrap().len(), 3);
+
+    // B: the executor's own capability check fails, and the failure is
+    // audited as a synthetic denial.
+    let b = &capture.stages[1];
+    match &b.outcome {
+        StageOutcome::Failed {
+            error: VeritasError::CapabilityMissing { capability, action },
+        } => {
+            assert_eq!(capability, "patient-records.read");
+            assert_eq!(action, "query");
+        }
+        other => panic!("B: expected CapabilityMissing, got {:?}", other),
+    }
+    assert_single_audited_denial(b, "patient-records.read");
+
+    // C: no consent → the policy denies before the agent runs.
+    let c = &capture.stages[2];
+    match &c.outcome {
+        StageOutcome::Denied { reason, .. } => assert!(reason.contains("consent"), "{}", reason),
+        other => panic!("C: expected Denied, got {:?}", other),
+    }
+    assert_single_audited_denial(c, "consent");
+    // The consent rule's remediation hint reaches the stage report.
+    let hint = capture.report().stages[2].remediation.clone().unwrap();
+    assert!(hint.contains("consent"), "{}", hint);
+}
+
+#[test]
+fn patient_query_capability_audit_flags_sub_case_b() {
+    let report = patient_query::capture_scenario().unwrap().report();
+    let audit = &report.capability_audit;
+
+    let steps: Vec<&str> = audit.iter().map(|a| a.step.as_str()).collect();
+    assert_eq!(steps, ["A", "B", "C"]);
+    for entry in audit {
+        assert_eq!(entry.required, ["patient-records.read"], "{}", entry.step);
+    }
+
+    // A and C hold exactly what the agent declared.
+    assert!(audit[0].satisfied);
+    assert_eq!(audit[0].granted, ["patient-records.read"]);
+    assert!(audit[2].satisfied);
+
+    // B runs without the capability it declares.
+    let b = &audit[1];
+    assert!(!b.satisfied);
+    assert!(b.granted.is_empty(), "{:?}", b.granted);
+    let missing: Vec<&String> = b.required.iter().filter(|c| !b.granted.contains(c)).collect();
+    assert_eq!(missing, ["patient-records.read"]);
+
+    // The audit survives the JSON report.
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["capability_audit"][1]["satisfied"], false);
+    assert_eq!(json["capability_audit"][1]["required"][0], "patient-records.read");
+}
+
+#[test]
+fn patient_query_capability_audit_ignores_expired_grants() {
+    // The grant lapsed long ago: the executor refuses it, and so must the
+    // capability audit.
+    let mut capabilities = CapabilitySet::default();
+    let lapsed = "2000-01-01T00:00:00Z".parse().unwrap();
+    capabilities.grant_until(Capability::new("patient-records.read"), lapsed);
+    let grants = Grants::Only(capabilities);
+    let report = patient_query::capture_scenario_with(&grants).unwrap().report();
+
+    let a = &report.capability_audit[0];
+    assert_eq!(a.required, ["patient-records.read"]);
+    assert!(!a.satisfied);
+    assert!(a.granted.is_empty(), "{:?}", a.granted);
+    assert_eq!(report.stages[0].deny_code, Some(DenyCode::MissingCapability));
+}
+
+// ── Scenario 4: Multi-Agent Clinical Decision Pipeline ───────────────────────
+
+#[test]
+fn clinical_pipeline_links_four_allowed_chains() {
+    let capture = clinical_pipeline::capture_scenario().unwrap();
+
+    assert_eq!(
+        labels(&capture),
+        [
+            "stage-1/symptom-analyzer",
+            "stage-2/diagnosis-suggester",
+            "stage-3/treatment-planner",
+            "stage-4/drug-safety-checker",
+        ]
+    );
+    assert_chains_intact(&capture);
+
+    let payloads: Vec<_> = capture
+        .stages
+        .iter()
+        .map(|stage| completed_output(stage, &PolicyVerdict::Allow))
+        .collect();
+
+    // Cross-chain linkage: each stage's audited input is exactly the audited
+    // output of the stage before it, and every stage has its own chain.
+    assert_eq!(
+        capture.stages[0].audit_log.events[0].record.input.payload["patient_id"],
+        "patient-101"
+    );
+    for pair in capture.stages.windows(2) {
+        let upstream = &pair[0].audit_log.events[0].record;
+        let downstream = &pair[1].audit_log.events[0].record;
+        assert_eq!(
+            Some(&downstream.input.payload),
+            upstream.output.as_ref().map(|o| &o.payload),
+            "{} input is not {} output",
+            pair[1].label,
+            pair[0].label
+        );
+        assert_ne!(pair[0].audit_log.execution_id, pair[1].audit_log.execution_id);
+        assert_eq!(
+            pair[0].audit_log.execution_group_id,
+            pair[1].audit_log.execution_group_id,
+            "stages of one run share an execution group"
+        );
+    }
+    assert!(capture.stages[0].audit_log.execution_group_id.is_some());
+
+    assert!(payloads[0]["flags"].as_array().is_some_and(|f| !f.is_empty()));
+    assert!(payloads[1]["primary_hypothesis"].is_string());
+    assert_eq!(payloads[2]["medications"][0], "warfarin");
+    assert_eq!(payloads[2]["medications"][1], "aspirin");
+
+    let report = &payloads[3]["safety_report"];
+    assert_eq!(report["overall_risk"], "HIGH");
+    assert_eq!(report["interactions_found"], 1);
+    assert_eq!(report["reviewed"], true);
+}
+
+// ── Scenario 5: Prior Authorization Workflow ─────────────────────────────────
+
+#[test]
+fn prior_auth_suspends_then_submits_or_denies() {
+    let capture = prior_auth::capture_scenario().unwrap();
+
+    assert_eq!(
+        labels(&capture),
+        ["A/step-1", "A/step-2", "A/step-3", "B/step-1", "B/step-2"]
+    );
+    assert_chains_intact(&capture);
+
+    // Step 1 suspends both sub-cases for the attending physician, and the
+    // suspension is audited without an output.
+    for label in ["A/step-1", "B/step-1"] {
+        let stage = capture.stage(label).unwrap();
+        match &stage.outcome {
+            StageOutcome::AwaitingApproval { approver_role, .. } => {
+                assert_eq!(approver_role, "attending-physician")
+            }
+            other => panic!("{}: expected AwaitingApproval, got {:?}", label, other),
+        }
+        assert!(matches!(
+            stage.verdicts().as_slice(),
+            [PolicyVerdict::RequireApproval { approver_role, .. }] if approver_role == "attending-physician"
+        ));
+        assert!(stage.audit_log.events[0].record.output.is_none());
+    }
+
+    // Sub-case A: covered → eligibility and submission are both allowed, and
+    // the submission consumes the eligibility output.
+    let eligibility = completed_output(capture.stage("A/step-2").unwrap(), &PolicyVerdict::Allow);
+    assert_eq!(eligibility["procedure"], "cardiac-mri");
+    assert_eq!(eligibility["covered"], true);
+
+    let submission_stage = capture.stage("A/step-3").unwrap();
+    let submission = completed_output(submission_stage, &PolicyVerdict::Allow);
+    assert_eq!(submission["status"], "submitted");
+    assert_eq!(submission["pa_reference"], "PA-2026-0218-4471");
+    assert_eq!(submission_stage.audit_log.events[0].record.input.payload, eligibility);
+
+    // Sub-case B: uncovered → denied at eligibility, no submission.
+    let denied = capture.stage("B/step-2").unwrap();
+    match &denied.outcome {
+        StageOutcome::Denied { reason, .. } => assert!(reason.contains("not covered"), "{}", reason),
+        other => panic!("B/step-2: expected Denied, got {:?}", other),
+    }
+    assert_single_audited_denial(denied, "not covered");
+    assert!(capture.stage("B/step-3").is_none());
+}
+
+// ── Scenario 6: Conditional Disclosure ───────────────────────────────────────
+
+#[test]
+fn conditional_disclosure_delivers_clean_and_blocks_phi() {
+    let capture = conditional_disclosure::capture_scenario().unwrap();
+
+    assert_eq!(labels(&capture), ["A", "B"]);
+    assert_chains_intact(&capture);
+
+    // A: the policy demands the PHI scan; it passes and the summary is
+    // delivered under the RequireVerification verdict.
+    let payload = completed_output(
+        &capture.stages[0],
+        &PolicyVerdict::RequireVerification {
+            check_id: "phi-content-scan".to_string(),
+        },
+    );
+    assert_eq!(payload["patient_id"], "patient-042");
+    assert!(payload["summary"].as_str().unwrap().starts_with("Stable on metformin"));
+
+    // B: the scan fails, naming what it found; nothing is delivered or
+    // audited.
+    let b = &capture.stages[1];
+    match &b.outcome {
+        StageOutcome::Failed {
+            error: VeritasError::VerificationFailed { reason },
+        } => {
+            assert!(reason.contains("phi-content-scan"), "{}", reason);
+            assert!(reason.contains("Ssn at '/summary'"), "{}", reason);
+            assert!(reason.contains("Phone at '/summary'"), "{}", reason);
+        }
+        other => panic!("B: expected VerificationFailed, got {:?}", other),
+    }
+    assert!(b.audit_log.events.is_empty());
+}
+
+// ── Capability overrides ──────────────────────────────────────────────────────
+
+#[test]
+fn deny_all_grants_hit_the_missing_capability_path() {
+    let capture = drug_interaction::capture_scenario_with(&Grants::deny_all()).unwrap();
+    assert_chains_intact(&capture);
+
+    let stage = &capture.stages[0];
+    match &stage.outcome {
+        StageOutcome::Denied { reason, code, .. } => {
+            assert_eq!(*code, DenyCode::MissingCapability);
+            assert!(reason.contains("drug-database.read"), "{}", reason);
+        }
+        other => panic!("expected a missing-capability denial, got {:?}", other),
+    }
+    assert_single_audited_denial(stage, "drug-database.read");
+
+    let report = capture.report();
+    assert_eq!(report.stages[0].outcome, "denied");
+    assert_eq!(report.stages[0].deny_code, Some(DenyCode::MissingCapability));
+}
+
+#[test]
+fn granted_capabilities_replace_the_scenario_defaults() {
+    // Sub-case B normally runs without `patient-records.read`.
+    let grants = Grants::only(["patient-records.read"]);
+    let capture = patient_query::capture_scenario_with(&grants).unwrap();
+
+    assert_eq!(labels(&capture), ["A", "B", "C"]);
+    assert!(capture.stages[1].output().is_some(), "B: {:?}", capture.stages[1].outcome);
+    // The consent denial does not depend on capabilities.
+    assert!(matches!(capture.stages[2].outcome, StageOutcome::Denied { .. }));
+}
@@ -0,0 +1,294 @@
+//! Agents that check their own output against the schema they declare.
+//!
+//! In a multi-agent pipeline a verification failure says only that some
+//! output was rejected.  Wrapping each agent in a `SchemaBoundAgent` moves
+//! the first check into `propose`: an output that breaks the agent's own
+//! declared schema is returned as an error naming the agent, before the
+//! executor's verifier ever sees it.  The executor's verifier still runs in
+//! full — the self-check is a diagnostic, not a replacement.
+
+use veritas_contracts::{
+    agent::{AgentInput, AgentOutput, AgentState},
+    error::{VeritasError, VeritasResult},
+    execution::RejectionReason,
+    policy::ActionKind,
+    verify::{OutputSchema, VerificationRule, VerificationRuleType},
+};
+use veritas_core::traits::{Agent, Verifier};
+
+use crate::engine::SchemaVerifier;
+
+/// An `Agent` wrapper that verifies every proposed output against the
+/// agent's declared `OutputSchema`.
+///
+/// The self-check runs the schema's JSON Schema and its built-in rules.
+/// `Custom` and `AllowedFrom` rules are skipped: they depend on functions
+/// and value sets registered with the executor's verifier, which the
+/// wrapper does not have.
+pub struct SchemaBoundAgent<A> {
+    inner: A,
+    schema: OutputSchema,
+    self_check: SchemaVerifier,
+}
+
+impl<A: Agent> SchemaBoundAgent<A> {
+    /// Wrap `inner`, binding it to `schema`.
+    pub fn new(inner: A, schema: OutputSchema) -> Self {
+        let schema = OutputSchema {
+            rules: self_checkable(&schema.rules),
+            ..schema
+        };
+        Self {
+            inner,
+            schema,
+            self_check: SchemaVerifier::new(),
+        }
+    }
+
+    /// The schema the agent is bound to, without the rules the self-check
+    /// skips.
+    pub fn schema(&self) -> &OutputSchema {
+        &self.schema
+    }
+
+    /// The wrapped agent.
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+}
+
+/// `rules` without those the self-check cannot evaluate, including inside
+/// `EachElement`.
+fn self_checkable(rules: &[VerificationRule]) -> Vec<VerificationRule> {
+    rules
+        .iter()
+        .filter_map(|rule| match &rule.rule_type {
+            VerificationRuleType::Custom { .. } | VerificationRuleType::AllowedFrom { .. } => None,
+            VerificationRuleType::EachElement {
+                array_path,
+                element_rules,
+            } => Some(VerificationRule {
+                rule_type: VerificationRuleType::EachElement {
+                    array_path: array_path.clone(),
+                    element_rules: self_checkable(element_rules),
+                },
+                ..rule.clone()
+            }),
+            _ => Some(rule.clone()),
+        })
+        .collect()
+}
+
+impl<A: Agent> Agent for SchemaBoundAgent<A> {
+    /// Propose with the wrapped agent, then check the output against the
+    /// bound schema.
+    ///
+    /// Returns `VeritasError::SchemaValidation` naming the agent and every
+    /// error-severity failure if the output breaks the schema.
+    fn propose(&self, state: &AgentState, input: &AgentInput) -> VeritasResult<AgentOutput> {
+        let output = self.inner.propose(state, input)?;
+        let report = self.self_check.verify(&output, &self.schema)?;
+        if !report.passed {
+            let failures = report
+                .failures
+                .iter()
+                .map(|f| format!("[{}] {}", f.rule_id, f.message))
+                .collect::<Vec<_>>()
+                .join("; ");
+            return Err(VeritasError::SchemaValidation {
+                reason: format!(
+                    "agent '{}' proposed output violating its schema '{}': {}",
+                    state.agent_id.0, self.schema.schema_id, failures
+                ),
+            });
+        }
+        Ok(output)
+    }
+
+    fn transition(&self, state: &AgentState, output: &AgentOutput) -> VeritasResult<AgentState> {
+        self.inner.transition(state, output)
+    }
+
+    fn required_capabilities(&self, state: &AgentState, input: &AgentInput) -> Vec<String> {
+        self.inner.required_capabilities(state, input)
+    }
+
+    fn validate_input(&self, state: &AgentState, input: &AgentInput) -> VeritasResult<()> {
+        self.inner.validate_input(state, input)
+    }
+
+    fn describe_action(&self, state: &AgentState, input: &AgentInput) -> (String, String) {
+        self.inner.describe_action(state, input)
+    }
+
+    fn describe_action_kind(
+        &self,
+        state: &AgentState,
+        input: &AgentInput,
+        action: &str,
+    ) -> Option<ActionKind> {
+        self.inner.describe_action_kind(state, input, action)
+    }
+
+    fn action_metadata(&self, state: &AgentState, input: &AgentInput) -> serde_json::Value {
+        self.inner.action_metadata(state, input)
+    }
+
+    fn is_terminal(&self, state: &AgentState) -> bool {
+        self.inner.is_terminal(state)
+    }
+
+    fn on_rejected(&self, state: &AgentState, reason: &RejectionReason) -> Option<AgentInput> {
+        self.inner.on_rejected(state, reason)
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use veritas_contracts::{
+        agent::{AgentId, AgentInput, AgentOutput, AgentState, ExecutionId},
+        error::{VeritasError, VeritasResult},
+        verify::{OutputSchema, RuleSeverity, VerificationRule, VerificationRuleType},
+    };
+    use veritas_core::traits::Agent;
+
+    use super::SchemaBoundAgent;
+
+    /// Proposes a fixed payload.
+    struct FixedAgent {
+        payload: Value,
+    }
+
+    impl Agent for FixedAgent {
+        fn propose(&self, _state: &AgentState, _input: &AgentInput) -> VeritasResult<AgentOutput> {
+            Ok(AgentOutput {
+                kind: "summary".to_string(),
+                payload: self.payload.clone(),
+                provenance: None,
+                advisories: vec![],
+            })
+        }
+
+        fn transition(
+            &self,
+            state: &AgentState,
+            _output: &AgentOutput,
+        ) -> VeritasResult<AgentState> {
+            let mut next = state.clone();
+            next.step += 1;
+            Ok(next)
+        }
+
+        fn required_capabilities(&self, _state: &AgentState, _input: &AgentInput) -> Vec<String> {
+            vec![]
+        }
+
+        fn describe_action(&self, _state: &AgentState, _input: &AgentInput) -> (String, String) {
+            ("summarize".to_string(), "note".to_string())
+        }
+
+        fn is_terminal(&self, _state: &AgentState) -> bool {
+            false
+        }
+    }
+
+    fn make_state() -> AgentState {
+        AgentState {
+            agent_id: AgentId("summary-agent".to_string()),
+            execution_id: ExecutionId::new(),
+            phase: "start".to_string(),
+            context: Value::Null,
+            step: 0,
+            correlation_id: None,
+        }
+    }
+
+    fn make_input() -> AgentInput {
+        AgentInput {
+            kind: "user_message".to_string(),
+            payload: json!({}),
+        }
+    }
+
+    fn rule(id: &str, rule_type: VerificationRuleType) -> VerificationRule {
+        VerificationRule {
+            rule_id: id.to_string(),
+            description: String::new(),
+            severity: RuleSeverity::Error,
+            rule_type,
+        }
+    }
+
+    fn summary_schema() -> OutputSchema {
+        OutputSchema {
+            schema_id: "summary-v1".to_string(),
+            json_schema: json!({ "type": "object", "required": ["summary"] }),
+            rules: vec![
+                rule(
+                    "patient-id-required",
+                    VerificationRuleType::RequiredField {
+                        field_path: "patient_id".to_string(),
+                    },
+                ),
+                // Unregistered here; the self-check must skip it.
+                rule(
+                    "no-phi",
+                    VerificationRuleType::Custom {
+                        function_name: "no-phi".to_string(),
+                    },
+                ),
+            ],
+            defaults: vec![],
+        }
+    }
+
+    #[test]
+    fn test_conforming_output_passes_through() {
+        let agent = SchemaBoundAgent::new(
+            FixedAgent {
+                payload: json!({ "summary": "stable", "patient_id": "P-1" }),
+            },
+            summary_schema(),
+        );
+
+        let output = agent.propose(&make_state(), &make_input()).unwrap();
+        assert_eq!(output.payload["summary"], "stable");
+        assert_eq!(agent.schema().rules.len(), 1);
+    }
+
+    #[test]
+    fn test_missing_required_field_errors_with_agent_id() {
+        let agent = SchemaBoundAgent::new(
+            FixedAgent {
+                payload: json!({ "summary": "stable" }),
+            },
+            summary_schema(),
+        );
+
+        match agent.propose(&make_state(), &make_input()) {
+            Err(VeritasError::SchemaValidation { reason }) => {
+                assert!(reason.contains("agent 'summary-agent'"), "{reason}");
+                assert!(reason.contains("summary-v1"), "{reason}");
+                assert!(reason.contains("patient-id-required"), "{reason}");
+            }
+            other => panic!("expected SchemaValidation, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_missing_json_schema_required_field_errors() {
+        let agent = SchemaBoundAgent::new(
+            FixedAgent {
+                payload: json!({ "patient_id": "P-1" }),
+            },
+            summary_schema(),
+        );
+
+        let err = agent.propose(&make_state(), &make_input()).unwrap_err();
+        assert!(err.to_string().contains("agent 'summary-agent'"), "{err}");
+    }
+}
@@ -0,0 +1,210 @@
+//! Running several verifiers as one.
+//!
+//! The executor holds a single `Box<dyn Verifier>`.  `CompositeVerifier`
+//! lets an organization-wide verifier (a PII scan, say) and a
+//! scenario-specific one both inspect every output: it runs its members in
+//! order and merges their failures into one report.
+
+use veritas_contracts::{
+    agent::AgentOutput,
+    error::VeritasResult,
+    verify::{OutputSchema, RuleSeverity, VerificationFailure, VerificationReport},
+};
+use veritas_core::traits::Verifier;
+
+/// A `Verifier` that runs an ordered list of verifiers and merges their
+/// reports.
+///
+/// The merged report passes only if every member's report passes.  A member
+/// returning an error aborts verification with that error.
+#[derive(Default)]
+pub struct CompositeVerifier {
+    verifiers: Vec<Box<dyn Verifier>>,
+}
+
+impl CompositeVerifier {
+    /// Build a composite running `verifiers` in the given order.
+    pub fn new(verifiers: Vec<Box<dyn Verifier>>) -> Self {
+        Self { verifiers }
+    }
+
+    /// Append `verifier`; it runs after every verifier already added.
+    pub fn push(&mut self, verifier: Box<dyn Verifier>) {
+        self.verifiers.push(verifier);
+    }
+
+    /// Number of member verifiers.
+    pub fn len(&self) -> usize {
+        self.verifiers.len()
+    }
+
+    /// True if the composite has no members and so passes every output.
+    pub fn is_empty(&self) -> bool {
+        self.verifiers.is_empty()
+    }
+}
+
+impl Verifier for CompositeVerifier {
+    /// Run every member against `output` and merge all failures, in member
+    /// order, into one report.
+    fn verify(&self, output: &AgentOutput, schema: &OutputSchema) -> VeritasResult<VerificationReport> {
+        let mut failures = Vec::new();
+        for verifier in &self.verifiers {
+            failures.extend(verifier.verify(output, schema)?.failures);
+        }
+        Ok(VerificationReport::from_failures(failures))
+    }
+
+    /// Run the named check on the members in order and return the first
+    /// passing report.
+    ///
+    /// A check is usually registered with only one member, and members that
+    /// do not know it fail it; so the check passes as soon as one member
+    /// vouches for it.  If none does, the merged failures of every member
+    /// are returned.  An empty composite fails the check.
+    fn run_check(&self, check_id: &str, output: &AgentOutput) -> VeritasResult<VerificationReport> {
+        if self.verifiers.is_empty() {
+            return Ok(VerificationReport::from_failures(vec![VerificationFailure {
+                rule_id: check_id.to_string(),
+                message: format!("composite verifier has no member to run check '{}'", check_id),
+                severity: RuleSeverity::Error,
+            }]));
+        }
+        let mut failures = Vec::new();
+        for verifier in &self.verifiers {
+            let report = verifier.run_check(check_id, output)?;
+            if report.passed {
+                return Ok(report);
+            }
+            failures.extend(report.failures);
+        }
+        Ok(VerificationReport::from_failures(failures))
+    }
+
+    /// Pass `output` through every member's `normalize` in order, so each
+    /// member sees the defaults its predecessors filled.
+    fn normalize(&self, output: &AgentOutput, schema: &OutputSchema) -> AgentOutput {
+        self.verifiers
+            .iter()
+            .fold(output.clone(), |output, verifier| verifier.normalize(&output, schema))
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use veritas_contracts::verify::{VerificationRule, VerificationRuleType};
+
+    use super::*;
+    use crate::engine::SchemaVerifier;
+
+    fn make_output(payload: serde_json::Value) -> AgentOutput {
+        AgentOutput {
+            kind: "response".to_string(),
+            payload,
+            provenance: None,
+            advisories: vec![],
+        }
+    }
+
+    fn empty_schema() -> OutputSchema {
+        OutputSchema {
+            schema_id: "composite-v1".to_string(),
+            json_schema: serde_json::Value::Null,
+            rules: vec![],
+            defaults: vec![],
+        }
+    }
+
+    /// A verifier that reports the given failures for every output.
+    struct FixedVerifier(Vec<VerificationFailure>);
+
+    impl Verifier for FixedVerifier {
+        fn verify(&self, _output: &AgentOutput, _schema: &OutputSchema) -> VeritasResult<VerificationReport> {
+            Ok(VerificationReport::from_failures(self.0.clone()))
+        }
+    }
+
+    fn failure(rule_id: &str) -> VerificationFailure {
+        VerificationFailure {
+            rule_id: rule_id.to_string(),
+            message: format!("{rule_id} failed"),
+            severity: RuleSeverity::Error,
+        }
+    }
+
+    /// A passing member and a failing member merge into a failing report
+    /// carrying the second member's failure.
+    #[test]
+    fn test_merged_report_fails_if_any_member_fails() {
+        let composite = CompositeVerifier::new(vec![
+            Box::new(FixedVerifier(vec![])),
+            Box::new(FixedVerifier(vec![failure("org-pii-scan")])),
+        ]);
+
+        let report = composite.verify(&make_output(json!({})), &empty_schema()).unwrap();
+
+        assert!(!report.passed);
+        assert_eq!(report.error_count, 1);
+        assert_eq!(report.failures[0].rule_id, "org-pii-scan");
+
+        let passing = CompositeVerifier::new(vec![
+            Box::new(FixedVerifier(vec![])),
+            Box::new(FixedVerifier(vec![])),
+        ]);
+        assert!(passing.verify(&make_output(json!({})), &empty_schema()).unwrap().passed);
+    }
+
+    /// Failures from every member are kept, in member order.
+    #[test]
+    fn test_failures_from_all_members_are_merged_in_order() {
+        let mut composite = CompositeVerifier::default();
+        composite.push(Box::new(FixedVerifier(vec![failure("first")])));
+        composite.push(Box::new(FixedVerifier(vec![failure("second")])));
+
+        let report = composite.verify(&make_output(json!({})), &empty_schema()).unwrap();
+
+        let ids: Vec<&str> = report.failures.iter().map(|f| f.rule_id.as_str()).collect();
+        assert_eq!(ids, ["first", "second"]);
+    }
+
+    /// A named check passes when the member that registered it passes, even
+    /// though the other member does not implement it.
+    #[test]
+    fn test_run_check_passes_when_any_member_vouches() {
+        let mut local = SchemaVerifier::new();
+        local.register_rule(
+            "has-summary",
+            Box::new(|payload: &serde_json::Value| {
+                payload.get("summary").is_none().then(|| "summary missing".to_string())
+            }),
+        );
+        let composite = CompositeVerifier::new(vec![Box::new(FixedVerifier(vec![])), Box::new(local)]);
+
+        let report = composite
+            .run_check("has-summary", &make_output(json!({ "summary": "stable" })))
+            .unwrap();
+        assert!(report.passed, "failures: {:?}", report.failures);
+
+        let report = composite.run_check("has-summary", &make_output(json!({}))).unwrap();
+        assert!(!report.passed);
+        assert!(report.failures.iter().any(|f| f.message == "summary missing"));
+
+        // Members still apply their schema rules through `verify`.
+        let schema = OutputSchema {
+            rules: vec![VerificationRule {
+                rule_id: "req-summary".to_string(),
+                description: "summary is required".to_string(),
+                severity: RuleSeverity::Error,
+                rule_type: VerificationRuleType::RequiredField {
+                    field_path: "summary".to_string(),
+                },
+            }],
+            ..empty_schema()
+        };
+        assert!(!composite.verify(&make_output(json!({})), &schema).unwrap().passed);
+    }
+}
@@ -4,34 +4,98 @@
 //! Verification runs in two phases:
 //!
 //! 1. **Structural** — the `AgentOutput` payload is validated against the
-//!    `OutputSchema::json_schema` document using the `jsonschema` crate.
+//!    `OutputSchema::json_schema` document using the `jsonschema` crate, with
+//!    `format` keywords enforced, including the clinical formats of
+//!    [`crate::formats`].
 //! 2. **Semantic** — each `VerificationRule` in `OutputSchema::rules` is
 //!    evaluated in order.  All failures are collected before returning so
 //!    operators see the full failure set in one pass.
 //!
-//! Custom rules delegate to named functions registered via `register_rule`.
+//! `verify_streaming` runs the same phases but stops collecting failures
+//! after a configurable limit — both for JSON Schema errors and for the
+//! per-element failures of each `EachElement` rule — so a payload with a
+//! huge, mostly-broken array produces a bounded report instead of one
+//! failure per element.
+//!
+//! `AllowedFrom` rules look their permitted values up in named sets
+//! registered via `register_value_set`.
+//!
+//! Custom rules delegate to named functions registered via `register_rule`,
+//! or via `register_rule_ctx` for functions that also need the output kind
+//! and schema id (`VerifierContext`).
+//! The same functions back `Verifier::run_check`: a policy verdict of
+//! `RequireVerification { check_id }` runs the function registered as
+//! `check_id`.
 //! Keeping healthcare-specific logic out of the core verifier is a VERITAS
 //! design principle — domain adapters register what they need.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use regex::Regex;
 use tracing::{debug, warn};
 
 use veritas_contracts::{
     agent::AgentOutput,
     error::VeritasResult,
     verify::{
-        OutputSchema, VerificationFailure, VerificationReport, VerificationRuleType,
+        OutputSchema, RuleSeverity, VerificationFailure, VerificationReport, VerificationRule,
+        VerificationRuleType,
     },
 };
 use veritas_core::traits::Verifier;
 
+use crate::formats::CLINICAL_FORMATS;
+
 /// A caller-supplied verification function.
 ///
 /// Receives the full `AgentOutput` payload.  Returns `Some(message)` when the
 /// check fails with a human-readable explanation, or `None` on success.
 pub type CustomVerifierFn = Box<dyn Fn(&serde_json::Value) -> Option<String> + Send + Sync>;
 
+/// What a context-aware custom rule sees of the output being verified.
+#[derive(Debug, Clone, Copy)]
+pub struct VerifierContext<'a> {
+    /// The value the rule applies to: the output payload, or the current
+    /// array element inside an `EachElement` rule.
+    pub payload: &'a serde_json::Value,
+
+    /// `AgentOutput::kind` of the output being verified.
+    pub output_kind: &'a str,
+
+    /// `OutputSchema::schema_id` of the schema being applied, or `None` when
+    /// the function runs as a policy-required check (`Verifier::run_check`),
+    /// which has no schema.
+    pub schema_id: Option<&'a str>,
+}
+
+/// A caller-supplied verification function that receives a
+/// `VerifierContext` instead of the bare payload.
+///
+/// Lets one function serve several output kinds or schemas while tailoring
+/// its behaviour and messages to each.
+pub type ContextVerifierFn = Box<dyn Fn(&VerifierContext<'_>) -> Option<String> + Send + Sync>;
+
+/// A caller-supplied provider of the values an `AllowedFrom` rule permits.
+///
+/// Called each time a rule naming it is evaluated, so the set may change
+/// between verifications (e.g. a formulary reloaded in the background).
+pub type ValueSetFn = Box<dyn Fn() -> HashSet<serde_json::Value> + Send + Sync>;
+
+/// A registered custom function of either signature.
+enum RegisteredRule {
+    Payload(CustomVerifierFn),
+    Context(ContextVerifierFn),
+}
+
+impl RegisteredRule {
+    fn call(&self, ctx: &VerifierContext<'_>) -> Option<String> {
+        match self {
+            RegisteredRule::Payload(f) => f(ctx.payload),
+            RegisteredRule::Context(f) => f(ctx),
+        }
+    }
+}
+
 /// The VERITAS output verifier.
 ///
 /// Combines JSON Schema structural validation with a set of semantic rules.
@@ -39,32 +103,120 @@ pub type CustomVerifierFn = Box<dyn Fn(&serde_json::Value) -> Option<String> + S
 /// this keeps healthcare-specific knowledge out of the trusted runtime core.
 pub struct SchemaVerifier {
     /// Named custom verification functions provided by domain adapters.
-    custom_rules: HashMap<String, CustomVerifierFn>,
+    custom_rules: HashMap<String, RegisteredRule>,
+    /// Named value-set providers backing `AllowedFrom` rules.
+    value_sets: HashMap<String, ValueSetFn>,
+    /// How many failures `verify_streaming` collects per phase or per
+    /// `EachElement` rule before it stops scanning.
+    max_element_failures: usize,
 }
 
+/// The default `max_element_failures` for `verify_streaming`.
+pub const DEFAULT_MAX_ELEMENT_FAILURES: usize = 10;
+
 impl SchemaVerifier {
     /// Create a verifier with no custom rules registered.
     pub fn new() -> Self {
         Self {
             custom_rules: HashMap::new(),
+            value_sets: HashMap::new(),
+            max_element_failures: DEFAULT_MAX_ELEMENT_FAILURES,
         }
     }
 
+    /// Set how many failures `verify_streaming` collects before it stops
+    /// scanning (minimum 1).  Defaults to `DEFAULT_MAX_ELEMENT_FAILURES`.
+    ///
+    /// Has no effect on `verify`, which always reports every failure.
+    pub fn with_max_element_failures(mut self, max: usize) -> Self {
+        self.max_element_failures = max.max(1);
+        self
+    }
+
     /// Register a custom verification function under `name`.
     ///
     /// The name must match the `function_name` field used in
     /// `VerificationRuleType::Custom` rules. Registering the same name twice
     /// replaces the previous function.
     pub fn register_rule(&mut self, name: impl Into<String>, f: CustomVerifierFn) {
-        self.custom_rules.insert(name.into(), f);
+        self.custom_rules.insert(name.into(), RegisteredRule::Payload(f));
+    }
+
+    /// Register a context-aware verification function under `name`.
+    ///
+    /// Behaves like `register_rule`, except the function receives a
+    /// `VerifierContext` carrying the output kind and schema id alongside
+    /// the payload.  Both registrations share one namespace: registering
+    /// a name with either method replaces any previous function of that name.
+    pub fn register_rule_ctx(&mut self, name: impl Into<String>, f: ContextVerifierFn) {
+        self.custom_rules.insert(name.into(), RegisteredRule::Context(f));
+    }
+
+    /// Register the provider of the value set named `name`.
+    ///
+    /// The name must match the `set_name` field used in
+    /// `VerificationRuleType::AllowedFrom` rules. Registering the same name
+    /// twice replaces the previous provider.
+    pub fn register_value_set(&mut self, name: impl Into<String>, provider: ValueSetFn) {
+        self.value_sets.insert(name.into(), provider);
+    }
+
+    /// Verify `output` against `schema`, collecting at most
+    /// `max_element_failures` failures per phase and per `EachElement` rule.
+    ///
+    /// Intended for array-heavy payloads: validation walks each array element
+    /// in turn and stops as soon as the limit is reached, so the report — and
+    /// the work spent producing it — stays bounded however large the array
+    /// is.  A capped report is still a failing report; it is just not an
+    /// exhaustive one.
+    pub fn verify_streaming(
+        &self,
+        output: &AgentOutput,
+        schema: &OutputSchema,
+    ) -> VeritasResult<VerificationReport> {
+        self.verify_with_limit(output, schema, self.max_element_failures)
+    }
+
+    /// Verify `output` against `schema` `runs` times and report whether every
+    /// run produced the same result.
+    ///
+    /// Built-in rules are deterministic; a custom rule that consults the
+    /// clock, a random source or mutable state may not be.  Golden tests can
+    /// call this to flag such a rule before it makes them flaky.  Reports are
+    /// compared by their JSON form, and errors by their message.  Fewer than
+    /// two runs are trivially deterministic.
+    pub fn check_determinism(
+        &self,
+        output: &AgentOutput,
+        schema: &OutputSchema,
+        runs: usize,
+    ) -> bool {
+        let run_once = || -> Result<serde_json::Value, String> {
+            let report = self.verify(output, schema).map_err(|e| e.to_string())?;
+            serde_json::to_value(&report).map_err(|e| e.to_string())
+        };
+        if runs < 2 {
+            return true;
+        }
+        let first = run_once();
+        (1..runs).all(|_| run_once() == first)
     }
 
     // ── Internal helpers ──────────────────────────────────────────────────────
 
-    /// Resolve a dot-notation field path (e.g. `"patient.id"`) against a JSON
-    /// value.  Returns `None` when any segment is missing or the value is JSON
-    /// `null`.
+    /// Resolve a field path against a JSON value.
+    ///
+    /// A path starting with `/` is an RFC 6901 JSON Pointer (e.g.
+    /// `"/safety_report/details/0/severity"`); any other path is dot-notation
+    /// (e.g. `"patient.id"`).  Returns `None` when any segment is missing or
+    /// the value is JSON `null`.  The empty path resolves to `value` itself.
     fn resolve_path<'v>(value: &'v serde_json::Value, path: &str) -> Option<&'v serde_json::Value> {
+        if path.is_empty() {
+            return (!value.is_null()).then_some(value);
+        }
+        if path.starts_with('/') {
+            return value.pointer(path).filter(|v| !v.is_null());
+        }
         let mut current = value;
         for segment in path.split('.') {
             match current.get(segment) {
@@ -74,59 +226,201 @@ impl SchemaVerifier {
         }
         Some(current)
     }
-}
 
-impl Default for SchemaVerifier {
-    fn default() -> Self {
-        Self::new()
+    /// The `ForbiddenPattern` check of the field at `field_path`: a failure
+    /// message if its string value contains `pattern` (or, with `is_regex`,
+    /// a match of it) or `pattern` is an invalid regex.  An absent or
+    /// non-string field passes.
+    fn forbidden_pattern_failure(
+        payload: &serde_json::Value,
+        field_path: &str,
+        pattern: &str,
+        is_regex: bool,
+    ) -> Option<String> {
+        let s = Self::resolve_path(payload, field_path)?.as_str()?;
+        let found = if is_regex {
+            Regex::new(pattern).map(|re| re.is_match(s))
+        } else {
+            Ok(s.contains(pattern))
+        };
+        match found {
+            Ok(true) => Some(format!("field '{field_path}' contains forbidden pattern '{pattern}'")),
+            Ok(false) => None,
+            Err(e) => Some(format!(
+                "field '{field_path}' cannot be checked: invalid forbidden regex '{pattern}': {e}"
+            )),
+        }
     }
-}
 
-impl Verifier for SchemaVerifier {
-    /// Verify `output` against `schema`.
+    /// The `MonotonicField` check of `elements`, the array at `array_path`:
+    /// a failure message for the first element whose `field` is missing,
+    /// not a number, or out of order with the element before it.
+    fn monotonic_violation(
+        elements: &[serde_json::Value],
+        array_path: &str,
+        field: &str,
+        strict: bool,
+    ) -> Option<String> {
+        let order = if strict { "strictly increasing" } else { "non-decreasing" };
+        let mut previous: Option<f64> = None;
+        for (index, element) in elements.iter().enumerate() {
+            let Some(current) = Self::resolve_path(element, field).and_then(|v| v.as_f64()) else {
+                return Some(format!(
+                    "element {index} of '{array_path}' has no numeric '{field}'"
+                ));
+            };
+            if let Some(prev) = previous {
+                if current < prev || (strict && current == prev) {
+                    return Some(format!(
+                        "'{field}' in '{array_path}' is not {order}: element {index} has \
+                         {current} after {prev}"
+                    ));
+                }
+            }
+            previous = Some(current);
+        }
+        None
+    }
+
+    /// Resolve a dot-notation path whose `*` segments expand over every
+    /// element of an array (e.g. `"medications.*.dose"`).
     ///
-    /// Runs structural JSON Schema validation first, then evaluates every
-    /// semantic rule.  All failures are accumulated — the caller receives the
-    /// full picture in one report rather than only the first failure.
-    fn verify(
+    /// Returns one entry per expanded path, with the `*` segments replaced
+    /// by element indexes (`"medications.1.dose"`).  The value is `None`
+    /// where the path is missing or null, or where a `*` meets a non-array.
+    ///
+    /// A JSON Pointer does not expand: it resolves to exactly one entry.
+    fn resolve_wildcard<'v>(
+        value: &'v serde_json::Value,
+        path: &str,
+    ) -> Vec<(String, Option<&'v serde_json::Value>)> {
+        if path.starts_with('/') {
+            return vec![(path.to_string(), Self::resolve_path(value, path))];
+        }
+        let mut resolved = vec![(String::new(), Some(value))];
+        for segment in path.split('.') {
+            resolved = resolved
+                .into_iter()
+                .flat_map(|(prefix, current)| {
+                    let join = |s: &str| {
+                        if prefix.is_empty() {
+                            s.to_string()
+                        } else {
+                            format!("{prefix}.{s}")
+                        }
+                    };
+                    match current.and_then(|v| v.as_array()) {
+                        Some(items) if segment == "*" => items
+                            .iter()
+                            .enumerate()
+                            .map(|(i, item)| (join(&i.to_string()), (!item.is_null()).then_some(item)))
+                            .collect::<Vec<_>>(),
+                        _ => {
+                            let next = current
+                                .filter(|_| segment != "*")
+                                .and_then(|v| v.get(segment))
+                                .filter(|v| !v.is_null());
+                            vec![(join(segment), next)]
+                        }
+                    }
+                })
+                .collect();
+        }
+        resolved
+    }
+
+    /// Set the field at `path` in `value` to `default` if it is absent or
+    /// null, creating missing or null parent objects on the way.  Returns
+    /// whether the field was filled.
+    ///
+    /// A JSON Pointer may step into an existing array element; a dot path
+    /// names object keys only, and a `*` segment never fills anything.  A
+    /// path running into a non-container value fills nothing and leaves
+    /// `value` untouched.
+    fn fill_default(value: &mut serde_json::Value, path: &str, default: &serde_json::Value) -> bool {
+        let segments: Vec<String> = match path.strip_prefix('/') {
+            Some(pointer) => pointer
+                .split('/')
+                .map(|s| s.replace("~1", "/").replace("~0", "~"))
+                .collect(),
+            None if path.is_empty() => Vec::new(),
+            None => path.split('.').map(str::to_string).collect(),
+        };
+        let is_pointer = path.starts_with('/');
+
+        // Work on a copy so a path that turns out unfillable leaves no
+        // half-created parents behind.
+        let mut filled = value.clone();
+        let mut current = &mut filled;
+        for segment in &segments {
+            if !is_pointer && segment == "*" {
+                return false;
+            }
+            if current.is_null() {
+                *current = serde_json::Value::Object(serde_json::Map::new());
+            }
+            current = match current {
+                serde_json::Value::Object(map) => {
+                    map.entry(segment.clone()).or_insert(serde_json::Value::Null)
+                }
+                serde_json::Value::Array(items) if is_pointer => {
+                    match segment.parse::<usize>() {
+                        Ok(i) if i < items.len() => &mut items[i],
+                        _ => return false,
+                    }
+                }
+                _ => return false,
+            };
+        }
+        if !current.is_null() {
+            return false;
+        }
+        *current = default.clone();
+        *value = filled;
+        true
+    }
+
+    /// Check that `dose` reads `<number> <unit>` with a positive number and
+    /// an allowed unit.  Returns the reason it does not.
+    fn dose_format_error(dose: &str, allowed_units: &[String]) -> Option<String> {
+        let parts: Vec<&str> = dose.split_whitespace().collect();
+        let [quantity, unit] = parts.as_slice() else {
+            return Some("expected '<number> <unit>'".to_string());
+        };
+        match quantity.parse::<f64>() {
+            Ok(q) if q.is_finite() && q > 0.0 => {}
+            _ => return Some(format!("'{quantity}' is not a positive number")),
+        }
+        if allowed_units.iter().any(|u| u == unit) {
+            None
+        } else {
+            Some(format!("unit '{unit}' is not in the allowed set"))
+        }
+    }
+
+    /// The two-phase verification behind `verify` and `verify_streaming`.
+    fn verify_with_limit(
         &self,
         output: &AgentOutput,
         schema: &OutputSchema,
+        limit: usize,
     ) -> VeritasResult<VerificationReport> {
         let mut failures: Vec<VerificationFailure> = Vec::new();
         let payload = &output.payload;
+        let ctx = VerifierContext {
+            payload,
+            output_kind: &output.kind,
+            schema_id: Some(&schema.schema_id),
+        };
 
         // ── Phase 1: JSON Schema structural validation ────────────────────────
         //
         // A null json_schema means "no structural constraint" — skip validation.
         // This matches how the executor tests construct a bare OutputSchema.
         if !schema.json_schema.is_null() {
-            match jsonschema::validator_for(&schema.json_schema) {
-                Ok(validator) => {
-                    for error in validator.iter_errors(payload) {
-                        let message = format!(
-                            "JSON Schema violation at {}: {}",
-                            error.instance_path, error
-                        );
-                        warn!(schema_id = %schema.schema_id, %message, "structural validation failure");
-                        failures.push(VerificationFailure {
-                            rule_id: "json-schema".to_string(),
-                            message,
-                        });
-                    }
-                }
-                Err(e) => {
-                    // A malformed schema document is a configuration error; treat
-                    // it as a single structural failure so the run can still be
-                    // audited rather than crashing the executor.
-                    let message = format!("invalid JSON Schema document: {e}");
-                    warn!(schema_id = %schema.schema_id, %message, "schema compilation failure");
-                    failures.push(VerificationFailure {
-                        rule_id: "json-schema".to_string(),
-                        message,
-                    });
-                }
-            }
+            failures.extend(Self::guard_structural(&schema.schema_id, || {
+                Self::structural_failures(schema, payload, limit)
+            }));
         }
 
         // ── Phase 2: Semantic rule evaluation ────────────────────────────────
@@ -137,94 +431,560 @@ impl Verifier for SchemaVerifier {
                 "evaluating verification rule"
             );
 
-            let failure_msg: Option<String> = match &rule.rule_type {
-                // ── RequiredField ─────────────────────────────────────────────
-                // The field must be present at the resolved path and non-null.
-                VerificationRuleType::RequiredField { field_path } => {
-                    if Self::resolve_path(payload, field_path).is_none() {
-                        Some(format!("required field '{field_path}' is missing or null"))
-                    } else {
-                        None
+            // `Pass` is no rule severity: a rule built with it fails closed.
+            let severity = match rule.severity {
+                RuleSeverity::Pass => RuleSeverity::Error,
+                severity => severity,
+            };
+            for message in self.rule_failures(&rule.rule_type, &ctx, limit) {
+                warn!(
+                    rule_id = %rule.rule_id,
+                    severity = ?severity,
+                    %message,
+                    "semantic rule failed"
+                );
+                failures.push(VerificationFailure {
+                    rule_id: rule.rule_id.clone(),
+                    message,
+                    severity,
+                });
+            }
+        }
+
+        let report = VerificationReport::from_failures(failures);
+        debug!(
+            schema_id = %schema.schema_id,
+            passed = report.passed,
+            error_count = report.error_count,
+            warn_count = report.warn_count,
+            "verification complete"
+        );
+
+        Ok(report)
+    }
+
+    /// Compile `schema.json_schema` and validate `payload` against it,
+    /// returning at most `limit` failures.
+    ///
+    /// `format` keywords are asserted, not merely annotated, and the
+    /// `CLINICAL_FORMATS` are registered alongside the standard ones.
+    fn structural_failures(
+        schema: &OutputSchema,
+        payload: &serde_json::Value,
+        limit: usize,
+    ) -> Vec<VerificationFailure> {
+        let mut options = jsonschema::options();
+        options.should_validate_formats(true);
+        for (name, check) in CLINICAL_FORMATS {
+            options.with_format(name, check);
+        }
+        match options.build(&schema.json_schema) {
+            Ok(validator) => validator
+                .iter_errors(payload)
+                .take(limit)
+                .map(|error| {
+                    let message =
+                        format!("JSON Schema violation at {}: {}", error.instance_path, error);
+                    warn!(schema_id = %schema.schema_id, %message, "structural validation failure");
+                    VerificationFailure {
+                        rule_id: "json-schema".to_string(),
+                        message,
+                        severity: RuleSeverity::Error,
+                    }
+                })
+                .collect(),
+            Err(e) => {
+                // A malformed schema document is a configuration error; treat
+                // it as a single structural failure so the run can still be
+                // audited rather than crashing the executor.
+                let message = format!("invalid JSON Schema document: {e}");
+                warn!(schema_id = %schema.schema_id, %message, "schema compilation failure");
+                vec![VerificationFailure {
+                    rule_id: "json-schema".to_string(),
+                    message,
+                    severity: RuleSeverity::Error,
+                }]
+            }
+        }
+    }
+
+    /// Run the structural phase `f`, converting a panic into a single
+    /// `json-schema-panic` failure.
+    ///
+    /// Some exotic schema documents make the `jsonschema` crate panic rather
+    /// than return an error.  Unwinding out of `verify` would take the
+    /// executor down mid-step; a failing report instead blocks the output
+    /// and lets the semantic phase still run.
+    fn guard_structural<F>(schema_id: &str, f: F) -> Vec<VerificationFailure>
+    where
+        F: FnOnce() -> Vec<VerificationFailure>,
+    {
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)) {
+            Ok(failures) => failures,
+            Err(panic) => {
+                let detail = panic
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| panic.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".to_string());
+                let message = format!("JSON Schema validation panicked: {detail}");
+                warn!(schema_id, %message, "structural validation panicked");
+                vec![VerificationFailure {
+                    rule_id: "json-schema-panic".to_string(),
+                    message,
+                    severity: RuleSeverity::Error,
+                }]
+            }
+        }
+    }
+
+    /// Evaluate one rule against `ctx.payload` and return its failure
+    /// messages.
+    ///
+    /// Every rule type except `EachElement` yields at most one message.
+    fn rule_failures(
+        &self,
+        rule_type: &VerificationRuleType,
+        ctx: &VerifierContext<'_>,
+        limit: usize,
+    ) -> Vec<String> {
+        let payload = ctx.payload;
+        let failure_msg: Option<String> = match rule_type {
+            // ── RequiredField ─────────────────────────────────────────────
+            // The field must be present at the resolved path and non-null.
+            VerificationRuleType::RequiredField { field_path } => {
+                if Self::resolve_path(payload, field_path).is_none() {
+                    Some(format!("required field '{field_path}' is missing or null"))
+                } else {
+                    None
+                }
+            }
+
+            // ── ForbiddenField ────────────────────────────────────────────
+            // The field must be absent or null; null counts as absent, as it
+            // does for RequiredField.
+            VerificationRuleType::ForbiddenField { field_path } => {
+                Self::resolve_path(payload, field_path)
+                    .map(|_| format!("forbidden field '{field_path}' is present"))
+            }
+
+            // ── AllowedValues ─────────────────────────────────────────────
+            // The field value must appear in the exhaustive allowed set.
+            VerificationRuleType::AllowedValues { field_path, allowed } => {
+                match Self::resolve_path(payload, field_path) {
+                    None => Some(format!(
+                        "field '{field_path}' is missing; cannot check allowed values"
+                    )),
+                    Some(actual) => {
+                        if allowed.contains(actual) {
+                            None
+                        } else {
+                            Some(format!(
+                                "field '{field_path}' has value {actual} which is not in the allowed set"
+                            ))
+                        }
                     }
                 }
+            }
 
-                // ── AllowedValues ─────────────────────────────────────────────
-                // The field value must appear in the exhaustive allowed set.
-                VerificationRuleType::AllowedValues { field_path, allowed } => {
-                    match Self::resolve_path(payload, field_path) {
-                        None => Some(format!(
-                            "field '{field_path}' is missing; cannot check allowed values"
-                        )),
-                        Some(actual) => {
-                            if allowed.contains(actual) {
-                                None
-                            } else {
-                                Some(format!(
-                                    "field '{field_path}' has value {actual} which is not in the allowed set"
-                                ))
-                            }
+            // ── AllowedFrom ───────────────────────────────────────────────
+            // As AllowedValues, against a registered set.  A rule naming an
+            // unregistered set fails rather than passing unchecked.
+            VerificationRuleType::AllowedFrom { field_path, set_name } => {
+                let provider = self.value_sets.get(set_name.as_str());
+                match (provider, Self::resolve_path(payload, field_path)) {
+                    (None, _) => Some(format!("no value set registered under name '{set_name}'")),
+                    (Some(_), None) => Some(format!(
+                        "field '{field_path}' is missing; cannot check allowed values"
+                    )),
+                    (Some(provider), Some(actual)) => {
+                        if provider().contains(actual) {
+                            None
+                        } else {
+                            Some(format!(
+                                "field '{field_path}' has value {actual} which is not in value set '{set_name}'"
+                            ))
                         }
                     }
                 }
+            }
+
+            // ── ForbiddenPattern ──────────────────────────────────────────
+            // The field string value must not contain the forbidden pattern
+            // as a substring, or — with `is_regex` — a match of it as a
+            // regular expression.  An invalid regex fails the rule rather
+            // than silently passing.  Non-string fields pass silently — the
+            // rule is only meaningful for string values.
+            VerificationRuleType::ForbiddenPattern { field_path, pattern, is_regex } => {
+                Self::forbidden_pattern_failure(payload, field_path, pattern, *is_regex)
+            }
+
+            // ── ForbiddenPatternWhen ──────────────────────────────────────
+            // The ForbiddenPattern check, switched on by a condition field
+            // compared like ImpliesNull's.
+            VerificationRuleType::ForbiddenPatternWhen {
+                field_path,
+                pattern,
+                is_regex,
+                when_field,
+                when_equals,
+            } => {
+                let condition =
+                    Self::resolve_path(payload, when_field).unwrap_or(&serde_json::Value::Null);
+                if condition == when_equals {
+                    Self::forbidden_pattern_failure(payload, field_path, pattern, *is_regex)
+                        .map(|msg| format!("{msg} (checked because '{when_field}' is {when_equals})"))
+                } else {
+                    None
+                }
+            }
+
+            // ── MemberOfArray ─────────────────────────────────────────────
+            // The value must be one of the elements of another array field
+            // in the same payload.  Both fields must be present.
+            VerificationRuleType::MemberOfArray { value_field, array_field } => {
+                match (
+                    Self::resolve_path(payload, value_field),
+                    Self::resolve_path(payload, array_field),
+                ) {
+                    (None, _) => Some(format!(
+                        "field '{value_field}' is missing; cannot check membership in '{array_field}'"
+                    )),
+                    (Some(_), None) => Some(format!(
+                        "array field '{array_field}' is missing; cannot check membership of '{value_field}'"
+                    )),
+                    (Some(value), Some(array)) => match array.as_array() {
+                        None => Some(format!("field '{array_field}' is not an array")),
+                        Some(items) if items.contains(value) => None,
+                        Some(_) => Some(format!(
+                            "field '{value_field}' has value {value} which is not an element of '{array_field}'"
+                        )),
+                    },
+                }
+            }
+
+            // ── ImpliesNull ───────────────────────────────────────────────
+            // Once the condition field holds the trigger value, the target
+            // field must be absent or null.  A missing condition field is
+            // compared as null.
+            VerificationRuleType::ImpliesNull { when_field, when_equals, must_be_null_field } => {
+                let condition =
+                    Self::resolve_path(payload, when_field).unwrap_or(&serde_json::Value::Null);
+                match Self::resolve_path(payload, must_be_null_field) {
+                    Some(value) if condition == when_equals => Some(format!(
+                        "field '{must_be_null_field}' must be null when '{when_field}' is {when_equals}, but has value {value}"
+                    )),
+                    _ => None,
+                }
+            }
 
-                // ── ForbiddenPattern ──────────────────────────────────────────
-                // The field string value must not contain the forbidden pattern
-                // as a substring.  Non-string fields pass silently — the rule is
-                // only meaningful for string values.
-                VerificationRuleType::ForbiddenPattern { field_path, pattern } => {
-                    match Self::resolve_path(payload, field_path) {
-                        None => None, // field absent — nothing to check
-                        Some(v) => {
-                            if let Some(s) = v.as_str() {
-                                if s.contains(pattern.as_str()) {
+            // ── SeverityRecommendationConsistency ─────────────────────────
+            // A mapped severity demands one of its phrases somewhere in the
+            // recommendation.  Severities and phrases both compare
+            // case-insensitively.  Unmapped or absent severities have nothing
+            // to check.
+            VerificationRuleType::SeverityRecommendationConsistency {
+                severity_field,
+                recommendation_field,
+                mapping,
+            } => {
+                let severity = Self::resolve_path(payload, severity_field).map(|value| {
+                    value.as_str().map_or_else(|| value.to_string(), str::to_string)
+                });
+                let mapped = severity.and_then(|s| {
+                    let key = s.to_lowercase();
+                    mapping
+                        .iter()
+                        .find(|(mapped, _)| mapped.to_lowercase() == key)
+                        .map(|(_, phrases)| (s, phrases))
+                });
+                match mapped {
+                    None => None,
+                    Some((severity, phrases)) => {
+                        match Self::resolve_path(payload, recommendation_field)
+                            .and_then(|v| v.as_str())
+                        {
+                            None => Some(format!(
+                                "field '{recommendation_field}' is missing or not a string; severity '{severity}' requires a recommendation containing one of: {}",
+                                phrases.join(", ")
+                            )),
+                            Some(text) => {
+                                let text = text.to_lowercase();
+                                if phrases.iter().any(|p| text.contains(&p.to_lowercase())) {
+                                    None
+                                } else {
                                     Some(format!(
-                                        "field '{field_path}' contains forbidden pattern '{pattern}'"
+                                        "field '{recommendation_field}' does not match severity '{severity}': expected one of: {}",
+                                        phrases.join(", ")
                                     ))
-                                } else {
-                                    None
                                 }
-                            } else {
-                                None // non-string value — rule does not apply
                             }
                         }
                     }
                 }
+            }
+
+            // ── OnlyAllowedKeys ───────────────────────────────────────────
+            // The object must exist and carry no key outside the allowed
+            // list.  Every offending key is named so smuggled fields are
+            // visible in the audit trail.
+            VerificationRuleType::OnlyAllowedKeys { object_path, allowed_keys } => {
+                match Self::resolve_path(payload, object_path) {
+                    None => Some(format!(
+                        "object '{object_path}' is missing; cannot check its keys"
+                    )),
+                    Some(value) => match value.as_object() {
+                        None => Some(format!("field '{object_path}' is not an object")),
+                        Some(object) => {
+                            let extra: Vec<&str> = object
+                                .keys()
+                                .filter(|key| !allowed_keys.contains(key))
+                                .map(String::as_str)
+                                .collect();
+                            if extra.is_empty() {
+                                None
+                            } else {
+                                Some(format!(
+                                    "object '{object_path}' contains keys not in the allowed set: {}",
+                                    extra.join(", ")
+                                ))
+                            }
+                        }
+                    },
+                }
+            }
 
-                // ── Custom ────────────────────────────────────────────────────
-                // Delegate to the registered function. An unregistered name is
-                // itself a failure so misconfigured rules surface immediately.
-                VerificationRuleType::Custom { function_name } => {
-                    match self.custom_rules.get(function_name.as_str()) {
-                        Some(f) => f(payload),
-                        None => Some(format!(
-                            "no custom rule registered for function name '{function_name}'"
+            // ── MaxArrayLength ────────────────────────────────────────────
+            // The array must not exceed the bound.  Like ForbiddenPattern,
+            // an absent field has nothing to check; a non-array value is
+            // reported as such rather than as an over-long array.
+            VerificationRuleType::MaxArrayLength { field_path, max } => {
+                match Self::resolve_path(payload, field_path) {
+                    None => None,
+                    Some(value) => match value.as_array() {
+                        None => Some(format!("field '{field_path}' is not an array")),
+                        Some(array) if array.len() > *max => Some(format!(
+                            "array '{field_path}' has {} elements, more than the maximum of {max}",
+                            array.len()
                         )),
+                        Some(_) => None,
+                    },
+                }
+            }
+
+            // ── RelativeRange ─────────────────────────────────────────────
+            // Both fields must be numbers, the value within the permitted
+            // percentage of the baseline.
+            VerificationRuleType::RelativeRange {
+                field_path,
+                baseline_field,
+                max_pct_delta,
+            } => {
+                let number = |path: &str| match Self::resolve_path(payload, path) {
+                    None | Some(serde_json::Value::Null) => {
+                        Err(format!("field '{path}' is missing or null"))
                     }
+                    Some(v) => v
+                        .as_f64()
+                        .ok_or_else(|| format!("field '{path}' is not a number")),
+                };
+                match (number(field_path), number(baseline_field)) {
+                    (Err(msg), _) | (_, Err(msg)) => Some(msg),
+                    (Ok(value), Ok(0.0)) => (value != 0.0).then(|| {
+                        format!(
+                            "field '{field_path}' is {value} but baseline '{baseline_field}' is 0; \
+                             no relative difference is permitted"
+                        )
+                    }),
+                    (Ok(value), Ok(baseline)) => {
+                        let pct_delta = (value - baseline).abs() / baseline.abs() * 100.0;
+                        (pct_delta > *max_pct_delta).then(|| {
+                            format!(
+                                "field '{field_path}' is {value}, {pct_delta:.1}% from baseline \
+                                 '{baseline_field}' ({baseline}); at most {max_pct_delta}% is permitted"
+                            )
+                        })
+                    }
+                }
+            }
+
+            // ── MonotonicField ────────────────────────────────────────────
+            // Walk the array comparing each element's number with the one
+            // before; stop at the first element that breaks the order.
+            VerificationRuleType::MonotonicField { array_path, field, strict } => {
+                match Self::resolve_path(payload, array_path) {
+                    None => None,
+                    Some(value) => match value.as_array() {
+                        None => Some(format!("field '{array_path}' is not an array")),
+                        Some(elements) => {
+                            Self::monotonic_violation(elements, array_path, field, *strict)
+                        }
+                    },
+                }
+            }
+
+            // ── DoseFormat ────────────────────────────────────────────────
+            // Every value the (possibly wildcard) path resolves to must be
+            // a well-formed dose string.  Each malformed dose is reported
+            // under its concrete path, up to `limit`.
+            VerificationRuleType::DoseFormat { field_path, allowed_units } => {
+                return Self::resolve_wildcard(payload, field_path)
+                    .into_iter()
+                    .filter_map(|(path, value)| {
+                        let error = match value {
+                            None => "missing or null".to_string(),
+                            Some(v) => match v.as_str() {
+                                None => "not a string".to_string(),
+                                Some(dose) => Self::dose_format_error(dose, allowed_units)?,
+                            },
+                        };
+                        Some(format!("field '{path}' is not a valid dose: {error}"))
+                    })
+                    .take(limit)
+                    .collect();
+            }
+
+            // ── Custom ────────────────────────────────────────────────────
+            // Delegate to the registered function. An unregistered name is
+            // itself a failure so misconfigured rules surface immediately.
+            VerificationRuleType::Custom { function_name } => {
+                match self.custom_rules.get(function_name.as_str()) {
+                    Some(f) => f.call(ctx),
+                    None => Some(format!(
+                        "no custom rule registered for function name '{function_name}'"
+                    )),
                 }
+            }
+
+            // ── EachElement ───────────────────────────────────────────────
+            // Apply the element rules to every element of the array.
+            VerificationRuleType::EachElement { array_path, element_rules } => {
+                return self.element_failures(ctx, array_path, element_rules, limit);
+            }
+        };
+
+        failure_msg.into_iter().collect()
+    }
+
+    /// Apply `element_rules` to each element of the array at `array_path`,
+    /// one element at a time, stopping after `limit` failures.
+    ///
+    /// Messages name the failing element's index and element rule, e.g.
+    /// `element 3: [req-code] required field 'code' is missing or null`.
+    fn element_failures(
+        &self,
+        ctx: &VerifierContext<'_>,
+        array_path: &str,
+        element_rules: &[VerificationRule],
+        limit: usize,
+    ) -> Vec<String> {
+        let elements = match Self::resolve_path(ctx.payload, array_path) {
+            None => {
+                return vec![format!(
+                    "array field '{array_path}' is missing; cannot check its elements"
+                )];
+            }
+            Some(value) => match value.as_array() {
+                Some(elements) => elements,
+                None => return vec![format!("field '{array_path}' is not an array")],
+            },
+        };
+
+        let mut messages = Vec::new();
+        for (index, element) in elements.iter().enumerate() {
+            let element_ctx = VerifierContext {
+                payload: element,
+                ..*ctx
             };
+            for rule in element_rules {
+                for message in self.rule_failures(&rule.rule_type, &element_ctx, limit) {
+                    messages.push(format!("element {index}: [{}] {message}", rule.rule_id));
+                    if messages.len() >= limit {
+                        debug!(
+                            array_path,
+                            limit,
+                            checked = index + 1,
+                            total = elements.len(),
+                            "element failure limit reached; stopping scan"
+                        );
+                        return messages;
+                    }
+                }
+            }
+        }
+        messages
+    }
+}
 
-            if let Some(message) = failure_msg {
-                warn!(
-                    rule_id = %rule.rule_id,
-                    %message,
-                    "semantic rule failed"
+impl Default for SchemaVerifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Verifier for SchemaVerifier {
+    /// Verify `output` against `schema`.
+    ///
+    /// Runs structural JSON Schema validation first, then evaluates every
+    /// semantic rule.  All failures are accumulated — the caller receives the
+    /// full picture in one report rather than only the first failure.
+    fn verify(
+        &self,
+        output: &AgentOutput,
+        schema: &OutputSchema,
+    ) -> VeritasResult<VerificationReport> {
+        self.verify_with_limit(output, schema, usize::MAX)
+    }
+
+    /// Fill every `schema.defaults` field that `output` leaves absent or
+    /// null.
+    ///
+    /// Fields the agent did produce are never replaced, and a default whose
+    /// path cannot be created (it runs through a string, say) is skipped.
+    fn normalize(&self, output: &AgentOutput, schema: &OutputSchema) -> AgentOutput {
+        let mut normalized = output.clone();
+        for default in &schema.defaults {
+            if Self::fill_default(&mut normalized.payload, &default.field_path, &default.value) {
+                debug!(
+                    schema_id = %schema.schema_id,
+                    field_path = %default.field_path,
+                    "filled missing output field from schema default"
                 );
-                failures.push(VerificationFailure {
-                    rule_id: rule.rule_id.clone(),
-                    message,
-                });
             }
         }
+        normalized
+    }
 
-        let passed = failures.is_empty();
-        debug!(
-            schema_id = %schema.schema_id,
-            passed,
-            failure_count = failures.len(),
-            "verification complete"
-        );
+    /// Run the custom function registered under `check_id` against the
+    /// payload.  An unregistered check fails, so a policy naming a check the
+    /// adapter never provided blocks the step rather than skipping it.
+    fn run_check(&self, check_id: &str, output: &AgentOutput) -> VeritasResult<VerificationReport> {
+        let ctx = VerifierContext {
+            payload: &output.payload,
+            output_kind: &output.kind,
+            schema_id: None,
+        };
+        let failure_msg = match self.custom_rules.get(check_id) {
+            Some(f) => f.call(&ctx),
+            None => Some(format!("no custom rule registered for check '{check_id}'")),
+        };
+
+        let failures: Vec<VerificationFailure> = failure_msg
+            .map(|message| {
+                warn!(check_id, %message, "policy-required check failed");
+                VerificationFailure {
+                    rule_id: check_id.to_string(),
+                    message,
+                    severity: RuleSeverity::Error,
+                }
+            })
+            .into_iter()
+            .collect();
+
+        let report = VerificationReport::from_failures(failures);
+        debug!(check_id, passed = report.passed, "policy-required check complete");
 
-        Ok(VerificationReport { passed, failures })
+        Ok(report)
     }
 }
 
@@ -236,7 +996,9 @@ mod tests {
 
     use veritas_contracts::{
         agent::AgentOutput,
-        verify::{OutputSchema, VerificationRule, VerificationRuleType},
+        verify::{
+            FieldDefault, OutputSchema, RuleSeverity, VerificationRule, VerificationRuleType,
+        },
     };
 
     use super::SchemaVerifier;
@@ -248,6 +1010,8 @@ mod tests {
         AgentOutput {
             kind: "response".to_string(),
             payload,
+            provenance: None,
+            advisories: vec![],
         }
     }
 
@@ -256,6 +1020,7 @@ mod tests {
             schema_id: "test-schema-v1".to_string(),
             json_schema,
             rules,
+            defaults: vec![],
         }
     }
 
@@ -263,6 +1028,7 @@ mod tests {
         VerificationRule {
             rule_id: id.to_string(),
             description: desc.to_string(),
+            severity: RuleSeverity::Error,
             rule_type,
         }
     }
@@ -293,21 +1059,63 @@ mod tests {
         assert!(report.failures.is_empty());
     }
 
-    /// A payload missing a field declared required by the JSON Schema must
-    /// produce passed: false.
+    /// `format: "date"` is enforced, not just annotated.
     #[test]
-    fn test_schema_fail() {
+    fn test_schema_date_format_rejects_non_date() {
         let verifier = SchemaVerifier::new();
+        let schema = make_schema(
+            json!({
+                "type": "object",
+                "properties": { "admitted_on": { "type": "string", "format": "date" } }
+            }),
+            vec![],
+        );
 
-        let json_schema = json!({
-            "type": "object",
-            "properties": {
-                "status": { "type": "string" }
-            },
-            "required": ["status"]
-        });
+        let report = verifier.verify(&make_output(json!({ "admitted_on": "2026-02-18" })), &schema).unwrap();
+        assert!(report.passed, "expected pass, failures: {:?}", report.failures);
 
-        // Payload is missing "status".
+        let report = verifier.verify(&make_output(json!({ "admitted_on": "not-a-date" })), &schema).unwrap();
+        assert!(!report.passed);
+        assert_eq!(report.failures[0].rule_id, "json-schema");
+        assert!(report.failures[0].message.contains("not-a-date"), "{}", report.failures[0].message);
+    }
+
+    /// The custom `icd10` format accepts dotted codes and rejects `D509`.
+    #[test]
+    fn test_schema_icd10_format_rejects_undotted_code() {
+        let verifier = SchemaVerifier::new();
+        let schema = make_schema(
+            json!({
+                "type": "object",
+                "properties": { "diagnosis": { "type": "string", "format": "icd10" } }
+            }),
+            vec![],
+        );
+
+        let report = verifier.verify(&make_output(json!({ "diagnosis": "D50.9" })), &schema).unwrap();
+        assert!(report.passed, "expected pass, failures: {:?}", report.failures);
+
+        let report = verifier.verify(&make_output(json!({ "diagnosis": "D509" })), &schema).unwrap();
+        assert!(!report.passed);
+        assert_eq!(report.failures[0].rule_id, "json-schema");
+        assert!(report.failures[0].message.contains("D509"), "{}", report.failures[0].message);
+    }
+
+    /// A payload missing a field declared required by the JSON Schema must
+    /// produce passed: false.
+    #[test]
+    fn test_schema_fail() {
+        let verifier = SchemaVerifier::new();
+
+        let json_schema = json!({
+            "type": "object",
+            "properties": {
+                "status": { "type": "string" }
+            },
+            "required": ["status"]
+        });
+
+        // Payload is missing "status".
         let output = make_output(json!({ "other_field": 42 }));
         let schema = make_schema(json_schema, vec![]);
 
@@ -318,6 +1126,164 @@ mod tests {
         assert_eq!(report.failures[0].rule_id, "json-schema");
     }
 
+    /// A pathological schema document yields a failing report — never an
+    /// unwind — and the semantic rules still run after the structural phase.
+    #[test]
+    fn test_pathological_schema_returns_report() {
+        let verifier = SchemaVerifier::new();
+
+        let json_schema = json!({
+            "$ref": "#",
+            "type": 12,
+            "pattern": "(unclosed[",
+            "properties": { "status": { "$ref": "#/definitions/missing" } }
+        });
+        let output = make_output(json!({ "status": "ok" }));
+        let schema = make_schema(
+            json_schema,
+            vec![rule(
+                "req-id",
+                "id must be present",
+                VerificationRuleType::RequiredField {
+                    field_path: "id".to_string(),
+                },
+            )],
+        );
+
+        let report = verifier.verify(&output, &schema).unwrap();
+
+        assert!(!report.passed);
+        assert!(
+            report
+                .failures
+                .iter()
+                .any(|f| f.rule_id == "json-schema" || f.rule_id == "json-schema-panic"),
+            "expected a structural failure: {:?}",
+            report.failures
+        );
+        assert!(
+            report.failures.iter().any(|f| f.rule_id == "req-id"),
+            "semantic rules must still run: {:?}",
+            report.failures
+        );
+    }
+
+    /// A panic inside the structural phase becomes one `json-schema-panic`
+    /// failure carrying the panic message.
+    #[test]
+    fn test_structural_panic_becomes_failure() {
+        let failures = SchemaVerifier::guard_structural("test-schema-v1", || {
+            panic!("recursion limit reached while compiling schema")
+        });
+
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].rule_id, "json-schema-panic");
+        assert!(
+            failures[0].message.contains("recursion limit reached"),
+            "message: {}",
+            failures[0].message
+        );
+    }
+
+    // ── Severity tests ───────────────────────────────────────────────────────
+
+    /// Warn-severity failures are reported and counted but do not fail the
+    /// report; one error does, and sets `max_severity`.
+    #[test]
+    fn test_report_aggregates_severities() {
+        let verifier = SchemaVerifier::new();
+        let warn = |id: &str, field: &str| VerificationRule {
+            severity: RuleSeverity::Warn,
+            ..rule(
+                id,
+                "recommended field",
+                VerificationRuleType::RequiredField {
+                    field_path: field.to_string(),
+                },
+            )
+        };
+        let output = make_output(json!({ "status": "ok" }));
+
+        let warnings_only = make_schema(
+            serde_json::Value::Null,
+            vec![warn("warn-notes", "notes"), warn("warn-source", "source")],
+        );
+        let report = verifier.verify(&output, &warnings_only).unwrap();
+        assert!(report.passed, "warnings alone must not fail the report");
+        assert_eq!(report.max_severity, RuleSeverity::Warn);
+        assert_eq!((report.error_count, report.warn_count), (0, 2));
+
+        let with_error = make_schema(
+            serde_json::Value::Null,
+            vec![
+                warn("warn-notes", "notes"),
+                rule(
+                    "req-id",
+                    "id is mandatory",
+                    VerificationRuleType::RequiredField {
+                        field_path: "id".to_string(),
+                    },
+                ),
+                warn("warn-source", "source"),
+            ],
+        );
+        let report = verifier.verify(&output, &with_error).unwrap();
+        assert!(!report.passed);
+        assert_eq!(report.max_severity, RuleSeverity::Error);
+        assert_eq!(report.error_count, 1);
+        assert_eq!(report.warn_count, 2);
+        assert_eq!(report.failures.len(), 3);
+    }
+
+    /// A clean report has the lowest severity, `Pass`, and zero counts.
+    #[test]
+    fn test_clean_report_has_pass_severity() {
+        let report = SchemaVerifier::new()
+            .verify(&make_output(json!({})), &make_schema(serde_json::Value::Null, vec![]))
+            .unwrap();
+
+        assert!(report.passed);
+        assert_eq!(report.max_severity, RuleSeverity::Pass);
+        assert_eq!((report.error_count, report.warn_count), (0, 0));
+    }
+
+    /// A rule declared `pass` cannot mask a failure: such a schema does not
+    /// load, and a rule built with `Pass` in code fails as an error.
+    #[test]
+    fn test_pass_severity_rule_cannot_mask_failure() {
+        let err = OutputSchema::from_toml_str(
+            r#"
+schema_id = "masked-v1"
+
+[[rules]]
+rule_id = "req-id"
+description = "id is mandatory"
+severity = "pass"
+rule_type = { RequiredField = { field_path = "id" } }
+"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("not \"pass\""), "unexpected error: {}", err);
+
+        let schema = make_schema(
+            serde_json::Value::Null,
+            vec![VerificationRule {
+                severity: RuleSeverity::Pass,
+                ..rule(
+                    "req-id",
+                    "id is mandatory",
+                    VerificationRuleType::RequiredField {
+                        field_path: "id".to_string(),
+                    },
+                )
+            }],
+        );
+        let report = SchemaVerifier::new().verify(&make_output(json!({})), &schema).unwrap();
+        assert!(!report.passed);
+        assert_eq!(report.max_severity, RuleSeverity::Error);
+        assert_eq!(report.error_count, 1);
+    }
+
     // ── RequiredField tests ───────────────────────────────────────────────────
 
     /// A payload containing the required field at the given dot-path passes.
@@ -373,6 +1339,120 @@ mod tests {
         );
     }
 
+    // ── ForbiddenField tests ──────────────────────────────────────────────────
+
+    fn no_debug_prompt() -> OutputSchema {
+        make_schema(
+            serde_json::Value::Null,
+            vec![rule(
+                "no-debug-prompt",
+                "debug_prompt must never be delivered",
+                VerificationRuleType::ForbiddenField {
+                    field_path: "meta.debug_prompt".to_string(),
+                },
+            )],
+        )
+    }
+
+    #[test]
+    fn test_forbidden_field_absent_passes() {
+        let verifier = SchemaVerifier::new();
+        let output = make_output(json!({ "meta": { "model": "m1" } }));
+
+        let report = verifier.verify(&output, &no_debug_prompt()).unwrap();
+        assert!(report.passed, "expected pass, got failures: {:?}", report.failures);
+    }
+
+    #[test]
+    fn test_forbidden_field_present_fails() {
+        let verifier = SchemaVerifier::new();
+        let output = make_output(json!({ "meta": { "debug_prompt": "You are..." } }));
+
+        let report = verifier.verify(&output, &no_debug_prompt()).unwrap();
+        assert!(!report.passed);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].rule_id, "no-debug-prompt");
+        assert!(
+            report.failures[0].message.contains("meta.debug_prompt"),
+            "failure message should name the field: {}",
+            report.failures[0].message
+        );
+    }
+
+    /// Null counts as absent, matching `RequiredField`.
+    #[test]
+    fn test_forbidden_field_null_passes() {
+        let verifier = SchemaVerifier::new();
+        let output = make_output(json!({ "meta": { "debug_prompt": null } }));
+
+        let report = verifier.verify(&output, &no_debug_prompt()).unwrap();
+        assert!(report.passed, "expected pass, got failures: {:?}", report.failures);
+    }
+
+    // ── JSON Pointer paths ────────────────────────────────────────────────────
+
+    /// A pointer path indexes into arrays, which dot-paths cannot.
+    #[test]
+    fn test_pointer_path_resolves_array_index() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({
+            "safety_report": {
+                "details": [
+                    { "severity": "HIGH" },
+                    { "severity": "LOW" }
+                ]
+            }
+        }));
+        let severity_is = |allowed: &str| {
+            make_schema(
+                serde_json::Value::Null,
+                vec![rule(
+                    "first-detail-severity",
+                    "first interaction must carry the expected severity",
+                    VerificationRuleType::AllowedValues {
+                        field_path: "/safety_report/details/0/severity".to_string(),
+                        allowed: vec![json!(allowed)],
+                    },
+                )],
+            )
+        };
+
+        let report = verifier.verify(&output, &severity_is("HIGH")).unwrap();
+        assert!(report.passed, "expected pass, failures: {:?}", report.failures);
+
+        let report = verifier.verify(&output, &severity_is("LOW")).unwrap();
+        assert!(!report.passed);
+        assert!(report.failures[0].message.contains("/safety_report/details/0/severity"));
+    }
+
+    /// A pointer path addresses a key containing a dot, which the dot-path
+    /// form splits apart.
+    #[test]
+    fn test_pointer_path_addresses_key_with_dot() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({ "labs": { "2345-7.serum": 98 } }));
+        let required = |field_path: &str| {
+            make_schema(
+                serde_json::Value::Null,
+                vec![rule(
+                    "req-glucose",
+                    "serum glucose result must be present",
+                    VerificationRuleType::RequiredField {
+                        field_path: field_path.to_string(),
+                    },
+                )],
+            )
+        };
+
+        let report = verifier.verify(&output, &required("/labs/2345-7.serum")).unwrap();
+        assert!(report.passed, "expected pass, failures: {:?}", report.failures);
+
+        let report = verifier.verify(&output, &required("labs.2345-7.serum")).unwrap();
+        assert!(!report.passed);
+    }
+
     // ── AllowedValues tests ───────────────────────────────────────────────────
 
     /// When the field value is in the allowed set the rule passes.
@@ -422,6 +1502,71 @@ mod tests {
         assert_eq!(report.failures[0].rule_id, "allowed-status");
     }
 
+    // ── AllowedFrom tests ─────────────────────────────────────────────────────
+
+    fn formulary_schema() -> OutputSchema {
+        make_schema(
+            serde_json::Value::Null,
+            vec![rule(
+                "formulary-drug",
+                "prescribed drug must be on the formulary",
+                VerificationRuleType::AllowedFrom {
+                    field_path: "drug".to_string(),
+                    set_name: "formulary".to_string(),
+                },
+            )],
+        )
+    }
+
+    fn with_formulary() -> SchemaVerifier {
+        let mut verifier = SchemaVerifier::new();
+        verifier.register_value_set(
+            "formulary",
+            Box::new(|| {
+                ["metformin", "lisinopril", "atorvastatin"]
+                    .into_iter()
+                    .map(|drug| json!(drug))
+                    .collect()
+            }),
+        );
+        verifier
+    }
+
+    /// A drug on the registered formulary passes.
+    #[test]
+    fn test_allowed_from_member_passes() {
+        let output = make_output(json!({ "drug": "lisinopril" }));
+        let report = with_formulary().verify(&output, &formulary_schema()).unwrap();
+
+        assert!(report.passed, "expected pass, failures: {:?}", report.failures);
+    }
+
+    /// A drug missing from the formulary fails, naming the value and the set.
+    #[test]
+    fn test_allowed_from_non_member_fails() {
+        let output = make_output(json!({ "drug": "oxycodone" }));
+        let report = with_formulary().verify(&output, &formulary_schema()).unwrap();
+
+        assert!(!report.passed);
+        assert_eq!(report.failures[0].rule_id, "formulary-drug");
+        let message = &report.failures[0].message;
+        assert!(message.contains("oxycodone"), "message: {message}");
+        assert!(message.contains("'formulary'"), "message: {message}");
+    }
+
+    /// A rule naming a set nobody registered fails instead of passing.
+    #[test]
+    fn test_allowed_from_unregistered_set_fails() {
+        let output = make_output(json!({ "drug": "metformin" }));
+        let report = SchemaVerifier::new().verify(&output, &formulary_schema()).unwrap();
+
+        assert!(!report.passed);
+        assert_eq!(
+            report.failures[0].message,
+            "no value set registered under name 'formulary'"
+        );
+    }
+
     // ── ForbiddenPattern tests ────────────────────────────────────────────────
 
     /// A string field containing the forbidden substring causes a failure.
@@ -438,6 +1583,7 @@ mod tests {
                 VerificationRuleType::ForbiddenPattern {
                     field_path: "notes".to_string(),
                     pattern: "SSN".to_string(),
+                    is_regex: false,
                 },
             )],
         );
@@ -453,93 +1599,1164 @@ mod tests {
         );
     }
 
-    // ── Custom rule tests ─────────────────────────────────────────────────────
-
-    /// A registered custom function that returns None causes the rule to pass.
-    #[test]
-    fn test_custom_rule_pass() {
-        let mut verifier = SchemaVerifier::new();
-        verifier.register_rule(
-            "always-pass",
-            Box::new(|_payload| None),
-        );
-
-        let output = make_output(json!({ "field": "value" }));
-        let schema = make_schema(
+    fn forbidden(pattern: &str, is_regex: bool) -> OutputSchema {
+        make_schema(
             serde_json::Value::Null,
             vec![rule(
-                "custom-check",
-                "delegate to always-pass function",
-                VerificationRuleType::Custom {
-                    function_name: "always-pass".to_string(),
+                "no-ssn",
+                "output must not contain SSN patterns",
+                VerificationRuleType::ForbiddenPattern {
+                    field_path: "notes".to_string(),
+                    pattern: pattern.to_string(),
+                    is_regex,
                 },
             )],
-        );
+        )
+    }
 
-        let report = verifier.verify(&output, &schema).unwrap();
+    /// With `is_regex`, the pattern is searched as a regular expression, so
+    /// digit shapes are caught in free text.
+    #[test]
+    fn test_forbidden_regex_detects_ssn_shape() {
+        let verifier = SchemaVerifier::new();
+        let schema = forbidden(r"\d{3}-\d{2}-\d{4}", true);
 
-        assert!(report.passed, "expected pass, failures: {:?}", report.failures);
+        let leaked = make_output(json!({ "notes": "callback re 123-45-6789 tomorrow" }));
+        let report = verifier.verify(&leaked, &schema).unwrap();
+        assert!(!report.passed);
+        assert_eq!(report.failures[0].rule_id, "no-ssn");
+
+        let clean = make_output(json!({ "notes": "callback re MRN 12345 tomorrow" }));
+        assert!(verifier.verify(&clean, &schema).unwrap().passed);
     }
 
-    /// A registered custom function that returns Some(msg) causes a failure
-    /// with the rule_id of the enclosing rule.
+    /// Without `is_regex` the pattern is a literal substring: regex syntax
+    /// matches only itself.
     #[test]
-    fn test_custom_rule_fail() {
-        let mut verifier = SchemaVerifier::new();
-        verifier.register_rule(
-            "always-fail",
-            Box::new(|_payload| Some("custom check failed: condition not met".to_string())),
+    fn test_forbidden_literal_ignores_regex_syntax() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({ "notes": "patient SSN: 123-45-6789 recorded" }));
+        assert!(!verifier.verify(&output, &forbidden("SSN", false)).unwrap().passed);
+        assert!(verifier
+            .verify(&output, &forbidden(r"\d{3}-\d{2}-\d{4}", false))
+            .unwrap()
+            .passed);
+    }
+
+    /// An invalid regex fails the rule instead of letting the output through.
+    #[test]
+    fn test_forbidden_invalid_regex_fails_rule() {
+        let verifier = SchemaVerifier::new();
+        let output = make_output(json!({ "notes": "anything" }));
+
+        let report = verifier.verify(&output, &forbidden("(unclosed", true)).unwrap();
+
+        assert!(!report.passed);
+        assert!(
+            report.failures[0].message.contains("invalid forbidden regex"),
+            "message: {}",
+            report.failures[0].message
         );
+    }
 
-        let output = make_output(json!({ "field": "value" }));
-        let schema = make_schema(
+    // ── ForbiddenPatternWhen tests ────────────────────────────────────────────
+
+    fn no_ssn_when_shared() -> OutputSchema {
+        make_schema(
             serde_json::Value::Null,
             vec![rule(
-                "custom-check",
-                "delegate to always-fail function",
-                VerificationRuleType::Custom {
-                    function_name: "always-fail".to_string(),
+                "no-ssn-external",
+                "externally shared summaries must not contain an SSN",
+                VerificationRuleType::ForbiddenPatternWhen {
+                    field_path: "summary".to_string(),
+                    pattern: r"\b\d{3}-\d{2}-\d{4}\b".to_string(),
+                    is_regex: true,
+                    when_field: "shared_externally".to_string(),
+                    when_equals: json!(true),
                 },
             )],
-        );
+        )
+    }
 
-        let report = verifier.verify(&output, &schema).unwrap();
+    /// With the flag set, the pattern is enforced.
+    #[test]
+    fn test_forbidden_pattern_when_condition_holds_fails() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({
+            "summary": "Patient 123-45-6789 seen for follow-up",
+            "shared_externally": true
+        }));
+        let report = verifier.verify(&output, &no_ssn_when_shared()).unwrap();
 
         assert!(!report.passed);
-        assert_eq!(report.failures[0].rule_id, "custom-check");
+        assert_eq!(report.failures[0].rule_id, "no-ssn-external");
         assert!(
-            report.failures[0].message.contains("condition not met"),
-            "failure should carry the message from the custom function: {}",
+            report.failures[0].message.contains("'shared_externally' is true"),
+            "{}",
             report.failures[0].message
         );
+
+        let clean = make_output(json!({
+            "summary": "Patient seen for follow-up",
+            "shared_externally": true
+        }));
+        assert!(verifier.verify(&clean, &no_ssn_when_shared()).unwrap().passed);
     }
 
-    /// Referencing a custom function name that was never registered is itself
-    /// a failure — misconfigured schemas must surface immediately.
+    /// With the flag false or absent, the pattern is ignored.
     #[test]
-    fn test_unregistered_custom_rule() {
-        let verifier = SchemaVerifier::new(); // no rules registered
+    fn test_forbidden_pattern_when_condition_fails_passes() {
+        let verifier = SchemaVerifier::new();
 
-        let output = make_output(json!({ "field": "value" }));
-        let schema = make_schema(
+        for payload in [
+            json!({ "summary": "Patient 123-45-6789", "shared_externally": false }),
+            json!({ "summary": "Patient 123-45-6789" }),
+        ] {
+            let report = verifier.verify(&make_output(payload), &no_ssn_when_shared()).unwrap();
+            assert!(report.passed, "failures: {:?}", report.failures);
+        }
+    }
+
+    // ── MemberOfArray tests ───────────────────────────────────────────────────
+
+    fn member_of_medications() -> OutputSchema {
+        make_schema(
             serde_json::Value::Null,
             vec![rule(
-                "phantom-check",
-                "references a function that does not exist",
-                VerificationRuleType::Custom {
-                    function_name: "does-not-exist".to_string(),
+                "primary-in-medications",
+                "primary_medication must be one of medications",
+                VerificationRuleType::MemberOfArray {
+                    value_field: "primary_medication".to_string(),
+                    array_field: "medications".to_string(),
                 },
             )],
-        );
+        )
+    }
 
-        let report = verifier.verify(&output, &schema).unwrap();
+    /// A value that appears in the referenced array passes.
+    #[test]
+    fn test_member_of_array_pass() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({
+            "primary_medication": "warfarin",
+            "medications": ["warfarin", "aspirin"]
+        }));
+
+        let report = verifier.verify(&output, &member_of_medications()).unwrap();
+
+        assert!(report.passed, "expected pass, failures: {:?}", report.failures);
+    }
+
+    /// A value that is not an element of the referenced array fails.
+    #[test]
+    fn test_member_of_array_non_member() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({
+            "primary_medication": "ibuprofen",
+            "medications": ["warfarin", "aspirin"]
+        }));
+
+        let report = verifier.verify(&output, &member_of_medications()).unwrap();
 
         assert!(!report.passed);
-        assert_eq!(report.failures[0].rule_id, "phantom-check");
+        assert_eq!(report.failures[0].rule_id, "primary-in-medications");
+        assert!(
+            report.failures[0].message.contains("ibuprofen"),
+            "failure should name the offending value: {}",
+            report.failures[0].message
+        );
+    }
+
+    /// A missing array field fails with a message naming the array.
+    #[test]
+    fn test_member_of_array_missing_array() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({ "primary_medication": "warfarin" }));
+
+        let report = verifier.verify(&output, &member_of_medications()).unwrap();
+
+        assert!(!report.passed);
+        assert!(
+            report.failures[0].message.contains("'medications' is missing"),
+            "failure should name the missing array field: {}",
+            report.failures[0].message
+        );
+    }
+
+    // ── ImpliesNull tests ─────────────────────────────────────────────────────
+
+    fn uncovered_has_no_copay() -> OutputSchema {
+        make_schema(
+            serde_json::Value::Null,
+            vec![rule(
+                "uncovered-no-copay",
+                "Uncovered procedures must not have a copay",
+                VerificationRuleType::ImpliesNull {
+                    when_field: "covered".to_string(),
+                    when_equals: json!(false),
+                    must_be_null_field: "copay_usd".to_string(),
+                },
+            )],
+        )
+    }
+
+    /// A covered procedure may carry a copay.
+    #[test]
+    fn test_implies_null_covered_with_copay_passes() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({ "covered": true, "copay_usd": 40 }));
+
+        let report = verifier.verify(&output, &uncovered_has_no_copay()).unwrap();
+
+        assert!(report.passed, "expected pass, failures: {:?}", report.failures);
+    }
+
+    /// An uncovered procedure with a null copay passes.
+    #[test]
+    fn test_implies_null_uncovered_with_null_copay_passes() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({ "covered": false, "copay_usd": null }));
+
+        let report = verifier.verify(&output, &uncovered_has_no_copay()).unwrap();
+
+        assert!(report.passed, "expected pass, failures: {:?}", report.failures);
+    }
+
+    /// An uncovered procedure with a copay fails, naming the offending value.
+    #[test]
+    fn test_implies_null_uncovered_with_copay_fails() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({ "covered": false, "copay_usd": 40 }));
+
+        let report = verifier.verify(&output, &uncovered_has_no_copay()).unwrap();
+
+        assert!(!report.passed);
+        assert_eq!(report.failures[0].rule_id, "uncovered-no-copay");
+        assert!(
+            report.failures[0].message.contains("'copay_usd' must be null")
+                && report.failures[0].message.contains("40"),
+            "failure should name the field and its value: {}",
+            report.failures[0].message
+        );
+    }
+
+    // ── SeverityRecommendationConsistency tests ───────────────────────────────
+
+    fn recommendation_matches_severity() -> OutputSchema {
+        make_schema(
+            serde_json::Value::Null,
+            vec![rule(
+                "recommendation-matches-severity",
+                "Serious interactions must not be downplayed",
+                VerificationRuleType::SeverityRecommendationConsistency {
+                    severity_field: "severity".to_string(),
+                    recommendation_field: "recommendation".to_string(),
+                    mapping: [
+                        ("HIGH", vec!["avoid", "monitor"]),
+                        ("MODERATE", vec!["monitor"]),
+                    ]
+                    .into_iter()
+                    .map(|(k, v)| (k.to_string(), v.into_iter().map(String::from).collect()))
+                    .collect(),
+                },
+            )],
+        )
+    }
+
+    /// A HIGH severity with an "avoid" recommendation passes, whatever the
+    /// case of the phrase.
+    #[test]
+    fn test_severity_recommendation_high_with_avoid_passes() {
+        let verifier = SchemaVerifier::new();
+
+        for recommendation in ["avoid concurrent use", "Avoid concurrent use"] {
+            let output = make_output(json!({
+                "severity": "HIGH",
+                "recommendation": recommendation
+            }));
+            let report = verifier.verify(&output, &recommendation_matches_severity()).unwrap();
+            assert!(report.passed, "expected pass, failures: {:?}", report.failures);
+        }
+    }
+
+    /// A HIGH severity with a benign note fails, naming the severity and
+    /// the phrases it needs.
+    #[test]
+    fn test_severity_recommendation_high_with_benign_note_fails() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({
+            "severity": "HIGH",
+            "recommendation": "generally safe"
+        }));
+
+        let report = verifier.verify(&output, &recommendation_matches_severity()).unwrap();
+
+        assert!(!report.passed);
+        assert_eq!(report.failures[0].rule_id, "recommendation-matches-severity");
+        assert!(
+            report.failures[0].message.contains("severity 'HIGH'")
+                && report.failures[0].message.contains("avoid, monitor"),
+            "failure should name the severity and its phrases: {}",
+            report.failures[0].message
+        );
+    }
+
+    /// A severity is looked up whatever its case, so a lowercase "high" is
+    /// held to the phrases mapped for "HIGH".
+    #[test]
+    fn test_severity_recommendation_matches_severity_case_insensitively() {
+        let verifier = SchemaVerifier::new();
+        let schema = recommendation_matches_severity();
+
+        let downplayed = make_output(json!({
+            "severity": "high",
+            "recommendation": "generally safe"
+        }));
+        let report = verifier.verify(&downplayed, &schema).unwrap();
+        assert!(!report.passed);
+        assert!(report.failures[0].message.contains("severity 'high'"), "{:?}", report.failures);
+
+        let monitored = make_output(json!({
+            "severity": "Moderate",
+            "recommendation": "monitor INR weekly"
+        }));
+        assert!(verifier.verify(&monitored, &schema).unwrap().passed);
+    }
+
+    /// Unmapped and absent severities pass; a mapped severity without a
+    /// recommendation fails.
+    #[test]
+    fn test_severity_recommendation_unmapped_and_missing() {
+        let verifier = SchemaVerifier::new();
+        let schema = recommendation_matches_severity();
+
+        let low = make_output(json!({ "severity": "LOW", "recommendation": "generally safe" }));
+        assert!(verifier.verify(&low, &schema).unwrap().passed);
+
+        let none = make_output(json!({ "recommendation": "generally safe" }));
+        assert!(verifier.verify(&none, &schema).unwrap().passed);
+
+        let silent = make_output(json!({ "severity": "MODERATE" }));
+        let report = verifier.verify(&silent, &schema).unwrap();
+        assert!(!report.passed);
+        assert!(
+            report.failures[0].message.contains("'recommendation' is missing"),
+            "{}",
+            report.failures[0].message
+        );
+    }
+
+    // ── OnlyAllowedKeys tests ─────────────────────────────────────────────────
+
+    fn allowed_result_keys() -> OutputSchema {
+        make_schema(
+            serde_json::Value::Null,
+            vec![rule(
+                "result-keys",
+                "result may only carry severity and mechanism",
+                VerificationRuleType::OnlyAllowedKeys {
+                    object_path: "result".to_string(),
+                    allowed_keys: vec!["severity".to_string(), "mechanism".to_string()],
+                },
+            )],
+        )
+    }
+
+    /// A nested object carrying only allowed keys passes.
+    #[test]
+    fn test_only_allowed_keys_pass() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({
+            "result": { "severity": "HIGH", "mechanism": "platelet inhibition" },
+            "recommendation": "avoid"
+        }));
+
+        let report = verifier.verify(&output, &allowed_result_keys()).unwrap();
+
+        assert!(report.passed, "expected pass, failures: {:?}", report.failures);
+    }
+
+    /// An extra key smuggled into the nested object fails and is named.
+    #[test]
+    fn test_only_allowed_keys_extra_key() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({
+            "result": {
+                "severity": "HIGH",
+                "mechanism": "platelet inhibition",
+                "debug_token": "sk-live-0000"
+            }
+        }));
+
+        let report = verifier.verify(&output, &allowed_result_keys()).unwrap();
+
+        assert!(!report.passed);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].rule_id, "result-keys");
+        assert!(
+            report.failures[0].message.contains("debug_token"),
+            "failure should name the offending key: {}",
+            report.failures[0].message
+        );
+        assert!(
+            !report.failures[0].message.contains("severity"),
+            "failure should only name offending keys: {}",
+            report.failures[0].message
+        );
+    }
+
+    // ── MaxArrayLength tests ──────────────────────────────────────────────────
+
+    fn at_most_three_diagnoses() -> OutputSchema {
+        make_schema(
+            serde_json::Value::Null,
+            vec![rule(
+                "max-differentials",
+                "at most three differential diagnoses",
+                VerificationRuleType::MaxArrayLength {
+                    field_path: "differentials".to_string(),
+                    max: 3,
+                },
+            )],
+        )
+    }
+
+    /// An array holding exactly `max` elements passes.
+    #[test]
+    fn test_max_array_length_at_limit_passes() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({ "differentials": ["anemia", "CKD", "hypothyroidism"] }));
+        let report = verifier.verify(&output, &at_most_three_diagnoses()).unwrap();
+
+        assert!(report.passed, "expected pass, failures: {:?}", report.failures);
+    }
+
+    /// One element over the bound fails, stating actual and maximum counts.
+    #[test]
+    fn test_max_array_length_over_limit_fails() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({
+            "differentials": ["anemia", "CKD", "hypothyroidism", "heart failure"]
+        }));
+        let report = verifier.verify(&output, &at_most_three_diagnoses()).unwrap();
+
+        assert!(!report.passed);
+        assert_eq!(report.failures[0].rule_id, "max-differentials");
+        let message = &report.failures[0].message;
+        assert!(message.contains("has 4 elements"), "message: {message}");
+        assert!(message.contains("maximum of 3"), "message: {message}");
+    }
+
+    /// A non-array value fails with its own message.
+    #[test]
+    fn test_max_array_length_non_array_fails() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({ "differentials": "anemia" }));
+        let report = verifier.verify(&output, &at_most_three_diagnoses()).unwrap();
+
+        assert!(!report.passed);
+        assert_eq!(report.failures[0].message, "field 'differentials' is not an array");
+    }
+
+    // ── RelativeRange tests ───────────────────────────────────────────────────
+
+    fn follow_up_within_20_pct() -> OutputSchema {
+        make_schema(
+            serde_json::Value::Null,
+            vec![rule(
+                "follow-up-dose",
+                "follow-up dose within 20% of baseline",
+                VerificationRuleType::RelativeRange {
+                    field_path: "follow_up_mg".to_string(),
+                    baseline_field: "baseline_mg".to_string(),
+                    max_pct_delta: 20.0,
+                },
+            )],
+        )
+    }
+
+    /// A value within the tolerance, on either side of the baseline, passes.
+    #[test]
+    fn test_relative_range_within_tolerance_passes() {
+        let verifier = SchemaVerifier::new();
+
+        for follow_up in [100, 120, 85] {
+            let output = make_output(json!({ "baseline_mg": 100, "follow_up_mg": follow_up }));
+            let report = verifier.verify(&output, &follow_up_within_20_pct()).unwrap();
+            assert!(report.passed, "{follow_up}: failures: {:?}", report.failures);
+        }
+    }
+
+    /// A value beyond the tolerance fails, stating the actual delta.
+    #[test]
+    fn test_relative_range_out_of_tolerance_fails() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({ "baseline_mg": 100, "follow_up_mg": 150 }));
+        let report = verifier.verify(&output, &follow_up_within_20_pct()).unwrap();
+
+        assert!(!report.passed);
+        assert_eq!(report.failures[0].rule_id, "follow-up-dose");
+        assert!(report.failures[0].message.contains("50.0%"), "{}", report.failures[0].message);
+    }
+
+    /// A missing baseline or a non-numeric value fails descriptively.
+    #[test]
+    fn test_relative_range_missing_baseline_fails() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({ "follow_up_mg": 100 }));
+        let report = verifier.verify(&output, &follow_up_within_20_pct()).unwrap();
+        assert!(!report.passed);
+        assert!(report.failures[0].message.contains("'baseline_mg' is missing"));
+
+        let output = make_output(json!({ "baseline_mg": 100, "follow_up_mg": "100 mg" }));
+        let report = verifier.verify(&output, &follow_up_within_20_pct()).unwrap();
+        assert!(!report.passed);
+        assert!(report.failures[0].message.contains("'follow_up_mg' is not a number"));
+    }
+
+    // ── MonotonicField tests ──────────────────────────────────────────────────
+
+    fn ordered_plan(strict: bool) -> OutputSchema {
+        make_schema(
+            serde_json::Value::Null,
+            vec![rule(
+                "ordered-plan",
+                "treatment plan steps are in order",
+                VerificationRuleType::MonotonicField {
+                    array_path: "plan".to_string(),
+                    field: "step".to_string(),
+                    strict,
+                },
+            )],
+        )
+    }
+
+    /// Increasing step numbers pass, in either mode.
+    #[test]
+    fn test_monotonic_field_ordered_passes() {
+        let verifier = SchemaVerifier::new();
+        let output = make_output(json!({
+            "plan": [
+                { "step": 1, "action": "stop warfarin" },
+                { "step": 2, "action": "bridge with heparin" },
+                { "step": 5, "action": "resume warfarin" }
+            ]
+        }));
+
+        for strict in [true, false] {
+            let report = verifier.verify(&output, &ordered_plan(strict)).unwrap();
+            assert!(report.passed, "strict={strict}: failures: {:?}", report.failures);
+        }
+    }
+
+    /// A repeated step number fails only under `strict`, naming its index.
+    #[test]
+    fn test_monotonic_field_duplicate_fails_when_strict() {
+        let verifier = SchemaVerifier::new();
+        let output = make_output(json!({
+            "plan": [{ "step": 1 }, { "step": 2 }, { "step": 2 }, { "step": 3 }]
+        }));
+
+        let report = verifier.verify(&output, &ordered_plan(true)).unwrap();
+        assert!(!report.passed);
+        assert_eq!(report.failures.len(), 1);
+        assert!(
+            report.failures[0].message.contains("element 2 has 2 after 2"),
+            "{}",
+            report.failures[0].message
+        );
+
+        let report = verifier.verify(&output, &ordered_plan(false)).unwrap();
+        assert!(report.passed, "failures: {:?}", report.failures);
+    }
+
+    /// A decrease fails in either mode, reporting only the first one.
+    #[test]
+    fn test_monotonic_field_decrease_fails() {
+        let verifier = SchemaVerifier::new();
+        let output = make_output(json!({
+            "plan": [{ "step": 3 }, { "step": 1 }, { "step": 0 }]
+        }));
+
+        for strict in [true, false] {
+            let report = verifier.verify(&output, &ordered_plan(strict)).unwrap();
+            assert!(!report.passed);
+            assert_eq!(report.failures.len(), 1);
+            assert!(
+                report.failures[0].message.contains("element 1 has 1 after 3"),
+                "{}",
+                report.failures[0].message
+            );
+        }
+    }
+
+    // ── DoseFormat tests ──────────────────────────────────────────────────────
+
+    fn dose_schema() -> OutputSchema {
+        make_schema(
+            serde_json::Value::Null,
+            vec![rule(
+                "dose-format",
+                "every medication dose is '<number> <unit>' in an allowed unit",
+                VerificationRuleType::DoseFormat {
+                    field_path: "medications.*.dose".to_string(),
+                    allowed_units: vec!["mg".to_string(), "mcg".to_string(), "mL".to_string()],
+                },
+            )],
+        )
+    }
+
+    /// Well-formed doses in allowed units pass for every array element.
+    #[test]
+    fn test_dose_format_pass() {
+        let output = make_output(json!({
+            "medications": [
+                { "name": "metformin", "dose": "500 mg" },
+                { "name": "lisinopril", "dose": "10 mg" }
+            ]
+        }));
+
+        let report = SchemaVerifier::new().verify(&output, &dose_schema()).unwrap();
+
+        assert!(report.passed, "expected pass, failures: {:?}", report.failures);
+    }
+
+    /// A dose without a unit fails, naming the offending element.
+    #[test]
+    fn test_dose_format_missing_unit() {
+        let output = make_output(json!({
+            "medications": [
+                { "name": "metformin", "dose": "500 mg" },
+                { "name": "lisinopril", "dose": "500" }
+            ]
+        }));
+
+        let report = SchemaVerifier::new().verify(&output, &dose_schema()).unwrap();
+
+        assert!(!report.passed);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].rule_id, "dose-format");
+        assert!(
+            report.failures[0].message.contains("'medications.1.dose'"),
+            "failure should name the element: {}",
+            report.failures[0].message
+        );
+    }
+
+    /// A unit outside the allowed set fails.
+    #[test]
+    fn test_dose_format_disallowed_unit() {
+        let output = make_output(json!({
+            "medications": [{ "name": "metformin", "dose": "500 oz" }]
+        }));
+
+        let report = SchemaVerifier::new().verify(&output, &dose_schema()).unwrap();
+
+        assert!(!report.passed);
+        assert!(
+            report.failures[0].message.contains("unit 'oz' is not in the allowed set"),
+            "unexpected message: {}",
+            report.failures[0].message
+        );
+    }
+
+    // ── EachElement / verify_streaming tests ─────────────────────────────────
+
+    /// Every element of `details` must carry a `code` and an allowed `status`.
+    fn each_detail_schema() -> OutputSchema {
+        make_schema(
+            serde_json::Value::Null,
+            vec![rule(
+                "each-detail",
+                "every detail must be coded and have a known status",
+                VerificationRuleType::EachElement {
+                    array_path: "details".to_string(),
+                    element_rules: vec![
+                        rule(
+                            "req-code",
+                            "detail must carry a code",
+                            VerificationRuleType::RequiredField {
+                                field_path: "code".to_string(),
+                            },
+                        ),
+                        rule(
+                            "known-status",
+                            "detail status must be known",
+                            VerificationRuleType::AllowedValues {
+                                field_path: "status".to_string(),
+                                allowed: vec![json!("final"), json!("preliminary")],
+                            },
+                        ),
+                    ],
+                },
+            )],
+        )
+    }
+
+    fn details(count: usize) -> Vec<serde_json::Value> {
+        (0..count)
+            .map(|i| json!({ "code": format!("LOINC-{i}"), "status": "final" }))
+            .collect()
+    }
+
+    /// All 1000 valid elements are checked one by one and the report passes.
+    #[test]
+    fn test_each_element_streaming_pass() {
+        let verifier = SchemaVerifier::new();
+        let output = make_output(json!({ "details": details(1000) }));
+
+        let report = verifier.verify_streaming(&output, &each_detail_schema()).unwrap();
+
+        assert!(report.passed, "expected pass, got failures: {:?}", report.failures);
+    }
+
+    /// A single bad element is reported with its index and element rule.
+    #[test]
+    fn test_each_element_reports_failing_index() {
+        let verifier = SchemaVerifier::new();
+        let mut items = details(1000);
+        items[3] = json!({ "status": "final" });
+        let output = make_output(json!({ "details": items }));
+
+        let report = verifier.verify_streaming(&output, &each_detail_schema()).unwrap();
+
+        assert!(!report.passed);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].rule_id, "each-detail");
+        assert!(
+            report.failures[0].message.starts_with("element 3: [req-code]"),
+            "failure should name the element index and rule: {}",
+            report.failures[0].message
+        );
+    }
+
+    /// `verify_streaming` stops after the configured number of element
+    /// failures; `verify` still reports every one.
+    #[test]
+    fn test_each_element_short_circuits_after_limit() {
+        let verifier = SchemaVerifier::new().with_max_element_failures(5);
+        let broken: Vec<serde_json::Value> =
+            (0..1000).map(|_| json!({ "status": "retracted" })).collect();
+        let output = make_output(json!({ "details": broken }));
+        let schema = each_detail_schema();
+
+        let streamed = verifier.verify_streaming(&output, &schema).unwrap();
+        assert!(!streamed.passed);
+        assert_eq!(streamed.failures.len(), 5);
+        assert!(streamed.failures[4].message.starts_with("element 2: [req-code]"));
+
+        let full = verifier.verify(&output, &schema).unwrap();
+        assert_eq!(full.failures.len(), 2000, "two failing rules per element");
+    }
+
+    /// A missing array is a failure, not a vacuous pass.
+    #[test]
+    fn test_each_element_missing_array() {
+        let verifier = SchemaVerifier::new();
+        let output = make_output(json!({ "summary": "no details" }));
+
+        let report = verifier.verify_streaming(&output, &each_detail_schema()).unwrap();
+
+        assert!(!report.passed);
+        assert!(report.failures[0].message.contains("'details' is missing"));
+    }
+
+    // ── Defaults / normalize tests ────────────────────────────────────────────
+
+    fn prior_auth_defaulted(field_path: &str) -> OutputSchema {
+        OutputSchema {
+            defaults: vec![FieldDefault {
+                field_path: field_path.to_string(),
+                value: json!(true),
+            }],
+            ..make_schema(
+                serde_json::Value::Null,
+                vec![rule(
+                    "req-prior-auth",
+                    "requires_prior_auth must be present",
+                    VerificationRuleType::RequiredField {
+                        field_path: field_path.to_string(),
+                    },
+                )],
+            )
+        }
+    }
+
+    /// A missing optional field is filled from the schema default, after
+    /// which the rule requiring it passes.
+    #[test]
+    fn test_normalize_fills_missing_field() {
+        let verifier = SchemaVerifier::new();
+        let schema = prior_auth_defaulted("requires_prior_auth");
+        let output = make_output(json!({ "procedure": "MRI" }));
+
+        assert!(!verifier.verify(&output, &schema).unwrap().passed);
+
+        let normalized = verifier.normalize(&output, &schema);
+        assert_eq!(normalized.payload["requires_prior_auth"], json!(true));
+        assert_eq!(normalized.payload["procedure"], json!("MRI"));
+
+        let report = verifier.verify(&normalized, &schema).unwrap();
+        assert!(report.passed, "expected pass, got failures: {:?}", report.failures);
+    }
+
+    /// A null field counts as missing; a value the agent produced is kept.
+    #[test]
+    fn test_normalize_keeps_produced_value() {
+        let verifier = SchemaVerifier::new();
+        let schema = prior_auth_defaulted("requires_prior_auth");
+
+        let null = make_output(json!({ "requires_prior_auth": null }));
+        assert_eq!(
+            verifier.normalize(&null, &schema).payload["requires_prior_auth"],
+            json!(true)
+        );
+
+        let produced = make_output(json!({ "requires_prior_auth": false }));
+        assert_eq!(
+            verifier.normalize(&produced, &schema).payload["requires_prior_auth"],
+            json!(false)
+        );
+    }
+
+    /// Missing parents are created for both dot and pointer paths; a path
+    /// through a non-object is skipped without touching the payload.
+    #[test]
+    fn test_normalize_nested_paths() {
+        let verifier = SchemaVerifier::new();
+
+        let output = make_output(json!({ "auth": null }));
+        let normalized = verifier.normalize(&output, &prior_auth_defaulted("auth.required"));
+        assert_eq!(normalized.payload, json!({ "auth": { "required": true } }));
+
+        let output = make_output(json!({ "items": [{ "code": "A" }] }));
+        let normalized = verifier.normalize(&output, &prior_auth_defaulted("/items/0/needs_auth"));
+        assert_eq!(
+            normalized.payload,
+            json!({ "items": [{ "code": "A", "needs_auth": true }] })
+        );
+
+        let output = make_output(json!({ "auth": "pending" }));
+        let normalized = verifier.normalize(&output, &prior_auth_defaulted("auth.required"));
+        assert_eq!(normalized.payload, json!({ "auth": "pending" }));
+    }
+
+    /// Defaults load from TOML; `verify` alone never applies them.
+    #[test]
+    fn test_defaults_declared_in_toml() {
+        let schema = OutputSchema::from_toml_str(
+            r#"
+schema_id = "prior-auth-v1"
+
+[[rules]]
+rule_id = "req-prior-auth"
+description = "requires_prior_auth must be present"
+rule_type = { RequiredField = { field_path = "requires_prior_auth" } }
+
+[[defaults]]
+field_path = "requires_prior_auth"
+value = true
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            schema.defaults,
+            vec![FieldDefault {
+                field_path: "requires_prior_auth".to_string(),
+                value: json!(true),
+            }]
+        );
+
+        let verifier = SchemaVerifier::new();
+        let output = make_output(json!({}));
+        assert!(!verifier.verify(&output, &schema).unwrap().passed);
+        assert!(verifier.verify(&verifier.normalize(&output, &schema), &schema).unwrap().passed);
+    }
+
+    // ── TOML-loaded schema tests ──────────────────────────────────────────────
+
+    const CLINICAL_NOTE_TOML: &str = r#"
+schema_id = "clinical-note-v1"
+json_schema = { type = "object", required = ["notes"] }
+
+[[rules]]
+rule_id = "req-patient-id"
+description = "output must identify the patient"
+rule_type = { RequiredField = { field_path = "patient.id" } }
+
+[[rules]]
+rule_id = "no-ssn"
+description = "notes must not contain SSN labels"
+rule_type = { ForbiddenPattern = { field_path = "notes", pattern = "SSN" } }
+"#;
+
+    /// A schema loaded from TOML carries its rules through to verification;
+    /// a compliant payload passes.
+    #[test]
+    fn test_toml_schema_pass() {
+        let verifier = SchemaVerifier::new();
+        let schema = OutputSchema::from_toml_str(CLINICAL_NOTE_TOML).unwrap();
+
+        assert_eq!(schema.schema_id, "clinical-note-v1");
+        assert_eq!(schema.rules.len(), 2);
+
+        let output = make_output(json!({
+            "patient": { "id": "P-001" },
+            "notes": "stable, follow up in two weeks"
+        }));
+
+        let report = verifier.verify(&output, &schema).unwrap();
+
+        assert!(report.passed, "expected pass, got failures: {:?}", report.failures);
+    }
+
+    /// Both TOML-declared rules are enforced: a missing field and a forbidden
+    /// pattern each produce a failure with the configured rule_id.
+    #[test]
+    fn test_toml_schema_fail() {
+        let verifier = SchemaVerifier::new();
+        let schema = OutputSchema::from_toml_str(CLINICAL_NOTE_TOML).unwrap();
+
+        let output = make_output(json!({ "notes": "patient SSN: 123-45-6789" }));
+
+        let report = verifier.verify(&output, &schema).unwrap();
+
+        assert!(!report.passed);
+        let rule_ids: Vec<&str> = report.failures.iter().map(|f| f.rule_id.as_str()).collect();
+        assert_eq!(rule_ids, vec!["req-patient-id", "no-ssn"]);
+    }
+
+    /// A rule with an unknown rule type is a configuration error, not a
+    /// silently-ignored rule.
+    #[test]
+    fn test_toml_schema_unknown_rule_type() {
+        let toml = r#"
+schema_id = "bad-v1"
+
+[[rules]]
+rule_id = "mystery"
+description = "not a real rule type"
+rule_type = { NoSuchRule = { field_path = "x" } }
+"#;
+
+        let err = OutputSchema::from_toml_str(toml).unwrap_err();
+        assert!(
+            err.to_string().contains("failed to parse output schema TOML"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    // ── Custom rule tests ─────────────────────────────────────────────────────
+
+    /// A registered custom function that returns None causes the rule to pass.
+    #[test]
+    fn test_custom_rule_pass() {
+        let mut verifier = SchemaVerifier::new();
+        verifier.register_rule(
+            "always-pass",
+            Box::new(|_payload| None),
+        );
+
+        let output = make_output(json!({ "field": "value" }));
+        let schema = make_schema(
+            serde_json::Value::Null,
+            vec![rule(
+                "custom-check",
+                "delegate to always-pass function",
+                VerificationRuleType::Custom {
+                    function_name: "always-pass".to_string(),
+                },
+            )],
+        );
+
+        let report = verifier.verify(&output, &schema).unwrap();
+
+        assert!(report.passed, "expected pass, failures: {:?}", report.failures);
+    }
+
+    /// A registered custom function that returns Some(msg) causes a failure
+    /// with the rule_id of the enclosing rule.
+    #[test]
+    fn test_custom_rule_fail() {
+        let mut verifier = SchemaVerifier::new();
+        verifier.register_rule(
+            "always-fail",
+            Box::new(|_payload| Some("custom check failed: condition not met".to_string())),
+        );
+
+        let output = make_output(json!({ "field": "value" }));
+        let schema = make_schema(
+            serde_json::Value::Null,
+            vec![rule(
+                "custom-check",
+                "delegate to always-fail function",
+                VerificationRuleType::Custom {
+                    function_name: "always-fail".to_string(),
+                },
+            )],
+        );
+
+        let report = verifier.verify(&output, &schema).unwrap();
+
+        assert!(!report.passed);
+        assert_eq!(report.failures[0].rule_id, "custom-check");
+        assert!(
+            report.failures[0].message.contains("condition not met"),
+            "failure should carry the message from the custom function: {}",
+            report.failures[0].message
+        );
+    }
+
+    /// Referencing a custom function name that was never registered is itself
+    /// a failure — misconfigured schemas must surface immediately.
+    #[test]
+    fn test_unregistered_custom_rule() {
+        let verifier = SchemaVerifier::new(); // no rules registered
+
+        let output = make_output(json!({ "field": "value" }));
+        let schema = make_schema(
+            serde_json::Value::Null,
+            vec![rule(
+                "phantom-check",
+                "references a function that does not exist",
+                VerificationRuleType::Custom {
+                    function_name: "does-not-exist".to_string(),
+                },
+            )],
+        );
+
+        let report = verifier.verify(&output, &schema).unwrap();
+
+        assert!(!report.passed);
+        assert_eq!(report.failures[0].rule_id, "phantom-check");
         assert!(
             report.failures[0].message.contains("does-not-exist"),
             "failure should name the missing function: {}",
             report.failures[0].message
         );
     }
+
+    /// A context-aware rule can branch on the output kind, so one registered
+    /// function serves several kinds with tailored messages.
+    #[test]
+    fn test_custom_rule_ctx_branches_on_output_kind() {
+        let mut verifier = SchemaVerifier::new();
+        verifier.register_rule_ctx(
+            "no-ssn",
+            Box::new(|ctx| {
+                let text = ctx.payload["text"].as_str().unwrap_or("");
+                if !text.contains("SSN") {
+                    return None;
+                }
+                match ctx.output_kind {
+                    "internal-note" => None,
+                    kind => Some(format!(
+                        "{kind} output under schema '{}' must not mention an SSN",
+                        ctx.schema_id.unwrap_or("-")
+                    )),
+                }
+            }),
+        );
+
+        let schema = make_schema(
+            serde_json::Value::Null,
+            vec![rule(
+                "ssn-check",
+                "SSNs only in internal notes",
+                VerificationRuleType::Custom {
+                    function_name: "no-ssn".to_string(),
+                },
+            )],
+        );
+        let payload = json!({ "text": "SSN on file" });
+
+        let note = AgentOutput {
+            kind: "internal-note".to_string(),
+            payload: payload.clone(),
+            provenance: None,
+            advisories: vec![],
+        };
+        assert!(verifier.verify(&note, &schema).unwrap().passed);
+
+        let letter = AgentOutput {
+            kind: "patient-letter".to_string(),
+            payload,
+            provenance: None,
+            advisories: vec![],
+        };
+        let report = verifier.verify(&letter, &schema).unwrap();
+        assert!(!report.passed);
+        assert_eq!(report.failures[0].rule_id, "ssn-check");
+        assert_eq!(
+            report.failures[0].message,
+            "patient-letter output under schema 'test-schema-v1' must not mention an SSN"
+        );
+    }
+
+    // ── check_determinism tests ───────────────────────────────────────────────
+
+    /// A schema whose only rule delegates to the custom function `check`.
+    fn custom_check_schema() -> OutputSchema {
+        make_schema(
+            serde_json::Value::Null,
+            vec![rule(
+                "custom-check",
+                "delegate to the check function",
+                VerificationRuleType::Custom {
+                    function_name: "check".to_string(),
+                },
+            )],
+        )
+    }
+
+    /// A rule that always fails with the same message is deterministic.
+    #[test]
+    fn test_check_determinism_stable_rule() {
+        let mut verifier = SchemaVerifier::new();
+        verifier.register_rule(
+            "check",
+            Box::new(|payload| {
+                payload["field"]
+                    .as_str()
+                    .map(|v| format!("field is '{v}'"))
+            }),
+        );
+        let output = make_output(json!({ "field": "value" }));
+
+        assert!(verifier.check_determinism(&output, &custom_check_schema(), 5));
+    }
+
+    /// A rule whose message embeds the current instant differs run to run.
+    #[test]
+    fn test_check_determinism_clock_reading_rule() {
+        let mut verifier = SchemaVerifier::new();
+        verifier.register_rule(
+            "check",
+            Box::new(|_payload| Some(format!("checked at {:?}", std::time::Instant::now()))),
+        );
+        let output = make_output(json!({ "field": "value" }));
+        let schema = custom_check_schema();
+
+        assert!(!verifier.check_determinism(&output, &schema, 5));
+        // A single run has nothing to disagree with.
+        assert!(verifier.check_determinism(&output, &schema, 1));
+        assert!(verifier.check_determinism(&output, &schema, 0));
+    }
 }
@@ -0,0 +1,66 @@
+//! Clinical `format` validators for the structural (JSON Schema) phase.
+//!
+//! `SchemaVerifier` compiles every schema with format validation switched
+//! on, so the standard `date` and `date-time` formats are enforced rather
+//! than treated as annotations, and registers the custom formats below.  A
+//! schema declares them like any other format:
+//!
+//! ```json
+//! { "type": "string", "format": "icd10" }
+//! ```
+
+/// A format check: true if the string is written in the format.
+pub type FormatCheck = fn(&str) -> bool;
+
+/// The custom format names `SchemaVerifier` registers, with their checks.
+pub const CLINICAL_FORMATS: [(&str, FormatCheck); 1] = [("icd10", is_icd10)];
+
+/// Return true if `code` is written as an ICD-10 code: a three-character
+/// category (`D50`) — a letter, a digit, then a digit or letter — optionally
+/// followed by a dot and one to four characters of subcategory (`D50.9`,
+/// `S72.001A`).
+///
+/// Checks the notation only, not that the code exists in the
+/// classification.  An undotted subcategory (`D509`) is rejected.
+pub fn is_icd10(code: &str) -> bool {
+    let (category, subcategory) = match code.split_once('.') {
+        Some((category, subcategory)) => (category, Some(subcategory)),
+        None => (code, None),
+    };
+
+    let category_ok = matches!(
+        category.as_bytes(),
+        [letter, digit, third]
+            if letter.is_ascii_uppercase()
+                && digit.is_ascii_digit()
+                && (third.is_ascii_digit() || third.is_ascii_uppercase())
+    );
+    let subcategory_ok = subcategory.is_none_or(|sub| {
+        (1..=4).contains(&sub.len())
+            && sub.bytes().all(|b| b.is_ascii_digit() || b.is_ascii_uppercase())
+    });
+
+    category_ok && subcategory_ok
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::is_icd10;
+
+    #[test]
+    fn test_icd10_notation() {
+        assert!(is_icd10("D50"));
+        assert!(is_icd10("D50.9"));
+        assert!(is_icd10("S72.001A"));
+        assert!(is_icd10("M1A.0"));
+
+        assert!(!is_icd10("D509"));
+        assert!(!is_icd10("d50.9"));
+        assert!(!is_icd10("D50."));
+        assert!(!is_icd10("D50.12345"));
+        assert!(!is_icd10("5D0"));
+        assert!(!is_icd10(""));
+    }
+}
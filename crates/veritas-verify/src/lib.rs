@@ -7,8 +7,38 @@
 //! payloads in two phases:
 //!
 //! 1. **Structural** — JSON Schema validation via the `jsonschema` crate.
-//! 2. **Semantic** — domain rules (`RequiredField`, `AllowedValues`,
-//!    `ForbiddenPattern`, `Custom`) evaluated against the payload.
+//! 2. **Semantic** — domain rules (`RequiredField`, `ForbiddenField`,
+//!    `AllowedValues`, `AllowedFrom`, `ForbiddenPattern`,
+//!    `ForbiddenPatternWhen`, `MemberOfArray`, `ImpliesNull`,
+//!    `SeverityRecommendationConsistency`, `OnlyAllowedKeys`,
+//!    `MaxArrayLength`, `RelativeRange`, `MonotonicField`, `DoseFormat`,
+//!    `EachElement`, `Custom`) evaluated against the payload.
+//!
+//! Rule field paths are dot-notation (`"patient.id"`) or, when they start
+//! with `/`, RFC 6901 JSON Pointers (`"/details/0/severity"`).
+//!
+//! For payloads with very large arrays, `SchemaVerifier::verify_streaming`
+//! checks `EachElement` rules element by element and stops after a
+//! configurable number of failures.
+//!
+//! Custom functions registered with `SchemaVerifier::register_rule_ctx`
+//! receive a [`engine::VerifierContext`] carrying the output kind and schema
+//! id alongside the payload.
+//!
+//! [`composite::CompositeVerifier`] runs several verifiers — say an
+//! organization-wide PII scan and a scenario's own schema verifier — as one,
+//! merging their failures into a single report.
+//!
+//! Structural validation enforces JSON Schema `format` keywords; besides the
+//! standard `date` and `date-time`, [`formats`] adds clinical formats such as
+//! `icd10`.
+//!
+//! [`bound::SchemaBoundAgent`] wraps an agent so that output breaking the
+//! agent's own declared schema fails in `propose` with an error naming the
+//! agent, rather than as an anonymous verification failure.
+//!
+//! The [`pii`] module provides standalone PII detectors (`PiiKind`) that
+//! custom rules and post-hoc audit sweeps can share.
 //!
 //! ## Quick start
 //!
@@ -25,4 +55,8 @@
 //! }));
 //! ```
 
+pub mod bound;
+pub mod composite;
 pub mod engine;
+pub mod formats;
+pub mod pii;
@@ -0,0 +1,177 @@
+//! PII detectors for free-text payload values.
+//!
+//! Each `PiiKind` recognises one family of identifiers in a string.  The
+//! detectors are deliberately conservative, hand-rolled matchers — they look
+//! for the common US formats used in the healthcare reference scenarios and
+//! do not attempt to validate the identifier itself.
+//!
+//! `scan_value` walks an arbitrary JSON document and reports the JSON Pointer
+//! (RFC 6901) of every string leaf that contains PII.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Phone number layouts recognised by `PiiKind::Phone` (`d` = any digit).
+const PHONE_PATTERNS: [&[u8]; 3] = [b"ddd-ddd-dddd", b"ddd.ddd.dddd", b"(ddd) ddd-dddd"];
+
+/// A family of personally identifiable information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PiiKind {
+    /// US Social Security number: `123-45-6789`.
+    Ssn,
+    /// Email address: `jane.doe@example.org`.
+    Email,
+    /// US phone number: `555-123-4567`, `555.123.4567` or `(555) 123-4567`.
+    Phone,
+}
+
+impl PiiKind {
+    /// Every detector, in declaration order.
+    pub const ALL: [PiiKind; 3] = [PiiKind::Ssn, PiiKind::Email, PiiKind::Phone];
+
+    /// Return true if `text` contains an identifier of this kind.
+    pub fn detect(&self, text: &str) -> bool {
+        let bytes = text.as_bytes();
+        match self {
+            PiiKind::Ssn => contains_digit_pattern(bytes, b"ddd-dd-dddd"),
+            PiiKind::Phone => PHONE_PATTERNS
+                .iter()
+                .any(|pattern| contains_digit_pattern(bytes, pattern)),
+            PiiKind::Email => contains_email(text),
+        }
+    }
+}
+
+/// Return the JSON Pointer and kind of every PII occurrence in `value`.
+///
+/// Only string leaves are inspected.  A leaf matching several detectors is
+/// reported once per kind, in the order of `detectors`.  Pointers are relative
+/// to `value`; the root itself is the empty pointer `""`.
+pub fn scan_value(value: &Value, detectors: &[PiiKind]) -> Vec<(String, PiiKind)> {
+    let mut findings = Vec::new();
+    scan_into(value, String::new(), detectors, &mut findings);
+    findings
+}
+
+fn scan_into(
+    value: &Value,
+    pointer: String,
+    detectors: &[PiiKind],
+    findings: &mut Vec<(String, PiiKind)>,
+) {
+    match value {
+        Value::String(text) => {
+            for kind in detectors {
+                if kind.detect(text) {
+                    findings.push((pointer.clone(), *kind));
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                scan_into(item, format!("{}/{}", pointer, index), detectors, findings);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map {
+                let escaped = key.replace('~', "~0").replace('/', "~1");
+                scan_into(item, format!("{}/{}", pointer, escaped), detectors, findings);
+            }
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
+
+/// Return true if `text` contains `pattern`, where `d` in the pattern matches
+/// any ASCII digit and every other byte matches itself.
+///
+/// A match must not be directly preceded or followed by a digit, so
+/// `123-45-67890` is not an SSN.
+fn contains_digit_pattern(text: &[u8], pattern: &[u8]) -> bool {
+    if text.len() < pattern.len() {
+        return false;
+    }
+    (0..=text.len() - pattern.len()).any(|start| {
+        let end = start + pattern.len();
+        let window = &text[start..end];
+        let body_matches = window.iter().zip(pattern).all(|(&c, &p)| {
+            if p == b'd' {
+                c.is_ascii_digit()
+            } else {
+                c == p
+            }
+        });
+        let before_ok = start == 0 || !text[start - 1].is_ascii_digit();
+        let after_ok = end == text.len() || !text[end].is_ascii_digit();
+        body_matches && before_ok && after_ok
+    })
+}
+
+/// Return true if `text` contains something shaped like `local@domain.tld`.
+fn contains_email(text: &str) -> bool {
+    let is_local = |c: char| c.is_ascii_alphanumeric() || "._%+-".contains(c);
+    let is_domain = |c: char| c.is_ascii_alphanumeric() || c == '.' || c == '-';
+
+    text.match_indices('@').any(|(at, _)| {
+        let local_ok = text[..at].chars().next_back().is_some_and(is_local);
+        let domain: String = text[at + 1..].chars().take_while(|&c| is_domain(c)).collect();
+        let domain = domain.trim_end_matches('.');
+        let tld_ok = domain.rsplit_once('.').is_some_and(|(host, tld)| {
+            !host.is_empty() && tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic())
+        });
+        local_ok && tld_ok
+    })
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{scan_value, PiiKind};
+
+    #[test]
+    fn test_ssn_detection() {
+        assert!(PiiKind::Ssn.detect("patient SSN: 123-45-6789 on file"));
+        assert!(!PiiKind::Ssn.detect("order 123-45-67890"));
+        assert!(!PiiKind::Ssn.detect("no identifiers here"));
+    }
+
+    #[test]
+    fn test_phone_detection() {
+        assert!(PiiKind::Phone.detect("call 555-123-4567"));
+        assert!(PiiKind::Phone.detect("call (555) 123-4567"));
+        assert!(PiiKind::Phone.detect("call 555.123.4567"));
+        assert!(!PiiKind::Phone.detect("dose 5-10 mg"));
+    }
+
+    #[test]
+    fn test_email_detection() {
+        assert!(PiiKind::Email.detect("contact jane.doe@example.org today"));
+        assert!(!PiiKind::Email.detect("meet @ noon"));
+        assert!(!PiiKind::Email.detect("user@localhost"));
+    }
+
+    /// The scan reports the pointer of every offending leaf, escaping `/`
+    /// and `~` in keys as RFC 6901 requires.
+    #[test]
+    fn test_scan_value_reports_pointers() {
+        let value = json!({
+            "summary": "stable",
+            "contacts": [
+                { "email": "nurse@clinic.example.com" },
+                { "notes": "SSN 123-45-6789, phone 555-123-4567" }
+            ],
+            "a/b~c": "999-88-7777"
+        });
+
+        let findings = scan_value(&value, &PiiKind::ALL);
+
+        assert!(findings.contains(&("/contacts/0/email".to_string(), PiiKind::Email)));
+        assert!(findings.contains(&("/contacts/1/notes".to_string(), PiiKind::Ssn)));
+        assert!(findings.contains(&("/contacts/1/notes".to_string(), PiiKind::Phone)));
+        assert!(findings.contains(&("/a~1b~0c".to_string(), PiiKind::Ssn)));
+        assert_eq!(findings.len(), 4);
+    }
+}
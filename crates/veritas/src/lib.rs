@@ -0,0 +1,59 @@
+//! # veritas
+//!
+//! One dependency for the whole VERITAS runtime.
+//!
+//! This crate adds no behaviour of its own.  It re-exports the five runtime
+//! crates as modules (`contracts`, `runtime`, `policy`, `audit`, `verify` —
+//! `runtime` being `veritas-core`, renamed so it cannot shadow `core`) and
+//! the commonly used types at the top level, so an application wiring an
+//! executor needs a single import:
+//!
+//! ```rust,ignore
+//! use veritas::prelude::*;
+//!
+//! let executor = Executor::new(
+//!     Box::new(TomlPolicyEngine::from_toml_str(POLICY)?),
+//!     Box::new(InMemoryAuditWriter::new("exec-001")),
+//!     Box::new(SchemaVerifier::new()),
+//!     schema,
+//! );
+//! ```
+//!
+//! Anything not re-exported here is still reachable through the module of
+//! the crate that defines it, e.g. `veritas::policy::PolicyTrace`.
+
+pub use veritas_audit as audit;
+pub use veritas_contracts as contracts;
+pub use veritas_core as runtime;
+pub use veritas_policy as policy;
+pub use veritas_verify as verify;
+
+pub use veritas_audit::InMemoryAuditWriter;
+pub use veritas_core::{Executor, ExecutorBuilder};
+pub use veritas_policy::TomlPolicyEngine;
+pub use veritas_verify::engine::SchemaVerifier;
+
+/// The types and traits needed to define an agent and run it through an
+/// executor.
+///
+/// ```rust,ignore
+/// use veritas::prelude::*;
+/// ```
+pub mod prelude {
+    pub use veritas_contracts::{
+        agent::{AgentId, AgentInput, AgentOutput, AgentState, ExecutionId},
+        capability::{Capability, CapabilitySet},
+        error::{VeritasError, VeritasResult},
+        execution::{StepRecord, StepResult},
+        policy::{ApprovalRecord, PolicyContext, PolicyVerdict},
+        verify::{
+            OutputSchema, RuleSeverity, VerificationFailure, VerificationReport,
+            VerificationRule, VerificationRuleType,
+        },
+    };
+    pub use veritas_core::traits::{Agent, AuditWriter, PolicyEngine, Verifier};
+
+    pub use crate::{
+        Executor, ExecutorBuilder, InMemoryAuditWriter, SchemaVerifier, TomlPolicyEngine,
+    };
+}
@@ -0,0 +1,97 @@
+//! Smoke test for the facade: wire and run one executor step using nothing
+//! but `veritas::prelude`.
+
+use veritas::prelude::*;
+
+const POLICY: &str = r#"
+    [[rules]]
+    id = "allow-greet"
+    description = "Greeting is allowed for agents holding greet"
+    action = "greet"
+    resource = "*"
+    required_capabilities = ["greet"]
+    verdict = "allow"
+"#;
+
+struct GreetingAgent;
+
+impl Agent for GreetingAgent {
+    fn propose(&self, _state: &AgentState, input: &AgentInput) -> VeritasResult<AgentOutput> {
+        let name = input.payload["name"].as_str().unwrap_or("world");
+        Ok(AgentOutput {
+            kind: "greeting".to_string(),
+            payload: serde_json::json!({ "text": format!("hello, {name}") }),
+            provenance: None,
+            advisories: vec![],
+        })
+    }
+
+    fn transition(&self, state: &AgentState, _output: &AgentOutput) -> VeritasResult<AgentState> {
+        Ok(AgentState {
+            step: state.step + 1,
+            phase: "done".to_string(),
+            ..state.clone()
+        })
+    }
+
+    fn required_capabilities(&self, _state: &AgentState, _input: &AgentInput) -> Vec<String> {
+        vec!["greet".to_string()]
+    }
+
+    fn describe_action(&self, _state: &AgentState, _input: &AgentInput) -> (String, String) {
+        ("greet".to_string(), "visitor".to_string())
+    }
+
+    fn is_terminal(&self, state: &AgentState) -> bool {
+        state.phase == "done"
+    }
+}
+
+fn greeting_schema() -> OutputSchema {
+    OutputSchema {
+        schema_id: "greeting-v1".to_string(),
+        json_schema: serde_json::json!({ "type": "object", "required": ["text"] }),
+        rules: vec![VerificationRule {
+            rule_id: "req-text".to_string(),
+            description: "A greeting must contain text".to_string(),
+            severity: RuleSeverity::Error,
+            rule_type: VerificationRuleType::RequiredField {
+                field_path: "text".to_string(),
+            },
+        }],
+        defaults: vec![],
+    }
+}
+
+#[test]
+fn prelude_wires_and_runs_a_step() {
+    let executor = Executor::builder()
+        .policy(Box::new(TomlPolicyEngine::from_toml_str(POLICY).unwrap()))
+        .audit(Box::new(InMemoryAuditWriter::new("exec-prelude")))
+        .verifier(Box::new(SchemaVerifier::new()))
+        .schema(greeting_schema())
+        .build()
+        .unwrap();
+
+    let state = AgentState {
+        agent_id: AgentId("greeting-agent".to_string()),
+        execution_id: ExecutionId::new(),
+        phase: "start".to_string(),
+        context: serde_json::Value::Null,
+        step: 0,
+        correlation_id: None,
+    };
+    let input = AgentInput {
+        kind: "greet".to_string(),
+        payload: serde_json::json!({ "name": "veritas" }),
+    };
+    let mut capabilities = CapabilitySet::default();
+    capabilities.grant(Capability::new("greet"));
+
+    match executor.step(&GreetingAgent, state, input, &capabilities).unwrap() {
+        StepResult::Complete { output, .. } => {
+            assert_eq!(output.payload["text"], "hello, veritas");
+        }
+        other => panic!("expected Complete, got {:?}", other),
+    }
+}
@@ -1,6 +1,6 @@
 //! VERITAS Healthcare Reference Runtime — Demo CLI
 //!
-//! Runs one or all of the five healthcare demo scenarios.  Each scenario uses
+//! Runs one or all of the six healthcare demo scenarios.  Each scenario uses
 //! real VERITAS components (policy engine, audit writer, verifier, executor)
 //! wired together with mock clinical data.
 //!
@@ -11,19 +11,38 @@
 //!   cargo run -p demo -- patient-query
 //!   cargo run -p demo -- clinical-pipeline
 //!   cargo run -p demo -- prior-auth
+//!   cargo run -p demo -- conditional-disclosure
+//!   cargo run -p demo -- --format json run-all
+//!   cargo run -p demo -- --deny-all drug-interaction
+//!   cargo run -p demo -- --grant drug-database.read,insurance.read prior-auth
+//!
+//! Text output lists each stage's verifier warnings and agent advisories
+//! beneath its result.  `--format json` prints the selected scenarios as an
+//! array of `ScenarioReport`s instead, without the banner.
+//!
+//! Every scenario grants its agents the capabilities its story needs.
+//! `--grant` replaces those grants with the listed capabilities at every
+//! stage, and `--deny-all` with none, to exercise the capability-missing
+//! paths without editing a scenario.
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use tracing_subscriber::EnvFilter;
 
-use veritas_ref_healthcare::scenarios::{
-    clinical_pipeline, drug_interaction, note_summarizer, patient_query, prior_auth,
+use veritas_contracts::error::{VeritasError, VeritasResult};
+use veritas_ref_healthcare::{
+    grants::Grants,
+    report::{ScenarioCapture, ScenarioReport},
+    scenarios::{
+        clinical_pipeline, conditional_disclosure, drug_interaction, note_summarizer,
+        patient_query, prior_auth,
+    },
 };
 
 // ── CLI definition ────────────────────────────────────────────────────────────
 
 /// VERITAS — Policy-bound AI runtime healthcare demo.
 ///
-/// Each subcommand runs one or all of the five clinical AI scenarios,
+/// Each subcommand runs one or all of the six clinical AI scenarios,
 /// demonstrating VERITAS's policy, capability, and verification enforcement.
 #[derive(Parser)]
 #[command(
@@ -36,16 +55,51 @@ use veritas_ref_healthcare::scenarios::{
                   2. Clinical Note Summarizer     — PII custom verifier rule\n\
                   3. Patient Data Query           — Allow / CapabilityMissing / Deny\n\
                   4. Clinical Decision Pipeline   — 4-agent chain with output handoff\n\
-                  5. Prior Authorization Workflow — RequireApproval to completion"
+                  5. Prior Authorization Workflow — RequireApproval to completion\n\
+                  6. Conditional Disclosure       — RequireVerification PHI scan"
 )]
 struct Cli {
+    /// Output format: human-readable text or JSON scenario reports.
+    #[arg(long, global = true, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Grant exactly these capabilities (comma-separated) at every stage,
+    /// instead of each scenario's own grants.
+    #[arg(long, global = true, value_delimiter = ',', conflicts_with = "deny_all")]
+    grant: Option<Vec<String>>,
+
+    /// Grant no capabilities at all, instead of each scenario's own grants.
+    #[arg(long, global = true)]
+    deny_all: bool,
+
     #[command(subcommand)]
     command: Command,
 }
 
+impl Cli {
+    /// The capability grants the flags select.
+    fn grants(&self) -> Grants {
+        if self.deny_all {
+            Grants::deny_all()
+        } else if let Some(names) = &self.grant {
+            Grants::only(names.iter().map(|name| name.trim()))
+        } else {
+            Grants::Scenario
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Narrated walk-through of each scenario.
+    Text,
+    /// A JSON array with one `ScenarioReport` per scenario.
+    Json,
+}
+
 #[derive(Subcommand)]
 enum Command {
-    /// Run all five healthcare scenarios in sequence.
+    /// Run all six healthcare scenarios in sequence.
     RunAll,
     /// Scenario 1: Drug Interaction Checker (capability-gated DB query).
     DrugInteraction,
@@ -57,6 +111,8 @@ enum Command {
     ClinicalPipeline,
     /// Scenario 5: Prior Authorization Workflow (RequireApproval → approval → submit).
     PriorAuth,
+    /// Scenario 6: Conditional Disclosure (RequireVerification → PHI content scan).
+    ConditionalDisclosure,
 }
 
 // ── Entry point ───────────────────────────────────────────────────────────────
@@ -68,20 +124,31 @@ fn main() {
             EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")),
         )
         .with_target(false)
+        .with_writer(std::io::stderr)
         .compact()
         .init();
 
     let cli = Cli::parse();
+    let grants = cli.grants();
+
+    if cli.format == Format::Json {
+        if let Err(e) = print_reports(&cli.command, &grants) {
+            eprintln!("Demo error: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
 
     print_banner();
 
     let result = match cli.command {
-        Command::RunAll => run_all(),
-        Command::DrugInteraction => run_drug_interaction(),
-        Command::NoteSummarizer => run_note_summarizer(),
-        Command::PatientQuery => run_patient_query(),
-        Command::ClinicalPipeline => run_clinical_pipeline(),
-        Command::PriorAuth => run_prior_auth(),
+        Command::RunAll => run_all(&grants),
+        Command::DrugInteraction => run_drug_interaction(&grants),
+        Command::NoteSummarizer => run_note_summarizer(&grants),
+        Command::PatientQuery => run_patient_query(&grants),
+        Command::ClinicalPipeline => run_clinical_pipeline(&grants),
+        Command::PriorAuth => run_prior_auth(&grants),
+        Command::ConditionalDisclosure => run_conditional_disclosure(&grants),
     };
 
     match result {
@@ -97,33 +164,77 @@ fn main() {
 
 // ── Scenario dispatch ─────────────────────────────────────────────────────────
 
-fn run_all() -> veritas_contracts::error::VeritasResult<()> {
-    run_drug_interaction()?;
-    run_note_summarizer()?;
-    run_patient_query()?;
-    run_clinical_pipeline()?;
-    run_prior_auth()?;
+fn run_all(grants: &Grants) -> VeritasResult<()> {
+    run_drug_interaction(grants)?;
+    run_note_summarizer(grants)?;
+    run_patient_query(grants)?;
+    run_clinical_pipeline(grants)?;
+    run_prior_auth(grants)?;
+    run_conditional_disclosure(grants)?;
     Ok(())
 }
 
-fn run_drug_interaction() -> veritas_contracts::error::VeritasResult<()> {
-    drug_interaction::run_scenario()
+fn run_drug_interaction(grants: &Grants) -> VeritasResult<()> {
+    drug_interaction::run_scenario_with(grants)
+}
+
+fn run_note_summarizer(grants: &Grants) -> VeritasResult<()> {
+    note_summarizer::run_scenario_with(grants)
+}
+
+fn run_patient_query(grants: &Grants) -> VeritasResult<()> {
+    patient_query::run_scenario_with(grants)
 }
 
-fn run_note_summarizer() -> veritas_contracts::error::VeritasResult<()> {
-    note_summarizer::run_scenario()
+fn run_clinical_pipeline(grants: &Grants) -> VeritasResult<()> {
+    clinical_pipeline::run_scenario_with(grants)
 }
 
-fn run_patient_query() -> veritas_contracts::error::VeritasResult<()> {
-    patient_query::run_scenario()
+fn run_prior_auth(grants: &Grants) -> VeritasResult<()> {
+    prior_auth::run_scenario_with(grants)
 }
 
-fn run_clinical_pipeline() -> veritas_contracts::error::VeritasResult<()> {
-    clinical_pipeline::run_scenario()
+fn run_conditional_disclosure(grants: &Grants) -> VeritasResult<()> {
+    conditional_disclosure::run_scenario_with(grants)
 }
 
-fn run_prior_auth() -> veritas_contracts::error::VeritasResult<()> {
-    prior_auth::run_scenario()
+// ── JSON reports ──────────────────────────────────────────────────────────────
+
+/// A scenario's `capture_scenario_with` function.
+type Capture = fn(&Grants) -> VeritasResult<ScenarioCapture>;
+
+/// The capture functions of the scenarios `command` selects, in run order.
+fn captures(command: &Command) -> &'static [Capture] {
+    match command {
+        Command::RunAll => &[
+            drug_interaction::capture_scenario_with,
+            note_summarizer::capture_scenario_with,
+            patient_query::capture_scenario_with,
+            clinical_pipeline::capture_scenario_with,
+            prior_auth::capture_scenario_with,
+            conditional_disclosure::capture_scenario_with,
+        ],
+        Command::DrugInteraction => &[drug_interaction::capture_scenario_with],
+        Command::NoteSummarizer => &[note_summarizer::capture_scenario_with],
+        Command::PatientQuery => &[patient_query::capture_scenario_with],
+        Command::ClinicalPipeline => &[clinical_pipeline::capture_scenario_with],
+        Command::PriorAuth => &[prior_auth::capture_scenario_with],
+        Command::ConditionalDisclosure => &[conditional_disclosure::capture_scenario_with],
+    }
+}
+
+/// Run the selected scenarios under `grants` and print their reports as a
+/// JSON array.
+fn print_reports(command: &Command, grants: &Grants) -> VeritasResult<()> {
+    let reports = captures(command)
+        .iter()
+        .map(|capture| capture(grants).map(|c| c.report()))
+        .collect::<VeritasResult<Vec<ScenarioReport>>>()?;
+    let json = serde_json::to_string_pretty(&reports).map_err(|e| VeritasError::ConfigError {
+        reason: format!("cannot serialize scenario reports: {e}"),
+    })?;
+    println!("{json}");
+    Ok(())
 }
 
 // ── Banner ────────────────────────────────────────────────────────────────────
@@ -136,6 +247,7 @@ fn print_banner() {
     println!();
     println!("VERITAS enforcement pipeline per step:");
     println!("  [1] Policy engine evaluates (action, resource) → Allow / Deny / RequireApproval");
+    println!("      / RequireVerification (output must also pass a policy-named check)");
     println!("  [2] Capability check: agent must hold all declared capabilities");
     println!("  [3] Agent propose() called — ONLY after steps 1 & 2 pass");
     println!("  [4] Verifier checks output against JSON Schema + semantic rules");
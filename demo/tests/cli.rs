@@ -0,0 +1,45 @@
+//! Runs the demo binary and checks its JSON reports.
+
+use std::process::Command;
+
+/// Run the demo with `args` and parse its `--format json` output.
+fn reports(args: &[&str]) -> serde_json::Value {
+    let output = Command::new(env!("CARGO_BIN_EXE_demo"))
+        .arg("--format")
+        .arg("json")
+        .args(args)
+        .output()
+        .expect("demo binary runs");
+    assert!(
+        output.status.success(),
+        "demo failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("demo prints JSON reports")
+}
+
+#[test]
+fn deny_all_reports_missing_capability() {
+    let reports = reports(&["--deny-all", "drug-interaction"]);
+    let stage = &reports[0]["stages"][0];
+
+    assert_eq!(stage["outcome"], "denied");
+    assert_eq!(stage["deny_code"], "missing-capability");
+    assert!(stage["detail"]
+        .as_str()
+        .is_some_and(|detail| detail.contains("drug-database.read")));
+}
+
+#[test]
+fn granted_capabilities_reach_the_scenario() {
+    let stage = |args: &[&str]| reports(args)[0]["stages"][0]["outcome"].clone();
+
+    assert_eq!(
+        stage(&["--grant", "drug-database.read", "drug-interaction"]),
+        "completed"
+    );
+    assert_eq!(
+        stage(&["--grant", "clinical-notes.read", "drug-interaction"]),
+        "denied"
+    );
+}
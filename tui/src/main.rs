@@ -3,6 +3,7 @@
 //! Layout:
 //!   ┌─── header ──────────────────────────────────────────────────────────┐
 //!   │  [1] Drug Interaction  [2] Note Summarizer  [3] Patient Query       │
+//!   │  [4] Clinical Pipeline  [5] Prior Auth                              │
 //!   ├─── left panel ──────────────────┬─── right panel ───────────────────┤
 //!   │  Execution Pipeline             │  Audit Trail                      │
 //!   ├─────────────────────────────────┴───────────────────────────────────┤
@@ -38,15 +39,18 @@ use veritas_contracts::{
     capability::{Capability, CapabilitySet},
     error::{VeritasError, VeritasResult},
     execution::{StepRecord, StepResult},
-    policy::PolicyVerdict,
-    verify::{OutputSchema, VerificationRule, VerificationRuleType},
+    policy::{DenyCode, PolicyVerdict},
+    verify::{OutputSchema, RuleSeverity, VerificationRule, VerificationRuleType},
 };
 use veritas_core::{executor::Executor, traits::AuditWriter};
 use veritas_policy::engine::TomlPolicyEngine;
 use veritas_ref_healthcare::{
+    report::{ScenarioCapture, StageCapture, StageOutcome},
+    scenarios::clinical_pipeline,
     scenarios::drug_interaction::DrugInteractionAgent,
     scenarios::note_summarizer::NoteSummarizerAgent,
     scenarios::patient_query::PatientQueryAgent,
+    scenarios::prior_auth,
 };
 use veritas_verify::engine::SchemaVerifier;
 
@@ -87,6 +91,8 @@ enum Scenario {
     DrugInteraction,
     NoteSummarizer,
     PatientQuery,
+    ClinicalPipeline,
+    PriorAuth,
 }
 
 impl Scenario {
@@ -95,6 +101,8 @@ impl Scenario {
             Scenario::DrugInteraction => "Drug Interaction",
             Scenario::NoteSummarizer => "Note Summarizer",
             Scenario::PatientQuery => "Patient Query",
+            Scenario::ClinicalPipeline => "Clinical Pipeline",
+            Scenario::PriorAuth => "Prior Auth",
         }
     }
 }
@@ -127,6 +135,9 @@ struct AuditEntryDisplay {
     kind: String,
     /// Whether the chain was VERIFIED after adding this entry.
     verified: bool,
+    /// Label of the stage whose chain holds this entry; `None` for the
+    /// single-step scenarios.
+    chain: Option<String>,
 }
 
 /// Everything captured from one execution run.
@@ -143,10 +154,16 @@ struct ExecutionCapture {
     output: Option<AgentOutput>,
     /// Error if the executor returned Err (e.g. CapabilityMissing).
     error: Option<VeritasError>,
-    /// Audit chain entries at execution time.
+    /// Audit chain entries at execution time (single-step scenarios only).
     audit_events: Vec<AuditEvent>,
-    /// Result of verify_integrity().
+    /// Result of verify_integrity(); for multi-stage runs, whether every
+    /// stage's chain verified.
     chain_integrity: bool,
+    /// The deny code when the executor returned `StepResult::Denied`.
+    deny_code: Option<DenyCode>,
+    /// Per-step captures of a multi-stage scenario (4 and 5), each with its
+    /// own audit chain.  Empty for the single-step scenarios.
+    stages: Vec<StageCapture>,
 }
 
 // ── App state ─────────────────────────────────────────────────────────────────
@@ -163,7 +180,8 @@ struct App {
 
     // Animated display: how many pipeline steps are currently revealed.
     animation_step: usize,
-    // All pipeline steps derived from the last capture (up to 5).
+    // All pipeline steps derived from the last capture (5 phases, or one per
+    // stage for multi-stage scenarios).
     pipeline_steps: Vec<PipelineStep>,
     // Audit entries derived from the last capture.
     audit_entries: Vec<AuditEntryDisplay>,
@@ -207,6 +225,8 @@ impl App {
             Scenario::PatientQuery => {
                 run_patient_query(self.consent_enabled, self.capability_enabled)
             }
+            Scenario::ClinicalPipeline => run_clinical_pipeline(),
+            Scenario::PriorAuth => run_prior_auth(),
         };
 
         self.pipeline_steps = build_pipeline_steps(&capture);
@@ -228,6 +248,7 @@ fn run_drug_interaction() -> ExecutionCapture {
             return ExecutionCapture {
                 policy_verdict: PolicyVerdict::Deny {
                     reason: format!("policy load error: {}", e),
+                    remediation: None,
                 },
                 action: "drug-interaction-check".to_string(),
                 resource: "drug-database".to_string(),
@@ -237,6 +258,8 @@ fn run_drug_interaction() -> ExecutionCapture {
                 error: Some(e),
                 audit_events: vec![],
                 chain_integrity: false,
+                deny_code: None,
+                stages: Vec::new(),
             };
         }
     };
@@ -252,6 +275,7 @@ fn run_drug_interaction() -> ExecutionCapture {
         phase: "active".to_string(),
         context: serde_json::Value::Null,
         step: 0,
+        correlation_id: None,
     };
 
     let mut capabilities = CapabilitySet::default();
@@ -272,19 +296,32 @@ fn run_drug_interaction() -> ExecutionCapture {
 
     let result = executor.step(&agent, state, input, &capabilities);
 
+    let deny_code = match &result {
+        Ok(StepResult::Denied { code, .. }) => Some(*code),
+        _ => None,
+    };
     let (verdict, output, error) = match result {
         Ok(StepResult::Complete { output, .. }) | Ok(StepResult::Transitioned { output, .. }) => {
             (PolicyVerdict::Allow, Some(output), None)
         }
-        Ok(StepResult::Denied { reason, .. }) => {
-            (PolicyVerdict::Deny { reason }, None, None)
+        Ok(StepResult::Denied { reason, remediation, .. }) => {
+            (PolicyVerdict::Deny { reason, remediation }, None, None)
         }
         Ok(StepResult::AwaitingApproval { reason, approver_role, .. }) => {
-            (PolicyVerdict::RequireApproval { reason, approver_role }, None, None)
+            (
+                PolicyVerdict::RequireApproval {
+                    reason,
+                    approver_role,
+                    required_attributes: Default::default(),
+                },
+                None,
+                None,
+            )
         }
         Err(e) => {
             let v = PolicyVerdict::Deny {
                 reason: e.to_string(),
+                remediation: None,
             };
             (v, None, Some(e))
         }
@@ -303,6 +340,8 @@ fn run_drug_interaction() -> ExecutionCapture {
         error,
         audit_events: log.events,
         chain_integrity,
+        deny_code,
+        stages: Vec::new(),
     }
 }
 
@@ -314,6 +353,7 @@ fn run_note_summarizer() -> ExecutionCapture {
             return ExecutionCapture {
                 policy_verdict: PolicyVerdict::Deny {
                     reason: format!("policy load error: {}", e),
+                    remediation: None,
                 },
                 action: "summarize".to_string(),
                 resource: "clinical-notes".to_string(),
@@ -323,6 +363,8 @@ fn run_note_summarizer() -> ExecutionCapture {
                 error: Some(e),
                 audit_events: vec![],
                 chain_integrity: false,
+                deny_code: None,
+                stages: Vec::new(),
             };
         }
     };
@@ -357,6 +399,7 @@ fn run_note_summarizer() -> ExecutionCapture {
         phase: "active".to_string(),
         context: serde_json::Value::Null,
         step: 0,
+        correlation_id: None,
     };
 
     let mut capabilities = CapabilitySet::default();
@@ -376,18 +419,30 @@ fn run_note_summarizer() -> ExecutionCapture {
 
     let result = executor.step(&agent, state, input, &capabilities);
 
+    let deny_code = match &result {
+        Ok(StepResult::Denied { code, .. }) => Some(*code),
+        _ => None,
+    };
     let (verdict, output, error) = match result {
         Ok(StepResult::Complete { output, .. }) | Ok(StepResult::Transitioned { output, .. }) => {
             (PolicyVerdict::Allow, Some(output), None)
         }
-        Ok(StepResult::Denied { reason, .. }) => {
-            (PolicyVerdict::Deny { reason }, None, None)
+        Ok(StepResult::Denied { reason, remediation, .. }) => {
+            (PolicyVerdict::Deny { reason, remediation }, None, None)
         }
         Ok(StepResult::AwaitingApproval { reason, approver_role, .. }) => {
-            (PolicyVerdict::RequireApproval { reason, approver_role }, None, None)
+            (
+                PolicyVerdict::RequireApproval {
+                    reason,
+                    approver_role,
+                    required_attributes: Default::default(),
+                },
+                None,
+                None,
+            )
         }
         Err(e) => {
-            let v = PolicyVerdict::Deny { reason: e.to_string() };
+            let v = PolicyVerdict::Deny { reason: e.to_string(), remediation: None };
             (v, None, Some(e))
         }
     };
@@ -405,6 +460,8 @@ fn run_note_summarizer() -> ExecutionCapture {
         error,
         audit_events: log.events,
         chain_integrity,
+        deny_code,
+        stages: Vec::new(),
     }
 }
 
@@ -433,6 +490,7 @@ fn run_patient_query(consent_enabled: bool, capability_enabled: bool) -> Executi
             return ExecutionCapture {
                 policy_verdict: PolicyVerdict::Deny {
                     reason: format!("policy load error: {}", e),
+                    remediation: None,
                 },
                 action: "query".to_string(),
                 resource: "patient-records".to_string(),
@@ -442,6 +500,8 @@ fn run_patient_query(consent_enabled: bool, capability_enabled: bool) -> Executi
                 error: Some(e),
                 audit_events: vec![],
                 chain_integrity: false,
+                deny_code: None,
+                stages: Vec::new(),
             };
         }
     };
@@ -458,6 +518,7 @@ fn run_patient_query(consent_enabled: bool, capability_enabled: bool) -> Executi
         phase: "active".to_string(),
         context: serde_json::Value::Null,
         step: 0,
+        correlation_id: None,
     };
 
     let mut capabilities = CapabilitySet::default();
@@ -479,18 +540,30 @@ fn run_patient_query(consent_enabled: bool, capability_enabled: bool) -> Executi
 
     let result = executor.step(&agent, state, input, &capabilities);
 
+    let deny_code = match &result {
+        Ok(StepResult::Denied { code, .. }) => Some(*code),
+        _ => None,
+    };
     let (verdict, output, error) = match result {
         Ok(StepResult::Complete { output, .. }) | Ok(StepResult::Transitioned { output, .. }) => {
             (PolicyVerdict::Allow, Some(output), None)
         }
-        Ok(StepResult::Denied { reason, .. }) => {
-            (PolicyVerdict::Deny { reason }, None, None)
+        Ok(StepResult::Denied { reason, remediation, .. }) => {
+            (PolicyVerdict::Deny { reason, remediation }, None, None)
         }
         Ok(StepResult::AwaitingApproval { reason, approver_role, .. }) => {
-            (PolicyVerdict::RequireApproval { reason, approver_role }, None, None)
+            (
+                PolicyVerdict::RequireApproval {
+                    reason,
+                    approver_role,
+                    required_attributes: Default::default(),
+                },
+                None,
+                None,
+            )
         }
         Err(e) => {
-            let v = PolicyVerdict::Deny { reason: e.to_string() };
+            let v = PolicyVerdict::Deny { reason: e.to_string(), remediation: None };
             (v, None, Some(e))
         }
     };
@@ -515,6 +588,107 @@ fn run_patient_query(consent_enabled: bool, capability_enabled: bool) -> Executi
         error,
         audit_events: log.events,
         chain_integrity,
+        deny_code,
+        stages: Vec::new(),
+    }
+}
+
+/// Run Scenario 4: Multi-Agent Clinical Decision Pipeline.
+fn run_clinical_pipeline() -> ExecutionCapture {
+    capture_from_stages(
+        clinical_pipeline::capture_scenario(),
+        "multi-agent-pipeline",
+        "patient-101",
+        "per-stage grants",
+    )
+}
+
+/// Run Scenario 5: Prior Authorization Workflow (both sub-cases).
+fn run_prior_auth() -> ExecutionCapture {
+    capture_from_stages(
+        prior_auth::capture_scenario(),
+        "prior-authorization",
+        "cardiac-mri",
+        "per-stage grants",
+    )
+}
+
+/// Convert a multi-stage scenario run into an `ExecutionCapture`.
+///
+/// The summary fields describe the final stage — the one that decided how
+/// the run ended — while `stages` keeps every step and its own chain.
+fn capture_from_stages(
+    scenario: VeritasResult<ScenarioCapture>,
+    action: &str,
+    resource: &str,
+    capability_name: &str,
+) -> ExecutionCapture {
+    let scenario = match scenario {
+        Ok(scenario) => scenario,
+        Err(e) => {
+            return ExecutionCapture {
+                policy_verdict: PolicyVerdict::Deny {
+                    reason: format!("scenario error: {}", e),
+                    remediation: None,
+                },
+                action: action.to_string(),
+                resource: resource.to_string(),
+                capability_name: capability_name.to_string(),
+                capability_granted: false,
+                output: None,
+                error: Some(e),
+                audit_events: vec![],
+                chain_integrity: false,
+                deny_code: None,
+                stages: Vec::new(),
+            };
+        }
+    };
+
+    let (verdict, output, deny_code) = match scenario.stages.last().map(|s| &s.outcome) {
+        Some(StageOutcome::Completed { output }) => (PolicyVerdict::Allow, Some(output.clone()), None),
+        Some(StageOutcome::Denied { reason, code, remediation }) => (
+            PolicyVerdict::Deny {
+                reason: reason.clone(),
+                remediation: remediation.clone(),
+            },
+            None,
+            Some(*code),
+        ),
+        Some(StageOutcome::AwaitingApproval { reason, approver_role }) => (
+            PolicyVerdict::RequireApproval {
+                reason: reason.clone(),
+                approver_role: approver_role.clone(),
+                required_attributes: Default::default(),
+            },
+            None,
+            None,
+        ),
+        Some(StageOutcome::Failed { error }) => {
+            (PolicyVerdict::Deny { reason: error.to_string(), remediation: None }, None, None)
+        }
+        None => (
+            PolicyVerdict::Deny {
+                reason: "scenario ran no stages".to_string(),
+                remediation: None,
+            },
+            None,
+            None,
+        ),
+    };
+
+    ExecutionCapture {
+        policy_verdict: verdict,
+        action: action.to_string(),
+        resource: resource.to_string(),
+        capability_name: capability_name.to_string(),
+        capability_granted: true,
+        output,
+        error: None,
+        audit_events: vec![],
+        chain_integrity: scenario.all_chains_verified(),
+        deny_code,
+        stages: scenario.stages,
     }
 }
 
@@ -528,6 +702,7 @@ fn drug_interaction_schema() -> OutputSchema {
             VerificationRule {
                 rule_id: "req-query".to_string(),
                 description: "Output must contain the queried drug pair".to_string(),
+                severity: RuleSeverity::Error,
                 rule_type: VerificationRuleType::RequiredField {
                     field_path: "query".to_string(),
                 },
@@ -535,6 +710,7 @@ fn drug_interaction_schema() -> OutputSchema {
             VerificationRule {
                 rule_id: "req-result".to_string(),
                 description: "Output must contain an interaction result".to_string(),
+                severity: RuleSeverity::Error,
                 rule_type: VerificationRuleType::RequiredField {
                     field_path: "result".to_string(),
                 },
@@ -542,11 +718,13 @@ fn drug_interaction_schema() -> OutputSchema {
             VerificationRule {
                 rule_id: "req-recommendation".to_string(),
                 description: "Output must contain a clinical recommendation".to_string(),
+                severity: RuleSeverity::Error,
                 rule_type: VerificationRuleType::RequiredField {
                     field_path: "recommendation".to_string(),
                 },
             },
         ],
+        defaults: vec![],
     }
 }
 
@@ -558,6 +736,7 @@ fn note_summarizer_schema() -> OutputSchema {
             VerificationRule {
                 rule_id: "req-patient-id".to_string(),
                 description: "Output must identify the patient".to_string(),
+                severity: RuleSeverity::Error,
                 rule_type: VerificationRuleType::RequiredField {
                     field_path: "patient_id".to_string(),
                 },
@@ -565,6 +744,7 @@ fn note_summarizer_schema() -> OutputSchema {
             VerificationRule {
                 rule_id: "req-summary".to_string(),
                 description: "Output must contain a summary text".to_string(),
+                severity: RuleSeverity::Error,
                 rule_type: VerificationRuleType::RequiredField {
                     field_path: "summary".to_string(),
                 },
@@ -572,11 +752,13 @@ fn note_summarizer_schema() -> OutputSchema {
             VerificationRule {
                 rule_id: "no-pii-labels".to_string(),
                 description: "Summary must not contain PII labels such as DOB: or SSN:".to_string(),
+                severity: RuleSeverity::Error,
                 rule_type: VerificationRuleType::Custom {
                     function_name: "no-pii-labels".to_string(),
                 },
             },
         ],
+        defaults: vec![],
     }
 }
 
@@ -587,19 +769,26 @@ fn patient_query_schema() -> OutputSchema {
         rules: vec![VerificationRule {
             rule_id: "req-patient-id".to_string(),
             description: "Output must contain the patient ID".to_string(),
+            severity: RuleSeverity::Error,
             rule_type: VerificationRuleType::RequiredField {
                 field_path: "patient_id".to_string(),
             },
         }],
+        defaults: vec![],
     }
 }
 
 // ── Capture → display converters ──────────────────────────────────────────────
 
-/// Build the 5 pipeline steps from a capture.
+/// Build the pipeline steps from a capture.
 ///
-/// Steps: Policy → Capability → Agent → Verify → Audit
+/// Single-step scenarios show the 5 phases Policy → Capability → Agent →
+/// Verify → Audit; multi-stage scenarios show one step per stage.
 fn build_pipeline_steps(cap: &ExecutionCapture) -> Vec<PipelineStep> {
+    if !cap.stages.is_empty() {
+        return cap.stages.iter().map(build_stage_step).collect();
+    }
+
     let mut steps = Vec::with_capacity(5);
 
     // ── Step 1: Policy ────────────────────────────────────────────────────────
@@ -608,7 +797,7 @@ fn build_pipeline_steps(cap: &ExecutionCapture) -> Vec<PipelineStep> {
             StepStatus::Pass,
             format!("Allow — {}: {}", cap.action, cap.resource),
         ),
-        PolicyVerdict::Deny { reason } => (
+        PolicyVerdict::Deny { reason, .. } => (
             StepStatus::Denied,
             format!("Deny — {}", truncate(reason, 60)),
         ),
@@ -620,6 +809,14 @@ fn build_pipeline_steps(cap: &ExecutionCapture) -> Vec<PipelineStep> {
             StepStatus::Pass,
             format!("RequireVerification — check: {}", check_id),
         ),
+        PolicyVerdict::RequireBoth { approver_role, check_ids, .. } => (
+            StepStatus::AwaitingApproval,
+            format!(
+                "RequireBoth — checks: {}; approver: {}",
+                check_ids.join(", "),
+                approver_role
+            ),
+        ),
     };
     steps.push(PipelineStep {
         name: "Policy".to_string(),
@@ -713,36 +910,80 @@ fn build_pipeline_steps(cap: &ExecutionCapture) -> Vec<PipelineStep> {
     steps
 }
 
+/// Build the pipeline step for one stage of a multi-stage scenario.
+fn build_stage_step(stage: &StageCapture) -> PipelineStep {
+    let (status, detail) = match &stage.outcome {
+        StageOutcome::Completed { output } => (StepStatus::Pass, format!("output: {}", output.kind)),
+        StageOutcome::Denied { reason, code, .. } => (
+            StepStatus::Denied,
+            format!("Deny ({}) — {}", code.as_str(), truncate(reason, 50)),
+        ),
+        StageOutcome::AwaitingApproval { approver_role, .. } => (
+            StepStatus::AwaitingApproval,
+            format!("RequireApproval — approver: {}", approver_role),
+        ),
+        StageOutcome::Failed { error } => (StepStatus::Fail, truncate(&error.to_string(), 60)),
+    };
+    let integrity_str = if stage.chain_verified { "VERIFIED" } else { "FAILED" };
+
+    PipelineStep {
+        name: stage.label.clone(),
+        status: if stage.chain_verified { status } else { StepStatus::Fail },
+        detail: format!("{}; chain: {}", detail, integrity_str),
+    }
+}
+
 /// Build the audit trail entries for display.
+///
+/// Multi-stage scenarios list every stage's chain in stage order, each entry
+/// tagged with its stage label.
 fn build_audit_entries(cap: &ExecutionCapture) -> Vec<AuditEntryDisplay> {
+    if !cap.stages.is_empty() {
+        return cap
+            .stages
+            .iter()
+            .flat_map(|stage| {
+                stage
+                    .audit_log
+                    .events
+                    .iter()
+                    .map(|e| audit_entry(e, stage.chain_verified, Some(&stage.label)))
+            })
+            .collect();
+    }
+
     cap.audit_events
         .iter()
-        .map(|e| {
-            let kind = match &e.record.verdict {
-                PolicyVerdict::Allow => "allow",
-                PolicyVerdict::Deny { .. } => "deny",
-                PolicyVerdict::RequireApproval { .. } => "require-approval",
-                PolicyVerdict::RequireVerification { .. } => "require-verify",
-            };
-            // Genesis detection: first event's prev_hash is the genesis sentinel.
-            let is_genesis = e.sequence == 0
-                && e.prev_hash
-                    == "0000000000000000000000000000000000000000000000000000000000000000";
-
-            AuditEntryDisplay {
-                sequence: e.sequence,
-                hash_short: shorten_hash(&e.this_hash),
-                kind: if is_genesis {
-                    "genesis".to_string()
-                } else {
-                    kind.to_string()
-                },
-                verified: cap.chain_integrity,
-            }
-        })
+        .map(|e| audit_entry(e, cap.chain_integrity, None))
         .collect()
 }
 
+/// Build the display entry for one audit event.
+fn audit_entry(e: &AuditEvent, verified: bool, chain: Option<&str>) -> AuditEntryDisplay {
+    let kind = match &e.record.verdict {
+        PolicyVerdict::Allow => "allow",
+        PolicyVerdict::Deny { .. } => "deny",
+        PolicyVerdict::RequireApproval { .. } => "require-approval",
+        PolicyVerdict::RequireVerification { .. } => "require-verify",
+        PolicyVerdict::RequireBoth { .. } => "require-both",
+    };
+    // Genesis detection: first event's prev_hash is the genesis sentinel.
+    let is_genesis = e.sequence == 0
+        && e.prev_hash == "0000000000000000000000000000000000000000000000000000000000000000";
+
+    AuditEntryDisplay {
+        sequence: e.sequence,
+        hash_short: shorten_hash(&e.this_hash),
+        kind: if is_genesis {
+            "genesis".to_string()
+        } else {
+            kind.to_string()
+        },
+        verified,
+        chain: chain.map(str::to_string),
+    }
+}
+
 // ── Rendering ─────────────────────────────────────────────────────────────────
 
 fn ui(f: &mut Frame, app: &App) {
@@ -784,6 +1025,8 @@ fn render_header(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
         ("[1]", Scenario::DrugInteraction),
         ("[2]", Scenario::NoteSummarizer),
         ("[3]", Scenario::PatientQuery),
+        ("[4]", Scenario::ClinicalPipeline),
+        ("[5]", Scenario::PriorAuth),
     ];
 
     for (key, scenario) in &scenarios {
@@ -874,7 +1117,24 @@ fn render_audit_trail(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
             Style::default().fg(Color::DarkGray),
         )));
     } else {
+        let mut current_chain: Option<&str> = None;
         for entry in &app.audit_entries {
+            // Multi-stage runs: open each chain with its label and integrity.
+            if let Some(chain) = entry.chain.as_deref() {
+                if current_chain != Some(chain) {
+                    current_chain = Some(chain);
+                    let (label, color) = if entry.verified {
+                        ("VERIFIED", Color::Green)
+                    } else {
+                        ("FAILED", Color::Red)
+                    };
+                    items.push(ListItem::new(Line::from(vec![
+                        Span::styled(format!("  {} ", chain), Style::default().fg(Color::White)),
+                        Span::styled(label, Style::default().fg(color)),
+                    ])));
+                }
+            }
+
             let kind_color = match entry.kind.as_str() {
                 "allow" | "genesis" => Color::Green,
                 "deny" => Color::Red,
@@ -912,7 +1172,13 @@ fn render_audit_trail(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
             .map(|c| c.chain_integrity)
             .unwrap_or(false)
         {
-            ("  Chain integrity: VERIFIED", Color::Green)
+            if current_chain.is_some() {
+                ("  All chains: VERIFIED", Color::Green)
+            } else {
+                ("  Chain integrity: VERIFIED", Color::Green)
+            }
+        } else if current_chain.is_some() {
+            ("  All chains: FAILED", Color::Red)
         } else if app.capture.is_some() {
             ("  Chain integrity: FAILED", Color::Red)
         } else {
@@ -959,6 +1225,7 @@ fn render_output(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
         PolicyVerdict::Deny { .. } => ("Deny", Color::Red),
         PolicyVerdict::RequireApproval { .. } => ("RequireApproval", Color::Yellow),
         PolicyVerdict::RequireVerification { .. } => ("RequireVerification", Color::Yellow),
+        PolicyVerdict::RequireBoth { .. } => ("RequireBoth", Color::Yellow),
     };
     lines.push(Line::from(vec![
         Span::styled("  Verdict:     ", Style::default().fg(Color::Gray)),
@@ -1063,18 +1330,52 @@ fn render_output(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
                     Span::raw(format!("{} condition(s) returned", cond_count)),
                 ]));
             }
+            Scenario::ClinicalPipeline => {
+                let report = &output.payload["safety_report"];
+                let overall = report["overall_risk"].as_str().unwrap_or("?");
+                let found = report["interactions_found"].as_u64().unwrap_or(0);
+                lines.push(Line::from(vec![
+                    Span::styled("  Stages:      ", Style::default().fg(Color::Gray)),
+                    Span::raw(format!("{} agent(s) completed", cap.stages.len())),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::styled("  Risk:        ", Style::default().fg(Color::Gray)),
+                    Span::raw(format!("{} ({} known interaction(s))", overall, found)),
+                ]));
+            }
+            Scenario::PriorAuth => {
+                let reference = output.payload["pa_reference"].as_str().unwrap_or("?");
+                let status = output.payload["status"].as_str().unwrap_or("?");
+                lines.push(Line::from(vec![
+                    Span::styled("  PA:          ", Style::default().fg(Color::Gray)),
+                    Span::raw(format!("{} ({})", reference, status)),
+                ]));
+            }
+        }
+        for advisory in &output.advisories {
+            lines.push(Line::from(vec![
+                Span::styled("  Advisory:    ", Style::default().fg(Color::Gray)),
+                Span::styled(truncate(advisory, 80), Style::default().fg(Color::Yellow)),
+            ]));
         }
     } else {
         // No output — show denial / error reason.
         let reason = match &cap.policy_verdict {
-            PolicyVerdict::Deny { reason } => reason.clone(),
-            PolicyVerdict::RequireApproval { reason, .. } => reason.clone(),
+            PolicyVerdict::Deny { reason, .. } => reason.clone(),
+            PolicyVerdict::RequireApproval { reason, .. }
+            | PolicyVerdict::RequireBoth { reason, .. } => reason.clone(),
             _ => cap
                 .error
                 .as_ref()
                 .map(|e| e.to_string())
                 .unwrap_or_default(),
         };
+        if let Some(code) = cap.deny_code {
+            lines.push(Line::from(vec![
+                Span::styled("  Deny code:   ", Style::default().fg(Color::Gray)),
+                Span::styled(code.as_str(), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            ]));
+        }
         if !reason.is_empty() {
             lines.push(Line::from(vec![
                 Span::styled("  Reason:      ", Style::default().fg(Color::Gray)),
@@ -1084,6 +1385,12 @@ fn render_output(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
                 ),
             ]));
         }
+        if let PolicyVerdict::Deny { remediation: Some(remediation), .. } = &cap.policy_verdict {
+            lines.push(Line::from(vec![
+                Span::styled("  Remediation: ", Style::default().fg(Color::Gray)),
+                Span::styled(truncate(remediation, 80), Style::default().fg(Color::Yellow)),
+            ]));
+        }
     }
 
     let paragraph = Paragraph::new(lines).block(block).wrap(Wrap { trim: false });
@@ -1092,7 +1399,7 @@ fn render_output(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
 
 fn render_footer(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
     let mut spans: Vec<Span> = vec![
-        Span::styled(" [1-3] ", Style::default().fg(Color::Cyan)),
+        Span::styled(" [1-5] ", Style::default().fg(Color::Cyan)),
         Span::raw("Select scenario  "),
         Span::styled("[r] ", Style::default().fg(Color::Cyan)),
         Span::raw("Run  "),
@@ -1238,6 +1545,20 @@ fn main() -> io::Result<()> {
                         app.audit_entries.clear();
                         app.animating = false;
                     }
+                    KeyCode::Char('4') => {
+                        app.selected = Scenario::ClinicalPipeline;
+                        app.capture = None;
+                        app.pipeline_steps.clear();
+                        app.audit_entries.clear();
+                        app.animating = false;
+                    }
+                    KeyCode::Char('5') => {
+                        app.selected = Scenario::PriorAuth;
+                        app.capture = None;
+                        app.pipeline_steps.clear();
+                        app.audit_entries.clear();
+                        app.animating = false;
+                    }
 
                     // Run selected scenario.
                     KeyCode::Char('r') | KeyCode::Char('R') => {
@@ -1271,3 +1592,73 @@ fn main() -> io::Result<()> {
     restore_terminal(&mut terminal)?;
     Ok(())
 }
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The pipeline run shows one passing step per agent and one verified
+    /// chain per stage, in stage order.
+    #[test]
+    fn test_pipeline_capture_shows_one_step_per_stage() {
+        let cap = run_clinical_pipeline();
+        assert!(cap.chain_integrity);
+        assert!(matches!(cap.policy_verdict, PolicyVerdict::Allow));
+
+        let steps = build_pipeline_steps(&cap);
+        let names: Vec<&str> = steps.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "stage-1/symptom-analyzer",
+                "stage-2/diagnosis-suggester",
+                "stage-3/treatment-planner",
+                "stage-4/drug-safety-checker",
+            ]
+        );
+        assert!(steps.iter().all(|s| s.status == StepStatus::Pass));
+        assert!(steps.iter().all(|s| s.detail.ends_with("chain: VERIFIED")));
+
+        let entries = build_audit_entries(&cap);
+        let mut chains: Vec<&str> = entries.iter().filter_map(|e| e.chain.as_deref()).collect();
+        chains.dedup();
+        assert_eq!(chains, names);
+        assert!(entries.iter().all(|e| e.verified));
+    }
+
+    /// Prior auth shows each sub-case's suspension, A's completed steps and
+    /// B's denial, which also decides the summary verdict.
+    #[test]
+    fn test_prior_auth_capture_maps_stage_outcomes() {
+        let cap = run_prior_auth();
+
+        let statuses: Vec<StepStatus> =
+            build_pipeline_steps(&cap).into_iter().map(|s| s.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                StepStatus::AwaitingApproval,
+                StepStatus::Pass,
+                StepStatus::Pass,
+                StepStatus::AwaitingApproval,
+                StepStatus::Denied,
+            ]
+        );
+        assert!(matches!(cap.policy_verdict, PolicyVerdict::Deny { .. }));
+        assert_eq!(cap.deny_code, Some(DenyCode::RuleDenied));
+        assert!(cap.output.is_none());
+    }
+
+    /// Single-step scenarios keep the five-phase view with untagged entries.
+    #[test]
+    fn test_single_step_capture_keeps_phase_view() {
+        let cap = run_drug_interaction();
+
+        let steps = build_pipeline_steps(&cap);
+        assert_eq!(steps.len(), 5);
+        assert_eq!(steps[0].name, "Policy");
+        assert!(build_audit_entries(&cap).iter().all(|e| e.chain.is_none()));
+    }
+}